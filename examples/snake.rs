@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use rand::prelude::ThreadRng;
+use rand::Rng;
+use sdl2::event::Event;
+use sdl2::EventPump;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::render::{Texture, TextureCreator, WindowCanvas};
+use sdl2::video::WindowContext;
+use rust_nes::components::bus::Bus;
+use rust_nes::components::cartridge::Rom;
+use rust_nes::components::cpu::CPU;
+use rust_nes::components::input_device::{InputDevices, InputSetup};
+use rust_nes::components::joypad::JoypadButton;
+use rust_nes::components::mem::Mem;
+use rust_nes::roms::input_config::InputConfig;
+use rust_nes::roms::input_mapping::InputMapping;
+
+const INPUT_CONFIG_PATH: &str = "input.cfg";
+
+// The snake.nes demo predates the PPU support, it draws a 32x32 screen from
+// $0200-$05FF and expects a random byte at $FE and the last pressed key at $FF.
+// Run it with `cargo run --example snake` from the repository root.
+fn main() {
+    let game_code = std::fs::read("snake.nes").unwrap();
+    let (mut screen_state, mut rng, mut canvas, mut event_pump, mut input) = initialize_sdl_components();
+
+    let mut creator = canvas.texture_creator();
+    let mut texture = initialize_texture(&mut creator);
+
+    let rom = Rom::new(&game_code).unwrap();
+    let bus = Bus::new(rom).unwrap();
+    let mut cpu = CPU::new(bus);
+    let devices = InputDevices::connect(InputSetup::Joypads, &mut cpu.bus);
+    cpu.power_on();
+
+    cpu.run_with_callback(move |cpu| {
+        handle_user_input(cpu, &mut event_pump, &mut input, &devices);
+        cpu.mem_write(0xfe, rng.gen_range(1, 16));
+
+        if read_screen_state(cpu, &mut screen_state) {
+            texture.update(None, &screen_state, 32 * 3).unwrap();
+            canvas.copy(&texture, None, None).unwrap();
+            canvas.present();
+        }
+    });
+}
+
+fn initialize_texture(creator: &mut TextureCreator<WindowContext>) -> Texture<'_> {
+    creator
+        .create_texture_target(PixelFormatEnum::RGB24, 32, 32)
+        .unwrap()
+}
+
+fn initialize_sdl_components() -> ([u8; 3072], ThreadRng, WindowCanvas, EventPump, InputMapping) {
+    let screen_state = [0u8; 32 * 3 * 32];
+    let rng = rand::thread_rng();
+
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("Snake game", (32.0 * 10.0) as u32, (32.0 * 10.0) as u32)
+        .position_centered()
+        .build()
+        .unwrap();
+
+    let mut canvas = window.into_canvas().present_vsync().build().unwrap();
+    let event_pump = sdl_context.event_pump().unwrap();
+    canvas.set_scale(10.0, 10.0).unwrap();
+
+    let config = InputConfig::load_or_create(Path::new(INPUT_CONFIG_PATH)).unwrap();
+    let input = InputMapping::new(&config, sdl_context.game_controller().unwrap()).unwrap();
+
+    (screen_state, rng, canvas, event_pump, input)
+}
+
+fn handle_user_input(cpu: &mut CPU, event_pump: &mut EventPump, input: &mut InputMapping, devices: &InputDevices) {
+    for event in event_pump.poll_iter() {
+        match event {
+            Event::Quit { .. }
+            | Event::KeyDown {
+                keycode: Some(Keycode::Escape),
+                ..
+            } => std::process::exit(0),
+            _ => input.handle_event(&event),
+        }
+    }
+
+    let buttons = input.buttons(0);
+    devices.set_joypad_buttons(0, buttons);
+
+    // the snake program doesn't read the controller port, it expects the ascii code of w/a/s/d at $FF
+    let directions = [
+        (JoypadButton::Up, 0x77),
+        (JoypadButton::Down, 0x73),
+        (JoypadButton::Left, 0x61),
+        (JoypadButton::Right, 0x64),
+    ];
+    for (button, key) in directions {
+        if buttons & button.bit() != 0 {
+            cpu.mem_write(0xff, key);
+        }
+    }
+}
+
+fn color(byte: u8) -> Color {
+    match byte {
+        0 => Color::BLACK,
+        1 => Color::WHITE,
+        2 | 9 => Color::GREY,
+        3 | 10 => Color::RED,
+        4 | 11 => Color::GREEN,
+        5 | 12 => Color::BLUE,
+        6 | 13 => Color::MAGENTA,
+        7 | 14 => Color::YELLOW,
+        _ => Color::CYAN,
+    }
+}
+
+fn read_screen_state(cpu: &mut CPU, frame: &mut [u8; 32 * 3 * 32]) -> bool {
+    let mut frame_idx = 0;
+    let mut update = false;
+    for i in 0x0200..0x600 {
+        let color_idx = cpu.mem_read(i as u16);
+        let (b1, b2, b3) = color(color_idx).rgb();
+        if frame[frame_idx] != b1 || frame[frame_idx + 1] != b2 || frame[frame_idx + 2] != b3 {
+            frame[frame_idx] = b1;
+            frame[frame_idx + 1] = b2;
+            frame[frame_idx + 2] = b3;
+            update = true;
+        }
+        frame_idx += 3;
+    }
+    update
+}
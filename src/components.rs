@@ -3,4 +3,19 @@ pub mod  bus;
 pub mod  opcodes;
 pub mod  mem;
 pub mod  cartridge;
-pub mod ppu;
\ No newline at end of file
+pub mod ppu;
+pub mod mapper;
+pub mod nsf_player;
+pub mod joypad;
+pub mod frame;
+pub mod zapper;
+pub mod multitap;
+pub mod input_device;
+pub mod arkanoid_paddle;
+pub mod power_pad;
+pub mod family_keyboard;
+pub mod apu;
+pub mod expansion_audio;
+pub mod crc32;
+pub mod md5;
+pub mod save_state;
\ No newline at end of file
@@ -0,0 +1,312 @@
+use crate::components::apu::dmc::Dmc;
+use crate::components::apu::noise::Noise;
+use crate::components::apu::pulse::Pulse;
+use crate::components::apu::triangle::Triangle;
+use crate::components::mapper::SharedMapper;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+mod dmc;
+pub(crate) mod envelope;
+mod noise;
+pub(crate) mod pulse;
+mod triangle;
+
+pub const CPU_CLOCK_RATE: f64 = 1_789_773.0;
+
+// Frame counter steps in CPU cycles
+//https://www.nesdev.org/wiki/APU_Frame_Counter
+const QUARTER_FRAME_1: u32 = 7457;
+const HALF_FRAME_1: u32 = 14913;
+const QUARTER_FRAME_3: u32 = 22371;
+const FOUR_STEP_LAST: u32 = 29829;
+const FIVE_STEP_LAST: u32 = 37281;
+
+//https://www.nesdev.org/wiki/APU
+pub struct Apu {
+    pulse1: Pulse,
+    pulse2: Pulse,
+    triangle: Triangle,
+    noise: Noise,
+    dmc: Dmc,
+
+    five_step_mode: bool,
+    irq_inhibit: bool,
+    frame_irq: bool,
+    frame_cycle: u32,
+    odd_cycle: bool,
+
+    output: Option<AudioOutput>,
+}
+
+// Samples are the average level over the CPU cycles they cover, which is enough of a low pass
+// to keep the square waves from aliasing badly
+struct AudioOutput {
+    cycles_per_sample: f64,
+    cycles_until_sample: f64,
+    level_sum: f32,
+    level_count: u32,
+    samples: Vec<f32>,
+}
+
+impl Apu {
+    pub fn new() -> Self {
+        Apu {
+            pulse1: Pulse::new(true),
+            pulse2: Pulse::new(false),
+            triangle: Triangle::new(),
+            noise: Noise::new(),
+            dmc: Dmc::new(),
+            five_step_mode: false,
+            irq_inhibit: false,
+            frame_irq: false,
+            frame_cycle: 0,
+            odd_cycle: false,
+            output: None,
+        }
+    }
+
+    // No samples are collected until a frontend asks for them
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        let cycles_per_sample = CPU_CLOCK_RATE / sample_rate as f64;
+        self.output = Some(AudioOutput {
+            cycles_per_sample,
+            cycles_until_sample: cycles_per_sample,
+            level_sum: 0.0,
+            level_count: 0,
+            samples: vec![],
+        });
+    }
+
+    //https://www.nesdev.org/wiki/CPU_power_up_state#APU
+    // Every channel and the frame counter start over, samples keep going where they went
+    pub fn power_on(&mut self) {
+        let output = self.output.take();
+        *self = Apu::new();
+        self.output = output;
+    }
+
+    // Reset silences the channels as if $4015 was cleared, the frame counter keeps its mode
+    pub fn reset(&mut self) {
+        self.write_register(0x4015, 0);
+    }
+
+    pub fn take_samples(&mut self) -> Vec<f32> {
+        match &mut self.output {
+            Some(output) => std::mem::take(&mut output.samples),
+            None => vec![],
+        }
+    }
+
+    pub fn write_register(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4000 => self.pulse1.write_control(data),
+            0x4001 => self.pulse1.write_sweep(data),
+            0x4002 => self.pulse1.write_timer_low(data),
+            0x4003 => self.pulse1.write_timer_high(data),
+            0x4004 => self.pulse2.write_control(data),
+            0x4005 => self.pulse2.write_sweep(data),
+            0x4006 => self.pulse2.write_timer_low(data),
+            0x4007 => self.pulse2.write_timer_high(data),
+            0x4008 => self.triangle.write_linear_counter(data),
+            0x400A => self.triangle.write_timer_low(data),
+            0x400B => self.triangle.write_timer_high(data),
+            0x400C => self.noise.write_control(data),
+            0x400E => self.noise.write_period(data),
+            0x400F => self.noise.write_length(data),
+            0x4010 => self.dmc.write_control(data),
+            0x4011 => self.dmc.write_output_level(data),
+            0x4012 => self.dmc.write_sample_address(data),
+            0x4013 => self.dmc.write_sample_length(data),
+            0x4015 => {
+                self.pulse1.length_counter.set_enabled(data & 0b0001 != 0);
+                self.pulse2.length_counter.set_enabled(data & 0b0010 != 0);
+                self.triangle.length_counter.set_enabled(data & 0b0100 != 0);
+                self.noise.length_counter.set_enabled(data & 0b1000 != 0);
+                self.dmc.set_enabled(data & 0b1_0000 != 0);
+            }
+            0x4017 => {
+                self.five_step_mode = data & 0b1000_0000 != 0;
+                self.irq_inhibit = data & 0b0100_0000 != 0;
+                if self.irq_inhibit {
+                    self.frame_irq = false;
+                }
+                self.frame_cycle = 0;
+                if self.five_step_mode {
+                    self.clock_quarter_frame();
+                    self.clock_half_frame();
+                }
+            }
+            0x4009 | 0x400D => { /* unused */ }
+            _ => panic!("Attempt to write to non APU address {:x}", addr),
+        }
+    }
+
+    pub fn read_status(&mut self) -> u8 {
+        let mut status = 0;
+        if self.pulse1.length_counter.is_active() {
+            status |= 0b0000_0001;
+        }
+        if self.pulse2.length_counter.is_active() {
+            status |= 0b0000_0010;
+        }
+        if self.triangle.length_counter.is_active() {
+            status |= 0b0000_0100;
+        }
+        if self.noise.length_counter.is_active() {
+            status |= 0b0000_1000;
+        }
+        if self.dmc.is_active() {
+            status |= 0b0001_0000;
+        }
+        if self.frame_irq {
+            status |= 0b0100_0000;
+        }
+        if self.dmc.irq {
+            status |= 0b1000_0000;
+        }
+        self.frame_irq = false;
+        status
+    }
+
+    pub fn irq_pending(&self) -> bool {
+        self.frame_irq || self.dmc.irq
+    }
+
+    // The cartridge is needed for DMC sample fetches and its expansion audio
+    pub fn tick(&mut self, cycles: u8, cartridge: &SharedMapper) {
+        for _ in 0..cycles {
+            self.clock(cartridge);
+            if self.output.is_some() {
+                let level = self.mix() + cartridge.borrow().expansion_audio();
+                self.collect_sample(level);
+            }
+        }
+    }
+
+    fn clock(&mut self, cartridge: &SharedMapper) {
+        self.clock_frame_counter();
+
+        self.triangle.clock_timer();
+        self.noise.clock_timer();
+        self.dmc.clock_timer();
+        if self.odd_cycle {
+            self.pulse1.clock_timer();
+            self.pulse2.clock_timer();
+        }
+        self.odd_cycle = !self.odd_cycle;
+
+        if let Some(addr) = self.dmc.pending_read() {
+            let data = cartridge.borrow_mut().read_prg(addr);
+            self.dmc.fill_sample_buffer(data);
+        }
+    }
+
+    fn clock_frame_counter(&mut self) {
+        self.frame_cycle += 1;
+        match self.frame_cycle {
+            QUARTER_FRAME_1 | QUARTER_FRAME_3 => self.clock_quarter_frame(),
+            HALF_FRAME_1 => {
+                self.clock_quarter_frame();
+                self.clock_half_frame();
+            }
+            FOUR_STEP_LAST if !self.five_step_mode => {
+                self.clock_quarter_frame();
+                self.clock_half_frame();
+                if !self.irq_inhibit {
+                    self.frame_irq = true;
+                }
+                self.frame_cycle = 0;
+            }
+            FIVE_STEP_LAST => {
+                self.clock_quarter_frame();
+                self.clock_half_frame();
+                self.frame_cycle = 0;
+            }
+            _ => {}
+        }
+    }
+
+    fn clock_quarter_frame(&mut self) {
+        self.pulse1.envelope.clock();
+        self.pulse2.envelope.clock();
+        self.noise.envelope.clock();
+        self.triangle.clock_linear_counter();
+    }
+
+    fn clock_half_frame(&mut self) {
+        self.pulse1.length_counter.clock();
+        self.pulse2.length_counter.clock();
+        self.triangle.length_counter.clock();
+        self.noise.length_counter.clock();
+        self.pulse1.clock_sweep();
+        self.pulse2.clock_sweep();
+    }
+
+    // Level between 0.0 and 1.0
+    //https://www.nesdev.org/wiki/APU_Mixer
+    pub fn mix(&self) -> f32 {
+        let pulse = (self.pulse1.output() + self.pulse2.output()) as f32;
+        let pulse_out = if pulse == 0.0 { 0.0 } else { 95.88 / (8128.0 / pulse + 100.0) };
+
+        let tnd = self.triangle.output() as f32 / 8227.0
+            + self.noise.output() as f32 / 12241.0
+            + self.dmc.output() as f32 / 22638.0;
+        let tnd_out = if tnd == 0.0 { 0.0 } else { 159.79 / (1.0 / tnd + 100.0) };
+
+        pulse_out + tnd_out
+    }
+
+    fn collect_sample(&mut self, level: f32) {
+        let output = self.output.as_mut().unwrap();
+        output.level_sum += level;
+        output.level_count += 1;
+        output.cycles_until_sample -= 1.0;
+        if output.cycles_until_sample <= 0.0 {
+            output.samples.push(output.level_sum / output.level_count as f32);
+            output.level_sum = 0.0;
+            output.level_count = 0;
+            output.cycles_until_sample += output.cycles_per_sample;
+        }
+    }
+}
+
+impl Default for Apu {
+    fn default() -> Self {
+        Apu::new()
+    }
+}
+
+// Sample collection belongs to the frontend and is left alone
+impl SaveState for Apu {
+    fn save_state(&self, state: &mut StateWriter) {
+        self.pulse1.save_state(state);
+        self.pulse2.save_state(state);
+        self.triangle.save_state(state);
+        self.noise.save_state(state);
+        self.dmc.save_state(state);
+        state.write_bool(self.five_step_mode);
+        state.write_bool(self.irq_inhibit);
+        state.write_bool(self.frame_irq);
+        state.write_u32(self.frame_cycle);
+        state.write_bool(self.odd_cycle);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.pulse1.load_state(state)?;
+        self.pulse2.load_state(state)?;
+        self.triangle.load_state(state)?;
+        self.noise.load_state(state)?;
+        self.dmc.load_state(state)?;
+        self.five_step_mode = state.read_bool()?;
+        self.irq_inhibit = state.read_bool()?;
+        self.frame_irq = state.read_bool()?;
+        self.frame_cycle = state.read_u32()?;
+        self.odd_cycle = state.read_bool()?;
+        Ok(())
+    }
+}
+
+// A lone 2A03 pulse at full volume and the DMC at its highest level, expansion chips scale
+// their output against these
+pub const PULSE_FULL_VOLUME: f32 = 95.88 / (8128.0 / 15.0 + 100.0);
+pub const DMC_FULL_VOLUME: f32 = 159.79 / (22638.0 / 127.0 + 100.0);
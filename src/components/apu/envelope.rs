@@ -0,0 +1,142 @@
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/APU_Envelope
+pub struct Envelope {
+    start: bool,
+    divider: u8,
+    decay_level: u8,
+    pub looping: bool,
+    pub constant_volume: bool,
+    pub period: u8,
+}
+
+impl Envelope {
+    pub fn new() -> Self {
+        Envelope {
+            start: false,
+            divider: 0,
+            decay_level: 0,
+            looping: false,
+            constant_volume: false,
+            period: 0,
+        }
+    }
+
+    // --LC VVVV as written to the channel's first register
+    pub fn write(&mut self, data: u8) {
+        self.looping = data & 0b0010_0000 != 0;
+        self.constant_volume = data & 0b0001_0000 != 0;
+        self.period = data & 0b1111;
+    }
+
+    pub fn restart(&mut self) {
+        self.start = true;
+    }
+
+    pub fn clock(&mut self) {
+        if self.start {
+            self.start = false;
+            self.decay_level = 15;
+            self.divider = self.period;
+        } else if self.divider == 0 {
+            self.divider = self.period;
+            if self.decay_level > 0 {
+                self.decay_level -= 1;
+            } else if self.looping {
+                self.decay_level = 15;
+            }
+        } else {
+            self.divider -= 1;
+        }
+    }
+
+    pub fn volume(&self) -> u8 {
+        if self.constant_volume {
+            self.period
+        } else {
+            self.decay_level
+        }
+    }
+}
+
+impl SaveState for Envelope {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bool(self.start);
+        state.write_u8(self.divider);
+        state.write_u8(self.decay_level);
+        state.write_bool(self.looping);
+        state.write_bool(self.constant_volume);
+        state.write_u8(self.period);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.start = state.read_bool()?;
+        self.divider = state.read_u8()?;
+        self.decay_level = state.read_u8()?;
+        self.looping = state.read_bool()?;
+        self.constant_volume = state.read_bool()?;
+        self.period = state.read_u8()?;
+        Ok(())
+    }
+}
+
+//https://www.nesdev.org/wiki/APU_Length_Counter
+const LENGTH_TABLE: [u8; 32] = [
+    10, 254, 20, 2, 40, 4, 80, 6, 160, 8, 60, 10, 14, 12, 26, 14, 12, 16, 24, 18, 48, 20, 96, 22, 192, 24, 72, 26, 16, 28,
+    32, 30,
+];
+
+pub struct LengthCounter {
+    enabled: bool,
+    pub halted: bool,
+    counter: u8,
+}
+
+impl LengthCounter {
+    pub fn new() -> Self {
+        LengthCounter {
+            enabled: false,
+            halted: false,
+            counter: 0,
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.counter = 0;
+        }
+    }
+
+    // the 5 bit index comes from the top bits of the channel's last register
+    pub fn load(&mut self, index: u8) {
+        if self.enabled {
+            self.counter = LENGTH_TABLE[(index & 0b1_1111) as usize];
+        }
+    }
+
+    pub fn clock(&mut self) {
+        if !self.halted && self.counter > 0 {
+            self.counter -= 1;
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.counter > 0
+    }
+}
+
+impl SaveState for LengthCounter {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bool(self.enabled);
+        state.write_bool(self.halted);
+        state.write_u8(self.counter);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.enabled = state.read_bool()?;
+        self.halted = state.read_bool()?;
+        self.counter = state.read_u8()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::components::apu::envelope::{Envelope, LengthCounter};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/APU_Noise
+// periods in CPU cycles
+const PERIOD_TABLE: [u16; 16] = [4, 8, 16, 32, 64, 96, 128, 160, 202, 254, 380, 508, 762, 1016, 2034, 4068];
+
+pub struct Noise {
+    short_mode: bool,
+    timer_period: u16,
+    timer: u16,
+    shift_register: u16,
+    pub envelope: Envelope,
+    pub length_counter: LengthCounter,
+}
+
+impl Noise {
+    pub fn new() -> Self {
+        Noise {
+            short_mode: false,
+            timer_period: PERIOD_TABLE[0],
+            timer: 0,
+            shift_register: 1,
+            envelope: Envelope::new(),
+            length_counter: LengthCounter::new(),
+        }
+    }
+
+    pub fn write_control(&mut self, data: u8) {
+        self.length_counter.halted = data & 0b0010_0000 != 0;
+        self.envelope.write(data);
+    }
+
+    pub fn write_period(&mut self, data: u8) {
+        self.short_mode = data & 0b1000_0000 != 0;
+        self.timer_period = PERIOD_TABLE[(data & 0b1111) as usize];
+    }
+
+    pub fn write_length(&mut self, data: u8) {
+        self.length_counter.load(data >> 3);
+        self.envelope.restart();
+    }
+
+    // clocked every CPU cycle
+    pub fn clock_timer(&mut self) {
+        if self.timer == 0 {
+            self.timer = self.timer_period - 1;
+            let tap = if self.short_mode { 6 } else { 1 };
+            let feedback = (self.shift_register ^ (self.shift_register >> tap)) & 1;
+            self.shift_register = (self.shift_register >> 1) | (feedback << 14);
+        } else {
+            self.timer -= 1;
+        }
+    }
+
+    pub fn output(&self) -> u8 {
+        if !self.length_counter.is_active() || self.shift_register & 1 != 0 {
+            return 0;
+        }
+        self.envelope.volume()
+    }
+}
+
+impl SaveState for Noise {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bool(self.short_mode);
+        state.write_u16(self.timer_period);
+        state.write_u16(self.timer);
+        state.write_u16(self.shift_register);
+        self.envelope.save_state(state);
+        self.length_counter.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.short_mode = state.read_bool()?;
+        self.timer_period = state.read_u16()?;
+        self.timer = state.read_u16()?;
+        self.shift_register = state.read_u16()?;
+        self.envelope.load_state(state)?;
+        self.length_counter.load_state(state)
+    }
+}
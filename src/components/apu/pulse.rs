@@ -0,0 +1,164 @@
+use crate::components::apu::envelope::{Envelope, LengthCounter};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/APU_Pulse
+const DUTY_TABLE: [[u8; 8]; 4] = [
+    [0, 1, 0, 0, 0, 0, 0, 0],
+    [0, 1, 1, 0, 0, 0, 0, 0],
+    [0, 1, 1, 1, 1, 0, 0, 0],
+    [1, 0, 0, 1, 1, 1, 1, 1],
+];
+
+pub struct Pulse {
+    // the first pulse negates its sweep with one's complement, the second with two's
+    ones_complement_sweep: bool,
+    // MMC5 pulses have no sweep unit, so they also aren't muted by it
+    has_sweep: bool,
+    duty: u8,
+    sequence_step: u8,
+    timer_period: u16,
+    timer: u16,
+    pub envelope: Envelope,
+    pub length_counter: LengthCounter,
+
+    sweep_enabled: bool,
+    sweep_period: u8,
+    sweep_negate: bool,
+    sweep_shift: u8,
+    sweep_divider: u8,
+    sweep_reload: bool,
+}
+
+impl Pulse {
+    pub fn new(ones_complement_sweep: bool) -> Self {
+        Pulse {
+            ones_complement_sweep,
+            has_sweep: true,
+            duty: 0,
+            sequence_step: 0,
+            timer_period: 0,
+            timer: 0,
+            envelope: Envelope::new(),
+            length_counter: LengthCounter::new(),
+            sweep_enabled: false,
+            sweep_period: 0,
+            sweep_negate: false,
+            sweep_shift: 0,
+            sweep_divider: 0,
+            sweep_reload: false,
+        }
+    }
+
+    pub fn without_sweep() -> Self {
+        Pulse {
+            has_sweep: false,
+            ..Pulse::new(false)
+        }
+    }
+
+    pub fn write_control(&mut self, data: u8) {
+        self.duty = data >> 6;
+        self.length_counter.halted = data & 0b0010_0000 != 0;
+        self.envelope.write(data);
+    }
+
+    pub fn write_sweep(&mut self, data: u8) {
+        self.sweep_enabled = data & 0b1000_0000 != 0;
+        self.sweep_period = (data >> 4) & 0b111;
+        self.sweep_negate = data & 0b1000 != 0;
+        self.sweep_shift = data & 0b111;
+        self.sweep_reload = true;
+    }
+
+    pub fn write_timer_low(&mut self, data: u8) {
+        self.timer_period = (self.timer_period & 0xFF00) | data as u16;
+    }
+
+    pub fn write_timer_high(&mut self, data: u8) {
+        self.timer_period = (self.timer_period & 0x00FF) | ((data as u16 & 0b111) << 8);
+        self.length_counter.load(data >> 3);
+        self.sequence_step = 0;
+        self.envelope.restart();
+    }
+
+    // clocked every other CPU cycle
+    pub fn clock_timer(&mut self) {
+        if self.timer == 0 {
+            self.timer = self.timer_period;
+            self.sequence_step = (self.sequence_step + 1) % 8;
+        } else {
+            self.timer -= 1;
+        }
+    }
+
+    pub fn clock_sweep(&mut self) {
+        let target = self.sweep_target();
+        if self.sweep_divider == 0 && self.sweep_enabled && self.sweep_shift > 0 && !self.is_muted(target) {
+            self.timer_period = target as u16;
+        }
+        if self.sweep_divider == 0 || self.sweep_reload {
+            self.sweep_divider = self.sweep_period;
+            self.sweep_reload = false;
+        } else {
+            self.sweep_divider -= 1;
+        }
+    }
+
+    fn sweep_target(&self) -> i32 {
+        let change = (self.timer_period >> self.sweep_shift) as i32;
+        if !self.sweep_negate {
+            self.timer_period as i32 + change
+        } else if self.ones_complement_sweep {
+            self.timer_period as i32 - change - 1
+        } else {
+            self.timer_period as i32 - change
+        }
+    }
+
+    fn is_muted(&self, target: i32) -> bool {
+        self.has_sweep && (self.timer_period < 8 || target > 0x7FF)
+    }
+
+    pub fn output(&self) -> u8 {
+        if !self.length_counter.is_active()
+            || self.is_muted(self.sweep_target())
+            || DUTY_TABLE[self.duty as usize][self.sequence_step as usize] == 0
+        {
+            return 0;
+        }
+        self.envelope.volume()
+    }
+}
+
+impl SaveState for Pulse {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.duty);
+        state.write_u8(self.sequence_step);
+        state.write_u16(self.timer_period);
+        state.write_u16(self.timer);
+        self.envelope.save_state(state);
+        self.length_counter.save_state(state);
+        state.write_bool(self.sweep_enabled);
+        state.write_u8(self.sweep_period);
+        state.write_bool(self.sweep_negate);
+        state.write_u8(self.sweep_shift);
+        state.write_u8(self.sweep_divider);
+        state.write_bool(self.sweep_reload);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.duty = state.read_u8()?;
+        self.sequence_step = state.read_u8()?;
+        self.timer_period = state.read_u16()?;
+        self.timer = state.read_u16()?;
+        self.envelope.load_state(state)?;
+        self.length_counter.load_state(state)?;
+        self.sweep_enabled = state.read_bool()?;
+        self.sweep_period = state.read_u8()?;
+        self.sweep_negate = state.read_bool()?;
+        self.sweep_shift = state.read_u8()?;
+        self.sweep_divider = state.read_u8()?;
+        self.sweep_reload = state.read_bool()?;
+        Ok(())
+    }
+}
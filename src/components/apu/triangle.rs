@@ -0,0 +1,100 @@
+use crate::components::apu::envelope::LengthCounter;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/APU_Triangle
+const SEQUENCE: [u8; 32] = [
+    15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+];
+
+pub struct Triangle {
+    control: bool,
+    linear_reload_value: u8,
+    linear_counter: u8,
+    linear_reload: bool,
+    timer_period: u16,
+    timer: u16,
+    sequence_step: u8,
+    pub length_counter: LengthCounter,
+}
+
+impl Triangle {
+    pub fn new() -> Self {
+        Triangle {
+            control: false,
+            linear_reload_value: 0,
+            linear_counter: 0,
+            linear_reload: false,
+            timer_period: 0,
+            timer: 0,
+            sequence_step: 0,
+            length_counter: LengthCounter::new(),
+        }
+    }
+
+    pub fn write_linear_counter(&mut self, data: u8) {
+        self.control = data & 0b1000_0000 != 0;
+        self.length_counter.halted = self.control;
+        self.linear_reload_value = data & 0b0111_1111;
+    }
+
+    pub fn write_timer_low(&mut self, data: u8) {
+        self.timer_period = (self.timer_period & 0xFF00) | data as u16;
+    }
+
+    pub fn write_timer_high(&mut self, data: u8) {
+        self.timer_period = (self.timer_period & 0x00FF) | ((data as u16 & 0b111) << 8);
+        self.length_counter.load(data >> 3);
+        self.linear_reload = true;
+    }
+
+    // clocked every CPU cycle
+    pub fn clock_timer(&mut self) {
+        if self.timer == 0 {
+            self.timer = self.timer_period;
+            if self.length_counter.is_active() && self.linear_counter > 0 {
+                self.sequence_step = (self.sequence_step + 1) % 32;
+            }
+        } else {
+            self.timer -= 1;
+        }
+    }
+
+    pub fn clock_linear_counter(&mut self) {
+        if self.linear_reload {
+            self.linear_counter = self.linear_reload_value;
+        } else if self.linear_counter > 0 {
+            self.linear_counter -= 1;
+        }
+        if !self.control {
+            self.linear_reload = false;
+        }
+    }
+
+    pub fn output(&self) -> u8 {
+        SEQUENCE[self.sequence_step as usize]
+    }
+}
+
+impl SaveState for Triangle {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bool(self.control);
+        state.write_u8(self.linear_reload_value);
+        state.write_u8(self.linear_counter);
+        state.write_bool(self.linear_reload);
+        state.write_u16(self.timer_period);
+        state.write_u16(self.timer);
+        state.write_u8(self.sequence_step);
+        self.length_counter.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.control = state.read_bool()?;
+        self.linear_reload_value = state.read_u8()?;
+        self.linear_counter = state.read_u8()?;
+        self.linear_reload = state.read_bool()?;
+        self.timer_period = state.read_u16()?;
+        self.timer = state.read_u16()?;
+        self.sequence_step = state.read_u8()?;
+        self.length_counter.load_state(state)
+    }
+}
@@ -0,0 +1,101 @@
+use crate::components::frame::Frame;
+use crate::components::input_device::InputDevice;
+use crate::components::ppu::NesPPU;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Arkanoid_controller
+pub struct ArkanoidPaddle {
+    famicom: bool,
+    position: u8,
+    button_pressed: bool,
+    strobe: bool,
+    shift_register: u8,
+}
+
+impl ArkanoidPaddle {
+    // the range the potentiometer reports from one end of its travel to the other
+    pub const MIN_POSITION: u8 = 0x62;
+    pub const MAX_POSITION: u8 = 0xF2;
+
+    // plugs into the second controller port, reports on D3 and D4
+    pub fn nes() -> Self {
+        ArkanoidPaddle::new(false)
+    }
+
+    // plugs into the expansion port, reports on D1 of both registers
+    pub fn famicom() -> Self {
+        ArkanoidPaddle::new(true)
+    }
+
+    fn new(famicom: bool) -> Self {
+        ArkanoidPaddle {
+            famicom,
+            position: ArkanoidPaddle::MIN_POSITION,
+            button_pressed: false,
+            strobe: false,
+            shift_register: 0,
+        }
+    }
+
+    pub fn set_position(&mut self, position: u8) {
+        self.position = position.clamp(ArkanoidPaddle::MIN_POSITION, ArkanoidPaddle::MAX_POSITION);
+    }
+
+    // Maps a horizontal screen coordinate onto the knob travel
+    pub fn set_position_from_screen(&mut self, x: usize) {
+        let range = (ArkanoidPaddle::MAX_POSITION - ArkanoidPaddle::MIN_POSITION) as usize;
+        let x = x.min(Frame::WIDTH - 1);
+        self.position = ArkanoidPaddle::MIN_POSITION + (x * range / (Frame::WIDTH - 1)) as u8;
+    }
+
+    pub fn position(&self) -> u8 {
+        self.position
+    }
+
+    pub fn set_button_pressed(&mut self, pressed: bool) {
+        self.button_pressed = pressed;
+    }
+
+    // the position goes out most significant bit first and inverted
+    fn read_serial(&mut self) -> u8 {
+        let bit = (self.shift_register >> 7) & 1;
+        if !self.strobe {
+            self.shift_register <<= 1;
+        }
+        !bit & 1
+    }
+}
+
+impl SaveState for ArkanoidPaddle {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bool(self.strobe);
+        state.write_u8(self.shift_register);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.strobe = state.read_bool()?;
+        self.shift_register = state.read_u8()?;
+        Ok(())
+    }
+}
+
+impl InputDevice for ArkanoidPaddle {
+    fn write_port(&mut self, data: u8) {
+        self.strobe = data & 1 == 1;
+        if self.strobe {
+            self.shift_register = self.position;
+        }
+    }
+
+    fn read_port(&mut self, port: usize, _ppu: &NesPPU) -> u8 {
+        let button = self.button_pressed as u8;
+        if !self.famicom {
+            return (button << 3) | (self.read_serial() << 4);
+        }
+
+        match port {
+            0 => button << 1,
+            _ => self.read_serial() << 1,
+        }
+    }
+}
@@ -1,31 +1,205 @@
+use std::rc::Rc;
+
+use crate::components::apu::Apu;
 use crate::components::cartridge::Rom;
+use crate::components::input_device::{InputPort, SharedInputDevice};
+use crate::components::mapper;
+use crate::components::mapper::SharedMapper;
 use crate::components::mem::Mem;
 use crate::components::ppu::NesPPU;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+// What the 2KB of work RAM holds after power on. Real consoles come up with something close to a
+// pattern that differs from one console to the next, games shouldn't rely on it but some do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RamInit {
+    Zeros,
+    Ones,
+    // four $00 bytes followed by four $FF, what FCEUX fills RAM with and its movies expect
+    Alternating,
+    // pseudo-random bytes from the seed, the same seed gives the same RAM
+    Random(u64),
+}
+
+impl RamInit {
+    pub fn fill(&self, ram: &mut [u8]) {
+        let mut seed = match self {
+            RamInit::Random(seed) => *seed | 1,
+            _ => 0,
+        };
+        for (addr, value) in ram.iter_mut().enumerate() {
+            *value = match self {
+                RamInit::Zeros => 0x00,
+                RamInit::Ones => 0xFF,
+                RamInit::Alternating if addr & 4 != 0 => 0xFF,
+                RamInit::Alternating => 0x00,
+                RamInit::Random(_) => {
+                    // xorshift64
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    seed as u8
+                }
+            };
+        }
+    }
+}
 
 pub struct Bus {
     cpu_vram: [u8; 2048],
-    prg_rom: Vec<u8>,
+    cartridge: SharedMapper,
     ppu: NesPPU,
+    apu: Apu,
+    ports: [Option<SharedInputDevice>; 2],
+    expansion_port: Option<SharedInputDevice>,
+    cycles: usize,
+    new_frame: bool,
+    ram_init: RamInit,
 }
 
 impl Bus {
-    pub fn new(rom: Rom) -> Self {
-        let ppu = NesPPU::new(rom.chr_rom, rom.screen_mirroring);
+    pub fn new(rom: Rom) -> Result<Self, String> {
+        Ok(Bus::with_mapper(mapper::for_rom(rom)?))
+    }
+
+    pub fn with_mapper(cartridge: SharedMapper) -> Self {
+        let ppu = NesPPU::new(cartridge.clone());
 
         Bus {
             cpu_vram: [0; 2048],
-            prg_rom: rom.prg_rom,
+            cartridge,
             ppu: ppu,
+            apu: Apu::new(),
+            ports: [None, None],
+            expansion_port: None,
+            cycles: 0,
+            new_frame: false,
+            ram_init: RamInit::Zeros,
         }
     }
 
-    fn read_prg_rom(&self, mut addr: u16) -> u8 {
-        addr -= 0x8000;
-        if self.prg_rom.len() == 0x4000 && addr >= 0x4000 {
-            //mirror if needed
-            addr = addr % 0x4000;
+    // Takes effect on the next power on
+    pub fn set_ram_init(&mut self, ram_init: RamInit) {
+        self.ram_init = ram_init;
+    }
+
+    pub fn power_on(&mut self) {
+        self.ram_init.fill(&mut self.cpu_vram);
+        self.ppu.power_on();
+        self.apu.power_on();
+        self.cartridge.borrow_mut().power_on();
+    }
+
+    // The reset line only goes to the CPU and the APU, the PPU keeps running like on the Famicom
+    // and the top-loading NES
+    pub fn reset(&mut self) {
+        self.apu.reset();
+        self.cartridge.borrow_mut().reset();
+    }
+
+    pub fn tick(&mut self, cycles: u8) {
+        self.cycles += cycles as usize;
+        if self.ppu.tick(cycles as usize * 3) {
+            self.new_frame = true;
         }
-        self.prg_rom[addr as usize]
+        // one cycle at a time, so the APU mixes in the expansion audio as it is on every cycle
+        for _ in 0..cycles {
+            self.cartridge.borrow_mut().tick(1);
+            self.apu.tick(1, &self.cartridge);
+        }
+    }
+
+    pub fn cycles(&self) -> usize {
+        self.cycles
+    }
+
+    // Reads for debuggers and traces, which must not disturb the console. The PPU, APU and
+    // controller registers react to reads, so those show open bus instead: the high byte of
+    // the address, the last thing an absolute read put on the bus. The cartridge is peeked too.
+    pub fn peek(&self, addr: u16) -> u8 {
+        match addr {
+            RAM..=RAM_MIRRORS_END => self.cpu_vram[(addr & 0b00000111_11111111) as usize],
+            0x2000..=PPU_REGISTERS_MIRRORS_END => self.ppu.open_bus(),
+            CARTRIDGE_SPACE_START..=CARTRIDGE_SPACE_END => self.cartridge.borrow().peek_prg(addr),
+            _ => (addr >> 8) as u8,
+        }
+    }
+
+    pub fn poll_irq_status(&self) -> bool {
+        self.cartridge.borrow().irq_pending() || self.apu.irq_pending()
+    }
+
+    // True once after the PPU wrapped around to a new frame
+    pub fn poll_new_frame(&mut self) -> bool {
+        let new_frame = self.new_frame;
+        self.new_frame = false;
+        new_frame
+    }
+
+    pub fn poll_nmi_status(&mut self) -> bool {
+        self.ppu.poll_nmi_interrupt()
+    }
+
+    pub fn ppu(&self) -> &NesPPU {
+        &self.ppu
+    }
+
+    pub fn apu(&mut self) -> &mut Apu {
+        &mut self.apu
+    }
+
+    // A device spanning both ports, like the Four Score, is connected to each of them
+    pub fn connect_input_device(&mut self, port: InputPort, device: SharedInputDevice) {
+        match port {
+            InputPort::One => self.ports[0] = Some(device),
+            InputPort::Two => self.ports[1] = Some(device),
+            InputPort::Expansion => self.expansion_port = Some(device),
+        }
+    }
+
+    pub fn disconnect_input_device(&mut self, port: InputPort) {
+        match port {
+            InputPort::One => self.ports[0] = None,
+            InputPort::Two => self.ports[1] = None,
+            InputPort::Expansion => self.expansion_port = None,
+        }
+    }
+
+    fn write_input_devices(&mut self, data: u8) {
+        let mut written: Vec<&SharedInputDevice> = vec![];
+        for device in self.ports.iter().chain(std::iter::once(&self.expansion_port)).flatten() {
+            if !written.iter().any(|other| Rc::ptr_eq(other, device)) {
+                device.borrow_mut().write_port(data);
+                written.push(device);
+            }
+        }
+    }
+
+    // Nothing plugged in leaves the data lines low
+    fn read_input_devices(&mut self, port: usize) -> u8 {
+        let mut data = CONTROLLER_OPEN_BUS;
+        if let Some(device) = &self.ports[port] {
+            data |= device.borrow_mut().read_port(port, &self.ppu);
+        }
+        if let Some(device) = &self.expansion_port {
+            data |= device.borrow_mut().read_port(port, &self.ppu);
+        }
+        data
+    }
+
+    //https://www.nesdev.org/wiki/PPU_registers#OAMDMA
+    fn oam_dma(&mut self, page: u8) {
+        let mut data = [0u8; 256];
+        let start = (page as u16) << 8;
+        for (i, value) in data.iter_mut().enumerate() {
+            *value = self.mem_read(start + i as u16);
+        }
+        self.ppu.write_oam_dma(&data);
+
+        // the CPU is halted for 513 cycles while the copy happens
+        self.tick(255);
+        self.tick(255);
+        self.tick(3);
     }
 }
 
@@ -33,8 +207,10 @@ const RAM: u16 = 0x0000;
 const RAM_MIRRORS_END: u16 = 0x1FFF;
 const PPU_REGISTERS: u16 = 0x2008;
 const PPU_REGISTERS_MIRRORS_END: u16 = 0x3FFF;
-const ROM_SPACE_START: u16 = 0x8000;
-const ROM_SPACE_END: u16 = 0xFFFF;
+// only the low bits are driven by the controller ports, the rest keep the $40 of the address
+const CONTROLLER_OPEN_BUS: u8 = 0x40;
+const CARTRIDGE_SPACE_START: u16 = 0x4020;
+const CARTRIDGE_SPACE_END: u16 = 0xFFFF;
 
 impl Mem for Bus {
     fn mem_read(&mut self, addr: u16) -> u8 {
@@ -43,25 +219,30 @@ impl Mem for Bus {
                 let mirror_down_addr = addr & 0b00000111_11111111;
                 self.cpu_vram[mirror_down_addr as usize]
             }
-            0x2000 | 0x2001 | 0x2003 | 0x2005 | 0x2006 | 0x4014 => {
-                panic!("Attempt to read from write-only PPU address {:x}", addr);
-            }
+            0x2000 | 0x2001 | 0x2003 | 0x2005 | 0x2006 => self.ppu.open_bus(),
+
+            0x2002 => self.ppu.read_status(),
 
-            //0x2002 => self.ppu.read_status(),
+            0x2004 => self.ppu.read_oam_data(),
 
             0x2007 => self.ppu.read_data(),
 
+            0x4015 => self.apu.read_status(),
+
+            0x4016 => self.read_input_devices(0),
+
+            0x4017 => self.read_input_devices(1),
+
             PPU_REGISTERS..=PPU_REGISTERS_MIRRORS_END => {
                 let mirror_down_addr = addr & 0b00100000_00000111;
                 self.mem_read(mirror_down_addr)
             }
-            ROM_SPACE_START..=ROM_SPACE_END => {
-                self.read_prg_rom(addr)
-            }
-            _ => {
-                // println!("Ignoring mem access at {}", addr);
-                panic!("not supported for now");
+            CARTRIDGE_SPACE_START..=CARTRIDGE_SPACE_END => {
+                self.cartridge.borrow_mut().read_prg(addr)
             }
+            // the APU registers other than $4015, OAMDMA and the disabled test registers
+            // at $4018-$401F are write-only, reads see the high byte of the address
+            _ => (addr >> 8) as u8,
         }
     }
 
@@ -80,6 +261,18 @@ impl Mem for Bus {
                 self.ppu.write_to_mask_register(data);
             }
 
+            0x2002 => {
+                // PPUSTATUS is read only
+            }
+
+            0x2003 => {
+                self.ppu.write_to_oam_addr(data);
+            }
+
+            0x2004 => {
+                self.ppu.write_to_oam_data(data);
+            }
+
             0x2005 => {
                 self.ppu.write_to_scroll_register(data);
             }
@@ -92,17 +285,75 @@ impl Mem for Bus {
                 self.ppu.write_to_data(data);
             }
 
+            0x4000..=0x4013 | 0x4015 | 0x4017 => {
+                self.apu.write_register(addr, data);
+            }
+
+            0x4014 => {
+                self.oam_dma(data);
+            }
+
+            0x4016 => {
+                self.write_input_devices(data);
+            }
+
             PPU_REGISTERS..=PPU_REGISTERS_MIRRORS_END => {
                 let mirror_down_addr = addr & 0b00100000_00000111;
                 self.mem_write(mirror_down_addr, data);
             }
-            ROM_SPACE_START..=ROM_SPACE_END => {
-                panic!("Attempt to write to Cartridge ROM space")
+            CARTRIDGE_SPACE_START..=CARTRIDGE_SPACE_END => {
+                self.cartridge.borrow_mut().write_prg(addr, data);
             }
-            _ => {
-                // println!("Ignoring mem write-access at {}", addr);
-                panic!("not supported for now");
+            // the CPU test registers at $4018-$401F are disabled on retail consoles
+            _ => {}
+        }
+    }
+}
+
+// The devices in both controller ports and the expansion port save what they latched, like a joypad's
+// strobe and read position. Their buttons are left out, the host sets those every frame.
+impl SaveState for Bus {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.cpu_vram);
+        state.write_usize(self.cycles);
+        state.write_bool(self.new_frame);
+        // each port on its own, so a state only loads with the same kind of devices plugged in
+        for port in self.ports.iter().chain(std::iter::once(&self.expansion_port)) {
+            let mut device_state = StateWriter::new();
+            if let Some(device) = port {
+                device.borrow().save_state(&mut device_state);
             }
+            state.write_bool(port.is_some());
+            state.write_bytes(&device_state.into_bytes());
         }
+        self.ppu.save_state(state);
+        self.apu.save_state(state);
+        self.cartridge.borrow().save_state(state);
     }
-}
\ No newline at end of file
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.cpu_vram)?;
+        self.cycles = state.read_usize()?;
+        self.new_frame = state.read_bool()?;
+        for port in self.ports.iter().chain(std::iter::once(&self.expansion_port)) {
+            let connected = state.read_bool()?;
+            let data = state.read_vec()?;
+            let mut device_state = StateReader::new(&data);
+            let matches = match port {
+                Some(device) if connected => device
+                    .borrow_mut()
+                    .load_state(&mut device_state)
+                    .and_then(|_| device_state.finish())
+                    .is_ok(),
+                None => !connected,
+                _ => false,
+            };
+            if !matches {
+                return Err("Save state was made with other input devices connected".to_string());
+            }
+        }
+        self.ppu.load_state(state)?;
+        self.apu.load_state(state)?;
+        self.cartridge.borrow_mut().load_state(state)
+    }
+}
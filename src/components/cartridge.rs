@@ -1,9 +1,36 @@
-﻿#[derive(Debug, PartialEq)]
+﻿use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+pub mod unif;
+pub mod fds;
+pub mod ips;
+pub mod nsf;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 #[allow(non_camel_case_types)]
 pub enum Mirroring {
     VERTICAL,
     HORIZONTAL,
     FOUR_SCREEN,
+    SINGLE_SCREEN_LOWER,
+    SINGLE_SCREEN_UPPER,
+}
+
+impl SaveState for Mirroring {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(*self as u8);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        *self = match state.read_u8()? {
+            0 => Mirroring::VERTICAL,
+            1 => Mirroring::HORIZONTAL,
+            2 => Mirroring::FOUR_SCREEN,
+            3 => Mirroring::SINGLE_SCREEN_LOWER,
+            4 => Mirroring::SINGLE_SCREEN_UPPER,
+            value => return Err(format!("Invalid mirroring {} in save state", value)),
+        };
+        Ok(())
+    }
 }
 
 pub struct Rom {
@@ -11,6 +38,10 @@ pub struct Rom {
     pub chr_rom: Vec<u8>,
     pub mapper: u8,
     pub screen_mirroring: Mirroring,
+    pub battery: bool,
+    // NES 2.0 only, zero for everything else
+    pub submapper: u8,
+    pub default_expansion_device: u8,
 }
 
 impl Rom {
@@ -18,16 +49,29 @@ impl Rom {
     pub const PRG_ROM_PAGE_SIZE: usize = 16384;
     pub const CHR_ROM_PAGE_SIZE: usize = 8192;
 
+    // Picks the parser based on the container magic, so callers don't have to care
+    // whether the game came as an iNES or a UNIF dump
+    pub fn load(raw: &[u8]) -> Result<Rom, String> {
+        if raw.starts_with(&unif::UNIF_TAG) {
+            return unif::parse(raw);
+        }
+        Rom::new(&raw.to_vec())
+    }
+
     pub fn new(raw: &Vec<u8>) -> Result<Rom, String> {
+        if raw.len() < 16 {
+            return Err("File is too short to hold an iNES header".to_string());
+        }
         if &raw[0..4] != Rom::NES_TAG {
             return Err("File is not in iNES file format".to_string());
         }
 
         let mapper = (raw[7] & 0b1111_0000) | (raw[6] >> 4);
 
-        let ines_ver = (raw[7] >> 2) & 0b11;
-        if ines_ver != 0 {
-            return Err("NES2.0 format is not supported".to_string());
+        //https://www.nesdev.org/wiki/NES_2.0
+        let nes2 = (raw[7] >> 2) & 0b11 == 0b10;
+        if nes2 && raw[8] & 0b1111 != 0 {
+            return Err(format!("NES2.0 mapper {} is not supported", (raw[8] as u16 & 0b1111) << 8 | mapper as u16));
         }
 
         let four_screen = raw[6] & 0b1000 != 0;
@@ -38,19 +82,43 @@ impl Rom {
             (false, false) => Mirroring::HORIZONTAL,
         };
 
-        let prg_rom_size = raw[4] as usize * Rom::PRG_ROM_PAGE_SIZE;
-        let chr_rom_size = raw[5] as usize * Rom::CHR_ROM_PAGE_SIZE;
+        let (prg_rom_size, chr_rom_size) = if nes2 {
+            (
+                Rom::nes2_rom_size(raw[4], raw[9] & 0b1111, Rom::PRG_ROM_PAGE_SIZE),
+                Rom::nes2_rom_size(raw[5], raw[9] >> 4, Rom::CHR_ROM_PAGE_SIZE),
+            )
+        } else {
+            (raw[4] as usize * Rom::PRG_ROM_PAGE_SIZE, raw[5] as usize * Rom::CHR_ROM_PAGE_SIZE)
+        };
 
+        let battery = raw[6] & 0b10 != 0;
         let skip_trainer = raw[6] & 0b100 != 0;
 
-        let prg_rom_start = 16 + if skip_trainer { 512 } else { 0 };
-        let chr_rom_start = prg_rom_start + prg_rom_size;
+        let prg_rom_start: usize = 16 + if skip_trainer { 512 } else { 0 };
+        let chr_rom_start = prg_rom_start.saturating_add(prg_rom_size);
+        if raw.len() < chr_rom_start.saturating_add(chr_rom_size) {
+            return Err("ROM file is smaller than its header says".to_string());
+        }
 
         Ok(Rom {
             prg_rom: raw[prg_rom_start..(prg_rom_start + prg_rom_size)].to_vec(),
             chr_rom: raw[chr_rom_start..(chr_rom_start + chr_rom_size)].to_vec(),
             mapper: mapper,
             screen_mirroring: screen_mirroring,
+            battery,
+            submapper: if nes2 { raw[8] >> 4 } else { 0 },
+            default_expansion_device: if nes2 { raw[15] & 0b11_1111 } else { 0 },
         })
     }
+
+    // The size MSB nibble set to $F switches the LSB byte to an exponent-multiplier notation
+    fn nes2_rom_size(lsb: u8, msb: u8, page_size: usize) -> usize {
+        if msb == 0b1111 {
+            let exponent = (lsb >> 2) as u32;
+            let multiplier = (lsb & 0b11) as usize * 2 + 1;
+            2usize.checked_pow(exponent).and_then(|size| size.checked_mul(multiplier)).unwrap_or(usize::MAX)
+        } else {
+            ((msb as usize) << 8 | lsb as usize) * page_size
+        }
+    }
 }
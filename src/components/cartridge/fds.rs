@@ -0,0 +1,163 @@
+use crate::components::cartridge::ips;
+
+//https://www.nesdev.org/wiki/FDS_file_format
+pub const FDS_TAG: [u8; 4] = [0x46, 0x44, 0x53, 0x1A];
+pub const HEADER_SIZE: usize = 16;
+pub const SIDE_SIZE: usize = 65500;
+
+//https://www.nesdev.org/wiki/FDS_disk_format
+const LEADING_GAP_SIZE: usize = 28300 / 8;
+const BLOCK_GAP_SIZE: usize = 976 / 8;
+const GAP_END_MARK: u8 = 0x80;
+const CRC_SIZE: usize = 2;
+
+const DISK_INFO_BLOCK: u8 = 1;
+const FILE_AMOUNT_BLOCK: u8 = 2;
+const FILE_HEADER_BLOCK: u8 = 3;
+const FILE_DATA_BLOCK: u8 = 4;
+
+pub struct FdsImage {
+    pub sides: Vec<Vec<u8>>,
+    has_header: bool,
+}
+
+impl FdsImage {
+    pub fn new(raw: &[u8]) -> Result<FdsImage, String> {
+        let has_header = raw.starts_with(&FDS_TAG);
+        let data = if has_header { &raw[HEADER_SIZE.min(raw.len())..] } else { raw };
+
+        if data.is_empty() || data.len() % SIDE_SIZE != 0 {
+            return Err("File is not in FDS file format".to_string());
+        }
+
+        Ok(FdsImage {
+            sides: data.chunks(SIDE_SIZE).map(|side| side.to_vec()).collect(),
+            has_header,
+        })
+    }
+
+    // Loads an image together with the writes saved from an earlier session
+    pub fn with_changes(raw: &[u8], changes: &[u8]) -> Result<FdsImage, String> {
+        let image = FdsImage::new(raw)?;
+        FdsImage::new(&ips::apply(&image.to_bytes(), changes)?)
+    }
+
+    pub fn with_sides(&self, sides: Vec<Vec<u8>>) -> FdsImage {
+        FdsImage {
+            sides,
+            has_header: self.has_header,
+        }
+    }
+
+    pub fn side_count(&self) -> usize {
+        self.sides.len()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = vec![];
+        if self.has_header {
+            result.extend(FDS_TAG);
+            result.push(self.sides.len() as u8);
+            result.extend([0; HEADER_SIZE - 5]);
+        }
+        for side in &self.sides {
+            result.extend(side);
+        }
+        result
+    }
+
+    pub fn changes_since(&self, original: &FdsImage) -> Result<Vec<u8>, String> {
+        ips::create(&original.to_bytes(), &self.to_bytes())
+    }
+}
+
+// .fds files only hold the block contents, the drive also sees the gaps and CRCs between them
+pub fn add_gaps(side: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; LEADING_GAP_SIZE];
+    let mut pos = 0;
+    let mut file_size = 0;
+
+    while let Some(length) = block_length(side, pos, file_size) {
+        if side[pos] == FILE_HEADER_BLOCK {
+            file_size = u16::from_le_bytes([side[pos + 13], side[pos + 14]]) as usize;
+        }
+
+        result.push(GAP_END_MARK);
+        result.extend(&side[pos..pos + length]);
+        result.extend(crc(&side[pos..pos + length]).to_le_bytes());
+        result.extend([0; BLOCK_GAP_SIZE]);
+        pos += length;
+    }
+
+    // what's left of the side stays free for the BIOS to write new files to
+    result.resize(result.len() + SIDE_SIZE - pos, 0);
+    result
+}
+
+pub fn remove_gaps(disk: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(SIDE_SIZE);
+    let mut pos = 0;
+    let mut file_size = 0;
+
+    loop {
+        while pos < disk.len() && disk[pos] == 0 {
+            pos += 1;
+        }
+        if pos >= disk.len() || disk[pos] != GAP_END_MARK {
+            break;
+        }
+        pos += 1;
+
+        let length = match block_length(disk, pos, file_size) {
+            Some(length) if result.len() + length <= SIDE_SIZE => length,
+            _ => break,
+        };
+        if disk[pos] == FILE_HEADER_BLOCK {
+            file_size = u16::from_le_bytes([disk[pos + 13], disk[pos + 14]]) as usize;
+        }
+
+        result.extend(&disk[pos..pos + length]);
+        pos += length + CRC_SIZE;
+    }
+
+    result.resize(SIDE_SIZE, 0);
+    result
+}
+
+fn block_length(data: &[u8], pos: usize, file_size: usize) -> Option<usize> {
+    let length = match data.get(pos) {
+        Some(&DISK_INFO_BLOCK) => 56,
+        Some(&FILE_AMOUNT_BLOCK) => 2,
+        Some(&FILE_HEADER_BLOCK) => 16,
+        Some(&FILE_DATA_BLOCK) => 1 + file_size,
+        _ => return None,
+    };
+
+    if pos + length > data.len() {
+        return None;
+    }
+    Some(length)
+}
+
+// CRC-16 the drive appends to each block, the gap end mark is part of the checksum
+pub fn crc(block: &[u8]) -> u16 {
+    let mut crc = 0;
+    for value in [GAP_END_MARK].iter().chain(block).chain(&[0, 0]) {
+        crc = update_crc(crc, *value);
+    }
+    crc
+}
+
+pub fn update_crc(mut crc: u16, value: u8) -> u16 {
+    for bit in 0..8 {
+        let carry = crc & 1;
+        crc >>= 1;
+        if carry != 0 {
+            crc ^= 0x8408;
+        }
+        if value & (1 << bit) != 0 {
+            crc ^= 0x8000;
+        }
+    }
+    crc
+}
@@ -0,0 +1,81 @@
+//https://zerosoft.zophar.net/ips.php
+const PATCH_TAG: &[u8; 5] = b"PATCH";
+const EOF_TAG: &[u8; 3] = b"EOF";
+const EOF_OFFSET: usize = 0x454F46;
+const MAX_OFFSET: usize = 0xFFFFFF;
+const MAX_RECORD_SIZE: usize = 0xFFFF;
+
+pub fn apply(original: &[u8], patch: &[u8]) -> Result<Vec<u8>, String> {
+    if !patch.starts_with(PATCH_TAG) {
+        return Err("File is not an IPS patch".to_string());
+    }
+
+    let mut result = original.to_vec();
+    let mut pos = PATCH_TAG.len();
+
+    loop {
+        let record = patch.get(pos..pos + 3).ok_or("IPS patch is truncated".to_string())?;
+        if record == EOF_TAG {
+            return Ok(result);
+        }
+
+        let offset = (record[0] as usize) << 16 | (record[1] as usize) << 8 | record[2] as usize;
+        let size = read_u16(patch, pos + 3)? as usize;
+        pos += 5;
+
+        let data = if size == 0 {
+            // RLE record: a 16 bit run length followed by the byte to repeat
+            let run_length = read_u16(patch, pos)? as usize;
+            let value = *patch.get(pos + 2).ok_or("IPS patch is truncated".to_string())?;
+            pos += 3;
+            vec![value; run_length]
+        } else {
+            let data = patch.get(pos..pos + size).ok_or("IPS patch is truncated".to_string())?;
+            pos += size;
+            data.to_vec()
+        };
+
+        if result.len() < offset + data.len() {
+            result.resize(offset + data.len(), 0);
+        }
+        result[offset..offset + data.len()].copy_from_slice(&data);
+    }
+}
+
+pub fn create(original: &[u8], modified: &[u8]) -> Result<Vec<u8>, String> {
+    if modified.len() > MAX_OFFSET {
+        return Err("File is too large to be described by an IPS patch".to_string());
+    }
+
+    let mut patch = PATCH_TAG.to_vec();
+    let mut pos = 0;
+
+    while pos < modified.len() {
+        if original.get(pos) == Some(&modified[pos]) {
+            pos += 1;
+            continue;
+        }
+
+        // an offset spelling "EOF" would be read back as the end of the patch
+        let start = if pos == EOF_OFFSET { pos - 1 } else { pos };
+        let mut end = pos;
+        while end < modified.len() && end - start < MAX_RECORD_SIZE && original.get(end) != Some(&modified[end]) {
+            end += 1;
+        }
+
+        patch.extend(&(start as u32).to_be_bytes()[1..]);
+        patch.extend(((end - start) as u16).to_be_bytes());
+        patch.extend(&modified[start..end]);
+        pos = end;
+    }
+
+    patch.extend(EOF_TAG);
+    Ok(patch)
+}
+
+fn read_u16(patch: &[u8], pos: usize) -> Result<u16, String> {
+    match patch.get(pos..pos + 2) {
+        Some(bytes) => Ok(u16::from_be_bytes([bytes[0], bytes[1]])),
+        None => Err("IPS patch is truncated".to_string()),
+    }
+}
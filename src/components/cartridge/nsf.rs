@@ -0,0 +1,224 @@
+//https://www.nesdev.org/wiki/NSF
+pub const NSF_TAG: [u8; 5] = [0x4E, 0x45, 0x53, 0x4D, 0x1A];
+//https://www.nesdev.org/wiki/NSFe
+pub const NSFE_TAG: [u8; 4] = [0x4E, 0x53, 0x46, 0x45];
+
+const NSF_HEADER_SIZE: usize = 0x80;
+const NTSC_DEFAULT_SPEED: u16 = 16639;
+const PAL_DEFAULT_SPEED: u16 = 19997;
+
+pub struct Nsf {
+    pub title: String,
+    pub artist: String,
+    pub copyright: String,
+    pub total_songs: u8,
+    // zero based, unlike the NSF header
+    pub starting_song: u8,
+    pub load_address: u16,
+    pub init_address: u16,
+    pub play_address: u16,
+    // play routine period in microseconds
+    pub ntsc_speed: u16,
+    pub pal_speed: u16,
+    pub pal: bool,
+    pub bank_switch: [u8; 8],
+    pub expansion_audio: u8,
+    pub track_labels: Vec<String>,
+    pub data: Vec<u8>,
+}
+
+impl Nsf {
+    pub fn new(raw: &[u8]) -> Result<Nsf, String> {
+        if raw.starts_with(&NSF_TAG) {
+            Nsf::parse_nsf(raw)
+        } else if raw.starts_with(&NSFE_TAG) {
+            Nsf::parse_nsfe(raw)
+        } else {
+            Err("File is not in NSF file format".to_string())
+        }
+    }
+
+    pub fn is_bank_switched(&self) -> bool {
+        self.bank_switch.iter().any(|bank| *bank != 0)
+    }
+
+    fn parse_nsf(raw: &[u8]) -> Result<Nsf, String> {
+        if raw.len() < NSF_HEADER_SIZE {
+            return Err("NSF header is truncated".to_string());
+        }
+
+        let total_songs = raw[0x06];
+        if total_songs == 0 {
+            return Err("NSF file has no songs".to_string());
+        }
+
+        let mut bank_switch = [0; 8];
+        bank_switch.copy_from_slice(&raw[0x70..0x78]);
+
+        // NSF2 may append metadata after the program data
+        let data_length = raw[0x7D] as usize | (raw[0x7E] as usize) << 8 | (raw[0x7F] as usize) << 16;
+        let data_end = if raw[0x05] >= 2 && data_length != 0 {
+            (NSF_HEADER_SIZE + data_length).min(raw.len())
+        } else {
+            raw.len()
+        };
+        // without data there would be no bank to switch in
+        if data_end == NSF_HEADER_SIZE && bank_switch.iter().any(|bank| *bank != 0) {
+            return Err("Bank switched NSF file has no program data".to_string());
+        }
+
+        Ok(Nsf {
+            title: read_string(&raw[0x0E..0x2E]),
+            artist: read_string(&raw[0x2E..0x4E]),
+            copyright: read_string(&raw[0x4E..0x6E]),
+            total_songs,
+            starting_song: starting_song(raw[0x07].saturating_sub(1), total_songs),
+            load_address: read_u16(raw, 0x08),
+            init_address: read_u16(raw, 0x0A),
+            play_address: read_u16(raw, 0x0C),
+            ntsc_speed: speed_or_default(read_u16(raw, 0x6E), NTSC_DEFAULT_SPEED),
+            pal_speed: speed_or_default(read_u16(raw, 0x78), PAL_DEFAULT_SPEED),
+            pal: raw[0x7A] & 0b11 == 0b01,
+            bank_switch,
+            expansion_audio: raw[0x7B],
+            track_labels: vec![],
+            data: raw[NSF_HEADER_SIZE..data_end].to_vec(),
+        })
+    }
+
+    fn parse_nsfe(raw: &[u8]) -> Result<Nsf, String> {
+        let mut nsf = Nsf {
+            title: String::new(),
+            artist: String::new(),
+            copyright: String::new(),
+            total_songs: 1,
+            starting_song: 0,
+            load_address: 0,
+            init_address: 0,
+            play_address: 0,
+            ntsc_speed: NTSC_DEFAULT_SPEED,
+            pal_speed: PAL_DEFAULT_SPEED,
+            pal: false,
+            bank_switch: [0; 8],
+            expansion_audio: 0,
+            track_labels: vec![],
+            data: vec![],
+        };
+        let mut has_info = false;
+        let mut has_data = false;
+
+        let mut pos = NSFE_TAG.len();
+        loop {
+            if pos + 8 > raw.len() {
+                return Err("NSFe file has no NEND chunk".to_string());
+            }
+
+            let length = u32::from_le_bytes([raw[pos], raw[pos + 1], raw[pos + 2], raw[pos + 3]]) as usize;
+            let id = &raw[pos + 4..pos + 8];
+            let data_start = pos + 8;
+            let data_end = data_start.saturating_add(length);
+            if data_end > raw.len() {
+                return Err(format!("NSFe chunk {} is truncated", String::from_utf8_lossy(id)));
+            }
+            let data = &raw[data_start..data_end];
+
+            match id {
+                b"INFO" => {
+                    if data.len() < 8 {
+                        return Err("NSFe chunk INFO is truncated".to_string());
+                    }
+                    nsf.load_address = read_u16(data, 0);
+                    nsf.init_address = read_u16(data, 2);
+                    nsf.play_address = read_u16(data, 4);
+                    nsf.pal = data[6] & 0b11 == 0b01;
+                    nsf.expansion_audio = data[7];
+                    nsf.total_songs = data.get(8).copied().unwrap_or(1);
+                    nsf.starting_song = data.get(9).copied().unwrap_or(0);
+                    has_info = true;
+                }
+                b"DATA" => {
+                    nsf.data = data.to_vec();
+                    has_data = true;
+                }
+                b"BANK" => {
+                    for (bank, value) in nsf.bank_switch.iter_mut().zip(data) {
+                        *bank = *value;
+                    }
+                }
+                b"RATE" => {
+                    if data.len() >= 2 {
+                        nsf.ntsc_speed = speed_or_default(read_u16(data, 0), NTSC_DEFAULT_SPEED);
+                    }
+                    if data.len() >= 4 {
+                        nsf.pal_speed = speed_or_default(read_u16(data, 2), PAL_DEFAULT_SPEED);
+                    }
+                }
+                b"auth" => {
+                    let mut strings = read_strings(data).into_iter();
+                    nsf.title = strings.next().unwrap_or_default();
+                    nsf.artist = strings.next().unwrap_or_default();
+                    nsf.copyright = strings.next().unwrap_or_default();
+                }
+                b"tlbl" => nsf.track_labels = read_strings(data),
+                b"NEND" => break,
+                // chunks starting with an upper case letter must be understood to play the file
+                _ if id[0].is_ascii_uppercase() => {
+                    return Err(format!("NSFe chunk {} is not supported", String::from_utf8_lossy(id)));
+                }
+                _ => {}
+            }
+
+            pos = data_end;
+        }
+
+        if !has_info || !has_data {
+            return Err("NSFe file needs both INFO and DATA chunks".to_string());
+        }
+        if nsf.total_songs == 0 {
+            return Err("NSF file has no songs".to_string());
+        }
+        nsf.starting_song = starting_song(nsf.starting_song, nsf.total_songs);
+        if nsf.data.is_empty() && nsf.is_bank_switched() {
+            return Err("Bank switched NSF file has no program data".to_string());
+        }
+
+        Ok(nsf)
+    }
+}
+
+// A play rate of 0 would call PLAY without pause, rippers leave it at 0 meaning the usual 60/50 Hz
+fn speed_or_default(speed: u16, default: u16) -> u16 {
+    if speed == 0 {
+        default
+    } else {
+        speed
+    }
+}
+
+// Songs past the last one start at the first
+fn starting_song(song: u8, total_songs: u8) -> u8 {
+    if song < total_songs {
+        song
+    } else {
+        0
+    }
+}
+
+fn read_u16(raw: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([raw[pos], raw[pos + 1]])
+}
+
+fn read_string(data: &[u8]) -> String {
+    let end = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).to_string()
+}
+
+fn read_strings(data: &[u8]) -> Vec<String> {
+    if data.is_empty() {
+        return vec![];
+    }
+    let data = data.strip_suffix(&[0]).unwrap_or(data);
+    data.split(|b| *b == 0)
+        .map(|string| String::from_utf8_lossy(string).to_string())
+        .collect()
+}
@@ -0,0 +1,189 @@
+use crate::components::cartridge::{Mirroring, Rom};
+
+//https://www.nesdev.org/wiki/UNIF
+pub const UNIF_TAG: [u8; 4] = [0x55, 0x4E, 0x49, 0x46];
+const HEADER_SIZE: usize = 32;
+const CHUNK_HEADER_SIZE: usize = 8;
+
+// Board names are stored without their "NES-", "UNL-" ... prefix
+const BOARD_PREFIXES: [&str; 5] = ["NES-", "HVC-", "UNL-", "BMC-", "BTL-"];
+
+const BOARDS: [(&str, u8); 80] = [
+    ("NROM", 0),
+    ("NROM-128", 0),
+    ("NROM-256", 0),
+    ("RROM", 0),
+    ("RROM-128", 0),
+    ("SAROM", 1),
+    ("SBROM", 1),
+    ("SCROM", 1),
+    ("SEROM", 1),
+    ("SFROM", 1),
+    ("SGROM", 1),
+    ("SHROM", 1),
+    ("SJROM", 1),
+    ("SKROM", 1),
+    ("SLROM", 1),
+    ("SL1ROM", 1),
+    ("SNROM", 1),
+    ("SOROM", 1),
+    ("SUROM", 1),
+    ("SXROM", 1),
+    ("UNROM", 2),
+    ("UOROM", 2),
+    ("CNROM", 3),
+    ("TBROM", 4),
+    ("TEROM", 4),
+    ("TFROM", 4),
+    ("TGROM", 4),
+    ("TKROM", 4),
+    ("TLROM", 4),
+    ("TL1ROM", 4),
+    ("TR1ROM", 4),
+    ("TSROM", 4),
+    ("TVROM", 4),
+    ("B4", 4),
+    ("ELROM", 5),
+    ("EKROM", 5),
+    ("ETROM", 5),
+    ("EWROM", 5),
+    ("AMROM", 7),
+    ("ANROM", 7),
+    ("AN1ROM", 7),
+    ("AOROM", 7),
+    ("PNROM", 9),
+    ("PEEOROM", 9),
+    ("FJROM", 10),
+    ("FKROM", 10),
+    ("COLORDREAMS-74*377", 11),
+    ("CPROM", 13),
+    ("SL1632", 14),
+    ("CC-21", 27),
+    ("TC0190FMC", 33),
+    ("BNROM", 34),
+    ("AVE-NINA-01", 34),
+    ("TC0190FMC+PAL16R4", 48),
+    ("D1038", 59),
+    ("GNROM", 66),
+    ("MHROM", 66),
+    ("JLROM", 69),
+    ("JSROM", 69),
+    ("BTR", 69),
+    ("BF9093", 71),
+    ("BF9097", 71),
+    ("TLSROM", 118),
+    ("TKSROM", 118),
+    ("TQROM", 119),
+    ("H2288", 123),
+    ("22211", 132),
+    ("SA-72008", 133),
+    ("Sachen-8259D", 137),
+    ("Sachen-8259B", 138),
+    ("Sachen-8259C", 139),
+    ("Sachen-8259A", 141),
+    ("SA-NROM", 143),
+    ("SA-72007", 145),
+    ("TC-U01-1.5M", 147),
+    ("Sachen-74LS374N", 150),
+    ("Super24in1SC03", 176),
+    ("FK23C", 176),
+    ("DEROM", 206),
+    ("8237", 215),
+];
+
+pub fn parse(raw: &[u8]) -> Result<Rom, String> {
+    if raw.len() < HEADER_SIZE || raw[0..4] != UNIF_TAG {
+        return Err("File is not in UNIF file format".to_string());
+    }
+
+    let mut board: Option<String> = None;
+    let mut prg_chunks: [Option<&[u8]>; 16] = [None; 16];
+    let mut chr_chunks: [Option<&[u8]>; 16] = [None; 16];
+    let mut mirroring = Mirroring::HORIZONTAL;
+    let mut battery = false;
+
+    let mut pos = HEADER_SIZE;
+    while pos < raw.len() {
+        if pos + CHUNK_HEADER_SIZE > raw.len() {
+            return Err("UNIF chunk header is truncated".to_string());
+        }
+
+        let id = &raw[pos..pos + 4];
+        let length = u32::from_le_bytes([raw[pos + 4], raw[pos + 5], raw[pos + 6], raw[pos + 7]]) as usize;
+        let data_start = pos + CHUNK_HEADER_SIZE;
+        let data_end = data_start.saturating_add(length);
+        if data_end > raw.len() {
+            return Err(format!("UNIF chunk {} is truncated", String::from_utf8_lossy(id)));
+        }
+        let data = &raw[data_start..data_end];
+
+        match id {
+            b"MAPR" => board = Some(read_string(data)),
+            b"MIRR" => mirroring = read_mirroring(data)?,
+            b"BATR" => battery = true,
+            [b'P', b'R', b'G', n] => prg_chunks[chunk_index(*n)?] = Some(data),
+            [b'C', b'H', b'R', n] => chr_chunks[chunk_index(*n)?] = Some(data),
+            // NAME, TVCI, DINF, CTRL, PCKx, CCKx ... carry nothing the emulator needs
+            _ => {}
+        }
+
+        pos = data_end;
+    }
+
+    let board = board.ok_or("UNIF file has no MAPR chunk".to_string())?;
+    let mapper = mapper_for_board(&board).ok_or(format!("UNIF board {} is not supported", board))?;
+
+    let prg_rom: Vec<u8> = prg_chunks.iter().flatten().flat_map(|chunk| chunk.iter().copied()).collect();
+    if prg_rom.is_empty() {
+        return Err("UNIF file has no PRG chunk".to_string());
+    }
+    let chr_rom: Vec<u8> = chr_chunks.iter().flatten().flat_map(|chunk| chunk.iter().copied()).collect();
+
+    Ok(Rom {
+        prg_rom,
+        chr_rom,
+        mapper,
+        screen_mirroring: mirroring,
+        battery,
+        submapper: 0,
+        default_expansion_device: 0,
+    })
+}
+
+pub fn mapper_for_board(board: &str) -> Option<u8> {
+    let name = BOARD_PREFIXES
+        .iter()
+        .find_map(|prefix| board.strip_prefix(prefix))
+        .unwrap_or(board);
+
+    BOARDS
+        .iter()
+        .find(|(board_name, _)| board_name.eq_ignore_ascii_case(name))
+        .map(|(_, mapper)| *mapper)
+}
+
+fn read_string(data: &[u8]) -> String {
+    let end = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).trim().to_string()
+}
+
+fn read_mirroring(data: &[u8]) -> Result<Mirroring, String> {
+    match data.first() {
+        Some(0) => Ok(Mirroring::HORIZONTAL),
+        Some(1) => Ok(Mirroring::VERTICAL),
+        Some(2) => Ok(Mirroring::SINGLE_SCREEN_LOWER),
+        Some(3) => Ok(Mirroring::SINGLE_SCREEN_UPPER),
+        Some(4) => Ok(Mirroring::FOUR_SCREEN),
+        // mirroring is controlled by the mapper, which sets it up once it runs
+        Some(5) => Ok(Mirroring::HORIZONTAL),
+        Some(value) => Err(format!("Unknown UNIF mirroring type {}", value)),
+        None => Err("UNIF chunk MIRR is empty".to_string()),
+    }
+}
+
+fn chunk_index(digit: u8) -> Result<usize, String> {
+    (digit as char)
+        .to_digit(16)
+        .map(|index| index as usize)
+        .ok_or(format!("Invalid UNIF chunk index {}", digit as char))
+}
@@ -1,6 +1,7 @@
 ﻿use crate::components::bus::Bus;
 use crate::components::mem::Mem;
 use crate::components::opcodes::OPCodes;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
 
 pub struct CPU {
     pub register_a: u8,
@@ -49,12 +50,13 @@ impl CPU {
     pub fn new(bus: Bus) -> Self {
         CPU {
             register_a: 0,
-            status: CPU::STATUS_RESET,
+            // as the chip wakes up, the reset sequence takes it to STATUS_RESET and STACK_RESET
+            status: CPU::BREAK_COMMAND_FLAG_2,
             program_counter: 0,
             register_x: 0,
             register_y: 0,
             op_codes: OPCodes::new(),
-            stack_pointer: CPU::STACK_RESET,
+            stack_pointer: 0,
             bus,
         }
     }
@@ -72,6 +74,12 @@ impl CPU {
     pub const STACK_RESET: u8 = 0xFD;
     pub const STATUS_RESET: u8 = CPU::INTERRUPT_DISABLE_FLAG | CPU::BREAK_COMMAND_FLAG_2;
 
+    const NMI_VECTOR: u16 = 0xFFFA;
+    const IRQ_VECTOR: u16 = 0xFFFE;
+    const INTERRUPT_CYCLES: u8 = 7;
+    // nothing is ever executed here, the address only marks a finished call_subroutine
+    const SUBROUTINE_RETURN_ADDRESS: u16 = 0x4100;
+
     fn get_operand_address(&mut self, mode: &AddressingMode) -> u16 {
         match mode {
             AddressingMode::Immediate => self.program_counter,
@@ -149,6 +157,7 @@ impl CPU {
 
     pub fn load_and_run(&mut self, program: Vec<u8>) {
         self.load(program);
+        self.power_up_registers();
         self.reset();
         self.run();
     }
@@ -875,281 +884,399 @@ impl CPU {
         self.run_with_callback(|_| {});
     }
 
+    //https://www.nesdev.org/wiki/CPU_interrupts
+    fn interrupt(&mut self, vector: u16) {
+        self.stack_push_u16(self.program_counter);
+        let mut status = self.status;
+        status &= !CPU::BREAK_COMMAND_FLAG_1;
+        status |= CPU::BREAK_COMMAND_FLAG_2;
+        self.stack_push(status);
+        self.set_interrupt_flag();
+
+        self.bus.tick(CPU::INTERRUPT_CYCLES);
+        self.program_counter = self.mem_read_u16(vector);
+    }
+
     pub fn run_with_callback<F>(&mut self, mut callback: F)
         where
             F: FnMut(&mut CPU),
+    {
+        self.run_while(|cpu| {
+            callback(cpu);
+            true
+        });
+    }
+
+    // Same as run_with_callback, but stops before the next instruction once the callback returns false
+    pub fn run_while<F>(&mut self, mut callback: F)
+        where
+            F: FnMut(&mut CPU) -> bool,
     {
         loop {
-            callback(self);
-            let code = self.mem_read(self.program_counter);
-            self.program_counter += 1;
-            let pc_temp = self.program_counter;
-            // println!("the op code is {:#02x}", code);
-            let op_code_data = self.op_codes.get(code);
+            self.poll_interrupts();
 
-            match code {
-                0x00 => return,
+            if !callback(self) || !self.step() {
+                return;
+            }
+        }
+    }
 
-                0x69 | 0x65 | 0x75 | 0x6D | 0x7D | 0x79 | 0x61 | 0x71 => {
-                    self.adc(&op_code_data.addressing_mode)
-                }
+    // Runs until the PPU has drawn a whole frame, returns false if BRK is reached first
+    pub fn run_frame(&mut self) -> bool {
+        loop {
+            self.poll_interrupts();
+            if !self.step() {
+                return false;
+            }
+            if self.bus.poll_new_frame() {
+                return true;
+            }
+        }
+    }
 
-                0x29 | 0x25 | 0x35 | 0x2D | 0x3D | 0x39 | 0x21 | 0x31 => {
-                    self.and(&op_code_data.addressing_mode);
-                }
+    // Runs a subroutine the way JSR would and returns once its RTS is executed,
+    // within `max_cycles`. A routine that never returns leaves the stack as it was before the call.
+    pub fn call_subroutine(&mut self, addr: u16, max_cycles: usize) -> Result<(), String> {
+        let stack_pointer = self.stack_pointer;
+        let deadline = self.bus.cycles() + max_cycles;
+        self.stack_push_u16(CPU::SUBROUTINE_RETURN_ADDRESS - 1);
+        self.program_counter = addr;
 
-                0x06 | 0x16 | 0x0E | 0x1E => {
-                    self.asl(&op_code_data.addressing_mode);
-                }
+        while self.program_counter != CPU::SUBROUTINE_RETURN_ADDRESS {
+            self.poll_interrupts();
+            if !self.step() {
+                self.stack_pointer = stack_pointer;
+                return Err(format!("Subroutine at {:04X} hit BRK at {:04X}", addr, self.program_counter.wrapping_sub(1)));
+            }
+            if self.bus.cycles() >= deadline {
+                self.stack_pointer = stack_pointer;
+                return Err(format!("Subroutine at {:04X} didn't return within {} cycles", addr, max_cycles));
+            }
+        }
+        Ok(())
+    }
 
-                0x0A => self.asl_accumulator(),
+    fn poll_interrupts(&mut self) {
+        if self.bus.poll_nmi_status() {
+            self.interrupt(CPU::NMI_VECTOR);
+        } else if self.bus.poll_irq_status() && self.status & CPU::INTERRUPT_DISABLE_FLAG == 0 {
+            self.interrupt(CPU::IRQ_VECTOR);
+        }
+    }
 
-                0x90 => self.bcc(),
+    // Executes a single instruction, returns false once BRK is reached
+    pub fn step(&mut self) -> bool {
+        let code = self.mem_read(self.program_counter);
+        self.program_counter += 1;
+        let pc_temp = self.program_counter;
+        // println!("the op code is {:#02x}", code);
+        let op_code_data = self.op_codes.get(code);
 
-                0xB0 => self.bcs(),
+        match code {
+            0x00 => return false,
 
-                0xF0 => self.beq(),
+            0x69 | 0x65 | 0x75 | 0x6D | 0x7D | 0x79 | 0x61 | 0x71 => {
+                self.adc(&op_code_data.addressing_mode)
+            }
 
-                0x24 | 0x2C => {
-                    self.bit(&op_code_data.addressing_mode);
-                }
+            0x29 | 0x25 | 0x35 | 0x2D | 0x3D | 0x39 | 0x21 | 0x31 => {
+                self.and(&op_code_data.addressing_mode);
+            }
 
-                0x30 => self.bmi(),
+            0x06 | 0x16 | 0x0E | 0x1E => {
+                self.asl(&op_code_data.addressing_mode);
+            }
 
-                0xD0 => self.bne(),
+            0x0A => self.asl_accumulator(),
 
-                0x10 => self.bpl(),
+            0x90 => self.bcc(),
 
-                0x50 => self.bvc(),
+            0xB0 => self.bcs(),
 
-                0x70 => self.bvs(),
+            0xF0 => self.beq(),
 
-                0x18 => self.clc(),
+            0x24 | 0x2C => {
+                self.bit(&op_code_data.addressing_mode);
+            }
 
-                0xD8 => self.cld(),
+            0x30 => self.bmi(),
 
-                0x58 => self.cli(),
+            0xD0 => self.bne(),
 
-                0xB8 => self.clv(),
+            0x10 => self.bpl(),
 
-                0xC9 | 0xC5 | 0xD5 | 0xCD | 0xDD | 0xD9 | 0xC1 | 0xD1 => {
-                    self.cmp(&op_code_data.addressing_mode);
-                }
+            0x50 => self.bvc(),
 
-                0xE0 | 0xE4 | 0xEC => {
-                    self.cpx(&op_code_data.addressing_mode);
-                }
+            0x70 => self.bvs(),
 
-                0xC0 | 0xC4 | 0xCC => {
-                    self.cpy(&op_code_data.addressing_mode);
-                }
+            0x18 => self.clc(),
 
-                0xC6 | 0xD6 | 0xCE | 0xDE => {
-                    self.dec(&op_code_data.addressing_mode);
-                }
+            0xD8 => self.cld(),
 
-                0xCA => {
-                    self.dex();
-                }
+            0x58 => self.cli(),
 
-                0x88 => {
-                    self.dey();
-                }
+            0xB8 => self.clv(),
 
-                0x49 | 0x45 | 0x55 | 0x4D | 0x5D | 0x59 | 0x41 | 0x51 => {
-                    self.eor(&op_code_data.addressing_mode);
-                }
+            0xC9 | 0xC5 | 0xD5 | 0xCD | 0xDD | 0xD9 | 0xC1 | 0xD1 => {
+                self.cmp(&op_code_data.addressing_mode);
+            }
 
-                0xE6 | 0xF6 | 0xEE | 0xFE => {
-                    self.inc(&op_code_data.addressing_mode);
-                }
+            0xE0 | 0xE4 | 0xEC => {
+                self.cpx(&op_code_data.addressing_mode);
+            }
 
-                0xE8 => self.inx(),
+            0xC0 | 0xC4 | 0xCC => {
+                self.cpy(&op_code_data.addressing_mode);
+            }
 
-                0xC8 => self.iny(),
+            0xC6 | 0xD6 | 0xCE | 0xDE => {
+                self.dec(&op_code_data.addressing_mode);
+            }
 
-                0x4C => self.jmp(&op_code_data.addressing_mode),
+            0xCA => {
+                self.dex();
+            }
 
-                0x6C => self.jmp_indirect(),
+            0x88 => {
+                self.dey();
+            }
 
-                0x20 => self.jsr(&op_code_data.addressing_mode),
+            0x49 | 0x45 | 0x55 | 0x4D | 0x5D | 0x59 | 0x41 | 0x51 => {
+                self.eor(&op_code_data.addressing_mode);
+            }
 
-                0xA9 | 0xA5 | 0xB5 | 0xAD | 0xBD | 0xB9 | 0xA1 | 0xB1 => {
-                    self.lda(&op_code_data.addressing_mode);
-                }
+            0xE6 | 0xF6 | 0xEE | 0xFE => {
+                self.inc(&op_code_data.addressing_mode);
+            }
 
-                0xA2 | 0xA6 | 0xB6 | 0xAE | 0xBE => {
-                    self.ldx(&op_code_data.addressing_mode);
-                }
+            0xE8 => self.inx(),
 
-                0xA0 | 0xA4 | 0xB4 | 0xAC | 0xBC => {
-                    self.ldy(&op_code_data.addressing_mode);
-                }
+            0xC8 => self.iny(),
 
-                0x4A => {
-                    self.lsr_accumulator();
-                }
+            0x4C => self.jmp(&op_code_data.addressing_mode),
 
-                0x46 | 0x56 | 0x4E | 0x5E => {
-                    self.lsr(&op_code_data.addressing_mode);
-                }
+            0x6C => self.jmp_indirect(),
 
-                0xEA | 0x1A | 0x3A | 0x5A | 0x7A | 0xDA | 0xFA | 0x80 | 0x82 | 0x89 | 0xC2 | 0xE2 => {
-                    self.nop();
-                }
+            0x20 => self.jsr(&op_code_data.addressing_mode),
 
-                0x09 | 0x05 | 0x15 | 0x0D | 0x1D | 0x19 | 0x01 | 0x11 => {
-                    self.ora(&op_code_data.addressing_mode);
-                }
+            0xA9 | 0xA5 | 0xB5 | 0xAD | 0xBD | 0xB9 | 0xA1 | 0xB1 => {
+                self.lda(&op_code_data.addressing_mode);
+            }
 
-                0x48 => {
-                    self.pha();
-                }
+            0xA2 | 0xA6 | 0xB6 | 0xAE | 0xBE => {
+                self.ldx(&op_code_data.addressing_mode);
+            }
 
-                0x08 => {
-                    self.php();
-                }
+            0xA0 | 0xA4 | 0xB4 | 0xAC | 0xBC => {
+                self.ldy(&op_code_data.addressing_mode);
+            }
 
-                0x68 => {
-                    self.pla();
-                }
+            0x4A => {
+                self.lsr_accumulator();
+            }
 
-                0x28 => {
-                    self.plp();
-                }
+            0x46 | 0x56 | 0x4E | 0x5E => {
+                self.lsr(&op_code_data.addressing_mode);
+            }
 
-                0x2A => self.rol_accumulator(),
+            0xEA | 0x1A | 0x3A | 0x5A | 0x7A | 0xDA | 0xFA | 0x80 | 0x82 | 0x89 | 0xC2 | 0xE2 => {
+                self.nop();
+            }
 
-                0x26 | 0x36 | 0x2E | 0x3E => {
-                    self.rol(&op_code_data.addressing_mode);
-                }
+            0x09 | 0x05 | 0x15 | 0x0D | 0x1D | 0x19 | 0x01 | 0x11 => {
+                self.ora(&op_code_data.addressing_mode);
+            }
 
-                0x6A => self.ror_accumulator(),
+            0x48 => {
+                self.pha();
+            }
 
-                0x66 | 0x76 | 0x6E | 0x7E => {
-                    self.ror(&op_code_data.addressing_mode);
-                }
+            0x08 => {
+                self.php();
+            }
 
-                0x40 => self.rti(),
+            0x68 => {
+                self.pla();
+            }
 
-                0x60 => self.rts(),
+            0x28 => {
+                self.plp();
+            }
 
-                0xe9 | 0xe5 | 0xf5 | 0xed | 0xfd | 0xf9 | 0xe1 | 0xf1 | 0xeb => {
-                    self.sbc(&op_code_data.addressing_mode);
-                }
+            0x2A => self.rol_accumulator(),
 
-                0x38 => self.sec(),
+            0x26 | 0x36 | 0x2E | 0x3E => {
+                self.rol(&op_code_data.addressing_mode);
+            }
 
-                0xF8 => self.sed(),
+            0x6A => self.ror_accumulator(),
 
-                0x78 => self.sei(),
+            0x66 | 0x76 | 0x6E | 0x7E => {
+                self.ror(&op_code_data.addressing_mode);
+            }
 
-                0x85 | 0x95 | 0x8D | 0x9D | 0x99 | 0x81 | 0x91 => {
-                    self.sta(&op_code_data.addressing_mode);
-                }
+            0x40 => self.rti(),
 
-                0x86 | 0x96 | 0x8E => {
-                    self.stx(&op_code_data.addressing_mode);
-                }
+            0x60 => self.rts(),
 
-                0x84 | 0x94 | 0x8C => {
-                    self.sty(&op_code_data.addressing_mode);
-                }
+            0xe9 | 0xe5 | 0xf5 | 0xed | 0xfd | 0xf9 | 0xe1 | 0xf1 | 0xeb => {
+                self.sbc(&op_code_data.addressing_mode);
+            }
 
-                0xAA => self.tax(),
-                0xA8 => self.tay(),
+            0x38 => self.sec(),
 
-                0xBA => self.tsx(),
+            0xF8 => self.sed(),
 
-                0x8A => self.txa(),
+            0x78 => self.sei(),
 
-                0x9A => self.txs(),
+            0x85 | 0x95 | 0x8D | 0x9D | 0x99 | 0x81 | 0x91 => {
+                self.sta(&op_code_data.addressing_mode);
+            }
 
-                0x98 => self.tya(),
+            0x86 | 0x96 | 0x8E => {
+                self.stx(&op_code_data.addressing_mode);
+            }
 
-                0x9F | 0x93 => self.ahx(&op_code_data.addressing_mode, op_code_data.bytes),
+            0x84 | 0x94 | 0x8C => {
+                self.sty(&op_code_data.addressing_mode);
+            }
 
-                0x4b => self.alr(&op_code_data.addressing_mode),
+            0xAA => self.tax(),
+            0xA8 => self.tay(),
 
-                0x0b | 0x2b => self.anc(&op_code_data.addressing_mode),
+            0xBA => self.tsx(),
 
-                0x8F | 0x87 | 0x97 | 0x83 => self.sax(&op_code_data.addressing_mode),
+            0x8A => self.txa(),
 
-                0x6B => self.arr(&op_code_data.addressing_mode),
+            0x9A => self.txs(),
 
-                0xCB => self.axs(&op_code_data.addressing_mode),
+            0x98 => self.tya(),
 
-                0xA7 | 0xB7 | 0xAF | 0xBF | 0xA3 | 0xB3 => {
-                    self.lax(&op_code_data.addressing_mode);
-                }
+            0x9F | 0x93 => self.ahx(&op_code_data.addressing_mode, op_code_data.bytes),
 
-                0x0C | 0x1C | 0x3C | 0x5C | 0x7C | 0xDC | 0xFC | 0x04 | 0x44 | 0x64 | 0x14 | 0x34 | 0x54 | 0x74 | 0xD4 | 0xF4 => {
-                    self.ign(&op_code_data.addressing_mode);
-                }
+            0x4b => self.alr(&op_code_data.addressing_mode),
 
-                0xBB => {
-                    self.las(&op_code_data.addressing_mode);
-                }
+            0x0b | 0x2b => self.anc(&op_code_data.addressing_mode),
 
-                0x27 | 0x37 | 0x2F | 0x3F | 0x3B | 0x23 | 0x33 => {
-                    self.rla(&op_code_data.addressing_mode);
-                }
+            0x8F | 0x87 | 0x97 | 0x83 => self.sax(&op_code_data.addressing_mode),
 
-                0x67 | 0x77 | 0x6F | 0x7F | 0x7B | 0x63 | 0x73 => {
-                    self.rra(&op_code_data.addressing_mode);
-                }
+            0x6B => self.arr(&op_code_data.addressing_mode),
 
-                0x47 | 0x57 | 0x4F | 0x5F | 0x5B | 0x43 | 0x53 => {
-                    self.sre(&op_code_data.addressing_mode);
-                }
+            0xCB => self.axs(&op_code_data.addressing_mode),
 
-                0x07 | 0x17 | 0x0F | 0x1F | 0x1B | 0x03 | 0x13 => {
-                    self.slo(&op_code_data.addressing_mode);
-                }
+            0xA7 | 0xB7 | 0xAF | 0xBF | 0xA3 | 0xB3 => {
+                self.lax(&op_code_data.addressing_mode);
+            }
 
-                0x9B => {
-                    self.tas(&op_code_data.addressing_mode);
-                }
+            0x0C | 0x1C | 0x3C | 0x5C | 0x7C | 0xDC | 0xFC | 0x04 | 0x44 | 0x64 | 0x14 | 0x34 | 0x54 | 0x74 | 0xD4 | 0xF4 => {
+                self.ign(&op_code_data.addressing_mode);
+            }
 
-                0x9C => {
-                    self.shy(&op_code_data.addressing_mode);
-                }
+            0xBB => {
+                self.las(&op_code_data.addressing_mode);
+            }
 
-                0x9E => {
-                    self.shx(&op_code_data.addressing_mode);
-                }
+            0x27 | 0x37 | 0x2F | 0x3F | 0x3B | 0x23 | 0x33 => {
+                self.rla(&op_code_data.addressing_mode);
+            }
 
-                0xE7 | 0xF7 | 0xEF | 0xFF | 0xFB | 0xE3 | 0xF3 => {
-                    self.isc(&op_code_data.addressing_mode);
-                }
+            0x67 | 0x77 | 0x6F | 0x7F | 0x7B | 0x63 | 0x73 => {
+                self.rra(&op_code_data.addressing_mode);
+            }
 
-                0x02 | 0x12 | 0x22 | 0x32 | 0x42 | 0x52 | 0x62 | 0x72 | 0x92 | 0xB2 | 0xD2
-                | 0xF2 | 0xAB | 0x8B => {
-                    self.unsupported_command(&op_code_data.command_name);
-                }
+            0x47 | 0x57 | 0x4F | 0x5F | 0x5B | 0x43 | 0x53 => {
+                self.sre(&op_code_data.addressing_mode);
+            }
 
-                0xC7 | 0xD7 | 0xCF | 0xDF | 0xDB | 0xC3 | 0xD3 => {
-                    self.dcp(&op_code_data.addressing_mode);
-                }
+            0x07 | 0x17 | 0x0F | 0x1F | 0x1B | 0x03 | 0x13 => {
+                self.slo(&op_code_data.addressing_mode);
+            }
 
-                _ => todo!(),
+            0x9B => {
+                self.tas(&op_code_data.addressing_mode);
             }
 
-            if pc_temp == self.program_counter {
-                self.program_counter += (op_code_data.bytes - 1) as u16;
+            0x9C => {
+                self.shy(&op_code_data.addressing_mode);
             }
+
+            0x9E => {
+                self.shx(&op_code_data.addressing_mode);
+            }
+
+            0xE7 | 0xF7 | 0xEF | 0xFF | 0xFB | 0xE3 | 0xF3 => {
+                self.isc(&op_code_data.addressing_mode);
+            }
+
+            0x02 | 0x12 | 0x22 | 0x32 | 0x42 | 0x52 | 0x62 | 0x72 | 0x92 | 0xB2 | 0xD2
+            | 0xF2 | 0xAB | 0x8B => {
+                self.unsupported_command(&op_code_data.command_name);
+            }
+
+            0xC7 | 0xD7 | 0xCF | 0xDF | 0xDB | 0xC3 | 0xD3 => {
+                self.dcp(&op_code_data.addressing_mode);
+            }
+        }
+
+        self.bus.tick(op_code_data.cycles);
+
+        if pc_temp == self.program_counter {
+            self.program_counter += (op_code_data.bytes - 1) as u16;
         }
+
+        true
     }
 
+    //https://www.nesdev.org/wiki/CPU_power_up_state
+    // Switching the console on. RAM gets the pattern the bus was set up with, the PPU ignores its
+    // control registers while it warms up and everything else starts from scratch.
+    pub fn power_on(&mut self) {
+        self.bus.power_on();
+        self.power_up_registers();
+        self.reset_sequence();
+    }
+
+    // Pressing the reset button. RAM and the registers keep their values and the APU goes quiet.
     pub fn reset(&mut self) {
+        self.bus.reset();
+        self.reset_sequence();
+    }
+
+    fn power_up_registers(&mut self) {
         self.register_a = 0;
         self.register_x = 0;
         self.register_y = 0;
-        self.status = CPU::STATUS_RESET;
-        self.stack_pointer = CPU::STACK_RESET;
+        self.status = CPU::BREAK_COMMAND_FLAG_2;
+        self.stack_pointer = 0;
+    }
 
+    // The reset sequence goes through the motions of an interrupt with the stack writes turned
+    // into reads, so only the stack pointer moves
+    fn reset_sequence(&mut self) {
+        self.stack_pointer = self.stack_pointer.wrapping_sub(3);
+        self.status |= CPU::INTERRUPT_DISABLE_FLAG;
         self.program_counter = self.mem_read_u16(0xFFFC);
+    }
+}
 
-        // panic!("The program counter at start is {:x}",self.program_counter);
+impl SaveState for CPU {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.register_a);
+        state.write_u8(self.register_x);
+        state.write_u8(self.register_y);
+        state.write_u8(self.status);
+        state.write_u16(self.program_counter);
+        state.write_u8(self.stack_pointer);
+        self.bus.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.register_a = state.read_u8()?;
+        self.register_x = state.read_u8()?;
+        self.register_y = state.read_u8()?;
+        self.status = state.read_u8()?;
+        self.program_counter = state.read_u16()?;
+        self.stack_pointer = state.read_u8()?;
+        self.bus.load_state(state)
     }
 }
@@ -0,0 +1,18 @@
+//https://www.w3.org/TR/png/#D-CRCAppendix
+// The same reflected 0xEDB88320 polynomial is used by PNG chunks and by the ROM databases
+const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+pub fn crc32(data: &[u8]) -> u32 {
+    !update_crc32(0xFFFF_FFFF, data)
+}
+
+// Continues a checksum that was started with 0xFFFFFFFF, the caller inverts the final value
+pub fn update_crc32(mut crc: u32, data: &[u8]) -> u32 {
+    for value in data {
+        crc ^= *value as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ POLYNOMIAL } else { crc >> 1 };
+        }
+    }
+    crc
+}
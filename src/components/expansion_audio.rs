@@ -0,0 +1,7 @@
+// Sound chips found on Famicom cartridges, shared between the mappers that carry them and
+// the NSF player
+pub mod mmc5;
+pub mod namco163;
+pub mod sunsoft5b;
+pub mod vrc6;
+pub mod vrc7;
@@ -0,0 +1,147 @@
+use crate::components::apu::pulse::Pulse;
+use crate::components::apu::{DMC_FULL_VOLUME, PULSE_FULL_VOLUME};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/MMC5_audio
+pub struct Mmc5Audio {
+    pulses: [Pulse; 2],
+    pcm_read_mode: bool,
+    pcm_irq_enabled: bool,
+    pcm_irq: bool,
+    pcm: u8,
+    frame_cycle: u16,
+    odd_cycle: bool,
+}
+
+impl Mmc5Audio {
+    // envelopes and length counters run at a fixed 240 Hz instead of following $4017
+    const FRAME_PERIOD: u16 = 7457;
+
+    pub fn new() -> Self {
+        Mmc5Audio {
+            pulses: [Pulse::without_sweep(), Pulse::without_sweep()],
+            pcm_read_mode: false,
+            pcm_irq_enabled: false,
+            pcm_irq: false,
+            pcm: 0,
+            frame_cycle: 0,
+            odd_cycle: false,
+        }
+    }
+
+    pub fn write(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x5000 | 0x5004 => self.pulse(addr).write_control(data),
+            0x5002 | 0x5006 => self.pulse(addr).write_timer_low(data),
+            0x5003 | 0x5007 => self.pulse(addr).write_timer_high(data),
+            0x5010 => {
+                self.pcm_read_mode = data & 0b0000_0001 != 0;
+                self.pcm_irq_enabled = data & 0b1000_0000 != 0;
+            }
+            // zero can't be written, it's what raises the IRQ in read mode
+            0x5011 if !self.pcm_read_mode && data != 0 => self.pcm = data,
+            0x5015 => {
+                self.pulses[0].length_counter.set_enabled(data & 0b01 != 0);
+                self.pulses[1].length_counter.set_enabled(data & 0b10 != 0);
+            }
+            _ => {}
+        }
+    }
+
+    fn pulse(&mut self, addr: u16) -> &mut Pulse {
+        &mut self.pulses[((addr - 0x5000) / 4) as usize]
+    }
+
+    // Reading $5010 acknowledges the PCM interrupt
+    pub fn read(&mut self, addr: u16) -> u8 {
+        let data = self.peek(addr);
+        if addr == 0x5010 {
+            self.pcm_irq = false;
+        }
+        data
+    }
+
+    pub fn peek(&self, addr: u16) -> u8 {
+        match addr {
+            0x5010 => (self.pcm_irq as u8) << 7 | self.pcm_read_mode as u8,
+            0x5015 => {
+                self.pulses[0].length_counter.is_active() as u8 | (self.pulses[1].length_counter.is_active() as u8) << 1
+            }
+            _ => 0,
+        }
+    }
+
+    // In read mode the PCM channel plays whatever the CPU reads from $8000-$BFFF
+    pub fn observe_prg_read(&mut self, addr: u16, data: u8) {
+        if !self.pcm_read_mode || !(0x8000..=0xBFFF).contains(&addr) {
+            return;
+        }
+        if data == 0 {
+            self.pcm_irq = true;
+        } else {
+            self.pcm = data;
+        }
+    }
+
+    pub fn irq_pending(&self) -> bool {
+        self.pcm_irq && self.pcm_irq_enabled
+    }
+
+    pub fn clock(&mut self) {
+        if self.odd_cycle {
+            for pulse in self.pulses.iter_mut() {
+                pulse.clock_timer();
+            }
+        }
+        self.odd_cycle = !self.odd_cycle;
+
+        self.frame_cycle += 1;
+        if self.frame_cycle == Mmc5Audio::FRAME_PERIOD {
+            self.frame_cycle = 0;
+            for pulse in self.pulses.iter_mut() {
+                pulse.envelope.clock();
+                pulse.length_counter.clock();
+            }
+        }
+    }
+
+    // The pulses are as loud as the 2A03 ones but mixed linearly, the 8 bit PCM spans the
+    // same range as the 7 bit DMC
+    pub fn output(&self) -> f32 {
+        let pulses = (self.pulses[0].output() + self.pulses[1].output()) as f32;
+        pulses * PULSE_FULL_VOLUME / 15.0 + self.pcm as f32 * DMC_FULL_VOLUME / 255.0
+    }
+}
+
+impl Default for Mmc5Audio {
+    fn default() -> Self {
+        Mmc5Audio::new()
+    }
+}
+
+impl SaveState for Mmc5Audio {
+    fn save_state(&self, state: &mut StateWriter) {
+        for pulse in self.pulses.iter() {
+            pulse.save_state(state);
+        }
+        state.write_bool(self.pcm_read_mode);
+        state.write_bool(self.pcm_irq_enabled);
+        state.write_bool(self.pcm_irq);
+        state.write_u8(self.pcm);
+        state.write_u16(self.frame_cycle);
+        state.write_bool(self.odd_cycle);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        for pulse in self.pulses.iter_mut() {
+            pulse.load_state(state)?;
+        }
+        self.pcm_read_mode = state.read_bool()?;
+        self.pcm_irq_enabled = state.read_bool()?;
+        self.pcm_irq = state.read_bool()?;
+        self.pcm = state.read_u8()?;
+        self.frame_cycle = state.read_u16()?;
+        self.odd_cycle = state.read_bool()?;
+        Ok(())
+    }
+}
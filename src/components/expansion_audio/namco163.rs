@@ -0,0 +1,150 @@
+use crate::components::apu::PULSE_FULL_VOLUME;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Namco_163_audio
+pub struct Namco163Audio {
+    ram: [u8; Namco163Audio::RAM_SIZE],
+    address: u8,
+    auto_increment: bool,
+    // the chip updates one channel every 15 CPU cycles, starting from the last one
+    update_cycle: u8,
+    current_channel: usize,
+    channel_outputs: [i16; 8],
+}
+
+impl Namco163Audio {
+    pub const RAM_SIZE: usize = 0x80;
+    const CYCLES_PER_CHANNEL: u8 = 15;
+    // channel registers fill the top of RAM, channel 8 first
+    const CHANNEL_REGISTERS: usize = 0x40;
+    const CHANNEL_COUNT_REGISTER: usize = 0x7F;
+
+    // How loud the chip is next to the 2A03 varies between boards, this is a common middle
+    // ground where a lone full volume channel peaks at about three 2A03 pulses
+    const LEVEL: f32 = PULSE_FULL_VOLUME * 3.0 / (7.0 * 15.0);
+
+    pub fn new() -> Self {
+        Namco163Audio {
+            ram: [0; Namco163Audio::RAM_SIZE],
+            address: 0,
+            auto_increment: false,
+            update_cycle: 0,
+            current_channel: 7,
+            channel_outputs: [0; 8],
+        }
+    }
+
+    // Some boards keep save data in the sound RAM with a battery, so only the rest starts over
+    pub fn power_on(&mut self) {
+        let ram = self.ram;
+        *self = Namco163Audio::new();
+        self.ram = ram;
+    }
+
+    // $F800-$FFFF
+    pub fn write_address(&mut self, data: u8) {
+        self.address = data & 0x7F;
+        self.auto_increment = data & 0x80 != 0;
+    }
+
+    // $4800-$4FFF
+    pub fn write_data(&mut self, data: u8) {
+        self.ram[self.address as usize] = data;
+        self.increment_address();
+    }
+
+    pub fn read_data(&mut self) -> u8 {
+        let data = self.peek_data();
+        self.increment_address();
+        data
+    }
+
+    pub fn peek_data(&self) -> u8 {
+        self.ram[self.address as usize]
+    }
+
+    fn increment_address(&mut self) {
+        if self.auto_increment {
+            self.address = (self.address + 1) & 0x7F;
+        }
+    }
+
+    fn enabled_channels(&self) -> usize {
+        ((self.ram[Namco163Audio::CHANNEL_COUNT_REGISTER] >> 4) & 0b111) as usize + 1
+    }
+
+    pub fn clock(&mut self) {
+        self.update_cycle += 1;
+        if self.update_cycle < Namco163Audio::CYCLES_PER_CHANNEL {
+            return;
+        }
+        self.update_cycle = 0;
+
+        self.update_channel(self.current_channel);
+        if self.current_channel <= 8 - self.enabled_channels() {
+            self.current_channel = 7;
+        } else {
+            self.current_channel -= 1;
+        }
+    }
+
+    fn update_channel(&mut self, channel: usize) {
+        let base = Namco163Audio::CHANNEL_REGISTERS + channel * 8;
+        let registers = &self.ram[base..base + 8];
+
+        let frequency = registers[0] as u32 | (registers[2] as u32) << 8 | (registers[4] as u32 & 0b11) << 16;
+        let mut phase = registers[1] as u32 | (registers[3] as u32) << 8 | (registers[5] as u32) << 16;
+        let length = (256 - (registers[4] & 0xFC) as u32) << 16;
+        let wave_address = registers[6] as u32;
+        let volume = (registers[7] & 0x0F) as i16;
+
+        phase = (phase + frequency) % length;
+
+        let sample_address = ((phase >> 16) + wave_address) & 0xFF;
+        let byte = self.ram[(sample_address / 2) as usize % Namco163Audio::RAM_SIZE];
+        let sample = if sample_address & 1 == 0 { byte & 0x0F } else { byte >> 4 } as i16;
+        self.channel_outputs[channel] = (sample - 8) * volume;
+
+        self.ram[base + 1] = phase as u8;
+        self.ram[base + 3] = (phase >> 8) as u8;
+        self.ram[base + 5] = (phase >> 16) as u8;
+    }
+
+    // The channels are time multiplexed on one DAC, what the ear hears is their average
+    pub fn output(&self) -> f32 {
+        let enabled = self.enabled_channels();
+        let sum: i16 = self.channel_outputs[8 - enabled..].iter().sum();
+        sum as f32 / enabled as f32 * Namco163Audio::LEVEL
+    }
+}
+
+impl Default for Namco163Audio {
+    fn default() -> Self {
+        Namco163Audio::new()
+    }
+}
+
+impl SaveState for Namco163Audio {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.ram);
+        state.write_u8(self.address);
+        state.write_bool(self.auto_increment);
+        state.write_u8(self.update_cycle);
+        state.write_usize(self.current_channel);
+        for output in self.channel_outputs.iter() {
+            state.write_i32(*output as i32);
+        }
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.ram)?;
+        self.address = state.read_u8()?;
+        self.auto_increment = state.read_bool()?;
+        self.update_cycle = state.read_u8()?;
+        self.current_channel = state.read_usize()?;
+        for output in self.channel_outputs.iter_mut() {
+            *output = state.read_i32()? as i16;
+        }
+        Ok(())
+    }
+}
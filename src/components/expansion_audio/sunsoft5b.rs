@@ -0,0 +1,303 @@
+use crate::components::apu::PULSE_FULL_VOLUME;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Sunsoft_5B_audio
+pub struct Sunsoft5bAudio {
+    register: u8,
+    tones: [Sunsoft5bTone; 3],
+    noise_period: u8,
+    noise_counter: u8,
+    noise_shift_register: u32,
+    // bits 0-2 turn the tones off, bits 3-5 the noise, per channel
+    mixer: u8,
+    envelope: Sunsoft5bEnvelope,
+    divider: u8,
+}
+
+struct Sunsoft5bTone {
+    period: u16,
+    counter: u16,
+    high: bool,
+    volume: u8,
+    uses_envelope: bool,
+}
+
+impl Sunsoft5bAudio {
+    // the AY core runs at half the CPU clock and its tone and noise counters at 1/16 of that
+    const CYCLES_PER_STEP: u8 = 16;
+    // A full volume channel is about twice as loud as a full volume 2A03 pulse
+    const LEVEL: f32 = PULSE_FULL_VOLUME * 2.0;
+
+    pub fn new() -> Self {
+        Sunsoft5bAudio {
+            register: 0,
+            tones: [Sunsoft5bTone::new(), Sunsoft5bTone::new(), Sunsoft5bTone::new()],
+            noise_period: 0,
+            noise_counter: 0,
+            noise_shift_register: 1,
+            mixer: 0,
+            envelope: Sunsoft5bEnvelope::new(),
+            divider: 0,
+        }
+    }
+
+    // $C000-$DFFF
+    pub fn write_address(&mut self, data: u8) {
+        self.register = data & 0x0F;
+    }
+
+    // $E000-$FFFF
+    pub fn write_data(&mut self, data: u8) {
+        match self.register {
+            0 | 2 | 4 => {
+                let tone = &mut self.tones[(self.register / 2) as usize];
+                tone.period = (tone.period & 0x0F00) | data as u16;
+            }
+            1 | 3 | 5 => {
+                let tone = &mut self.tones[(self.register / 2) as usize];
+                tone.period = (tone.period & 0x00FF) | ((data as u16 & 0x0F) << 8);
+            }
+            6 => self.noise_period = data & 0b1_1111,
+            7 => self.mixer = data,
+            8..=10 => {
+                let tone = &mut self.tones[(self.register - 8) as usize];
+                tone.volume = data & 0x0F;
+                tone.uses_envelope = data & 0b1_0000 != 0;
+            }
+            11 => self.envelope.period = (self.envelope.period & 0xFF00) | data as u16,
+            12 => self.envelope.period = (self.envelope.period & 0x00FF) | (data as u16) << 8,
+            13 => self.envelope.write_shape(data),
+            _ => { /* I/O ports, not connected */ }
+        }
+    }
+
+    pub fn clock(&mut self) {
+        self.envelope.clock();
+
+        self.divider += 1;
+        if self.divider < Sunsoft5bAudio::CYCLES_PER_STEP {
+            return;
+        }
+        self.divider = 0;
+
+        for tone in self.tones.iter_mut() {
+            tone.clock();
+        }
+
+        self.noise_counter += 1;
+        if self.noise_counter >= self.noise_period.max(1) * 2 {
+            self.noise_counter = 0;
+            let feedback = (self.noise_shift_register ^ (self.noise_shift_register >> 3)) & 1;
+            self.noise_shift_register = (self.noise_shift_register >> 1) | (feedback << 16);
+        }
+    }
+
+    pub fn output(&self) -> f32 {
+        let noise_high = self.noise_shift_register & 1 != 0;
+        let mut output = 0.0;
+        for (channel, tone) in self.tones.iter().enumerate() {
+            let tone_off = self.mixer & (1 << channel) != 0;
+            let noise_off = self.mixer & (1 << (channel + 3)) != 0;
+            if (tone.high || tone_off) && (noise_high || noise_off) {
+                let level = if tone.uses_envelope {
+                    self.envelope.level()
+                } else if tone.volume == 0 {
+                    0
+                } else {
+                    tone.volume * 2 + 1
+                };
+                output += volume(level);
+            }
+        }
+        output * Sunsoft5bAudio::LEVEL
+    }
+}
+
+impl Default for Sunsoft5bAudio {
+    fn default() -> Self {
+        Sunsoft5bAudio::new()
+    }
+}
+
+// 32 logarithmic steps of 1.5dB, the fixed volumes only reach every odd one
+fn volume(level: u8) -> f32 {
+    if level == 0 {
+        return 0.0;
+    }
+    10f32.powf((level as f32 - 31.0) * 1.5 / 20.0)
+}
+
+impl Sunsoft5bTone {
+    fn new() -> Self {
+        Sunsoft5bTone {
+            period: 0,
+            counter: 0,
+            high: false,
+            volume: 0,
+            uses_envelope: false,
+        }
+    }
+
+    fn clock(&mut self) {
+        self.counter += 1;
+        if self.counter >= self.period.max(1) {
+            self.counter = 0;
+            self.high = !self.high;
+        }
+    }
+}
+
+struct Sunsoft5bEnvelope {
+    period: u16,
+    counter: u32,
+    step: u8,
+    attack: bool,
+    continues: bool,
+    alternate: bool,
+    hold: bool,
+    holding: bool,
+}
+
+impl Sunsoft5bEnvelope {
+    // 32 steps per ramp, each lasting period * 8 CPU cycles
+    const CYCLES_PER_STEP: u32 = 8;
+
+    fn new() -> Self {
+        Sunsoft5bEnvelope {
+            period: 0,
+            counter: 0,
+            step: 0,
+            attack: false,
+            continues: false,
+            alternate: false,
+            hold: false,
+            holding: false,
+        }
+    }
+
+    // Writing the shape restarts the envelope
+    fn write_shape(&mut self, data: u8) {
+        self.continues = data & 0b1000 != 0;
+        self.attack = data & 0b0100 != 0;
+        self.alternate = data & 0b0010 != 0;
+        self.hold = data & 0b0001 != 0;
+        self.step = 0;
+        self.counter = 0;
+        self.holding = false;
+    }
+
+    fn clock(&mut self) {
+        if self.holding {
+            return;
+        }
+        self.counter += 1;
+        if self.counter < self.period.max(1) as u32 * Sunsoft5bEnvelope::CYCLES_PER_STEP {
+            return;
+        }
+        self.counter = 0;
+
+        if self.step < 31 {
+            self.step += 1;
+            return;
+        }
+
+        if !self.continues {
+            // shapes 0-7 fall silent after the first ramp
+            self.attack = false;
+            self.holding = true;
+        } else if self.hold {
+            if self.alternate {
+                self.attack = !self.attack;
+            }
+            self.holding = true;
+        } else {
+            if self.alternate {
+                self.attack = !self.attack;
+            }
+            self.step = 0;
+        }
+    }
+
+    fn level(&self) -> u8 {
+        if self.holding && !self.continues {
+            0
+        } else if self.attack {
+            self.step
+        } else {
+            31 - self.step
+        }
+    }
+}
+
+impl SaveState for Sunsoft5bAudio {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.register);
+        for tone in self.tones.iter() {
+            tone.save_state(state);
+        }
+        state.write_u8(self.noise_period);
+        state.write_u8(self.noise_counter);
+        state.write_u32(self.noise_shift_register);
+        state.write_u8(self.mixer);
+        self.envelope.save_state(state);
+        state.write_u8(self.divider);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.register = state.read_u8()?;
+        for tone in self.tones.iter_mut() {
+            tone.load_state(state)?;
+        }
+        self.noise_period = state.read_u8()?;
+        self.noise_counter = state.read_u8()?;
+        self.noise_shift_register = state.read_u32()?;
+        self.mixer = state.read_u8()?;
+        self.envelope.load_state(state)?;
+        self.divider = state.read_u8()?;
+        Ok(())
+    }
+}
+
+impl SaveState for Sunsoft5bTone {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u16(self.period);
+        state.write_u16(self.counter);
+        state.write_bool(self.high);
+        state.write_u8(self.volume);
+        state.write_bool(self.uses_envelope);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.period = state.read_u16()?;
+        self.counter = state.read_u16()?;
+        self.high = state.read_bool()?;
+        self.volume = state.read_u8()?;
+        self.uses_envelope = state.read_bool()?;
+        Ok(())
+    }
+}
+
+impl SaveState for Sunsoft5bEnvelope {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u16(self.period);
+        state.write_u32(self.counter);
+        state.write_u8(self.step);
+        state.write_bool(self.attack);
+        state.write_bool(self.continues);
+        state.write_bool(self.alternate);
+        state.write_bool(self.hold);
+        state.write_bool(self.holding);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.period = state.read_u16()?;
+        self.counter = state.read_u32()?;
+        self.step = state.read_u8()?;
+        self.attack = state.read_bool()?;
+        self.continues = state.read_bool()?;
+        self.alternate = state.read_bool()?;
+        self.hold = state.read_bool()?;
+        self.holding = state.read_bool()?;
+        Ok(())
+    }
+}
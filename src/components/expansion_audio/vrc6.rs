@@ -0,0 +1,256 @@
+use crate::components::apu::PULSE_FULL_VOLUME;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/VRC6_audio
+pub struct Vrc6Audio {
+    pulses: [Vrc6Pulse; 2],
+    sawtooth: Vrc6Sawtooth,
+    halted: bool,
+    // $9003 speeds every channel up by 16 or 256 times, used by test code
+    frequency_shift: u8,
+}
+
+impl Vrc6Audio {
+    pub fn new() -> Self {
+        Vrc6Audio {
+            pulses: [Vrc6Pulse::new(), Vrc6Pulse::new()],
+            sawtooth: Vrc6Sawtooth::new(),
+            halted: false,
+            frequency_shift: 0,
+        }
+    }
+
+    // Takes mapper 24 register addresses, mapper 26 swaps its address lines before calling
+    pub fn write(&mut self, register: u16, data: u8) {
+        match register {
+            0x9000..=0x9002 => self.pulses[0].write(register & 0b11, data),
+            0x9003 => {
+                self.halted = data & 0b001 != 0;
+                self.frequency_shift = if data & 0b100 != 0 {
+                    8
+                } else if data & 0b010 != 0 {
+                    4
+                } else {
+                    0
+                };
+            }
+            0xA000..=0xA002 => self.pulses[1].write(register & 0b11, data),
+            0xB000..=0xB002 => self.sawtooth.write(register & 0b11, data),
+            _ => {}
+        }
+    }
+
+    pub fn clock(&mut self) {
+        if self.halted {
+            return;
+        }
+        for pulse in self.pulses.iter_mut() {
+            pulse.clock(self.frequency_shift);
+        }
+        self.sawtooth.clock(self.frequency_shift);
+    }
+
+    // The chip sums its channels linearly, a full volume VRC6 pulse is about as loud as a
+    // full volume 2A03 pulse
+    pub fn output(&self) -> f32 {
+        let level = self.pulses[0].output() + self.pulses[1].output() + self.sawtooth.output();
+        level as f32 * PULSE_FULL_VOLUME / 15.0
+    }
+}
+
+impl Default for Vrc6Audio {
+    fn default() -> Self {
+        Vrc6Audio::new()
+    }
+}
+
+struct Vrc6Pulse {
+    volume: u8,
+    duty: u8,
+    ignore_duty: bool,
+    enabled: bool,
+    period: u16,
+    timer: u16,
+    step: u8,
+}
+
+impl Vrc6Pulse {
+    fn new() -> Self {
+        Vrc6Pulse {
+            volume: 0,
+            duty: 0,
+            ignore_duty: false,
+            enabled: false,
+            period: 0,
+            timer: 0,
+            step: 15,
+        }
+    }
+
+    fn write(&mut self, register: u16, data: u8) {
+        match register {
+            0 => {
+                self.ignore_duty = data & 0b1000_0000 != 0;
+                self.duty = (data >> 4) & 0b111;
+                self.volume = data & 0b1111;
+            }
+            1 => self.period = (self.period & 0x0F00) | data as u16,
+            _ => {
+                self.period = (self.period & 0x00FF) | ((data as u16 & 0x0F) << 8);
+                self.enabled = data & 0b1000_0000 != 0;
+                if !self.enabled {
+                    self.step = 15;
+                }
+            }
+        }
+    }
+
+    fn clock(&mut self, frequency_shift: u8) {
+        if !self.enabled {
+            return;
+        }
+        if self.timer == 0 {
+            self.timer = self.period >> frequency_shift;
+            self.step = self.step.wrapping_sub(1) & 0x0F;
+        } else {
+            self.timer -= 1;
+        }
+    }
+
+    fn output(&self) -> u8 {
+        if self.enabled && (self.ignore_duty || self.step <= self.duty) {
+            self.volume
+        } else {
+            0
+        }
+    }
+}
+
+struct Vrc6Sawtooth {
+    rate: u8,
+    enabled: bool,
+    period: u16,
+    timer: u16,
+    step: u8,
+    accumulator: u8,
+}
+
+impl Vrc6Sawtooth {
+    fn new() -> Self {
+        Vrc6Sawtooth {
+            rate: 0,
+            enabled: false,
+            period: 0,
+            timer: 0,
+            step: 0,
+            accumulator: 0,
+        }
+    }
+
+    fn write(&mut self, register: u16, data: u8) {
+        match register {
+            0 => self.rate = data & 0b0011_1111,
+            1 => self.period = (self.period & 0x0F00) | data as u16,
+            _ => {
+                self.period = (self.period & 0x00FF) | ((data as u16 & 0x0F) << 8);
+                self.enabled = data & 0b1000_0000 != 0;
+                if !self.enabled {
+                    self.step = 0;
+                    self.accumulator = 0;
+                }
+            }
+        }
+    }
+
+    // The accumulator grows on every second step and is cleared on the fourteenth
+    fn clock(&mut self, frequency_shift: u8) {
+        if !self.enabled {
+            return;
+        }
+        if self.timer > 0 {
+            self.timer -= 1;
+            return;
+        }
+        self.timer = self.period >> frequency_shift;
+        self.step += 1;
+        if self.step == 14 {
+            self.step = 0;
+            self.accumulator = 0;
+        } else if self.step & 1 == 0 {
+            self.accumulator = self.accumulator.wrapping_add(self.rate);
+        }
+    }
+
+    fn output(&self) -> u8 {
+        if self.enabled {
+            self.accumulator >> 3
+        } else {
+            0
+        }
+    }
+}
+
+impl SaveState for Vrc6Audio {
+    fn save_state(&self, state: &mut StateWriter) {
+        for pulse in self.pulses.iter() {
+            pulse.save_state(state);
+        }
+        self.sawtooth.save_state(state);
+        state.write_bool(self.halted);
+        state.write_u8(self.frequency_shift);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        for pulse in self.pulses.iter_mut() {
+            pulse.load_state(state)?;
+        }
+        self.sawtooth.load_state(state)?;
+        self.halted = state.read_bool()?;
+        self.frequency_shift = state.read_u8()?;
+        Ok(())
+    }
+}
+
+impl SaveState for Vrc6Pulse {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.volume);
+        state.write_u8(self.duty);
+        state.write_bool(self.ignore_duty);
+        state.write_bool(self.enabled);
+        state.write_u16(self.period);
+        state.write_u16(self.timer);
+        state.write_u8(self.step);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.volume = state.read_u8()?;
+        self.duty = state.read_u8()?;
+        self.ignore_duty = state.read_bool()?;
+        self.enabled = state.read_bool()?;
+        self.period = state.read_u16()?;
+        self.timer = state.read_u16()?;
+        self.step = state.read_u8()?;
+        Ok(())
+    }
+}
+
+impl SaveState for Vrc6Sawtooth {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.rate);
+        state.write_bool(self.enabled);
+        state.write_u16(self.period);
+        state.write_u16(self.timer);
+        state.write_u8(self.step);
+        state.write_u8(self.accumulator);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.rate = state.read_u8()?;
+        self.enabled = state.read_bool()?;
+        self.period = state.read_u16()?;
+        self.timer = state.read_u16()?;
+        self.step = state.read_u8()?;
+        self.accumulator = state.read_u8()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,460 @@
+use std::f32::consts::PI;
+
+use crate::components::apu::PULSE_FULL_VOLUME;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+// The 15 instruments baked into the VRC7, instrument 0 is the custom one in registers $00-$07
+//https://www.nesdev.org/wiki/VRC7_audio#Internal_patch_set
+pub const BUILT_IN_PATCHES: [[u8; 8]; 15] = [
+    [0x03, 0x21, 0x05, 0x06, 0xE8, 0x81, 0x42, 0x27], // Buzzy Bell
+    [0x13, 0x41, 0x14, 0x0D, 0xD8, 0xF6, 0x23, 0x12], // Guitar
+    [0x11, 0x11, 0x08, 0x08, 0xFA, 0xB2, 0x20, 0x12], // Wurly
+    [0x31, 0x61, 0x0C, 0x07, 0xA8, 0x64, 0x61, 0x27], // Flute
+    [0x32, 0x21, 0x1E, 0x06, 0xE1, 0x76, 0x01, 0x28], // Clarinet
+    [0x02, 0x01, 0x06, 0x00, 0xA3, 0xE2, 0xF4, 0xF4], // Synth
+    [0x21, 0x61, 0x1D, 0x07, 0x82, 0x81, 0x11, 0x07], // Trumpet
+    [0x23, 0x21, 0x22, 0x17, 0xA2, 0x72, 0x01, 0x17], // Organ
+    [0x35, 0x11, 0x25, 0x00, 0x40, 0x73, 0x72, 0x01], // Bells
+    [0xB5, 0x01, 0x0F, 0x0F, 0xA8, 0xA5, 0x51, 0x02], // Vibes
+    [0x17, 0xC1, 0x24, 0x07, 0xF8, 0xF8, 0x22, 0x12], // Vibraphone
+    [0x71, 0x23, 0x11, 0x06, 0x65, 0x74, 0x18, 0x16], // Tutti
+    [0x01, 0x02, 0xD3, 0x05, 0xC9, 0x95, 0x03, 0x02], // Fretless
+    [0x61, 0x63, 0x0C, 0x00, 0x94, 0xC0, 0x33, 0xF6], // Synth Bass
+    [0x21, 0x72, 0x0D, 0x00, 0xC1, 0xD5, 0x56, 0x06], // Sweep
+];
+
+const CHANNELS: usize = 6;
+// The chip runs from a 3.58MHz crystal, twice the CPU clock, and outputs a sample every 72 ticks
+pub const CPU_CYCLES_PER_SAMPLE: u8 = 36;
+pub const SAMPLE_RATE: f32 = 1_789_773.0 / CPU_CYCLES_PER_SAMPLE as f32;
+
+// 19 bit phase accumulators, the top 10 bits index the waveform
+const PHASE_BITS: u32 = 19;
+const WAVE_BITS: u32 = 10;
+const WAVE_LENGTH: i32 = 1 << WAVE_BITS;
+
+// Attenuation is counted in 0.375dB steps, 128 of them silence an operator
+const ATTENUATION_STEP_DB: f32 = 0.375;
+const MAX_ATTENUATION: f32 = 127.0;
+
+// multiplier values times two, so 1/2 fits
+const MULTIPLIERS_X2: [u32; 16] = [1, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 20, 24, 24, 30, 30];
+
+// Key scaling in dB for the top 4 F-number bits at octave 7, 6dB less per octave below
+const KEY_SCALE_DB: [f32; 16] = [
+    0.0, 9.0, 12.0, 13.875, 15.0, 16.125, 16.875, 17.625, 18.0, 18.75, 19.125, 19.5, 19.875, 20.25, 20.625, 21.0,
+];
+
+// Tremolo is 4.8dB deep at 3.7Hz, vibrato 14 cents at 6.4Hz
+const TREMOLO_DB: f32 = 4.8;
+const TREMOLO_HZ: f32 = 3.7;
+const VIBRATO_CENTS: f32 = 14.0;
+const VIBRATO_HZ: f32 = 6.4;
+
+// A full volume channel peaks at about twice a full volume 2A03 pulse
+pub const LEVEL: f32 = PULSE_FULL_VOLUME * 2.0;
+
+//https://www.nesdev.org/wiki/VRC7_audio
+pub struct Vrc7Audio {
+    register: u8,
+    custom_patch: [u8; 8],
+    channels: [FmChannel; CHANNELS],
+    sine: Vec<f32>,
+    cycles_until_sample: u8,
+    sample_count: u32,
+    output: f32,
+}
+
+impl Vrc7Audio {
+    pub fn new() -> Self {
+        Vrc7Audio {
+            register: 0,
+            custom_patch: [0; 8],
+            channels: std::array::from_fn(|_| FmChannel::new()),
+            sine: (0..WAVE_LENGTH).map(|i| (2.0 * PI * i as f32 / WAVE_LENGTH as f32).sin()).collect(),
+            cycles_until_sample: CPU_CYCLES_PER_SAMPLE,
+            sample_count: 0,
+            output: 0.0,
+        }
+    }
+
+    // $9010
+    pub fn write_address(&mut self, data: u8) {
+        self.register = data;
+    }
+
+    // $9030
+    pub fn write_data(&mut self, data: u8) {
+        let channel = (self.register & 0x0F) as usize;
+        match self.register {
+            0x00..=0x07 => self.custom_patch[self.register as usize] = data,
+            0x10..=0x15 => self.channels[channel].fnum = (self.channels[channel].fnum & 0x100) | data as u16,
+            0x20..=0x25 => {
+                let channel = &mut self.channels[channel];
+                channel.fnum = (channel.fnum & 0xFF) | ((data as u16 & 1) << 8);
+                channel.block = (data >> 1) & 0b111;
+                channel.sustain = data & 0b0010_0000 != 0;
+                channel.set_key_on(data & 0b0001_0000 != 0);
+            }
+            0x30..=0x35 => {
+                self.channels[channel].instrument = data >> 4;
+                self.channels[channel].volume = data & 0x0F;
+            }
+            _ => {}
+        }
+    }
+
+    // Silencing the chip from the mapper also resets it
+    pub fn reset(&mut self) {
+        *self = Vrc7Audio::new();
+    }
+
+    pub fn clock(&mut self) {
+        self.cycles_until_sample -= 1;
+        if self.cycles_until_sample > 0 {
+            return;
+        }
+        self.cycles_until_sample = CPU_CYCLES_PER_SAMPLE;
+        self.output = self.generate_sample() * LEVEL;
+    }
+
+    // One sample at the chip's own rate, with every channel in -1.0..1.0
+    pub fn generate_sample(&mut self) -> f32 {
+        let time = self.sample_count as f32 / SAMPLE_RATE;
+        let tremolo_db = TREMOLO_DB * triangle(time * TREMOLO_HZ);
+        let vibrato = 2f32.powf(VIBRATO_CENTS / 1200.0 * (triangle(time * VIBRATO_HZ) * 2.0 - 1.0));
+        self.sample_count = self.sample_count.wrapping_add(1);
+
+        let mut sum = 0.0;
+        for channel in self.channels.iter_mut() {
+            let patch = if channel.instrument == 0 {
+                Patch::decode(&self.custom_patch)
+            } else {
+                Patch::decode(&BUILT_IN_PATCHES[channel.instrument as usize - 1])
+            };
+            sum += channel.generate_sample(&patch, &self.sine, tremolo_db, vibrato);
+        }
+        sum
+    }
+
+    pub fn output(&self) -> f32 {
+        self.output
+    }
+}
+
+impl Default for Vrc7Audio {
+    fn default() -> Self {
+        Vrc7Audio::new()
+    }
+}
+
+// 0.0 up to 1.0 and back over one period
+fn triangle(periods: f32) -> f32 {
+    let position = periods.fract();
+    if position < 0.5 {
+        position * 2.0
+    } else {
+        2.0 - position * 2.0
+    }
+}
+
+struct Patch {
+    // index 0 is the modulator, 1 the carrier
+    tremolo: [bool; 2],
+    vibrato: [bool; 2],
+    sustained: [bool; 2],
+    key_scale_rate: [bool; 2],
+    multiplier: [u8; 2],
+    key_scale_level: [u8; 2],
+    modulator_total_level: u8,
+    half_sine: [bool; 2],
+    feedback: u8,
+    attack_rate: [u8; 2],
+    decay_rate: [u8; 2],
+    sustain_level: [u8; 2],
+    release_rate: [u8; 2],
+}
+
+impl Patch {
+    fn decode(data: &[u8; 8]) -> Patch {
+        let operator = |f: fn(u8) -> u8| [f(data[0]), f(data[1])];
+        Patch {
+            tremolo: [data[0] & 0x80 != 0, data[1] & 0x80 != 0],
+            vibrato: [data[0] & 0x40 != 0, data[1] & 0x40 != 0],
+            sustained: [data[0] & 0x20 != 0, data[1] & 0x20 != 0],
+            key_scale_rate: [data[0] & 0x10 != 0, data[1] & 0x10 != 0],
+            multiplier: operator(|value| value & 0x0F),
+            key_scale_level: [data[2] >> 6, data[3] >> 6],
+            modulator_total_level: data[2] & 0x3F,
+            half_sine: [data[3] & 0x08 != 0, data[3] & 0x10 != 0],
+            feedback: data[3] & 0b111,
+            attack_rate: [data[4] >> 4, data[5] >> 4],
+            decay_rate: [data[4] & 0x0F, data[5] & 0x0F],
+            sustain_level: [data[6] >> 4, data[7] >> 4],
+            release_rate: [data[6] & 0x0F, data[7] & 0x0F],
+        }
+    }
+}
+
+struct FmChannel {
+    fnum: u16,
+    block: u8,
+    sustain: bool,
+    key_on: bool,
+    instrument: u8,
+    volume: u8,
+    operators: [Operator; 2],
+    feedback_history: [f32; 2],
+}
+
+impl FmChannel {
+    fn new() -> Self {
+        FmChannel {
+            fnum: 0,
+            block: 0,
+            sustain: false,
+            key_on: false,
+            instrument: 0,
+            volume: 0,
+            operators: [Operator::new(), Operator::new()],
+            feedback_history: [0.0; 2],
+        }
+    }
+
+    fn set_key_on(&mut self, key_on: bool) {
+        if key_on && !self.key_on {
+            for operator in self.operators.iter_mut() {
+                operator.phase = 0;
+                operator.state = EnvelopeState::Attack;
+            }
+            self.feedback_history = [0.0; 2];
+        } else if !key_on && self.key_on {
+            for operator in self.operators.iter_mut() {
+                operator.state = EnvelopeState::Release;
+            }
+        }
+        self.key_on = key_on;
+    }
+
+    fn generate_sample(&mut self, patch: &Patch, sine: &[f32], tremolo_db: f32, vibrato: f32) -> f32 {
+        let key_code = ((self.block << 1) | (self.fnum >> 8) as u8) as u32;
+        let key_scale_db = (KEY_SCALE_DB[(self.fnum >> 5) as usize] - 6.0 * (7 - self.block) as f32).max(0.0);
+
+        let mut values = [0.0; 2];
+        for op in 0..2 {
+            let operator = &mut self.operators[op];
+            let rate_offset = if patch.key_scale_rate[op] { key_code } else { key_code >> 2 };
+            operator.clock_envelope(patch, op, rate_offset, self.sustain);
+
+            let mut increment = ((self.fnum as u32) << self.block) * MULTIPLIERS_X2[patch.multiplier[op] as usize] / 2;
+            if patch.vibrato[op] {
+                increment = (increment as f32 * vibrato) as u32;
+            }
+            operator.phase = (operator.phase + increment) & ((1 << PHASE_BITS) - 1);
+
+            let mut attenuation_db = operator.attenuation * ATTENUATION_STEP_DB;
+            attenuation_db += match patch.key_scale_level[op] {
+                0 => 0.0,
+                level => key_scale_db / (1 << (3 - level)) as f32,
+            };
+            if patch.tremolo[op] {
+                attenuation_db += tremolo_db;
+            }
+            attenuation_db += if op == 0 {
+                patch.modulator_total_level as f32 * 0.75
+            } else {
+                self.volume as f32 * 3.0
+            };
+
+            // the modulator feeds back into itself with up to 4 pi, and into the carrier with 8 pi
+            let modulation = if op == 0 {
+                if patch.feedback == 0 {
+                    0.0
+                } else {
+                    let average = (self.feedback_history[0] + self.feedback_history[1]) / 2.0;
+                    average * (2 * WAVE_LENGTH) as f32 / (1 << (7 - patch.feedback)) as f32
+                }
+            } else {
+                values[0] * (4 * WAVE_LENGTH) as f32
+            };
+
+            let index = ((operator.phase >> (PHASE_BITS - WAVE_BITS)) as i32 + modulation as i32).rem_euclid(WAVE_LENGTH);
+            let mut wave = sine[index as usize];
+            if patch.half_sine[op] && wave < 0.0 {
+                wave = 0.0;
+            }
+            values[op] = if operator.state == EnvelopeState::Off || operator.attenuation >= MAX_ATTENUATION {
+                0.0
+            } else {
+                wave * 10f32.powf(-attenuation_db / 20.0)
+            };
+        }
+
+        self.feedback_history = [self.feedback_history[1], values[0]];
+        values[1]
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum EnvelopeState {
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+    Off,
+}
+
+struct Operator {
+    phase: u32,
+    state: EnvelopeState,
+    attenuation: f32,
+}
+
+impl Operator {
+    fn new() -> Self {
+        Operator {
+            phase: 0,
+            state: EnvelopeState::Off,
+            attenuation: MAX_ATTENUATION,
+        }
+    }
+
+    // Each rate step of 4 doubles the envelope speed, the low two bits add quarters
+    fn rate_step(rate: u8, rate_offset: u32) -> (u32, f32) {
+        if rate == 0 {
+            return (0, 0.0);
+        }
+        let rate = (rate as u32 * 4 + rate_offset).min(63);
+        (rate, (4 + (rate & 3)) as f32 * (1 << (rate >> 2)) as f32 / 65536.0)
+    }
+
+    fn clock_envelope(&mut self, patch: &Patch, op: usize, rate_offset: u32, sustain: bool) {
+        match self.state {
+            EnvelopeState::Attack => {
+                let (rate, step) = Operator::rate_step(patch.attack_rate[op], rate_offset);
+                if rate >= 60 {
+                    self.attenuation = 0.0;
+                } else {
+                    // the attack curve is exponential, fast at first and slowing down near full volume
+                    self.attenuation -= step * 8.0 * (self.attenuation / 16.0 + 1.0);
+                }
+                if self.attenuation <= 0.0 {
+                    self.attenuation = 0.0;
+                    self.state = EnvelopeState::Decay;
+                }
+            }
+            EnvelopeState::Decay => {
+                let sustain_level = patch.sustain_level[op] as f32 * 8.0;
+                self.attenuation += Operator::rate_step(patch.decay_rate[op], rate_offset).1;
+                if self.attenuation >= sustain_level {
+                    self.attenuation = sustain_level;
+                    self.state = EnvelopeState::Sustain;
+                }
+            }
+            EnvelopeState::Sustain => {
+                // percussive patches keep fading with the release rate while the key is held
+                if !patch.sustained[op] {
+                    self.attenuation += Operator::rate_step(patch.release_rate[op], rate_offset).1;
+                }
+            }
+            EnvelopeState::Release => {
+                let release_rate = if sustain {
+                    5
+                } else if patch.sustained[op] {
+                    patch.release_rate[op]
+                } else {
+                    7
+                };
+                self.attenuation += Operator::rate_step(release_rate, rate_offset).1;
+            }
+            EnvelopeState::Off => {}
+        }
+
+        if self.attenuation >= MAX_ATTENUATION {
+            self.attenuation = MAX_ATTENUATION;
+            if self.state != EnvelopeState::Attack {
+                self.state = EnvelopeState::Off;
+            }
+        }
+    }
+}
+
+impl SaveState for Vrc7Audio {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.register);
+        state.write_bytes(&self.custom_patch);
+        for channel in self.channels.iter() {
+            channel.save_state(state);
+        }
+        state.write_u8(self.cycles_until_sample);
+        state.write_u32(self.sample_count);
+        state.write_f32(self.output);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.register = state.read_u8()?;
+        state.read_bytes_into(&mut self.custom_patch)?;
+        for channel in self.channels.iter_mut() {
+            channel.load_state(state)?;
+        }
+        self.cycles_until_sample = state.read_u8()?;
+        self.sample_count = state.read_u32()?;
+        self.output = state.read_f32()?;
+        Ok(())
+    }
+}
+
+impl SaveState for FmChannel {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u16(self.fnum);
+        state.write_u8(self.block);
+        state.write_bool(self.sustain);
+        state.write_bool(self.key_on);
+        state.write_u8(self.instrument);
+        state.write_u8(self.volume);
+        for operator in self.operators.iter() {
+            operator.save_state(state);
+        }
+        for value in self.feedback_history.iter() {
+            state.write_f32(*value);
+        }
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.fnum = state.read_u16()?;
+        self.block = state.read_u8()?;
+        self.sustain = state.read_bool()?;
+        self.key_on = state.read_bool()?;
+        self.instrument = state.read_u8()?;
+        self.volume = state.read_u8()?;
+        for operator in self.operators.iter_mut() {
+            operator.load_state(state)?;
+        }
+        for value in self.feedback_history.iter_mut() {
+            *value = state.read_f32()?;
+        }
+        Ok(())
+    }
+}
+
+impl SaveState for Operator {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u32(self.phase);
+        state.write_u8(self.state as u8);
+        state.write_f32(self.attenuation);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.phase = state.read_u32()?;
+        self.state = match state.read_u8()? {
+            0 => EnvelopeState::Attack,
+            1 => EnvelopeState::Decay,
+            2 => EnvelopeState::Sustain,
+            3 => EnvelopeState::Release,
+            4 => EnvelopeState::Off,
+            value => return Err(format!("Invalid envelope state {} in save state", value)),
+        };
+        self.attenuation = state.read_f32()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::components::input_device::InputDevice;
+use crate::components::ppu::NesPPU;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Family_BASIC_Keyboard
+// Keys by row, column and the $4017 data line they report on, starting at D1
+#[rustfmt::skip]
+const KEY_MATRIX: [[[&str; 4]; 2]; FamilyKeyboard::ROWS] = [
+    [["]", "[", "RETURN", "F8"], ["STOP", "YEN", "RSHIFT", "KANA"]],
+    [[";", ":", "@", "F7"], ["^", "-", "/", "_"]],
+    [["K", "L", "O", "F6"], ["0", "P", ",", "."]],
+    [["J", "U", "I", "F5"], ["8", "9", "N", "M"]],
+    [["H", "G", "Y", "F4"], ["6", "7", "V", "B"]],
+    [["D", "R", "T", "F3"], ["4", "5", "C", "F"]],
+    [["A", "S", "W", "F2"], ["3", "E", "Z", "X"]],
+    [["CTR", "Q", "ESC", "F1"], ["2", "1", "GRPH", "LSHIFT"]],
+    [["LEFT", "RIGHT", "UP", "CLR"], ["INS", "DEL", "SPACE", "DOWN"]],
+];
+
+pub struct FamilyKeyboard {
+    pressed: [[u8; 2]; FamilyKeyboard::ROWS],
+    row: usize,
+    column: usize,
+    enabled: bool,
+}
+
+impl FamilyKeyboard {
+    const ROWS: usize = 9;
+    const NO_KEYS: u8 = 0b0001_1110;
+
+    pub fn new() -> Self {
+        FamilyKeyboard {
+            pressed: [[0; 2]; FamilyKeyboard::ROWS],
+            row: 0,
+            column: 0,
+            enabled: false,
+        }
+    }
+
+    // Keys are named after their label, e.g. "A", "RETURN", "F1" or "SPACE"
+    pub fn set_key_pressed(&mut self, key: &str, pressed: bool) -> Result<(), String> {
+        let (row, column, bit) = FamilyKeyboard::key_position(key)
+            .ok_or(format!("Family BASIC keyboard has no key {}", key))?;
+        if pressed {
+            self.pressed[row][column] |= 1 << bit;
+        } else {
+            self.pressed[row][column] &= !(1 << bit);
+        }
+        Ok(())
+    }
+
+    pub fn release_all_keys(&mut self) {
+        self.pressed = [[0; 2]; FamilyKeyboard::ROWS];
+    }
+
+    fn key_position(key: &str) -> Option<(usize, usize, usize)> {
+        for (row, columns) in KEY_MATRIX.iter().enumerate() {
+            for (column, keys) in columns.iter().enumerate() {
+                if let Some(bit) = keys.iter().position(|name| name.eq_ignore_ascii_case(key)) {
+                    return Some((row, column, bit));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Default for FamilyKeyboard {
+    fn default() -> Self {
+        FamilyKeyboard::new()
+    }
+}
+
+impl SaveState for FamilyKeyboard {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_usize(self.row);
+        state.write_usize(self.column);
+        state.write_bool(self.enabled);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.row = state.read_usize()?;
+        self.column = state.read_usize()? & 1;
+        self.enabled = state.read_bool()?;
+        Ok(())
+    }
+}
+
+impl InputDevice for FamilyKeyboard {
+    // bit 0 goes back to the first row, bit 1 picks the column and
+    // moving from the second column back to the first advances to the next row
+    fn write_port(&mut self, data: u8) {
+        self.enabled = data & 0b100 != 0;
+        let column = ((data >> 1) & 1) as usize;
+        if data & 1 != 0 {
+            self.row = 0;
+        } else if self.column == 1 && column == 0 {
+            self.row += 1;
+        }
+        self.column = column;
+    }
+
+    // pressed keys pull their data line low
+    fn read_port(&mut self, port: usize, _ppu: &NesPPU) -> u8 {
+        if port == 0 || !self.enabled {
+            return 0;
+        }
+        match self.pressed.get(self.row) {
+            Some(columns) => !(columns[self.column] << 1) & FamilyKeyboard::NO_KEYS,
+            None => FamilyKeyboard::NO_KEYS,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::components::crc32::update_crc32;
+
+#[derive(Clone)]
+pub struct Frame {
+    pub data: Vec<u8>,
+}
+
+impl Frame {
+    pub const WIDTH: usize = 256;
+    pub const HEIGHT: usize = 240;
+
+    pub fn new() -> Self {
+        Frame {
+            data: vec![0; Frame::WIDTH * Frame::HEIGHT * 3],
+        }
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, rgb: (u8, u8, u8)) {
+        let base = (y * Frame::WIDTH + x) * 3;
+        self.data[base] = rgb.0;
+        self.data[base + 1] = rgb.1;
+        self.data[base + 2] = rgb.2;
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> (u8, u8, u8) {
+        let base = (y * Frame::WIDTH + x) * 3;
+        (self.data[base], self.data[base + 1], self.data[base + 2])
+    }
+
+    //https://www.w3.org/TR/png/
+    // The pixels go into stored (uncompressed) deflate blocks, which keeps the encoder tiny
+    pub fn to_png(&self) -> Vec<u8> {
+        self.to_png_scaled(1)
+    }
+
+    // Every pixel becomes a `scale` by `scale` square, the way the window shows it
+    pub fn to_png_scaled(&self, scale: usize) -> Vec<u8> {
+        let scale = scale.max(1);
+        let (width, height) = (Frame::WIDTH * scale, Frame::HEIGHT * scale);
+        let mut scanlines = Vec::with_capacity(height * (width * 3 + 1));
+        for row in self.data.chunks(Frame::WIDTH * 3) {
+            let scaled: Vec<u8> = row
+                .chunks(3)
+                .flat_map(|pixel| std::iter::repeat_n(pixel, scale).flatten().copied())
+                .collect();
+            for _ in 0..scale {
+                scanlines.push(0); // filter type None
+                scanlines.extend_from_slice(&scaled);
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]); // 8 bit RGB, deflate, no filtering, no interlace
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        write_png_chunk(&mut png, b"IHDR", &header);
+        write_png_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend((!update_crc32(update_crc32(0xFFFF_FFFF, kind), data)).to_be_bytes());
+}
+
+//https://www.rfc-editor.org/rfc/rfc1950 and https://www.rfc-editor.org/rfc/rfc1951#section-3.2.4
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xFFFF;
+
+    let mut result = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        result.push(last as u8);
+        result.extend((block.len() as u16).to_le_bytes());
+        result.extend((!(block.len() as u16)).to_le_bytes());
+        result.extend(block);
+    }
+    result.extend(adler32(data).to_be_bytes());
+    result
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for value in data {
+        a = (a + *value as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Frame::new()
+    }
+}
@@ -0,0 +1,159 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::components::arkanoid_paddle::ArkanoidPaddle;
+use crate::components::bus::Bus;
+use crate::components::family_keyboard::FamilyKeyboard;
+use crate::components::joypad::Joypad;
+use crate::components::multitap::{Multitap, MultitapMode};
+use crate::components::power_pad::PowerPad;
+use crate::components::ppu::NesPPU;
+use crate::components::save_state::SaveState;
+use crate::components::zapper::Zapper;
+
+//https://www.nesdev.org/wiki/Input_devices
+// Save states hold the serial state of a device, never the buttons the player is holding
+pub trait InputDevice: SaveState {
+    // Writes to $4016, bit 0 is the strobe every port sees, bits 1 and 2 only reach the expansion port
+    fn write_port(&mut self, data: u8);
+
+    // The data lines the device drives on a read of $4016 (port 0) or $4017 (port 1)
+    fn read_port(&mut self, port: usize, ppu: &NesPPU) -> u8;
+}
+
+pub type SharedInputDevice = Rc<RefCell<dyn InputDevice>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputPort {
+    One,
+    Two,
+    // the Famicom expansion port sees both registers
+    Expansion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputSetup {
+    Joypads,
+    FourScore,
+    FamicomFourPlayers,
+    Zapper,
+    ArkanoidNes,
+    ArkanoidFamicom,
+    PowerPad,
+    FamilyKeyboard,
+}
+
+impl InputSetup {
+    //https://www.nesdev.org/wiki/NES_2.0#Default_Expansion_Device
+    pub fn from_expansion_device(device: u8) -> Option<InputSetup> {
+        match device {
+            0x01 => Some(InputSetup::Joypads),
+            0x02 => Some(InputSetup::FourScore),
+            0x03 => Some(InputSetup::FamicomFourPlayers),
+            0x08 => Some(InputSetup::Zapper),
+            0x0B | 0x0C => Some(InputSetup::PowerPad),
+            0x0F => Some(InputSetup::ArkanoidNes),
+            0x10 => Some(InputSetup::ArkanoidFamicom),
+            0x23 => Some(InputSetup::FamilyKeyboard),
+            _ => None,
+        }
+    }
+}
+
+// The devices plugged in for a setup, kept typed so the host can feed them input
+pub struct InputDevices {
+    pub joypads: Vec<Rc<RefCell<Joypad>>>,
+    pub multitap: Option<Rc<RefCell<Multitap>>>,
+    pub zapper: Option<Rc<RefCell<Zapper>>>,
+    pub arkanoid_paddle: Option<Rc<RefCell<ArkanoidPaddle>>>,
+    pub power_pad: Option<Rc<RefCell<PowerPad>>>,
+    pub family_keyboard: Option<Rc<RefCell<FamilyKeyboard>>>,
+}
+
+impl InputDevices {
+    pub fn connect(setup: InputSetup, bus: &mut Bus) -> InputDevices {
+        let mut devices = InputDevices {
+            joypads: vec![],
+            multitap: None,
+            zapper: None,
+            arkanoid_paddle: None,
+            power_pad: None,
+            family_keyboard: None,
+        };
+        bus.disconnect_input_device(InputPort::One);
+        bus.disconnect_input_device(InputPort::Two);
+        bus.disconnect_input_device(InputPort::Expansion);
+
+        let first_joypad_only = matches!(
+            setup,
+            InputSetup::Zapper | InputSetup::ArkanoidNes | InputSetup::PowerPad
+        );
+        match setup {
+            InputSetup::FourScore | InputSetup::FamicomFourPlayers => {
+                let mode = if setup == InputSetup::FourScore { MultitapMode::FourScore } else { MultitapMode::Famicom };
+                let multitap = Rc::new(RefCell::new(Multitap::new(mode)));
+                bus.connect_input_device(InputPort::One, multitap.clone());
+                bus.connect_input_device(InputPort::Two, multitap.clone());
+                devices.multitap = Some(multitap);
+                return devices;
+            }
+            _ => devices.connect_joypads(bus, if first_joypad_only { 1 } else { 2 }),
+        }
+
+        match setup {
+            InputSetup::Zapper => {
+                let zapper = Rc::new(RefCell::new(Zapper::new()));
+                bus.connect_input_device(InputPort::Two, zapper.clone());
+                devices.zapper = Some(zapper);
+            }
+            InputSetup::ArkanoidNes | InputSetup::ArkanoidFamicom => {
+                let (paddle, port) = if setup == InputSetup::ArkanoidNes {
+                    (ArkanoidPaddle::nes(), InputPort::Two)
+                } else {
+                    (ArkanoidPaddle::famicom(), InputPort::Expansion)
+                };
+                let paddle = Rc::new(RefCell::new(paddle));
+                bus.connect_input_device(port, paddle.clone());
+                devices.arkanoid_paddle = Some(paddle);
+            }
+            InputSetup::PowerPad => {
+                let power_pad = Rc::new(RefCell::new(PowerPad::new()));
+                bus.connect_input_device(InputPort::Two, power_pad.clone());
+                devices.power_pad = Some(power_pad);
+            }
+            InputSetup::FamilyKeyboard => {
+                let keyboard = Rc::new(RefCell::new(FamilyKeyboard::new()));
+                bus.connect_input_device(InputPort::Expansion, keyboard.clone());
+                devices.family_keyboard = Some(keyboard);
+            }
+            _ => {}
+        }
+
+        devices
+    }
+
+    // Players are counted from 0, no matter if their joypad is plugged directly or through a multitap
+    pub fn set_joypad_buttons(&self, player: usize, buttons: u8) {
+        if let Some(multitap) = &self.multitap {
+            multitap.borrow_mut().joypad(player).set_buttons(buttons);
+        } else if let Some(joypad) = self.joypads.get(player) {
+            joypad.borrow_mut().set_buttons(buttons);
+        }
+    }
+
+    pub fn joypad_buttons(&self, player: usize) -> u8 {
+        if let Some(multitap) = &self.multitap {
+            multitap.borrow_mut().joypad(player).buttons()
+        } else {
+            self.joypads.get(player).map_or(0, |joypad| joypad.borrow().buttons())
+        }
+    }
+
+    fn connect_joypads(&mut self, bus: &mut Bus, count: usize) {
+        for port in [InputPort::One, InputPort::Two].iter().take(count) {
+            let joypad = Rc::new(RefCell::new(Joypad::new()));
+            bus.connect_input_device(*port, joypad.clone());
+            self.joypads.push(joypad);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::components::input_device::InputDevice;
+use crate::components::ppu::NesPPU;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Standard_controller
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoypadButton {
+    A,
+    B,
+    Select,
+    Start,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl JoypadButton {
+    // in the order the shift register reports them
+    pub const ALL: [JoypadButton; 8] = [
+        JoypadButton::A,
+        JoypadButton::B,
+        JoypadButton::Select,
+        JoypadButton::Start,
+        JoypadButton::Up,
+        JoypadButton::Down,
+        JoypadButton::Left,
+        JoypadButton::Right,
+    ];
+
+    pub fn bit(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+pub struct Joypad {
+    strobe: bool,
+    button_index: u8,
+    button_status: u8,
+}
+
+impl Default for Joypad {
+    fn default() -> Self {
+        Joypad::new()
+    }
+}
+
+impl Joypad {
+    pub fn new() -> Self {
+        Joypad {
+            strobe: false,
+            button_index: 0,
+            button_status: 0,
+        }
+    }
+
+    pub fn write(&mut self, data: u8) {
+        self.strobe = data & 1 == 1;
+        if self.strobe {
+            self.button_index = 0;
+        }
+    }
+
+    pub fn read(&mut self) -> u8 {
+        // an official controller reports 1 once all 8 buttons were shifted out
+        if self.button_index > 7 {
+            return 1;
+        }
+
+        let response = (self.button_status >> self.button_index) & 1;
+        if !self.strobe {
+            self.button_index += 1;
+        }
+        response
+    }
+
+    pub fn set_button_pressed_status(&mut self, button: JoypadButton, pressed: bool) {
+        if pressed {
+            self.button_status |= button.bit();
+        } else {
+            self.button_status &= !button.bit();
+        }
+    }
+
+    // Replaces the state of all buttons at once, bit 0 is A up to bit 7 being Right
+    pub fn set_buttons(&mut self, button_status: u8) {
+        self.button_status = button_status;
+    }
+
+    pub fn buttons(&self) -> u8 {
+        self.button_status
+    }
+
+    pub fn is_pressed(&self, button: JoypadButton) -> bool {
+        self.button_status & button.bit() != 0
+    }
+}
+
+impl SaveState for Joypad {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bool(self.strobe);
+        state.write_u8(self.button_index);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.strobe = state.read_bool()?;
+        self.button_index = state.read_u8()?;
+        Ok(())
+    }
+}
+
+impl InputDevice for Joypad {
+    fn write_port(&mut self, data: u8) {
+        self.write(data);
+    }
+
+    fn read_port(&mut self, _port: usize, _ppu: &NesPPU) -> u8 {
+        self.read()
+    }
+}
@@ -0,0 +1,196 @@
+use std::cell::RefCell;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::ppu;
+use crate::components::mapper::bandai_fcg::BandaiFcg;
+use crate::components::mapper::bnrom::Bnrom;
+use crate::components::mapper::camerica::Camerica;
+use crate::components::mapper::color_dreams::ColorDreams;
+use crate::components::mapper::fme7::Fme7;
+use crate::components::mapper::mmc2::Mmc2;
+use crate::components::mapper::mmc5::Mmc5;
+use crate::components::mapper::namco108::Namco108;
+use crate::components::mapper::namco163::Namco163;
+use crate::components::mapper::nrom::Nrom;
+use crate::components::mapper::taito_tc0190::TaitoTc0190;
+use crate::components::mapper::vrc4::Vrc4;
+use crate::components::mapper::vrc6::Vrc6;
+use crate::components::mapper::vrc7::Vrc7;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+pub mod nrom;
+pub mod bandai_fcg;
+pub mod bnrom;
+pub mod camerica;
+pub mod color_dreams;
+pub mod eeprom;
+pub mod fds;
+pub mod nsf;
+pub mod namco108;
+pub mod namco163;
+pub mod fme7;
+pub mod mmc2;
+pub mod mmc5;
+pub mod taito_tc0190;
+pub mod vrc4;
+pub mod vrc6;
+pub mod vrc7;
+pub mod vrc_irq;
+
+// The cartridge is shared between the CPU bus (PRG space) and the PPU (CHR space)
+pub type SharedMapper = Rc<RefCell<dyn Mapper>>;
+
+// Save states cover the registers and RAM of the board, never its ROM
+pub trait Mapper: SaveState {
+    // CPU address space $4020-$FFFF
+    fn read_prg(&mut self, addr: u16) -> u8 {
+        self.peek_prg(addr)
+    }
+
+    // What a read would return, without the side effects some registers have when read. For
+    // debuggers and traces, boards with such registers override read_prg as well.
+    fn peek_prg(&self, addr: u16) -> u8;
+
+    fn write_prg(&mut self, addr: u16, data: u8);
+
+    // PPU address space $0000-$1FFF
+    fn read_chr(&mut self, addr: u16) -> u8;
+
+    fn write_chr(&mut self, addr: u16, data: u8);
+
+    fn mirroring(&self) -> Mirroring;
+
+    // Called with the CPU cycles that passed, the bus hands them over one at a time
+    fn tick(&mut self, _cycles: u8) {}
+
+    // Called when the console is switched on. Banks, IRQ counters and sound chips go back to how
+    // the board starts up, RAM on the board keeps what was in it.
+    fn power_on(&mut self);
+
+    // Called when the reset button is pressed. The cartridge slot has no reset line, but some
+    // boards notice the CPU clock stopping while the button is held.
+    fn reset(&mut self) {}
+
+    fn irq_pending(&self) -> bool {
+        false
+    }
+
+    // PPU address space $2000-$2FFF. The console's 2KB of nametable RAM (CIRAM) is handed in,
+    // most boards only pick how it's mirrored while some bring nametables of their own
+    fn read_nametable(&mut self, addr: u16, ciram: &[u8; 2048]) -> u8 {
+        ciram[ppu::mirror_nametable_addr(self.mirroring(), addr) as usize]
+    }
+
+    fn write_nametable(&mut self, addr: u16, data: u8, ciram: &mut [u8; 2048]) {
+        ciram[ppu::mirror_nametable_addr(self.mirroring(), addr) as usize] = data;
+    }
+
+    // Called when the PPU starts a scanline, 0-239 are visible and 261 is the pre-render line
+    fn ppu_scanline(&mut self, _scanline: u16, _rendering: bool) {}
+
+    // Lets the mapper tell background fetches from sprite fetches while a scanline renders
+    fn ppu_fetch_phase(&mut self, _phase: PpuFetchPhase) {}
+
+    // Writes to $2000 and $2001, which some boards snoop
+    fn ppu_register_written(&mut self, _addr: u16, _data: u8) {}
+
+    // Sound chips on the cartridge are mixed on top of the APU, on the same scale as its output
+    fn expansion_audio(&self) -> f32 {
+        0.0
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PpuFetchPhase {
+    Background,
+    Sprites,
+    // $2007 accesses and anything outside of rendering
+    Idle,
+}
+
+impl SaveState for PpuFetchPhase {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(*self as u8);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        *self = match state.read_u8()? {
+            0 => PpuFetchPhase::Background,
+            1 => PpuFetchPhase::Sprites,
+            2 => PpuFetchPhase::Idle,
+            value => return Err(format!("Invalid fetch phase {} in save state", value)),
+        };
+        Ok(())
+    }
+}
+
+// The pattern memory of a board. Cartridges that come without CHR ROM have 8KB of CHR RAM
+// instead, only that takes writes and goes into save states.
+pub struct Chr {
+    data: Vec<u8>,
+    is_ram: bool,
+}
+
+impl Chr {
+    const RAM_SIZE: usize = 0x2000;
+
+    pub fn new(chr_rom: Vec<u8>) -> Self {
+        if chr_rom.is_empty() {
+            Chr { data: vec![0; Chr::RAM_SIZE], is_ram: true }
+        } else {
+            Chr { data: chr_rom, is_ram: false }
+        }
+    }
+
+    pub fn write(&mut self, offset: usize, data: u8) {
+        if self.is_ram {
+            self.data[offset] = data;
+        }
+    }
+}
+
+impl Deref for Chr {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl SaveState for Chr {
+    fn save_state(&self, state: &mut StateWriter) {
+        if self.is_ram {
+            state.write_bytes(&self.data);
+        }
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        if self.is_ram {
+            state.read_bytes_into(&mut self.data)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn for_rom(rom: Rom) -> Result<SharedMapper, String> {
+    let mapper: SharedMapper = match rom.mapper {
+        0 => Rc::new(RefCell::new(Nrom::new(rom))),
+        5 => Rc::new(RefCell::new(Mmc5::new(rom))),
+        9 | 10 => Rc::new(RefCell::new(Mmc2::new(rom))),
+        11 => Rc::new(RefCell::new(ColorDreams::new(rom))),
+        16 | 159 => Rc::new(RefCell::new(BandaiFcg::new(rom))),
+        19 => Rc::new(RefCell::new(Namco163::new(rom)?)),
+        21 | 22 | 23 | 25 => Rc::new(RefCell::new(Vrc4::new(rom))),
+        24 | 26 => Rc::new(RefCell::new(Vrc6::new(rom))),
+        33 | 48 => Rc::new(RefCell::new(TaitoTc0190::new(rom))),
+        34 => Rc::new(RefCell::new(Bnrom::new(rom))),
+        69 => Rc::new(RefCell::new(Fme7::new(rom))),
+        71 => Rc::new(RefCell::new(Camerica::new(rom))),
+        85 => Rc::new(RefCell::new(Vrc7::new(rom))),
+        206 => Rc::new(RefCell::new(Namco108::new(rom))),
+        _ => return Err(format!("Mapper {} is not supported", rom.mapper)),
+    };
+    Ok(mapper)
+}
@@ -0,0 +1,220 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::eeprom::{Eeprom, EepromKind};
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Bandai_FCG_board
+// The FCG-1/2 chips decode their registers at $6000-$7FFF and load the IRQ counter directly,
+// the LZ93D50 moved them to $8000-$FFFF and reloads the counter from a latch. Mapper 16
+// without a submapper can be either, so both ranges are decoded.
+pub struct BandaiFcg {
+    prg_rom: Vec<u8>,
+    chr: Chr,
+    registers_at_6000: bool,
+    registers_at_8000: bool,
+
+    chr_banks: [u8; 8],
+    prg_bank: u8,
+    mirroring: Mirroring,
+    initial_mirroring: Mirroring,
+
+    irq_enabled: bool,
+    irq_counter: u16,
+    irq_latch: u16,
+    irq_pending: bool,
+
+    eeprom: Option<Eeprom>,
+}
+
+impl BandaiFcg {
+
+    pub fn new(rom: Rom) -> Self {
+        let (registers_at_6000, registers_at_8000, eeprom) = match (rom.mapper, rom.submapper) {
+            (159, _) => (false, true, Some(EepromKind::X24C01)),
+            (_, 4) => (true, false, None),
+            (_, 5) => (false, true, Some(EepromKind::X24C02)),
+            _ => (true, true, Some(EepromKind::X24C02)),
+        };
+        BandaiFcg {
+            prg_rom: rom.prg_rom,
+            chr: Chr::new(rom.chr_rom),
+            registers_at_6000,
+            registers_at_8000,
+            chr_banks: [0; 8],
+            prg_bank: 0,
+            mirroring: rom.screen_mirroring,
+            initial_mirroring: rom.screen_mirroring,
+            irq_enabled: false,
+            irq_counter: 0,
+            irq_latch: 0,
+            irq_pending: false,
+            eeprom: eeprom.map(Eeprom::new),
+        }
+    }
+
+    // Battery backed EEPROM contents, for the frontend to persist
+    pub fn eeprom_data(&self) -> Option<&[u8]> {
+        self.eeprom.as_ref().map(|eeprom| eeprom.data())
+    }
+
+    pub fn load_eeprom(&mut self, data: &[u8]) -> Result<(), String> {
+        match self.eeprom.as_mut() {
+            Some(eeprom) => eeprom.load(data),
+            None => Err("This board has no EEPROM".to_string()),
+        }
+    }
+
+    fn prg_offset(&self, bank: usize, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / 0x4000;
+        (bank % bank_count) * 0x4000 + (addr as usize % 0x4000)
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let bank = self.chr_banks[addr as usize / 0x400] as usize;
+        let bank_count = self.chr.len() / 0x400;
+        (bank % bank_count) * 0x400 + (addr as usize % 0x400)
+    }
+
+    fn write_register(&mut self, register: u16, data: u8) {
+        match register {
+            0x0..=0x7 => self.chr_banks[register as usize] = data,
+            0x8 => self.prg_bank = data & 0x0F,
+            0x9 => {
+                self.mirroring = match data & 0b11 {
+                    0 => Mirroring::VERTICAL,
+                    1 => Mirroring::HORIZONTAL,
+                    2 => Mirroring::SINGLE_SCREEN_LOWER,
+                    _ => Mirroring::SINGLE_SCREEN_UPPER,
+                };
+            }
+            0xA => {
+                self.irq_enabled = data & 1 != 0;
+                self.irq_pending = false;
+                if self.registers_at_8000 {
+                    self.irq_counter = self.irq_latch;
+                }
+            }
+            0xB => {
+                self.irq_latch = (self.irq_latch & 0xFF00) | data as u16;
+                if self.registers_at_6000 {
+                    self.irq_counter = self.irq_latch;
+                }
+            }
+            0xC => {
+                self.irq_latch = (self.irq_latch & 0x00FF) | (data as u16) << 8;
+                if self.registers_at_6000 {
+                    self.irq_counter = self.irq_latch;
+                }
+            }
+            0xD => {
+                if let Some(eeprom) = self.eeprom.as_mut() {
+                    eeprom.write(data & 0b0010_0000 != 0, data & 0b0100_0000 != 0);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Mapper for BandaiFcg {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => 0,
+            // only the EEPROM data line is readable
+            0x6000..=0x7FFF => match &self.eeprom {
+                Some(eeprom) => (eeprom.output() as u8) << 4,
+                None => 0,
+            },
+            0x8000..=0xBFFF => self.prg_rom[self.prg_offset(self.prg_bank as usize, addr)],
+            0xC000..=0xFFFF => {
+                let last_bank = self.prg_rom.len() / 0x4000 - 1;
+                self.prg_rom[self.prg_offset(last_bank, addr)]
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x5FFF => {}
+            0x6000..=0x7FFF if self.registers_at_6000 => self.write_register(addr & 0x0F, data),
+            0x8000..=0xFFFF if self.registers_at_8000 => self.write_register(addr & 0x0F, data),
+            0x6000..=0xFFFF => {}
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.chr_banks = [0; 8];
+        self.prg_bank = 0;
+        self.mirroring = self.initial_mirroring;
+        self.irq_enabled = false;
+        self.irq_counter = 0;
+        self.irq_latch = 0;
+        self.irq_pending = false;
+        if let Some(eeprom) = &mut self.eeprom {
+            eeprom.power_on();
+        }
+    }
+
+    // The counter is checked before it's decremented, so a count of N fires after N+1 cycles
+    fn tick(&mut self, cycles: u8) {
+        for _ in 0..cycles {
+            if !self.irq_enabled {
+                return;
+            }
+            if self.irq_counter == 0 {
+                self.irq_pending = true;
+            }
+            self.irq_counter = self.irq_counter.wrapping_sub(1);
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq_pending
+    }
+}
+
+impl SaveState for BandaiFcg {
+    fn save_state(&self, state: &mut StateWriter) {
+        self.chr.save_state(state);
+        state.write_bytes(&self.chr_banks);
+        state.write_u8(self.prg_bank);
+        self.mirroring.save_state(state);
+        state.write_bool(self.irq_enabled);
+        state.write_u16(self.irq_counter);
+        state.write_u16(self.irq_latch);
+        state.write_bool(self.irq_pending);
+        if let Some(eeprom) = &self.eeprom {
+            eeprom.save_state(state);
+        }
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.chr.load_state(state)?;
+        state.read_bytes_into(&mut self.chr_banks)?;
+        self.prg_bank = state.read_u8()?;
+        self.mirroring.load_state(state)?;
+        self.irq_enabled = state.read_bool()?;
+        self.irq_counter = state.read_u16()?;
+        self.irq_latch = state.read_u16()?;
+        self.irq_pending = state.read_bool()?;
+        if let Some(eeprom) = &mut self.eeprom {
+            eeprom.load_state(state)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/INES_Mapper_034
+// Mapper 34 is two unrelated boards: BNROM switches 32KB of PRG through $8000-$FFFF with CHR RAM,
+// the NINA-001 has PRG RAM and its registers at $7FFD-$7FFF with two switchable 4KB CHR banks.
+// Submapper 1 is NINA-001 and 2 is BNROM, without one more than 8KB of CHR ROM means NINA-001.
+pub struct Bnrom {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Chr,
+    is_nina001: bool,
+    mirroring: Mirroring,
+    prg_bank: u8,
+    chr_banks: [u8; 2],
+}
+
+impl Bnrom {
+    const PRG_RAM_SIZE: usize = 0x2000;
+
+    pub fn new(rom: Rom) -> Self {
+        let is_nina001 = match rom.submapper {
+            1 => true,
+            2 => false,
+            _ => rom.chr_rom.len() > 0x2000,
+        };
+        Bnrom {
+            prg_rom: rom.prg_rom,
+            prg_ram: vec![0; Bnrom::PRG_RAM_SIZE],
+            chr: Chr::new(rom.chr_rom),
+            is_nina001,
+            mirroring: rom.screen_mirroring,
+            prg_bank: 0,
+            chr_banks: [0, 1],
+        }
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        if !self.is_nina001 {
+            return addr as usize % self.chr.len();
+        }
+        let bank = self.chr_banks[addr as usize / 0x1000] as usize;
+        (bank * 0x1000 + (addr as usize % 0x1000)) % self.chr.len()
+    }
+}
+
+impl Mapper for Bnrom {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => 0,
+            0x6000..=0x7FFF if self.is_nina001 => self.prg_ram[(addr - 0x6000) as usize],
+            0x6000..=0x7FFF => 0,
+            0x8000..=0xFFFF => {
+                let offset = self.prg_bank as usize * 0x8000 + (addr as usize - 0x8000);
+                self.prg_rom[offset % self.prg_rom.len()]
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x5FFF => {}
+            0x6000..=0x7FFF if self.is_nina001 => {
+                self.prg_ram[(addr - 0x6000) as usize] = data;
+                match addr {
+                    0x7FFD => self.prg_bank = data & 1,
+                    0x7FFE => self.chr_banks[0] = data & 0x0F,
+                    0x7FFF => self.chr_banks[1] = data & 0x0F,
+                    _ => {}
+                }
+            }
+            0x6000..=0x7FFF => {}
+            0x8000..=0xFFFF if !self.is_nina001 => self.prg_bank = data,
+            0x8000..=0xFFFF => {}
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.prg_bank = 0;
+        self.chr_banks = [0, 1];
+    }
+}
+
+impl SaveState for Bnrom {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        self.chr.save_state(state);
+        state.write_u8(self.prg_bank);
+        state.write_bytes(&self.chr_banks);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        self.chr.load_state(state)?;
+        self.prg_bank = state.read_u8()?;
+        state.read_bytes_into(&mut self.chr_banks)
+    }
+}
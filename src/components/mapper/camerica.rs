@@ -0,0 +1,94 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::Mapper;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/INES_Mapper_071
+pub struct Camerica {
+    prg_rom: Vec<u8>,
+    chr_ram: Vec<u8>,
+    mirroring: Mirroring,
+    initial_mirroring: Mirroring,
+    prg_bank: u8,
+}
+
+impl Camerica {
+    const CHR_RAM_SIZE: usize = 0x2000;
+
+    pub fn new(rom: Rom) -> Self {
+        Camerica {
+            prg_rom: rom.prg_rom,
+            chr_ram: vec![0; Camerica::CHR_RAM_SIZE],
+            mirroring: rom.screen_mirroring,
+            initial_mirroring: rom.screen_mirroring,
+            prg_bank: 0,
+        }
+    }
+
+    fn prg_offset(&self, bank: usize, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / 0x4000;
+        (bank % bank_count) * 0x4000 + (addr as usize % 0x4000)
+    }
+}
+
+impl Mapper for Camerica {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x7FFF => 0,
+            0x8000..=0xBFFF => self.prg_rom[self.prg_offset(self.prg_bank as usize, addr)],
+            0xC000..=0xFFFF => {
+                let last_bank = self.prg_rom.len() / 0x4000 - 1;
+                self.prg_rom[self.prg_offset(last_bank, addr)]
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x8FFF => {}
+            // only Fire Hawk's board wires up one screen mirroring here
+            0x9000..=0x9FFF => {
+                self.mirroring = if data & 0b0001_0000 != 0 {
+                    Mirroring::SINGLE_SCREEN_UPPER
+                } else {
+                    Mirroring::SINGLE_SCREEN_LOWER
+                };
+            }
+            0xA000..=0xBFFF => {}
+            0xC000..=0xFFFF => self.prg_bank = data & 0x0F,
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr_ram[addr as usize]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        self.chr_ram[addr as usize] = data;
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.mirroring = self.initial_mirroring;
+        self.prg_bank = 0;
+    }
+}
+
+impl SaveState for Camerica {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.chr_ram);
+        self.mirroring.save_state(state);
+        state.write_u8(self.prg_bank);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.chr_ram)?;
+        self.mirroring.load_state(state)?;
+        self.prg_bank = state.read_u8()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Color_Dreams
+pub struct ColorDreams {
+    prg_rom: Vec<u8>,
+    chr: Chr,
+    mirroring: Mirroring,
+    // bits 0-1 pick the 32KB PRG bank, bits 4-7 the 8KB CHR bank
+    bank_register: u8,
+}
+
+impl ColorDreams {
+    pub fn new(rom: Rom) -> Self {
+        ColorDreams {
+            prg_rom: rom.prg_rom,
+            chr: Chr::new(rom.chr_rom),
+            mirroring: rom.screen_mirroring,
+            bank_register: 0,
+        }
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let bank = (self.bank_register >> 4) as usize;
+        (bank * 0x2000 + addr as usize) % self.chr.len()
+    }
+}
+
+impl Mapper for ColorDreams {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x7FFF => 0,
+            0x8000..=0xFFFF => {
+                let bank = (self.bank_register & 0b11) as usize;
+                self.prg_rom[(bank * 0x8000 + (addr as usize - 0x8000)) % self.prg_rom.len()]
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x7FFF => {}
+            0x8000..=0xFFFF => self.bank_register = data,
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.bank_register = 0;
+    }
+}
+
+impl SaveState for ColorDreams {
+    fn save_state(&self, state: &mut StateWriter) {
+        self.chr.save_state(state);
+        state.write_u8(self.bank_register);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.chr.load_state(state)?;
+        self.bank_register = state.read_u8()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Bandai_FCG_board#Serial_EEPROM
+// Serial EEPROMs driven over I2C by toggling clock and data lines through a mapper register.
+// The 24C02 takes a device address, a word address and data MSB first. The older 24C01 skips
+// the device address, takes a 7 bit word address with the read/write bit and sends LSB first.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EepromKind {
+    X24C01,
+    X24C02,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Mode {
+    Idle,
+    DeviceAddress,
+    WordAddress,
+    Read,
+    Write,
+    SendAck,
+    WaitAck,
+}
+
+pub struct Eeprom {
+    kind: EepromKind,
+    data: Vec<u8>,
+    mode: Mode,
+    next_mode: Mode,
+    bit_counter: u8,
+    device_address: u8,
+    address: u8,
+    shift_register: u8,
+    output: bool,
+    previous_scl: bool,
+    previous_sda: bool,
+}
+
+impl Eeprom {
+    pub fn new(kind: EepromKind) -> Self {
+        let size = match kind {
+            EepromKind::X24C01 => 128,
+            EepromKind::X24C02 => 256,
+        };
+        Eeprom {
+            kind,
+            data: vec![0; size],
+            mode: Mode::Idle,
+            next_mode: Mode::Idle,
+            bit_counter: 0,
+            device_address: 0,
+            address: 0,
+            shift_register: 0,
+            output: true,
+            previous_scl: false,
+            previous_sda: false,
+        }
+    }
+
+    // The stored bytes survive, a transfer that was going on is forgotten
+    pub fn power_on(&mut self) {
+        self.mode = Mode::Idle;
+        self.next_mode = Mode::Idle;
+        self.bit_counter = 0;
+        self.device_address = 0;
+        self.address = 0;
+        self.shift_register = 0;
+        self.output = true;
+        self.previous_scl = false;
+        self.previous_sda = false;
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn load(&mut self, data: &[u8]) -> Result<(), String> {
+        if data.len() != self.data.len() {
+            return Err(format!("EEPROM save must be {} bytes, got {}", self.data.len(), data.len()));
+        }
+        self.data.copy_from_slice(data);
+        Ok(())
+    }
+
+    // The data line as driven by the EEPROM, it idles high
+    pub fn output(&self) -> bool {
+        self.output
+    }
+
+    pub fn write(&mut self, scl: bool, sda: bool) {
+        if self.previous_scl && scl && self.previous_sda && !sda {
+            self.start();
+        } else if self.previous_scl && scl && !self.previous_sda && sda {
+            self.mode = Mode::Idle;
+            self.output = true;
+        } else if !self.previous_scl && scl {
+            self.clock_rising(sda);
+        } else if self.previous_scl && !scl {
+            self.clock_falling();
+        }
+        self.previous_scl = scl;
+        self.previous_sda = sda;
+    }
+
+    fn start(&mut self) {
+        self.mode = match self.kind {
+            EepromKind::X24C01 => Mode::WordAddress,
+            EepromKind::X24C02 => Mode::DeviceAddress,
+        };
+        self.bit_counter = 0;
+        self.output = true;
+    }
+
+    fn address_mask(&self) -> u8 {
+        (self.data.len() - 1) as u8
+    }
+
+    // bit position of the n-th bit on the wire
+    fn bit_position(&self, bit: u8) -> u8 {
+        match self.kind {
+            EepromKind::X24C01 => bit,
+            EepromKind::X24C02 => 7 - bit,
+        }
+    }
+
+    fn receive_bit(&mut self, sda: bool) {
+        if self.bit_counter < 8 {
+            let position = self.bit_position(self.bit_counter);
+            self.shift_register = (self.shift_register & !(1 << position)) | (sda as u8) << position;
+            self.bit_counter += 1;
+        }
+    }
+
+    fn send_bit(&mut self) {
+        if self.bit_counter < 8 {
+            let position = self.bit_position(self.bit_counter);
+            self.output = self.shift_register & (1 << position) != 0;
+            self.bit_counter += 1;
+        }
+    }
+
+    fn start_read(&mut self) {
+        self.next_mode = Mode::Read;
+        self.shift_register = self.data[(self.address & self.address_mask()) as usize];
+    }
+
+    fn clock_rising(&mut self, sda: bool) {
+        match self.mode {
+            Mode::DeviceAddress | Mode::Write => self.receive_bit(sda),
+            // the 24C01 address is 7 bits followed by the read/write bit
+            Mode::WordAddress if self.kind == EepromKind::X24C01 && self.bit_counter == 7 => {
+                self.bit_counter = 8;
+                self.address = self.shift_register & 0x7F;
+                if sda {
+                    self.start_read();
+                } else {
+                    self.next_mode = Mode::Write;
+                }
+            }
+            Mode::WordAddress => self.receive_bit(sda),
+            Mode::Read => self.send_bit(),
+            Mode::SendAck => self.output = false,
+            Mode::WaitAck => {
+                // the host acknowledging asks for the next byte
+                if !sda {
+                    self.start_read();
+                } else {
+                    self.next_mode = Mode::Idle;
+                }
+            }
+            Mode::Idle => {}
+        }
+    }
+
+    fn clock_falling(&mut self) {
+        match self.mode {
+            Mode::DeviceAddress if self.bit_counter == 8 => {
+                self.device_address = self.shift_register;
+                if self.device_address & 0xF0 != 0xA0 {
+                    self.mode = Mode::Idle;
+                    self.output = true;
+                    return;
+                }
+                self.mode = Mode::SendAck;
+                if self.device_address & 1 != 0 {
+                    self.start_read();
+                } else {
+                    self.next_mode = Mode::WordAddress;
+                }
+            }
+            Mode::WordAddress if self.bit_counter == 8 => {
+                if self.kind == EepromKind::X24C02 {
+                    self.address = self.shift_register;
+                    self.next_mode = Mode::Write;
+                }
+                self.mode = Mode::SendAck;
+            }
+            Mode::Read if self.bit_counter == 8 => {
+                self.mode = Mode::WaitAck;
+                self.address = self.address.wrapping_add(1) & self.address_mask();
+                self.output = true;
+            }
+            Mode::Write if self.bit_counter == 8 => {
+                let address = (self.address & self.address_mask()) as usize;
+                self.data[address] = self.shift_register;
+                self.address = self.address.wrapping_add(1) & self.address_mask();
+                self.mode = Mode::SendAck;
+                self.next_mode = Mode::Write;
+            }
+            Mode::SendAck | Mode::WaitAck => {
+                self.mode = self.next_mode;
+                self.bit_counter = 0;
+                self.output = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl SaveState for Eeprom {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.data);
+        state.write_u8(self.mode as u8);
+        state.write_u8(self.next_mode as u8);
+        state.write_u8(self.bit_counter);
+        state.write_u8(self.device_address);
+        state.write_u8(self.address);
+        state.write_u8(self.shift_register);
+        state.write_bool(self.output);
+        state.write_bool(self.previous_scl);
+        state.write_bool(self.previous_sda);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.data)?;
+        self.mode = Mode::from_state(state.read_u8()?)?;
+        self.next_mode = Mode::from_state(state.read_u8()?)?;
+        self.bit_counter = state.read_u8()?;
+        self.device_address = state.read_u8()?;
+        self.address = state.read_u8()?;
+        self.shift_register = state.read_u8()?;
+        self.output = state.read_bool()?;
+        self.previous_scl = state.read_bool()?;
+        self.previous_sda = state.read_bool()?;
+        Ok(())
+    }
+}
+
+impl Mode {
+    fn from_state(value: u8) -> Result<Mode, String> {
+        match value {
+            0 => Ok(Mode::Idle),
+            1 => Ok(Mode::DeviceAddress),
+            2 => Ok(Mode::WordAddress),
+            3 => Ok(Mode::Read),
+            4 => Ok(Mode::Write),
+            5 => Ok(Mode::SendAck),
+            6 => Ok(Mode::WaitAck),
+            _ => Err(format!("Invalid EEPROM mode {} in save state", value)),
+        }
+    }
+}
@@ -0,0 +1,525 @@
+use crate::components::cartridge::fds;
+use crate::components::cartridge::fds::FdsImage;
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::Mapper;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Family_Computer_Disk_System
+pub struct Fds {
+    bios: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr_ram: Vec<u8>,
+    original_image: FdsImage,
+    sides: Vec<Vec<u8>>,
+    inserted_side: Option<usize>,
+    disk_modified: bool,
+    mirroring: Mirroring,
+
+    disk_io_enabled: bool,
+    external_connector: u8,
+
+    timer_reload: u16,
+    timer_counter: u16,
+    timer_repeat: bool,
+    timer_irq_enabled: bool,
+    timer_irq: bool,
+
+    motor_on: bool,
+    reset_transfer: bool,
+    read_mode: bool,
+    crc_control: bool,
+    previous_crc_control: bool,
+    disk_ready: bool,
+    disk_irq_enabled: bool,
+    disk_irq: bool,
+    transfer_complete: bool,
+    read_data: u8,
+    write_data: u8,
+    disk_position: usize,
+    delay: u32,
+    end_of_head: bool,
+    scanning_disk: bool,
+    gap_ended: bool,
+    crc: u16,
+}
+
+impl Fds {
+    pub const BIOS_SIZE: usize = 0x2000;
+    const PRG_RAM_SIZE: usize = 0x8000;
+    const CHR_RAM_SIZE: usize = 0x2000;
+
+    // CPU cycles the drive needs to spin up and to move one byte under the head
+    const MOTOR_SPIN_UP_CYCLES: u32 = 50000;
+    const BYTE_TRANSFER_CYCLES: u32 = 149;
+
+    pub fn new(bios: Vec<u8>, image: FdsImage) -> Result<Fds, String> {
+        if bios.len() != Fds::BIOS_SIZE {
+            return Err(format!("FDS BIOS must be {} bytes, got {}", Fds::BIOS_SIZE, bios.len()));
+        }
+
+        let sides: Vec<Vec<u8>> = image.sides.iter().map(|side| fds::add_gaps(side)).collect();
+
+        Ok(Fds {
+            bios,
+            prg_ram: vec![0; Fds::PRG_RAM_SIZE],
+            chr_ram: vec![0; Fds::CHR_RAM_SIZE],
+            original_image: image,
+            sides,
+            inserted_side: Some(0),
+            disk_modified: false,
+            mirroring: Mirroring::VERTICAL,
+            disk_io_enabled: false,
+            external_connector: 0,
+            timer_reload: 0,
+            timer_counter: 0,
+            timer_repeat: false,
+            timer_irq_enabled: false,
+            timer_irq: false,
+            motor_on: false,
+            reset_transfer: false,
+            read_mode: true,
+            crc_control: false,
+            previous_crc_control: false,
+            disk_ready: false,
+            disk_irq_enabled: false,
+            disk_irq: false,
+            transfer_complete: false,
+            read_data: 0,
+            write_data: 0,
+            disk_position: 0,
+            delay: 0,
+            end_of_head: true,
+            scanning_disk: false,
+            gap_ended: false,
+            crc: 0,
+        })
+    }
+
+    // Starts with the writes saved from an earlier session, disk_changes keeps including them
+    pub fn with_changes(bios: Vec<u8>, image: FdsImage, changes: &[u8]) -> Result<Fds, String> {
+        let changed = FdsImage::with_changes(&image.to_bytes(), changes)?;
+        if changed.side_count() != image.side_count() {
+            return Err("Disk changes are for a disk with a different number of sides".to_string());
+        }
+        let mut fds = Fds::new(bios, changed)?;
+        fds.original_image = image;
+        Ok(fds)
+    }
+
+    pub fn side_count(&self) -> usize {
+        self.sides.len()
+    }
+
+    pub fn inserted_side(&self) -> Option<usize> {
+        self.inserted_side
+    }
+
+    pub fn insert_disk(&mut self, side: usize) -> Result<(), String> {
+        if side >= self.sides.len() {
+            return Err(format!("Disk side {} doesn't exist, the image has {} sides", side, self.sides.len()));
+        }
+        self.inserted_side = Some(side);
+        self.end_of_head = true;
+        self.disk_position = 0;
+        self.scanning_disk = false;
+        Ok(())
+    }
+
+    pub fn eject_disk(&mut self) {
+        self.inserted_side = None;
+        self.scanning_disk = false;
+    }
+
+    // Writes are kept apart from the original image as an IPS patch against it
+    pub fn disk_changes(&self) -> Option<Vec<u8>> {
+        if !self.disk_modified {
+            return None;
+        }
+
+        let sides = self.sides.iter().map(|side| fds::remove_gaps(side)).collect();
+        self.original_image.with_sides(sides).changes_since(&self.original_image).ok()
+    }
+
+    fn write_register(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020 => {
+                self.timer_reload = (self.timer_reload & 0xFF00) | data as u16;
+            }
+            0x4021 => {
+                self.timer_reload = (self.timer_reload & 0x00FF) | ((data as u16) << 8);
+            }
+            0x4022 => {
+                self.timer_repeat = data & 0b01 != 0;
+                self.timer_irq_enabled = data & 0b10 != 0 && self.disk_io_enabled;
+                if self.timer_irq_enabled {
+                    self.timer_counter = self.timer_reload;
+                } else {
+                    self.timer_irq = false;
+                }
+            }
+            0x4023 => {
+                self.disk_io_enabled = data & 0b01 != 0;
+                if !self.disk_io_enabled {
+                    self.timer_irq_enabled = false;
+                    self.timer_irq = false;
+                    self.disk_irq = false;
+                }
+            }
+            0x4024 if self.disk_io_enabled => {
+                self.write_data = data;
+                self.transfer_complete = false;
+                self.disk_irq = false;
+            }
+            0x4025 if self.disk_io_enabled => {
+                self.motor_on = data & 0b0000_0001 != 0;
+                self.reset_transfer = data & 0b0000_0010 != 0;
+                self.read_mode = data & 0b0000_0100 != 0;
+                self.mirroring = if data & 0b0000_1000 != 0 { Mirroring::HORIZONTAL } else { Mirroring::VERTICAL };
+                self.crc_control = data & 0b0001_0000 != 0;
+                self.disk_ready = data & 0b0100_0000 != 0;
+                self.disk_irq_enabled = data & 0b1000_0000 != 0;
+                self.disk_irq = false;
+            }
+            0x4026 => {
+                self.external_connector = data;
+            }
+            _ => { /* sound registers are not emulated, disk ones ignore writes while disabled */ }
+        }
+    }
+
+    // Reading the status or the data acknowledges the interrupts
+    fn read_register(&mut self, addr: u16) -> u8 {
+        let data = self.peek_register(addr);
+        if self.disk_io_enabled {
+            match addr {
+                0x4030 => {
+                    self.transfer_complete = false;
+                    self.timer_irq = false;
+                    self.disk_irq = false;
+                }
+                0x4031 => {
+                    self.transfer_complete = false;
+                    self.disk_irq = false;
+                }
+                _ => {}
+            }
+        }
+        data
+    }
+
+    fn peek_register(&self, addr: u16) -> u8 {
+        if !self.disk_io_enabled {
+            return 0;
+        }
+
+        match addr {
+            0x4030 => {
+                let mut status = 0;
+                if self.timer_irq {
+                    status |= 0b0000_0001;
+                }
+                if self.transfer_complete {
+                    status |= 0b0000_0010;
+                }
+                if self.end_of_head {
+                    status |= 0b0100_0000;
+                }
+                status
+            }
+            0x4031 => self.read_data,
+            0x4032 => {
+                let mut status = 0b0100_0000;
+                if self.inserted_side.is_none() {
+                    status |= 0b0000_0111;
+                } else if !self.scanning_disk {
+                    status |= 0b0000_0010;
+                }
+                status
+            }
+            // bit 7 reports a good battery
+            0x4033 => 0b1000_0000 | (self.external_connector & 0b0111_1111),
+            _ => 0,
+        }
+    }
+
+    fn clock_timer(&mut self) {
+        if !self.timer_irq_enabled {
+            return;
+        }
+
+        if self.timer_counter == 0 {
+            self.timer_irq = true;
+            self.timer_counter = self.timer_reload;
+            if !self.timer_repeat {
+                self.timer_irq_enabled = false;
+            }
+        } else {
+            self.timer_counter -= 1;
+        }
+    }
+
+    fn clock_drive(&mut self) {
+        let side = match self.inserted_side {
+            Some(side) => side,
+            None => return,
+        };
+
+        if !self.motor_on {
+            self.end_of_head = true;
+            self.scanning_disk = false;
+            return;
+        }
+
+        if self.reset_transfer && !self.scanning_disk {
+            return;
+        }
+
+        if self.end_of_head {
+            self.delay = Fds::MOTOR_SPIN_UP_CYCLES;
+            self.end_of_head = false;
+            self.disk_position = 0;
+            self.gap_ended = false;
+            return;
+        }
+
+        if self.delay > 0 {
+            self.delay -= 1;
+            return;
+        }
+
+        self.scanning_disk = true;
+        if self.read_mode {
+            self.read_disk_byte(side);
+        } else {
+            self.write_disk_byte(side);
+        }
+        self.previous_crc_control = self.crc_control;
+
+        self.disk_position += 1;
+        if self.disk_position >= self.sides[side].len() {
+            // the head goes back to the start of the disk once it runs off the end
+            self.motor_on = false;
+            self.disk_position = 0;
+        } else {
+            self.delay = Fds::BYTE_TRANSFER_CYCLES;
+        }
+    }
+
+    fn read_disk_byte(&mut self, side: usize) {
+        let data = self.sides[side][self.disk_position];
+        let mut need_irq = self.disk_irq_enabled;
+
+        if !self.disk_ready {
+            self.gap_ended = false;
+        } else if data != 0 && !self.gap_ended {
+            // the gap end mark is latched without raising an interrupt
+            self.gap_ended = true;
+            need_irq = false;
+        }
+
+        if self.gap_ended {
+            self.transfer_complete = true;
+            self.read_data = data;
+            if need_irq {
+                self.disk_irq = true;
+            }
+        }
+    }
+
+    fn write_disk_byte(&mut self, side: usize) {
+        let mut data = 0;
+
+        if !self.crc_control {
+            self.transfer_complete = true;
+            data = self.write_data;
+            if self.disk_irq_enabled {
+                self.disk_irq = true;
+            }
+        }
+
+        if !self.disk_ready {
+            data = 0;
+            self.crc = 0;
+        } else if !self.crc_control {
+            self.crc = fds::update_crc(self.crc, data);
+        } else {
+            if !self.previous_crc_control {
+                self.crc = fds::update_crc(self.crc, 0);
+                self.crc = fds::update_crc(self.crc, 0);
+            }
+            data = (self.crc & 0xFF) as u8;
+            self.crc >>= 8;
+        }
+
+        self.sides[side][self.disk_position] = data;
+        self.disk_modified = true;
+        self.gap_ended = false;
+    }
+}
+
+impl Mapper for Fds {
+    fn read_prg(&mut self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => self.read_register(addr),
+            _ => self.peek_prg(addr),
+        }
+    }
+
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => self.peek_register(addr),
+            0x6000..=0xDFFF => self.prg_ram[(addr - 0x6000) as usize],
+            0xE000..=0xFFFF => self.bios[(addr - 0xE000) as usize],
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x5FFF => self.write_register(addr, data),
+            0x6000..=0xDFFF => self.prg_ram[(addr - 0x6000) as usize] = data,
+            0xE000..=0xFFFF => { /* BIOS ROM */ }
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr_ram[addr as usize]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        self.chr_ram[addr as usize] = data;
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        // the disk stays in the drive with everything written to it, only the RAM adapter starts over
+        self.mirroring = Mirroring::VERTICAL;
+        self.disk_io_enabled = false;
+        self.external_connector = 0;
+        self.timer_reload = 0;
+        self.timer_counter = 0;
+        self.timer_repeat = false;
+        self.timer_irq_enabled = false;
+        self.timer_irq = false;
+        self.motor_on = false;
+        self.reset_transfer = false;
+        self.read_mode = true;
+        self.crc_control = false;
+        self.previous_crc_control = false;
+        self.disk_ready = false;
+        self.disk_irq_enabled = false;
+        self.disk_irq = false;
+        self.transfer_complete = false;
+        self.read_data = 0;
+        self.write_data = 0;
+        self.disk_position = 0;
+        self.delay = 0;
+        self.end_of_head = true;
+        self.scanning_disk = false;
+        self.gap_ended = false;
+        self.crc = 0;
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        for _ in 0..cycles {
+            self.clock_timer();
+            self.clock_drive();
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.timer_irq || self.disk_irq
+    }
+}
+
+impl SaveState for Fds {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        state.write_bytes(&self.chr_ram);
+        state.write_usize(self.sides.len());
+        for side in self.sides.iter() {
+            state.write_bytes(side);
+        }
+        state.write_bool(self.inserted_side.is_some());
+        state.write_usize(self.inserted_side.unwrap_or(0));
+        state.write_bool(self.disk_modified);
+        self.mirroring.save_state(state);
+        state.write_bool(self.disk_io_enabled);
+        state.write_u8(self.external_connector);
+        state.write_u16(self.timer_reload);
+        state.write_u16(self.timer_counter);
+        state.write_bool(self.timer_repeat);
+        state.write_bool(self.timer_irq_enabled);
+        state.write_bool(self.timer_irq);
+        state.write_bool(self.motor_on);
+        state.write_bool(self.reset_transfer);
+        state.write_bool(self.read_mode);
+        state.write_bool(self.crc_control);
+        state.write_bool(self.previous_crc_control);
+        state.write_bool(self.disk_ready);
+        state.write_bool(self.disk_irq_enabled);
+        state.write_bool(self.disk_irq);
+        state.write_bool(self.transfer_complete);
+        state.write_u8(self.read_data);
+        state.write_u8(self.write_data);
+        state.write_usize(self.disk_position);
+        state.write_u32(self.delay);
+        state.write_bool(self.end_of_head);
+        state.write_bool(self.scanning_disk);
+        state.write_bool(self.gap_ended);
+        state.write_u16(self.crc);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        state.read_bytes_into(&mut self.chr_ram)?;
+        if state.read_usize()? != self.sides.len() {
+            return Err("Save state is for a disk with a different number of sides".to_string());
+        }
+        for side in self.sides.iter_mut() {
+            state.read_bytes_into(side)?;
+        }
+        let inserted = state.read_bool()?;
+        let side = state.read_usize()?;
+        if inserted && side >= self.sides.len() {
+            return Err(format!("Save state has disk side {} inserted, the disk only has {}", side, self.sides.len()));
+        }
+        self.inserted_side = if inserted { Some(side) } else { None };
+        self.disk_modified = state.read_bool()?;
+        self.mirroring.load_state(state)?;
+        self.disk_io_enabled = state.read_bool()?;
+        self.external_connector = state.read_u8()?;
+        self.timer_reload = state.read_u16()?;
+        self.timer_counter = state.read_u16()?;
+        self.timer_repeat = state.read_bool()?;
+        self.timer_irq_enabled = state.read_bool()?;
+        self.timer_irq = state.read_bool()?;
+        self.motor_on = state.read_bool()?;
+        self.reset_transfer = state.read_bool()?;
+        self.read_mode = state.read_bool()?;
+        self.crc_control = state.read_bool()?;
+        self.previous_crc_control = state.read_bool()?;
+        self.disk_ready = state.read_bool()?;
+        self.disk_irq_enabled = state.read_bool()?;
+        self.disk_irq = state.read_bool()?;
+        self.transfer_complete = state.read_bool()?;
+        self.read_data = state.read_u8()?;
+        self.write_data = state.read_u8()?;
+        self.disk_position = state.read_usize()?;
+        if let Some(side) = self.inserted_side {
+            if self.disk_position >= self.sides[side].len() {
+                return Err(format!("Invalid disk position {} in save state", self.disk_position));
+            }
+        }
+        self.delay = state.read_u32()?;
+        self.end_of_head = state.read_bool()?;
+        self.scanning_disk = state.read_bool()?;
+        self.gap_ended = state.read_bool()?;
+        self.crc = state.read_u16()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::expansion_audio::sunsoft5b::Sunsoft5bAudio;
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Sunsoft_FME-7
+// The Sunsoft 5B is an FME-7 with the audio chip on the same die, mapper 69 covers both
+pub struct Fme7 {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Chr,
+
+    command: u8,
+    chr_banks: [u8; 8],
+    // $6000-$7FFF: bits 0-5 bank, bit 6 RAM instead of ROM, bit 7 RAM enabled
+    prg_bank_6000: u8,
+    prg_banks: [u8; 3],
+    mirroring: Mirroring,
+    initial_mirroring: Mirroring,
+
+    irq_enabled: bool,
+    irq_counter_enabled: bool,
+    irq_counter: u16,
+    irq_pending: bool,
+
+    audio: Sunsoft5bAudio,
+}
+
+impl Fme7 {
+    const PRG_RAM_SIZE: usize = 0x8000;
+
+    pub fn new(rom: Rom) -> Self {
+        Fme7 {
+            prg_rom: rom.prg_rom,
+            prg_ram: vec![0; Fme7::PRG_RAM_SIZE],
+            chr: Chr::new(rom.chr_rom),
+            command: 0,
+            chr_banks: [0; 8],
+            prg_bank_6000: 0,
+            prg_banks: [0; 3],
+            mirroring: rom.screen_mirroring,
+            initial_mirroring: rom.screen_mirroring,
+            irq_enabled: false,
+            irq_counter_enabled: false,
+            irq_counter: 0,
+            irq_pending: false,
+            audio: Sunsoft5bAudio::new(),
+        }
+    }
+
+    fn prg_rom_offset(&self, bank: usize, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / 0x2000;
+        (bank % bank_count) * 0x2000 + (addr as usize % 0x2000)
+    }
+
+    fn prg_ram_offset(&self, addr: u16) -> usize {
+        let bank = (self.prg_bank_6000 & 0x3F) as usize;
+        (bank * 0x2000 + (addr as usize % 0x2000)) % self.prg_ram.len()
+    }
+
+    fn prg_ram_selected(&self) -> bool {
+        self.prg_bank_6000 & 0b0100_0000 != 0
+    }
+
+    fn prg_ram_enabled(&self) -> bool {
+        self.prg_bank_6000 & 0b1100_0000 == 0b1100_0000
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let bank = self.chr_banks[addr as usize / 0x400] as usize;
+        let bank_count = self.chr.len() / 0x400;
+        (bank % bank_count) * 0x400 + (addr as usize % 0x400)
+    }
+
+    fn write_parameter(&mut self, data: u8) {
+        match self.command {
+            0x0..=0x7 => self.chr_banks[self.command as usize] = data,
+            0x8 => self.prg_bank_6000 = data,
+            0x9..=0xB => self.prg_banks[(self.command - 0x9) as usize] = data & 0x3F,
+            0xC => {
+                self.mirroring = match data & 0b11 {
+                    0 => Mirroring::VERTICAL,
+                    1 => Mirroring::HORIZONTAL,
+                    2 => Mirroring::SINGLE_SCREEN_LOWER,
+                    _ => Mirroring::SINGLE_SCREEN_UPPER,
+                };
+            }
+            0xD => {
+                self.irq_enabled = data & 0b0000_0001 != 0;
+                self.irq_counter_enabled = data & 0b1000_0000 != 0;
+                self.irq_pending = false;
+            }
+            0xE => self.irq_counter = (self.irq_counter & 0xFF00) | data as u16,
+            _ => self.irq_counter = (self.irq_counter & 0x00FF) | (data as u16) << 8,
+        }
+    }
+
+    // The counter runs every CPU cycle and fires when it wraps from $0000 to $FFFF
+    fn clock_irq(&mut self) {
+        if !self.irq_counter_enabled {
+            return;
+        }
+        if self.irq_counter == 0 && self.irq_enabled {
+            self.irq_pending = true;
+        }
+        self.irq_counter = self.irq_counter.wrapping_sub(1);
+    }
+}
+
+impl Mapper for Fme7 {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => 0,
+            0x6000..=0x7FFF if self.prg_ram_selected() => {
+                if self.prg_ram_enabled() {
+                    self.prg_ram[self.prg_ram_offset(addr)]
+                } else {
+                    0
+                }
+            }
+            0x6000..=0x7FFF => self.prg_rom[self.prg_rom_offset((self.prg_bank_6000 & 0x3F) as usize, addr)],
+            0x8000..=0xDFFF => {
+                let bank = self.prg_banks[((addr - 0x8000) / 0x2000) as usize] as usize;
+                self.prg_rom[self.prg_rom_offset(bank, addr)]
+            }
+            0xE000..=0xFFFF => {
+                let last_bank = self.prg_rom.len() / 0x2000 - 1;
+                self.prg_rom[self.prg_rom_offset(last_bank, addr)]
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x5FFF => {}
+            0x6000..=0x7FFF => {
+                if self.prg_ram_enabled() {
+                    let offset = self.prg_ram_offset(addr);
+                    self.prg_ram[offset] = data;
+                }
+            }
+            0x8000..=0x9FFF => self.command = data & 0x0F,
+            0xA000..=0xBFFF => self.write_parameter(data),
+            0xC000..=0xDFFF => self.audio.write_address(data),
+            0xE000..=0xFFFF => self.audio.write_data(data),
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.command = 0;
+        self.chr_banks = [0; 8];
+        self.prg_bank_6000 = 0;
+        self.prg_banks = [0; 3];
+        self.mirroring = self.initial_mirroring;
+        self.irq_enabled = false;
+        self.irq_counter_enabled = false;
+        self.irq_counter = 0;
+        self.irq_pending = false;
+        self.audio = Sunsoft5bAudio::new();
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        for _ in 0..cycles {
+            self.clock_irq();
+            self.audio.clock();
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq_pending
+    }
+
+    fn expansion_audio(&self) -> f32 {
+        self.audio.output()
+    }
+}
+
+impl SaveState for Fme7 {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        self.chr.save_state(state);
+        state.write_u8(self.command);
+        state.write_bytes(&self.chr_banks);
+        state.write_u8(self.prg_bank_6000);
+        state.write_bytes(&self.prg_banks);
+        self.mirroring.save_state(state);
+        state.write_bool(self.irq_enabled);
+        state.write_bool(self.irq_counter_enabled);
+        state.write_u16(self.irq_counter);
+        state.write_bool(self.irq_pending);
+        self.audio.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        self.chr.load_state(state)?;
+        self.command = state.read_u8()?;
+        state.read_bytes_into(&mut self.chr_banks)?;
+        self.prg_bank_6000 = state.read_u8()?;
+        state.read_bytes_into(&mut self.prg_banks)?;
+        self.mirroring.load_state(state)?;
+        self.irq_enabled = state.read_bool()?;
+        self.irq_counter_enabled = state.read_bool()?;
+        self.irq_counter = state.read_u16()?;
+        self.irq_pending = state.read_bool()?;
+        self.audio.load_state(state)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/MMC2
+//https://www.nesdev.org/wiki/MMC4
+// Both pick between two CHR banks per pattern table with latches the PPU flips itself
+// by fetching tiles $FD and $FE, MMC4 only differs in its PRG banking and PRG RAM.
+pub struct Mmc2 {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Chr,
+    is_mmc4: bool,
+    prg_bank: u8,
+    // [pattern table][latch], latch 0 is $FD and 1 is $FE
+    chr_banks: [[u8; 2]; 2],
+    latches: [usize; 2],
+    mirroring: Mirroring,
+    initial_mirroring: Mirroring,
+}
+
+impl Mmc2 {
+    const PRG_RAM_SIZE: usize = 0x2000;
+    const CHR_BANK_SIZE: usize = 0x1000;
+    const LATCH_FD: usize = 0;
+    const LATCH_FE: usize = 1;
+
+    pub fn new(rom: Rom) -> Self {
+        Mmc2 {
+            is_mmc4: rom.mapper == 10,
+            prg_rom: rom.prg_rom,
+            prg_ram: vec![0; Mmc2::PRG_RAM_SIZE],
+            chr: Chr::new(rom.chr_rom),
+            prg_bank: 0,
+            chr_banks: [[0; 2]; 2],
+            latches: [Mmc2::LATCH_FE; 2],
+            mirroring: rom.screen_mirroring,
+            initial_mirroring: rom.screen_mirroring,
+        }
+    }
+
+    fn prg_offset(&self, addr: u16) -> usize {
+        let bank_size = if self.is_mmc4 { 0x4000 } else { 0x2000 };
+        let bank_count = self.prg_rom.len() / bank_size;
+        let slot = (addr as usize - 0x8000) / bank_size;
+        // the first slot is switchable, the rest hold the last banks
+        let bank = if slot == 0 {
+            self.prg_bank as usize % bank_count
+        } else {
+            bank_count - (0x8000 / bank_size - slot)
+        };
+        bank * bank_size + (addr as usize % bank_size)
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let table = (addr >> 12) as usize & 1;
+        let bank = self.chr_banks[table][self.latches[table]] as usize;
+        (bank * Mmc2::CHR_BANK_SIZE + (addr as usize & 0x0FFF)) % self.chr.len()
+    }
+
+    // The latch flips after the fetch that triggers it, so the tile doing it still uses the old bank
+    fn update_latch(&mut self, addr: u16) {
+        let table = (addr >> 12) as usize & 1;
+        // MMC2 only reacts to the exact addresses for the left pattern table
+        let whole_range = table == 1 || self.is_mmc4;
+        match addr & 0x0FFF {
+            0x0FD8 => self.latches[table] = Mmc2::LATCH_FD,
+            0x0FE8 => self.latches[table] = Mmc2::LATCH_FE,
+            0x0FD9..=0x0FDF if whole_range => self.latches[table] = Mmc2::LATCH_FD,
+            0x0FE9..=0x0FEF if whole_range => self.latches[table] = Mmc2::LATCH_FE,
+            _ => {}
+        }
+    }
+}
+
+impl Mapper for Mmc2 {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => 0,
+            0x6000..=0x7FFF => self.prg_ram[(addr - 0x6000) as usize],
+            0x8000..=0xFFFF => self.prg_rom[self.prg_offset(addr)],
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x5FFF => {}
+            0x6000..=0x7FFF => self.prg_ram[(addr - 0x6000) as usize] = data,
+            0x8000..=0x9FFF => {}
+            0xA000..=0xAFFF => self.prg_bank = data & 0x0F,
+            0xB000..=0xEFFF => {
+                let register = ((addr - 0xB000) / 0x1000) as usize;
+                self.chr_banks[register / 2][register % 2] = data & 0x1F;
+            }
+            0xF000..=0xFFFF => {
+                self.mirroring = if data & 1 != 0 { Mirroring::HORIZONTAL } else { Mirroring::VERTICAL };
+            }
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        let data = self.chr[self.chr_offset(addr)];
+        self.update_latch(addr);
+        data
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.prg_bank = 0;
+        self.chr_banks = [[0; 2]; 2];
+        self.latches = [Mmc2::LATCH_FE; 2];
+        self.mirroring = self.initial_mirroring;
+    }
+}
+
+impl SaveState for Mmc2 {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        self.chr.save_state(state);
+        state.write_u8(self.prg_bank);
+        state.write_bytes(&self.chr_banks.concat());
+        for latch in self.latches.iter() {
+            state.write_usize(*latch);
+        }
+        self.mirroring.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        self.chr.load_state(state)?;
+        self.prg_bank = state.read_u8()?;
+        let mut banks = [0; 4];
+        state.read_bytes_into(&mut banks)?;
+        self.chr_banks = [[banks[0], banks[1]], [banks[2], banks[3]]];
+        for latch in self.latches.iter_mut() {
+            *latch = state.read_usize()? & 1;
+        }
+        self.mirroring.load_state(state)
+    }
+}
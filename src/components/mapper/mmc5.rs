@@ -0,0 +1,528 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::expansion_audio::mmc5::Mmc5Audio;
+use crate::components::mapper::{Chr, Mapper, PpuFetchPhase};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/MMC5
+pub struct Mmc5 {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Chr,
+
+    prg_mode: u8,
+    chr_mode: u8,
+    prg_ram_protect: [u8; 2],
+    prg_ram_bank: u8,
+    // $5114-$5117, bit 7 picks ROM over RAM
+    prg_banks: [u8; 4],
+    // $5120-$5127 are set A, $5128-$512B set B
+    chr_banks: [u16; 12],
+    chr_upper_bits: u8,
+    last_chr_set_b: bool,
+
+    exram_mode: u8,
+    exram: Vec<u8>,
+    nametable_mapping: u8,
+    fill_tile: u8,
+    fill_attribute: u8,
+
+    split_control: u8,
+    split_scroll: u8,
+    split_bank: u8,
+
+    irq_compare: u8,
+    irq_enabled: bool,
+    irq_pending: bool,
+    in_frame: bool,
+    scanline_counter: u8,
+
+    multiplicand: u8,
+    multiplier: u8,
+
+    large_sprites: bool,
+    fetch_phase: PpuFetchPhase,
+    tile_column: u8,
+    split_y: u8,
+    // the ExRAM byte that goes with the background tile being fetched
+    tile_exram: Option<u8>,
+    in_split: bool,
+
+    audio: Mmc5Audio,
+}
+
+impl Mmc5 {
+    const PRG_RAM_SIZE: usize = 0x10000;
+    const EXRAM_SIZE: usize = 0x400;
+
+    pub fn new(rom: Rom) -> Self {
+        Mmc5 {
+            prg_rom: rom.prg_rom,
+            prg_ram: vec![0; Mmc5::PRG_RAM_SIZE],
+            chr: Chr::new(rom.chr_rom),
+            prg_mode: 3,
+            chr_mode: 0,
+            prg_ram_protect: [0; 2],
+            prg_ram_bank: 0,
+            prg_banks: [0, 0, 0, 0xFF],
+            chr_banks: [0; 12],
+            chr_upper_bits: 0,
+            last_chr_set_b: false,
+            exram_mode: 0,
+            exram: vec![0; Mmc5::EXRAM_SIZE],
+            nametable_mapping: 0,
+            fill_tile: 0,
+            fill_attribute: 0,
+            split_control: 0,
+            split_scroll: 0,
+            split_bank: 0,
+            irq_compare: 0,
+            irq_enabled: false,
+            irq_pending: false,
+            in_frame: false,
+            scanline_counter: 0,
+            multiplicand: 0xFF,
+            multiplier: 0xFF,
+            large_sprites: false,
+            fetch_phase: PpuFetchPhase::Idle,
+            tile_column: 0,
+            split_y: 0,
+            tile_exram: None,
+            in_split: false,
+            audio: Mmc5Audio::new(),
+        }
+    }
+
+    fn prg_ram_writable(&self) -> bool {
+        self.prg_ram_protect == [0b10, 0b01]
+    }
+
+    // Which register and bank size serve a CPU address in the current PRG mode
+    fn prg_bank(&self, addr: u16) -> (u8, usize) {
+        let slot = ((addr - 0x8000) / 0x2000) as usize;
+        match self.prg_mode {
+            0 => (self.prg_banks[3] | 0x80, 0x8000),
+            1 => (self.prg_banks[1 + (slot / 2) * 2], 0x4000),
+            2 if slot < 2 => (self.prg_banks[1], 0x4000),
+            2 => (self.prg_banks[slot], 0x2000),
+            _ => (self.prg_banks[slot], 0x2000),
+        }
+    }
+
+    // $5117 and the 32K mode always map ROM, the rest pick with bit 7
+    fn prg_offset(&self, addr: u16) -> (bool, usize) {
+        let slot = ((addr - 0x8000) / 0x2000) as usize;
+        let (register, size) = self.prg_bank(addr);
+        let is_rom = register & 0x80 != 0 || slot == 3 || self.prg_mode == 0;
+        // banks are numbered in 8K units whatever their size
+        let bank_8k = (register & 0x7F) as usize & !(size / 0x2000 - 1);
+        let offset = bank_8k * 0x2000 + (addr as usize & (size - 1));
+        (is_rom, offset)
+    }
+
+    fn read_prg_ram(&self, bank: usize, addr: u16) -> u8 {
+        self.prg_ram[(bank * 0x2000 + (addr as usize & 0x1FFF)) % self.prg_ram.len()]
+    }
+
+    fn chr_set_b(&self) -> bool {
+        if !self.large_sprites {
+            return false;
+        }
+        match self.fetch_phase {
+            PpuFetchPhase::Background => true,
+            PpuFetchPhase::Sprites => false,
+            PpuFetchPhase::Idle => self.last_chr_set_b,
+        }
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let (bank, size) = if self.chr_set_b() {
+            let slot = (addr as usize & 0x0FFF) / 0x400;
+            match self.chr_mode {
+                0 | 1 => (self.chr_banks[11], 0x1000 << (1 - self.chr_mode.min(1))),
+                2 => (self.chr_banks[9 + (slot / 2) * 2], 0x800),
+                _ => (self.chr_banks[8 + slot], 0x400),
+            }
+        } else {
+            let slot = addr as usize / 0x400;
+            match self.chr_mode {
+                0 => (self.chr_banks[7], 0x2000),
+                1 => (self.chr_banks[3 + (slot / 4) * 4], 0x1000),
+                2 => (self.chr_banks[1 + (slot / 2) * 2], 0x800),
+                _ => (self.chr_banks[slot], 0x400),
+            }
+        };
+        // set B repeats in both pattern tables even in 8K mode
+        let size = size.min(if self.chr_set_b() { 0x1000 } else { 0x2000 });
+        (bank as usize * size + (addr as usize % size)) % self.chr.len()
+    }
+
+    fn split_enabled(&self) -> bool {
+        self.split_control & 0x80 != 0 && self.exram_mode <= 1
+    }
+
+    fn is_split_column(&self, column: u8) -> bool {
+        let tiles = self.split_control & 0x1F;
+        if self.split_control & 0x40 != 0 {
+            column >= tiles
+        } else {
+            column < tiles
+        }
+    }
+
+    fn nametable_source(&self, addr: u16) -> u8 {
+        let nametable = ((addr - 0x2000) / 0x400) % 4;
+        (self.nametable_mapping >> (nametable * 2)) & 0b11
+    }
+
+    fn replicate_palette(palette: u8) -> u8 {
+        let palette = palette & 0b11;
+        palette | palette << 2 | palette << 4 | palette << 6
+    }
+}
+
+impl Mapper for Mmc5 {
+    fn read_prg(&mut self, addr: u16) -> u8 {
+        match addr {
+            0x5000..=0x5015 => self.audio.read(addr),
+            0x5204 => {
+                let status = self.peek_prg(addr);
+                self.irq_pending = false;
+                status
+            }
+            0x8000..=0xFFFF => {
+                let data = self.peek_prg(addr);
+                self.audio.observe_prg_read(addr, data);
+                data
+            }
+            _ => self.peek_prg(addr),
+        }
+    }
+
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x5000..=0x5015 => self.audio.peek(addr),
+            0x5204 => (self.irq_pending as u8) << 7 | (self.in_frame as u8) << 6,
+            0x5205 => (self.multiplicand as u16 * self.multiplier as u16) as u8,
+            0x5206 => ((self.multiplicand as u16 * self.multiplier as u16) >> 8) as u8,
+            0x5C00..=0x5FFF => {
+                if self.exram_mode >= 2 {
+                    self.exram[(addr - 0x5C00) as usize]
+                } else {
+                    0
+                }
+            }
+            0x4020..=0x5FFF => 0,
+            0x6000..=0x7FFF => self.read_prg_ram(self.prg_ram_bank as usize, addr),
+            0x8000..=0xFFFF => {
+                let (is_rom, offset) = self.prg_offset(addr);
+                if is_rom {
+                    self.prg_rom[offset % self.prg_rom.len()]
+                } else {
+                    self.prg_ram[offset % self.prg_ram.len()]
+                }
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x5000..=0x5015 => self.audio.write(addr, data),
+            0x5100 => self.prg_mode = data & 0b11,
+            0x5101 => self.chr_mode = data & 0b11,
+            0x5102 => self.prg_ram_protect[0] = data & 0b11,
+            0x5103 => self.prg_ram_protect[1] = data & 0b11,
+            0x5104 => self.exram_mode = data & 0b11,
+            0x5105 => self.nametable_mapping = data,
+            0x5106 => self.fill_tile = data,
+            0x5107 => self.fill_attribute = data & 0b11,
+            0x5113 => self.prg_ram_bank = data & 0b111,
+            0x5114..=0x5117 => self.prg_banks[(addr - 0x5114) as usize] = data,
+            0x5120..=0x512B => {
+                let register = (addr - 0x5120) as usize;
+                self.chr_banks[register] = data as u16 | (self.chr_upper_bits as u16) << 8;
+                self.last_chr_set_b = register >= 8;
+            }
+            0x5130 => self.chr_upper_bits = data & 0b11,
+            0x5200 => self.split_control = data,
+            0x5201 => self.split_scroll = data,
+            0x5202 => self.split_bank = data,
+            0x5203 => self.irq_compare = data,
+            0x5204 => self.irq_enabled = data & 0x80 != 0,
+            0x5205 => self.multiplicand = data,
+            0x5206 => self.multiplier = data,
+            0x5C00..=0x5FFF => {
+                if self.exram_mode != 3 {
+                    self.exram[(addr - 0x5C00) as usize] = data;
+                }
+            }
+            0x4020..=0x5FFF => {}
+            0x6000..=0x7FFF => {
+                if self.prg_ram_writable() {
+                    let offset = (self.prg_ram_bank as usize * 0x2000 + (addr as usize & 0x1FFF)) % self.prg_ram.len();
+                    self.prg_ram[offset] = data;
+                }
+            }
+            0x8000..=0xFFFF => {
+                let (is_rom, offset) = self.prg_offset(addr);
+                if !is_rom && self.prg_ram_writable() {
+                    let length = self.prg_ram.len();
+                    self.prg_ram[offset % length] = data;
+                }
+            }
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        if self.fetch_phase == PpuFetchPhase::Background {
+            if self.in_split {
+                // the split region has its own 4K page and vertical scroll
+                let tile = self.tile_exram.unwrap_or(0) as usize;
+                let offset = (addr as usize & 8) | (self.split_y as usize & 7);
+                return self.chr[(self.split_bank as usize * 0x1000 + tile * 16 + offset) % self.chr.len()];
+            }
+            if let (1, Some(exram)) = (self.exram_mode, self.tile_exram) {
+                // extended attributes pick a 4K page for every tile
+                let bank = (exram & 0x3F) as usize | (self.chr_upper_bits as usize) << 6;
+                return self.chr[(bank * 0x1000 + (addr as usize & 0x0FFF)) % self.chr.len()];
+            }
+        }
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        // nametables are mapped one by one through read_nametable
+        Mirroring::VERTICAL
+    }
+
+    fn power_on(&mut self) {
+        self.prg_mode = 3;
+        self.chr_mode = 0;
+        self.prg_ram_protect = [0; 2];
+        self.prg_ram_bank = 0;
+        self.prg_banks = [0, 0, 0, 0xFF];
+        self.chr_banks = [0; 12];
+        self.chr_upper_bits = 0;
+        self.last_chr_set_b = false;
+        self.exram_mode = 0;
+        self.nametable_mapping = 0;
+        self.fill_tile = 0;
+        self.fill_attribute = 0;
+        self.split_control = 0;
+        self.split_scroll = 0;
+        self.split_bank = 0;
+        self.irq_compare = 0;
+        self.irq_enabled = false;
+        self.irq_pending = false;
+        self.in_frame = false;
+        self.scanline_counter = 0;
+        self.multiplicand = 0xFF;
+        self.multiplier = 0xFF;
+        self.large_sprites = false;
+        self.fetch_phase = PpuFetchPhase::Idle;
+        self.tile_column = 0;
+        self.split_y = 0;
+        self.tile_exram = None;
+        self.in_split = false;
+        self.audio = Mmc5Audio::new();
+    }
+
+    //https://www.nesdev.org/wiki/MMC5#Scanline_Detection_and_Scanline_IRQ
+    // The PPU stops fetching while the button is held, which drops the scanline detector out of
+    // the frame like any other pause in rendering
+    fn reset(&mut self) {
+        self.in_frame = false;
+        self.fetch_phase = PpuFetchPhase::Idle;
+    }
+
+    fn read_nametable(&mut self, addr: u16, ciram: &[u8; 2048]) -> u8 {
+        let offset = (addr & 0x3FF) as usize;
+        let is_attribute = offset >= 0x3C0;
+
+        if self.fetch_phase == PpuFetchPhase::Background {
+            if !is_attribute {
+                self.in_split = self.split_enabled() && self.is_split_column(self.tile_column);
+                self.tile_column += 1;
+                if self.in_split {
+                    let column = (self.tile_column - 1) as usize % 32;
+                    let tile_offset = (self.split_y as usize / 8) * 32 + column;
+                    self.tile_exram = Some(self.exram[tile_offset]);
+                    return self.exram[tile_offset];
+                }
+                self.tile_exram = if self.exram_mode == 1 { Some(self.exram[offset]) } else { None };
+            } else if self.in_split {
+                let column = (self.tile_column - 1) as usize % 32;
+                let row = self.split_y as usize / 8;
+                let attribute = self.exram[0x3C0 + (row / 4) * 8 + column / 4];
+                let shift = ((row % 4) / 2) * 4 + ((column % 4) / 2) * 2;
+                return Mmc5::replicate_palette(attribute >> shift);
+            } else if let (1, Some(exram)) = (self.exram_mode, self.tile_exram) {
+                return Mmc5::replicate_palette(exram >> 6);
+            }
+        }
+
+        match self.nametable_source(addr) {
+            0 => ciram[offset],
+            1 => ciram[0x400 + offset],
+            2 => {
+                if self.exram_mode <= 1 {
+                    self.exram[offset]
+                } else {
+                    0
+                }
+            }
+            _ => {
+                if is_attribute {
+                    Mmc5::replicate_palette(self.fill_attribute)
+                } else {
+                    self.fill_tile
+                }
+            }
+        }
+    }
+
+    fn write_nametable(&mut self, addr: u16, data: u8, ciram: &mut [u8; 2048]) {
+        let offset = (addr & 0x3FF) as usize;
+        match self.nametable_source(addr) {
+            0 => ciram[offset] = data,
+            1 => ciram[0x400 + offset] = data,
+            2 if self.exram_mode <= 1 => self.exram[offset] = data,
+            _ => {}
+        }
+    }
+
+    //https://www.nesdev.org/wiki/MMC5#Scanline_Detection_and_Scanline_IRQ
+    fn ppu_scanline(&mut self, scanline: u16, rendering: bool) {
+        if !rendering || scanline >= 240 {
+            self.in_frame = false;
+            return;
+        }
+
+        if !self.in_frame {
+            self.in_frame = true;
+            self.scanline_counter = 0;
+            self.split_y = self.split_scroll;
+        } else {
+            self.scanline_counter = self.scanline_counter.wrapping_add(1);
+            if self.scanline_counter == self.irq_compare && self.irq_compare != 0 {
+                self.irq_pending = true;
+            }
+            self.split_y = self.split_y.wrapping_add(1);
+            if self.split_y >= 240 {
+                self.split_y -= 240;
+            }
+        }
+        self.tile_column = 0;
+    }
+
+    fn ppu_fetch_phase(&mut self, phase: PpuFetchPhase) {
+        self.fetch_phase = phase;
+        self.tile_column = 0;
+        self.tile_exram = None;
+        self.in_split = false;
+    }
+
+    fn ppu_register_written(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x2000 => self.large_sprites = data & 0b0010_0000 != 0,
+            0x2001 if data & 0b0001_1000 == 0 => self.in_frame = false,
+            _ => {}
+        }
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        for _ in 0..cycles {
+            self.audio.clock();
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        (self.irq_pending && self.irq_enabled) || self.audio.irq_pending()
+    }
+
+    fn expansion_audio(&self) -> f32 {
+        self.audio.output()
+    }
+}
+
+impl SaveState for Mmc5 {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        self.chr.save_state(state);
+        state.write_u8(self.prg_mode);
+        state.write_u8(self.chr_mode);
+        state.write_bytes(&self.prg_ram_protect);
+        state.write_u8(self.prg_ram_bank);
+        state.write_bytes(&self.prg_banks);
+        for bank in self.chr_banks.iter() {
+            state.write_u16(*bank);
+        }
+        state.write_u8(self.chr_upper_bits);
+        state.write_bool(self.last_chr_set_b);
+        state.write_u8(self.exram_mode);
+        state.write_bytes(&self.exram);
+        state.write_u8(self.nametable_mapping);
+        state.write_u8(self.fill_tile);
+        state.write_u8(self.fill_attribute);
+        state.write_u8(self.split_control);
+        state.write_u8(self.split_scroll);
+        state.write_u8(self.split_bank);
+        state.write_u8(self.irq_compare);
+        state.write_bool(self.irq_enabled);
+        state.write_bool(self.irq_pending);
+        state.write_bool(self.in_frame);
+        state.write_u8(self.scanline_counter);
+        state.write_u8(self.multiplicand);
+        state.write_u8(self.multiplier);
+        state.write_bool(self.large_sprites);
+        self.fetch_phase.save_state(state);
+        state.write_u8(self.tile_column);
+        state.write_u8(self.split_y);
+        state.write_option_u8(self.tile_exram);
+        state.write_bool(self.in_split);
+        self.audio.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        self.chr.load_state(state)?;
+        self.prg_mode = state.read_u8()?;
+        self.chr_mode = state.read_u8()?;
+        state.read_bytes_into(&mut self.prg_ram_protect)?;
+        self.prg_ram_bank = state.read_u8()?;
+        state.read_bytes_into(&mut self.prg_banks)?;
+        for bank in self.chr_banks.iter_mut() {
+            *bank = state.read_u16()?;
+        }
+        self.chr_upper_bits = state.read_u8()?;
+        self.last_chr_set_b = state.read_bool()?;
+        self.exram_mode = state.read_u8()?;
+        state.read_bytes_into(&mut self.exram)?;
+        self.nametable_mapping = state.read_u8()?;
+        self.fill_tile = state.read_u8()?;
+        self.fill_attribute = state.read_u8()?;
+        self.split_control = state.read_u8()?;
+        self.split_scroll = state.read_u8()?;
+        self.split_bank = state.read_u8()?;
+        self.irq_compare = state.read_u8()?;
+        self.irq_enabled = state.read_bool()?;
+        self.irq_pending = state.read_bool()?;
+        self.in_frame = state.read_bool()?;
+        self.scanline_counter = state.read_u8()?;
+        self.multiplicand = state.read_u8()?;
+        self.multiplier = state.read_u8()?;
+        self.large_sprites = state.read_bool()?;
+        self.fetch_phase.load_state(state)?;
+        self.tile_column = state.read_u8()?;
+        self.split_y = state.read_u8()?;
+        self.tile_exram = state.read_option_u8()?;
+        self.in_split = state.read_bool()?;
+        self.audio.load_state(state)
+    }
+}
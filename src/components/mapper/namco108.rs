@@ -0,0 +1,105 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/INES_Mapper_206
+// The predecessor of the MMC3: the same bank select/data pair without its modes, IRQ or mirroring
+pub struct Namco108 {
+    prg_rom: Vec<u8>,
+    chr: Chr,
+    mirroring: Mirroring,
+    bank_select: u8,
+    // R0-R1 are 2KB CHR banks, R2-R5 1KB CHR banks, R6-R7 8KB PRG banks
+    banks: [u8; 8],
+}
+
+impl Namco108 {
+
+    pub fn new(rom: Rom) -> Self {
+        Namco108 {
+            prg_rom: rom.prg_rom,
+            chr: Chr::new(rom.chr_rom),
+            mirroring: rom.screen_mirroring,
+            bank_select: 0,
+            banks: [0; 8],
+        }
+    }
+
+    fn prg_offset(&self, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / 0x2000;
+        let bank = match addr {
+            0x8000..=0x9FFF => self.banks[6] as usize,
+            0xA000..=0xBFFF => self.banks[7] as usize,
+            0xC000..=0xDFFF => bank_count - 2,
+            _ => bank_count - 1,
+        };
+        (bank % bank_count) * 0x2000 + (addr as usize % 0x2000)
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let bank = match addr {
+            0x0000..=0x07FF => (self.banks[0] & 0x3E) as usize + (addr as usize / 0x400) % 2,
+            0x0800..=0x0FFF => (self.banks[1] & 0x3E) as usize + (addr as usize / 0x400) % 2,
+            _ => self.banks[2 + (addr as usize - 0x1000) / 0x400] as usize,
+        };
+        let bank_count = self.chr.len() / 0x400;
+        (bank % bank_count) * 0x400 + (addr as usize % 0x400)
+    }
+}
+
+impl Mapper for Namco108 {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x7FFF => 0,
+            0x8000..=0xFFFF => self.prg_rom[self.prg_offset(addr)],
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x7FFF => {}
+            0x8000..=0x9FFF if addr & 1 == 0 => self.bank_select = data & 0b111,
+            0x8000..=0x9FFF => {
+                let mask = if self.bank_select >= 6 { 0x0F } else { 0x3F };
+                self.banks[self.bank_select as usize] = data & mask;
+            }
+            0xA000..=0xFFFF => {}
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.bank_select = 0;
+        self.banks = [0; 8];
+    }
+}
+
+impl SaveState for Namco108 {
+    fn save_state(&self, state: &mut StateWriter) {
+        self.chr.save_state(state);
+        self.mirroring.save_state(state);
+        state.write_u8(self.bank_select);
+        state.write_bytes(&self.banks);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.chr.load_state(state)?;
+        self.mirroring.load_state(state)?;
+        self.bank_select = state.read_u8()?;
+        state.read_bytes_into(&mut self.banks)
+    }
+}
@@ -0,0 +1,200 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::expansion_audio::namco163::Namco163Audio;
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/INES_Mapper_019
+pub struct Namco163 {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Chr,
+
+    prg_banks: [u8; 3],
+    chr_banks: [u8; 8],
+    nametable_banks: [u8; 4],
+    irq_counter: u16,
+    irq_enabled: bool,
+    irq: bool,
+    sound_enabled: bool,
+    audio: Namco163Audio,
+}
+
+impl Namco163 {
+    const PRG_RAM_SIZE: usize = 0x2000;
+    const IRQ_COUNTER_END: u16 = 0x7FFF;
+    // bank values from here up select the console's nametable RAM instead of CHR
+    const CIRAM_BANKS: u8 = 0xE0;
+
+    pub fn new(rom: Rom) -> Result<Self, String> {
+        // the last 8KB bank is fixed at $E000, so there has to be one
+        if rom.prg_rom.len() < 0x2000 {
+            return Err(format!("Namco 163 needs at least 8KB of PRG ROM, the ROM has {} bytes", rom.prg_rom.len()));
+        }
+        Ok(Namco163 {
+            prg_rom: rom.prg_rom,
+            prg_ram: vec![0; Namco163::PRG_RAM_SIZE],
+            chr: Chr::new(rom.chr_rom),
+            prg_banks: [0; 3],
+            chr_banks: [0; 8],
+            nametable_banks: [Namco163::CIRAM_BANKS; 4],
+            irq_counter: 0,
+            irq_enabled: false,
+            irq: false,
+            sound_enabled: true,
+            audio: Namco163Audio::new(),
+        })
+    }
+
+    fn prg_offset(&self, bank: usize, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / 0x2000;
+        (bank % bank_count) * 0x2000 + (addr as usize & 0x1FFF)
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let bank = self.chr_banks[(addr / 0x400) as usize] as usize;
+        let bank_count = self.chr.len() / 0x400;
+        (bank % bank_count) * 0x400 + (addr as usize & 0x3FF)
+    }
+}
+
+impl Mapper for Namco163 {
+    fn read_prg(&mut self, addr: u16) -> u8 {
+        match addr {
+            0x4800..=0x4FFF => self.audio.read_data(),
+            _ => self.peek_prg(addr),
+        }
+    }
+
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4800..=0x4FFF => self.audio.peek_data(),
+            0x5000..=0x57FF => self.irq_counter as u8,
+            0x5800..=0x5FFF => (self.irq_counter >> 8) as u8 | (self.irq_enabled as u8) << 7,
+            0x4020..=0x47FF => 0,
+            0x6000..=0x7FFF => self.prg_ram[(addr - 0x6000) as usize],
+            0x8000..=0xDFFF => {
+                let slot = ((addr - 0x8000) / 0x2000) as usize;
+                self.prg_rom[self.prg_offset(self.prg_banks[slot] as usize, addr)]
+            }
+            0xE000..=0xFFFF => {
+                let last_bank = self.prg_rom.len() / 0x2000 - 1;
+                self.prg_rom[self.prg_offset(last_bank, addr)]
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4800..=0x4FFF => self.audio.write_data(data),
+            0x5000..=0x57FF => {
+                self.irq_counter = (self.irq_counter & 0x7F00) | data as u16;
+                self.irq = false;
+            }
+            0x5800..=0x5FFF => {
+                self.irq_counter = (self.irq_counter & 0x00FF) | ((data as u16 & 0x7F) << 8);
+                self.irq_enabled = data & 0x80 != 0;
+                self.irq = false;
+            }
+            0x4020..=0x47FF => {}
+            0x6000..=0x7FFF => self.prg_ram[(addr - 0x6000) as usize] = data,
+            0x8000..=0xBFFF => self.chr_banks[((addr - 0x8000) / 0x800) as usize] = data,
+            0xC000..=0xDFFF => self.nametable_banks[((addr - 0xC000) / 0x800) as usize] = data,
+            0xE000..=0xE7FF => {
+                self.prg_banks[0] = data & 0b0011_1111;
+                self.sound_enabled = data & 0b0100_0000 == 0;
+            }
+            0xE800..=0xEFFF => self.prg_banks[1] = data & 0b0011_1111,
+            0xF000..=0xF7FF => self.prg_banks[2] = data & 0b0011_1111,
+            0xF800..=0xFFFF => self.audio.write_address(data),
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    // Only layouts built from the console's nametable RAM can be told to the PPU, nametables
+    // taken from CHR ROM fall back to vertical mirroring
+    fn mirroring(&self) -> Mirroring {
+        if self.nametable_banks.iter().any(|bank| *bank < Namco163::CIRAM_BANKS) {
+            return Mirroring::VERTICAL;
+        }
+        match self.nametable_banks.map(|bank| bank & 1) {
+            [0, 1, 0, 1] => Mirroring::VERTICAL,
+            [0, 0, 1, 1] => Mirroring::HORIZONTAL,
+            [0, 0, 0, 0] => Mirroring::SINGLE_SCREEN_LOWER,
+            [1, 1, 1, 1] => Mirroring::SINGLE_SCREEN_UPPER,
+            _ => Mirroring::VERTICAL,
+        }
+    }
+
+    fn power_on(&mut self) {
+        self.prg_banks = [0; 3];
+        self.chr_banks = [0; 8];
+        self.nametable_banks = [Namco163::CIRAM_BANKS; 4];
+        self.irq_counter = 0;
+        self.irq_enabled = false;
+        self.irq = false;
+        self.sound_enabled = true;
+        self.audio.power_on();
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        for _ in 0..cycles {
+            if self.irq_enabled && self.irq_counter < Namco163::IRQ_COUNTER_END {
+                self.irq_counter += 1;
+                if self.irq_counter == Namco163::IRQ_COUNTER_END {
+                    self.irq = true;
+                }
+            }
+            self.audio.clock();
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq
+    }
+
+    fn expansion_audio(&self) -> f32 {
+        if self.sound_enabled {
+            self.audio.output()
+        } else {
+            0.0
+        }
+    }
+}
+
+impl SaveState for Namco163 {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        self.chr.save_state(state);
+        state.write_bytes(&self.prg_banks);
+        state.write_bytes(&self.chr_banks);
+        state.write_bytes(&self.nametable_banks);
+        state.write_u16(self.irq_counter);
+        state.write_bool(self.irq_enabled);
+        state.write_bool(self.irq);
+        state.write_bool(self.sound_enabled);
+        self.audio.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        self.chr.load_state(state)?;
+        state.read_bytes_into(&mut self.prg_banks)?;
+        state.read_bytes_into(&mut self.chr_banks)?;
+        state.read_bytes_into(&mut self.nametable_banks)?;
+        self.irq_counter = state.read_u16()?;
+        self.irq_enabled = state.read_bool()?;
+        self.irq = state.read_bool()?;
+        self.sound_enabled = state.read_bool()?;
+        self.audio.load_state(state)
+    }
+}
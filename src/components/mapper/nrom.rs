@@ -0,0 +1,74 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/NROM
+pub struct Nrom {
+    prg_rom: Vec<u8>,
+    chr: Chr,
+    mirroring: Mirroring,
+}
+
+impl Nrom {
+
+    pub fn new(rom: Rom) -> Self {
+        Nrom {
+            prg_rom: rom.prg_rom,
+            chr: Chr::new(rom.chr_rom),
+            mirroring: rom.screen_mirroring,
+        }
+    }
+}
+
+impl Mapper for Nrom {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x8000..=0xFFFF => {
+                let mut addr = addr - 0x8000;
+                if self.prg_rom.len() == 0x4000 && addr >= 0x4000 {
+                    //mirror if needed
+                    addr %= 0x4000;
+                }
+                self.prg_rom[addr as usize]
+            }
+            // no PRG RAM on the board
+            0x4020..=0x7FFF => 0,
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, _data: u8) {
+        match addr {
+            // ROM and the empty space below it don't listen to writes
+            0x4020..=0xFFFF => {}
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[addr as usize]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        self.chr.write(addr as usize, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        // no registers to set up
+    }
+}
+
+impl SaveState for Nrom {
+    fn save_state(&self, state: &mut StateWriter) {
+        self.chr.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.chr.load_state(state)?;
+        Ok(())
+    }
+}
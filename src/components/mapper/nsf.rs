@@ -0,0 +1,261 @@
+use crate::components::cartridge::nsf::Nsf;
+use crate::components::cartridge::Mirroring;
+use crate::components::expansion_audio::mmc5::Mmc5Audio;
+use crate::components::expansion_audio::namco163::Namco163Audio;
+use crate::components::expansion_audio::sunsoft5b::Sunsoft5bAudio;
+use crate::components::expansion_audio::vrc6::Vrc6Audio;
+use crate::components::expansion_audio::vrc7::Vrc7Audio;
+use crate::components::mapper::Mapper;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/NSF#Bankswitching
+pub struct NsfMapper {
+    prg: Vec<u8>,
+    banks: [u8; 8],
+    initial_banks: [u8; 8],
+    prg_ram: Vec<u8>,
+    chr_ram: Vec<u8>,
+
+    //https://www.nesdev.org/wiki/NSF#Expansion_audio
+    vrc6: Option<Vrc6Audio>,
+    vrc7: Option<Vrc7Audio>,
+    mmc5: Option<Mmc5Audio>,
+    // tunes written for the MMC5 may also use its ExRAM and multiplier
+    mmc5_exram: Vec<u8>,
+    mmc5_multiplier: [u8; 2],
+    namco163: Option<Namco163Audio>,
+    sunsoft5b: Option<Sunsoft5bAudio>,
+}
+
+impl NsfMapper {
+    const BANK_SIZE: usize = 0x1000;
+    const PRG_RAM_SIZE: usize = 0x2000;
+    const CHR_RAM_SIZE: usize = 0x2000;
+    const MMC5_EXRAM_SIZE: usize = 0x400;
+
+    const VRC6: u8 = 0b0000_0001;
+    const VRC7: u8 = 0b0000_0010;
+    const MMC5: u8 = 0b0000_1000;
+    const NAMCO163: u8 = 0b0001_0000;
+    const SUNSOFT5B: u8 = 0b0010_0000;
+
+    pub fn new(nsf: &Nsf) -> Result<NsfMapper, String> {
+        let (prg, initial_banks) = if nsf.is_bank_switched() {
+            // the data is placed at the load address offset inside its first bank
+            let padding = (nsf.load_address & 0x0FFF) as usize;
+            let mut prg = vec![0; padding];
+            prg.extend(&nsf.data);
+            let bank_count = prg.len().div_ceil(NsfMapper::BANK_SIZE);
+            prg.resize(bank_count * NsfMapper::BANK_SIZE, 0);
+            (prg, nsf.bank_switch)
+        } else {
+            if nsf.load_address < 0x8000 {
+                return Err(format!("NSF load address {:04X} is below $8000", nsf.load_address));
+            }
+            let start = (nsf.load_address - 0x8000) as usize;
+            let mut prg = vec![0; 8 * NsfMapper::BANK_SIZE];
+            let length = nsf.data.len().min(prg.len() - start);
+            prg[start..start + length].copy_from_slice(&nsf.data[..length]);
+            (prg, [0, 1, 2, 3, 4, 5, 6, 7])
+        };
+
+        Ok(NsfMapper {
+            prg,
+            banks: initial_banks,
+            initial_banks,
+            prg_ram: vec![0; NsfMapper::PRG_RAM_SIZE],
+            chr_ram: vec![0; NsfMapper::CHR_RAM_SIZE],
+            vrc6: (nsf.expansion_audio & NsfMapper::VRC6 != 0).then(Vrc6Audio::new),
+            vrc7: (nsf.expansion_audio & NsfMapper::VRC7 != 0).then(Vrc7Audio::new),
+            mmc5: (nsf.expansion_audio & NsfMapper::MMC5 != 0).then(Mmc5Audio::new),
+            mmc5_exram: vec![0; NsfMapper::MMC5_EXRAM_SIZE],
+            mmc5_multiplier: [0; 2],
+            namco163: (nsf.expansion_audio & NsfMapper::NAMCO163 != 0).then(Namco163Audio::new),
+            sunsoft5b: (nsf.expansion_audio & NsfMapper::SUNSOFT5B != 0).then(Sunsoft5bAudio::new),
+        })
+    }
+
+    pub fn reset_banks(&mut self) {
+        self.banks = self.initial_banks;
+    }
+
+    fn bank_count(&self) -> usize {
+        self.prg.len() / NsfMapper::BANK_SIZE
+    }
+}
+
+impl Mapper for NsfMapper {
+    fn read_prg(&mut self, addr: u16) -> u8 {
+        match addr {
+            0x4800..=0x4FFF if self.namco163.is_some() => self.namco163.as_mut().unwrap().read_data(),
+            0x5010 | 0x5015 if self.mmc5.is_some() => self.mmc5.as_mut().unwrap().read(addr),
+            _ => self.peek_prg(addr),
+        }
+    }
+
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4800..=0x4FFF if self.namco163.is_some() => self.namco163.as_ref().unwrap().peek_data(),
+            0x5010 | 0x5015 if self.mmc5.is_some() => self.mmc5.as_ref().unwrap().peek(addr),
+            0x5205 if self.mmc5.is_some() => {
+                (self.mmc5_multiplier[0] as u16 * self.mmc5_multiplier[1] as u16) as u8
+            }
+            0x5206 if self.mmc5.is_some() => {
+                ((self.mmc5_multiplier[0] as u16 * self.mmc5_multiplier[1] as u16) >> 8) as u8
+            }
+            0x5C00..=0x5FF5 if self.mmc5.is_some() => self.mmc5_exram[(addr - 0x5C00) as usize],
+            0x6000..=0x7FFF => self.prg_ram[(addr - 0x6000) as usize],
+            0x8000..=0xFFFF => {
+                let slot = ((addr - 0x8000) as usize) / NsfMapper::BANK_SIZE;
+                let bank = self.banks[slot] as usize % self.bank_count();
+                self.prg[bank * NsfMapper::BANK_SIZE + (addr as usize & 0x0FFF)]
+            }
+            // open bus, nothing is mapped here without a cartridge
+            _ => 0,
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x5FF8..=0x5FFF => self.banks[(addr - 0x5FF8) as usize] = data,
+            0x6000..=0x7FFF => self.prg_ram[(addr - 0x6000) as usize] = data,
+            _ => {}
+        }
+
+        if let Some(vrc6) = &mut self.vrc6 {
+            if let 0x9000..=0xB002 = addr {
+                vrc6.write(addr, data);
+            }
+        }
+        if let Some(vrc7) = &mut self.vrc7 {
+            match addr {
+                0x9010 => vrc7.write_address(data),
+                0x9030 => vrc7.write_data(data),
+                _ => {}
+            }
+        }
+        if let Some(mmc5) = &mut self.mmc5 {
+            match addr {
+                0x5000..=0x5015 => mmc5.write(addr, data),
+                0x5205 | 0x5206 => self.mmc5_multiplier[(addr - 0x5205) as usize] = data,
+                0x5C00..=0x5FF5 => self.mmc5_exram[(addr - 0x5C00) as usize] = data,
+                _ => {}
+            }
+        }
+        if let Some(namco163) = &mut self.namco163 {
+            match addr {
+                0x4800..=0x4FFF => namco163.write_data(data),
+                0xF800..=0xFFFF => namco163.write_address(data),
+                _ => {}
+            }
+        }
+        if let Some(sunsoft5b) = &mut self.sunsoft5b {
+            match addr {
+                0xC000..=0xDFFF => sunsoft5b.write_address(data),
+                0xE000..=0xFFFF => sunsoft5b.write_data(data),
+                _ => {}
+            }
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr_ram[addr as usize]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        self.chr_ram[addr as usize] = data;
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        Mirroring::VERTICAL
+    }
+
+    fn power_on(&mut self) {
+        self.banks = self.initial_banks;
+        self.mmc5_multiplier = [0; 2];
+        self.vrc6 = self.vrc6.as_ref().map(|_| Vrc6Audio::new());
+        self.vrc7 = self.vrc7.as_ref().map(|_| Vrc7Audio::new());
+        self.mmc5 = self.mmc5.as_ref().map(|_| Mmc5Audio::new());
+        if let Some(namco163) = &mut self.namco163 {
+            namco163.power_on();
+        }
+        self.sunsoft5b = self.sunsoft5b.as_ref().map(|_| Sunsoft5bAudio::new());
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        for _ in 0..cycles {
+            if let Some(vrc6) = &mut self.vrc6 {
+                vrc6.clock();
+            }
+            if let Some(vrc7) = &mut self.vrc7 {
+                vrc7.clock();
+            }
+            if let Some(mmc5) = &mut self.mmc5 {
+                mmc5.clock();
+            }
+            if let Some(namco163) = &mut self.namco163 {
+                namco163.clock();
+            }
+            if let Some(sunsoft5b) = &mut self.sunsoft5b {
+                sunsoft5b.clock();
+            }
+        }
+    }
+
+    fn expansion_audio(&self) -> f32 {
+        self.vrc6.as_ref().map_or(0.0, |vrc6| vrc6.output())
+            + self.vrc7.as_ref().map_or(0.0, |vrc7| vrc7.output())
+            + self.mmc5.as_ref().map_or(0.0, |mmc5| mmc5.output())
+            + self.namco163.as_ref().map_or(0.0, |namco163| namco163.output())
+            + self.sunsoft5b.as_ref().map_or(0.0, |sunsoft5b| sunsoft5b.output())
+    }
+}
+
+impl SaveState for NsfMapper {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.banks);
+        state.write_bytes(&self.prg_ram);
+        state.write_bytes(&self.chr_ram);
+        if let Some(audio) = &self.vrc6 {
+            audio.save_state(state);
+        }
+        if let Some(audio) = &self.vrc7 {
+            audio.save_state(state);
+        }
+        if let Some(audio) = &self.mmc5 {
+            audio.save_state(state);
+        }
+        state.write_bytes(&self.mmc5_exram);
+        state.write_bytes(&self.mmc5_multiplier);
+        if let Some(audio) = &self.namco163 {
+            audio.save_state(state);
+        }
+        if let Some(audio) = &self.sunsoft5b {
+            audio.save_state(state);
+        }
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.banks)?;
+        state.read_bytes_into(&mut self.prg_ram)?;
+        state.read_bytes_into(&mut self.chr_ram)?;
+        if let Some(audio) = &mut self.vrc6 {
+            audio.load_state(state)?;
+        }
+        if let Some(audio) = &mut self.vrc7 {
+            audio.load_state(state)?;
+        }
+        if let Some(audio) = &mut self.mmc5 {
+            audio.load_state(state)?;
+        }
+        state.read_bytes_into(&mut self.mmc5_exram)?;
+        state.read_bytes_into(&mut self.mmc5_multiplier)?;
+        if let Some(audio) = &mut self.namco163 {
+            audio.load_state(state)?;
+        }
+        if let Some(audio) = &mut self.sunsoft5b {
+            audio.load_state(state)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/INES_Mapper_033
+//https://www.nesdev.org/wiki/INES_Mapper_048
+// Mapper 48 is the TC0690, which moves mirroring to $E000 and adds an MMC3 style scanline IRQ
+pub struct TaitoTc0190 {
+    prg_rom: Vec<u8>,
+    chr: Chr,
+    is_tc0690: bool,
+    prg_banks: [u8; 2],
+    // two 2KB banks for $0000-$0FFF then four 1KB banks for $1000-$1FFF
+    chr_banks: [u8; 6],
+    mirroring: Mirroring,
+    initial_mirroring: Mirroring,
+
+    irq_latch: u8,
+    irq_counter: u8,
+    irq_reload: bool,
+    irq_enabled: bool,
+    irq_pending: bool,
+}
+
+impl TaitoTc0190 {
+
+    pub fn new(rom: Rom) -> Self {
+        TaitoTc0190 {
+            is_tc0690: rom.mapper == 48,
+            prg_rom: rom.prg_rom,
+            chr: Chr::new(rom.chr_rom),
+            prg_banks: [0; 2],
+            chr_banks: [0; 6],
+            mirroring: rom.screen_mirroring,
+            initial_mirroring: rom.screen_mirroring,
+            irq_latch: 0,
+            irq_counter: 0,
+            irq_reload: false,
+            irq_enabled: false,
+            irq_pending: false,
+        }
+    }
+
+    fn prg_offset(&self, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / 0x2000;
+        let bank = match addr {
+            0x8000..=0x9FFF => self.prg_banks[0] as usize,
+            0xA000..=0xBFFF => self.prg_banks[1] as usize,
+            0xC000..=0xDFFF => bank_count - 2,
+            _ => bank_count - 1,
+        };
+        (bank % bank_count) * 0x2000 + (addr as usize % 0x2000)
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let slot = addr as usize / 0x400;
+        let bank = if slot < 4 {
+            self.chr_banks[slot / 2] as usize * 2 + slot % 2
+        } else {
+            self.chr_banks[slot - 2] as usize
+        };
+        let bank_count = self.chr.len() / 0x400;
+        (bank % bank_count) * 0x400 + (addr as usize % 0x400)
+    }
+
+    fn set_mirroring(&mut self, data: u8) {
+        self.mirroring = if data & 0b0100_0000 != 0 { Mirroring::HORIZONTAL } else { Mirroring::VERTICAL };
+    }
+
+    // Clocked once per rendered scanline the way the MMC3 counter sees PPU A12 rise
+    fn clock_irq(&mut self) {
+        if self.irq_counter == 0 || self.irq_reload {
+            self.irq_counter = self.irq_latch;
+            self.irq_reload = false;
+        } else {
+            self.irq_counter -= 1;
+        }
+        if self.irq_counter == 0 && self.irq_enabled {
+            self.irq_pending = true;
+        }
+    }
+}
+
+impl Mapper for TaitoTc0190 {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x7FFF => 0,
+            0x8000..=0xFFFF => self.prg_rom[self.prg_offset(addr)],
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x7FFF => {}
+            0x8000..=0xFFFF => match addr & 0xE003 {
+                0x8000 => {
+                    self.prg_banks[0] = data & 0x3F;
+                    if !self.is_tc0690 {
+                        self.set_mirroring(data);
+                    }
+                }
+                0x8001 => self.prg_banks[1] = data & 0x3F,
+                register @ 0x8002..=0x8003 => self.chr_banks[(register - 0x8002) as usize] = data,
+                register @ 0xA000..=0xA003 => self.chr_banks[(register - 0xA000 + 2) as usize] = data,
+                0xC000 if self.is_tc0690 => self.irq_latch = data,
+                0xC001 if self.is_tc0690 => {
+                    self.irq_counter = 0;
+                    self.irq_reload = true;
+                }
+                0xC002 if self.is_tc0690 => self.irq_enabled = true,
+                0xC003 if self.is_tc0690 => {
+                    self.irq_enabled = false;
+                    self.irq_pending = false;
+                }
+                0xE000 if self.is_tc0690 => self.set_mirroring(data),
+                _ => {}
+            },
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.prg_banks = [0; 2];
+        self.chr_banks = [0; 6];
+        self.mirroring = self.initial_mirroring;
+        self.irq_latch = 0;
+        self.irq_counter = 0;
+        self.irq_reload = false;
+        self.irq_enabled = false;
+        self.irq_pending = false;
+    }
+
+    fn ppu_scanline(&mut self, scanline: u16, rendering: bool) {
+        if self.is_tc0690 && rendering && (scanline < 240 || scanline == 261) {
+            self.clock_irq();
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq_pending
+    }
+}
+
+impl SaveState for TaitoTc0190 {
+    fn save_state(&self, state: &mut StateWriter) {
+        self.chr.save_state(state);
+        state.write_bytes(&self.prg_banks);
+        state.write_bytes(&self.chr_banks);
+        self.mirroring.save_state(state);
+        state.write_u8(self.irq_latch);
+        state.write_u8(self.irq_counter);
+        state.write_bool(self.irq_reload);
+        state.write_bool(self.irq_enabled);
+        state.write_bool(self.irq_pending);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.chr.load_state(state)?;
+        state.read_bytes_into(&mut self.prg_banks)?;
+        state.read_bytes_into(&mut self.chr_banks)?;
+        self.mirroring.load_state(state)?;
+        self.irq_latch = state.read_u8()?;
+        self.irq_counter = state.read_u8()?;
+        self.irq_reload = state.read_bool()?;
+        self.irq_enabled = state.read_bool()?;
+        self.irq_pending = state.read_bool()?;
+        Ok(())
+    }
+}
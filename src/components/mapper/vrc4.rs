@@ -0,0 +1,207 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::vrc_irq::VrcIrq;
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/VRC2_and_VRC4
+// The boards differ in which CPU address lines reach the chip's two register select pins,
+// an iNES 1.0 header can't tell them apart so both candidates are decoded at once.
+pub struct Vrc4 {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Chr,
+    is_vrc2: bool,
+    // VRC2a drops the lowest CHR bank bit
+    chr_shift: u8,
+    // address bits that drive register select 0 and 1
+    select_lines: (u16, u16),
+
+    prg_banks: [u8; 2],
+    prg_swap_mode: bool,
+    chr_banks: [u16; 8],
+    mirroring: Mirroring,
+    initial_mirroring: Mirroring,
+    irq: VrcIrq,
+}
+
+impl Vrc4 {
+    const PRG_RAM_SIZE: usize = 0x2000;
+
+    const A0: u16 = 1 << 0;
+    const A1: u16 = 1 << 1;
+    const A2: u16 = 1 << 2;
+    const A3: u16 = 1 << 3;
+    const A6: u16 = 1 << 6;
+    const A7: u16 = 1 << 7;
+
+    pub fn new(rom: Rom) -> Self {
+        // (is VRC2, select lines) for every mapper/submapper pair
+        let (is_vrc2, select_lines) = match (rom.mapper, rom.submapper) {
+            (21, 1) => (false, (Vrc4::A1, Vrc4::A2)),
+            (21, 2) => (false, (Vrc4::A6, Vrc4::A7)),
+            (21, _) => (false, (Vrc4::A1 | Vrc4::A6, Vrc4::A2 | Vrc4::A7)),
+            (22, _) => (true, (Vrc4::A1, Vrc4::A0)),
+            (23, 1) => (false, (Vrc4::A0, Vrc4::A1)),
+            (23, 2) => (false, (Vrc4::A2, Vrc4::A3)),
+            (23, 3) => (true, (Vrc4::A0, Vrc4::A1)),
+            (23, _) => (false, (Vrc4::A0 | Vrc4::A2, Vrc4::A1 | Vrc4::A3)),
+            (25, 1) => (false, (Vrc4::A1, Vrc4::A0)),
+            (25, 2) => (false, (Vrc4::A3, Vrc4::A2)),
+            (25, 3) => (true, (Vrc4::A1, Vrc4::A0)),
+            (25, _) => (false, (Vrc4::A1 | Vrc4::A3, Vrc4::A0 | Vrc4::A2)),
+            (mapper, _) => panic!("Mapper {} is not a VRC2 or VRC4 board", mapper),
+        };
+
+        Vrc4 {
+            chr_shift: if rom.mapper == 22 { 1 } else { 0 },
+            prg_rom: rom.prg_rom,
+            prg_ram: vec![0; Vrc4::PRG_RAM_SIZE],
+            chr: Chr::new(rom.chr_rom),
+            is_vrc2,
+            select_lines,
+            prg_banks: [0; 2],
+            prg_swap_mode: false,
+            chr_banks: [0; 8],
+            mirroring: rom.screen_mirroring,
+            initial_mirroring: rom.screen_mirroring,
+            irq: VrcIrq::new(),
+        }
+    }
+
+    // Registers are decoded from A12-A15 plus the two select pins
+    fn register(&self, addr: u16) -> u16 {
+        let select_0 = (addr & self.select_lines.0 != 0) as u16;
+        let select_1 = (addr & self.select_lines.1 != 0) as u16;
+        (addr & 0xF000) | select_1 << 1 | select_0
+    }
+
+    fn prg_offset(&self, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / 0x2000;
+        let second_last = bank_count - 2;
+        let bank = match (addr, self.prg_swap_mode) {
+            (0x8000..=0x9FFF, false) | (0xC000..=0xDFFF, true) => self.prg_banks[0] as usize,
+            (0x8000..=0x9FFF, true) | (0xC000..=0xDFFF, false) => second_last,
+            (0xA000..=0xBFFF, _) => self.prg_banks[1] as usize,
+            _ => bank_count - 1,
+        };
+        (bank % bank_count) * 0x2000 + (addr as usize % 0x2000)
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let bank = (self.chr_banks[addr as usize / 0x400] >> self.chr_shift) as usize;
+        let bank_count = self.chr.len() / 0x400;
+        (bank % bank_count) * 0x400 + (addr as usize % 0x400)
+    }
+
+    // Each 1KB CHR bank is written as a low and a high nibble
+    fn write_chr_bank(&mut self, register: u16, data: u8) {
+        let index = (((register - 0xB000) >> 12) * 2 + ((register & 0b10) >> 1)) as usize;
+        let bank = &mut self.chr_banks[index];
+        if register & 0b01 == 0 {
+            *bank = (*bank & 0x1F0) | (data & 0x0F) as u16;
+        } else {
+            *bank = (*bank & 0x0F) | ((data & 0x1F) as u16) << 4;
+        }
+    }
+}
+
+impl Mapper for Vrc4 {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => 0,
+            0x6000..=0x7FFF => self.prg_ram[(addr - 0x6000) as usize],
+            0x8000..=0xFFFF => self.prg_rom[self.prg_offset(addr)],
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x5FFF => {}
+            0x6000..=0x7FFF => self.prg_ram[(addr - 0x6000) as usize] = data,
+            0x8000..=0xFFFF => match self.register(addr) {
+                0x8000..=0x8003 => self.prg_banks[0] = data & 0x1F,
+                0x9000..=0x9001 if self.is_vrc2 => {
+                    self.mirroring = if data & 1 != 0 { Mirroring::HORIZONTAL } else { Mirroring::VERTICAL };
+                }
+                0x9000..=0x9001 => {
+                    self.mirroring = match data & 0b11 {
+                        0 => Mirroring::VERTICAL,
+                        1 => Mirroring::HORIZONTAL,
+                        2 => Mirroring::SINGLE_SCREEN_LOWER,
+                        _ => Mirroring::SINGLE_SCREEN_UPPER,
+                    };
+                }
+                0x9002..=0x9003 if !self.is_vrc2 => self.prg_swap_mode = data & 0b10 != 0,
+                0xA000..=0xA003 => self.prg_banks[1] = data & 0x1F,
+                register @ 0xB000..=0xEFFF => self.write_chr_bank(register, data),
+                0xF000 if !self.is_vrc2 => self.irq.write_latch_low(data),
+                0xF001 if !self.is_vrc2 => self.irq.write_latch_high(data),
+                0xF002 if !self.is_vrc2 => self.irq.write_control(data),
+                0xF003 if !self.is_vrc2 => self.irq.acknowledge(),
+                _ => {}
+            },
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn power_on(&mut self) {
+        self.prg_banks = [0; 2];
+        self.prg_swap_mode = false;
+        self.chr_banks = [0; 8];
+        self.mirroring = self.initial_mirroring;
+        self.irq = VrcIrq::new();
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        if self.is_vrc2 {
+            return;
+        }
+        for _ in 0..cycles {
+            self.irq.clock();
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq.is_pending()
+    }
+}
+
+impl SaveState for Vrc4 {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        self.chr.save_state(state);
+        state.write_bytes(&self.prg_banks);
+        state.write_bool(self.prg_swap_mode);
+        for bank in self.chr_banks.iter() {
+            state.write_u16(*bank);
+        }
+        self.mirroring.save_state(state);
+        self.irq.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        self.chr.load_state(state)?;
+        state.read_bytes_into(&mut self.prg_banks)?;
+        self.prg_swap_mode = state.read_bool()?;
+        for bank in self.chr_banks.iter_mut() {
+            *bank = state.read_u16()?;
+        }
+        self.mirroring.load_state(state)?;
+        self.irq.load_state(state)
+    }
+}
@@ -0,0 +1,189 @@
+use crate::components::expansion_audio::vrc6::Vrc6Audio;
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper::vrc_irq::VrcIrq;
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/VRC6
+pub struct Vrc6 {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Chr,
+    // mapper 26 (Madara, Esper Dream 2) has A0 and A1 swapped compared to mapper 24
+    swapped_address_lines: bool,
+
+    prg_bank_16k: u8,
+    prg_bank_8k: u8,
+    chr_banks: [u8; 8],
+    banking_control: u8,
+    irq: VrcIrq,
+    audio: Vrc6Audio,
+}
+
+impl Vrc6 {
+    const PRG_RAM_SIZE: usize = 0x2000;
+
+    pub fn new(rom: Rom) -> Self {
+        Vrc6 {
+            swapped_address_lines: rom.mapper == 26,
+            prg_rom: rom.prg_rom,
+            prg_ram: vec![0; Vrc6::PRG_RAM_SIZE],
+            chr: Chr::new(rom.chr_rom),
+            prg_bank_16k: 0,
+            prg_bank_8k: 0,
+            chr_banks: [0; 8],
+            banking_control: 0,
+            irq: VrcIrq::new(),
+            audio: Vrc6Audio::new(),
+        }
+    }
+
+    // Registers are decoded from A12-A15 plus the two low address lines
+    fn register(&self, addr: u16) -> u16 {
+        let low = if self.swapped_address_lines {
+            ((addr & 0b01) << 1) | ((addr & 0b10) >> 1)
+        } else {
+            addr & 0b11
+        };
+        (addr & 0xF000) | low
+    }
+
+    fn prg_ram_enabled(&self) -> bool {
+        self.banking_control & 0b1000_0000 != 0
+    }
+
+    fn prg_offset(&self, bank: usize, bank_size: usize, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / bank_size;
+        (bank % bank_count) * bank_size + (addr as usize % bank_size)
+    }
+
+    // Which of the eight 1KB windows maps to which register depends on the banking mode, the
+    // 2KB windows take A10 from the PPU when $B003 bit 5 is set
+    fn chr_offset(&self, addr: u16) -> usize {
+        let slot = (addr / 0x400) as usize;
+        let (register, is_2k_window) = match self.banking_control & 0b11 {
+            0 => (slot, false),
+            1 => (slot / 2, true),
+            _ if slot < 4 => (slot, false),
+            _ => (4 + (slot - 4) / 2, true),
+        };
+        let mut bank = self.chr_banks[register] as usize;
+        if is_2k_window && self.banking_control & 0b0010_0000 != 0 {
+            bank = (bank & !1) | ((addr as usize >> 10) & 1);
+        }
+        let bank_count = self.chr.len() / 0x400;
+        (bank % bank_count) * 0x400 + (addr as usize % 0x400)
+    }
+}
+
+impl Mapper for Vrc6 {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => 0,
+            0x6000..=0x7FFF => {
+                if self.prg_ram_enabled() {
+                    self.prg_ram[(addr - 0x6000) as usize]
+                } else {
+                    0
+                }
+            }
+            0x8000..=0xBFFF => self.prg_rom[self.prg_offset(self.prg_bank_16k as usize, 0x4000, addr)],
+            0xC000..=0xDFFF => self.prg_rom[self.prg_offset(self.prg_bank_8k as usize, 0x2000, addr)],
+            0xE000..=0xFFFF => {
+                let last_bank = self.prg_rom.len() / 0x2000 - 1;
+                self.prg_rom[self.prg_offset(last_bank, 0x2000, addr)]
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4020..=0x5FFF => {}
+            0x6000..=0x7FFF => {
+                if self.prg_ram_enabled() {
+                    self.prg_ram[(addr - 0x6000) as usize] = data;
+                }
+            }
+            0x8000..=0xFFFF => match self.register(addr) {
+                0x8000..=0x8003 => self.prg_bank_16k = data & 0x0F,
+                register @ 0x9000..=0xB002 => self.audio.write(register, data),
+                0xB003 => self.banking_control = data,
+                0xC000..=0xC003 => self.prg_bank_8k = data & 0x1F,
+                register @ 0xD000..=0xD003 => self.chr_banks[(register - 0xD000) as usize] = data,
+                register @ 0xE000..=0xE003 => self.chr_banks[(register - 0xE000 + 4) as usize] = data,
+                0xF000 => self.irq.write_latch(data),
+                0xF001 => self.irq.write_control(data),
+                0xF002 => self.irq.acknowledge(),
+                _ => {}
+            },
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        match (self.banking_control >> 2) & 0b11 {
+            0 => Mirroring::VERTICAL,
+            1 => Mirroring::HORIZONTAL,
+            2 => Mirroring::SINGLE_SCREEN_LOWER,
+            _ => Mirroring::SINGLE_SCREEN_UPPER,
+        }
+    }
+
+    fn power_on(&mut self) {
+        self.prg_bank_16k = 0;
+        self.prg_bank_8k = 0;
+        self.chr_banks = [0; 8];
+        self.banking_control = 0;
+        self.irq = VrcIrq::new();
+        self.audio = Vrc6Audio::new();
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        for _ in 0..cycles {
+            self.irq.clock();
+            self.audio.clock();
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq.is_pending()
+    }
+
+    fn expansion_audio(&self) -> f32 {
+        self.audio.output()
+    }
+}
+
+impl SaveState for Vrc6 {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        self.chr.save_state(state);
+        state.write_u8(self.prg_bank_16k);
+        state.write_u8(self.prg_bank_8k);
+        state.write_bytes(&self.chr_banks);
+        state.write_u8(self.banking_control);
+        self.irq.save_state(state);
+        self.audio.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        self.chr.load_state(state)?;
+        self.prg_bank_16k = state.read_u8()?;
+        self.prg_bank_8k = state.read_u8()?;
+        state.read_bytes_into(&mut self.chr_banks)?;
+        self.banking_control = state.read_u8()?;
+        self.irq.load_state(state)?;
+        self.audio.load_state(state)
+    }
+}
@@ -0,0 +1,193 @@
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::expansion_audio::vrc7::Vrc7Audio;
+use crate::components::mapper::vrc_irq::VrcIrq;
+use crate::components::mapper::{Chr, Mapper};
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/VRC7
+pub struct Vrc7 {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Chr,
+
+    prg_banks: [u8; 3],
+    chr_banks: [u8; 8],
+    control: u8,
+    irq: VrcIrq,
+    audio: Vrc7Audio,
+}
+
+impl Vrc7 {
+    const PRG_RAM_SIZE: usize = 0x2000;
+
+    pub fn new(rom: Rom) -> Self {
+        Vrc7 {
+            prg_rom: rom.prg_rom,
+            prg_ram: vec![0; Vrc7::PRG_RAM_SIZE],
+            chr: Chr::new(rom.chr_rom),
+            prg_banks: [0; 3],
+            chr_banks: [0; 8],
+            control: 0,
+            irq: VrcIrq::new(),
+            audio: Vrc7Audio::new(),
+        }
+    }
+
+    fn prg_ram_enabled(&self) -> bool {
+        self.control & 0b1000_0000 != 0
+    }
+
+    fn sound_silenced(&self) -> bool {
+        self.control & 0b0100_0000 != 0
+    }
+
+    fn prg_offset(&self, bank: usize, addr: u16) -> usize {
+        let bank_count = self.prg_rom.len() / 0x2000;
+        (bank % bank_count) * 0x2000 + (addr as usize & 0x1FFF)
+    }
+
+    fn chr_offset(&self, addr: u16) -> usize {
+        let bank = self.chr_banks[(addr / 0x400) as usize] as usize;
+        let bank_count = self.chr.len() / 0x400;
+        (bank % bank_count) * 0x400 + (addr as usize & 0x3FF)
+    }
+}
+
+impl Mapper for Vrc7 {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        match addr {
+            0x4020..=0x5FFF => 0,
+            0x6000..=0x7FFF => {
+                if self.prg_ram_enabled() {
+                    self.prg_ram[(addr - 0x6000) as usize]
+                } else {
+                    0
+                }
+            }
+            0x8000..=0xDFFF => {
+                let slot = ((addr - 0x8000) / 0x2000) as usize;
+                self.prg_rom[self.prg_offset(self.prg_banks[slot] as usize, addr)]
+            }
+            0xE000..=0xFFFF => {
+                let last_bank = self.prg_rom.len() / 0x2000 - 1;
+                self.prg_rom[self.prg_offset(last_bank, addr)]
+            }
+            _ => panic!("Attempt to read from non cartridge address {:x}", addr),
+        }
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        // VRC7a boards (Lagrange Point) pick the second register of a pair with A4, VRC7b
+        // boards (Tiny Toon Adventures 2) with A3
+        let second = addr & 0x0018 != 0;
+        match addr {
+            0x4020..=0x5FFF => {}
+            0x6000..=0x7FFF => {
+                if self.prg_ram_enabled() {
+                    self.prg_ram[(addr - 0x6000) as usize] = data;
+                }
+            }
+            0x8000..=0x8FFF => self.prg_banks[second as usize] = data & 0b0011_1111,
+            0x9000..=0x9FFF => {
+                if addr & 0x0010 == 0 {
+                    self.prg_banks[2] = data & 0b0011_1111;
+                } else if addr & 0x0020 == 0 {
+                    self.audio.write_address(data);
+                } else {
+                    self.audio.write_data(data);
+                }
+            }
+            0xA000..=0xDFFF => {
+                let register = ((addr - 0xA000) / 0x1000) as usize * 2 + second as usize;
+                self.chr_banks[register] = data;
+            }
+            0xE000..=0xEFFF => {
+                if second {
+                    self.irq.write_latch(data);
+                } else {
+                    self.control = data;
+                    if self.sound_silenced() {
+                        self.audio.reset();
+                    }
+                }
+            }
+            0xF000..=0xFFFF => {
+                if second {
+                    self.irq.acknowledge();
+                } else {
+                    self.irq.write_control(data);
+                }
+            }
+            _ => panic!("Attempt to write to non cartridge address {:x}", addr),
+        }
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.chr[self.chr_offset(addr)]
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        let offset = self.chr_offset(addr);
+        self.chr.write(offset, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        match self.control & 0b11 {
+            0 => Mirroring::VERTICAL,
+            1 => Mirroring::HORIZONTAL,
+            2 => Mirroring::SINGLE_SCREEN_LOWER,
+            _ => Mirroring::SINGLE_SCREEN_UPPER,
+        }
+    }
+
+    fn power_on(&mut self) {
+        self.prg_banks = [0; 3];
+        self.chr_banks = [0; 8];
+        self.control = 0;
+        self.irq = VrcIrq::new();
+        self.audio.reset();
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        for _ in 0..cycles {
+            self.irq.clock();
+            if !self.sound_silenced() {
+                self.audio.clock();
+            }
+        }
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq.is_pending()
+    }
+
+    fn expansion_audio(&self) -> f32 {
+        if self.sound_silenced() {
+            0.0
+        } else {
+            self.audio.output()
+        }
+    }
+}
+
+impl SaveState for Vrc7 {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.prg_ram);
+        self.chr.save_state(state);
+        state.write_bytes(&self.prg_banks);
+        state.write_bytes(&self.chr_banks);
+        state.write_u8(self.control);
+        self.irq.save_state(state);
+        self.audio.save_state(state);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.prg_ram)?;
+        self.chr.load_state(state)?;
+        state.read_bytes_into(&mut self.prg_banks)?;
+        state.read_bytes_into(&mut self.chr_banks)?;
+        self.control = state.read_u8()?;
+        self.irq.load_state(state)?;
+        self.audio.load_state(state)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/VRC_IRQ
+// Shared by the Konami VRC4, VRC6 and VRC7
+pub struct VrcIrq {
+    latch: u8,
+    counter: u8,
+    prescaler: i16,
+    enabled: bool,
+    enable_after_ack: bool,
+    cycle_mode: bool,
+    pending: bool,
+}
+
+impl VrcIrq {
+    // The prescaler approximates scanlines as 113 2/3 CPU cycles
+    const PRESCALER_PERIOD: i16 = 341;
+
+    pub fn new() -> Self {
+        VrcIrq {
+            latch: 0,
+            counter: 0,
+            prescaler: VrcIrq::PRESCALER_PERIOD,
+            enabled: false,
+            enable_after_ack: false,
+            cycle_mode: false,
+            pending: false,
+        }
+    }
+
+    pub fn write_latch(&mut self, data: u8) {
+        self.latch = data;
+    }
+
+    // VRC4 splits the latch across two registers
+    pub fn write_latch_low(&mut self, data: u8) {
+        self.latch = (self.latch & 0xF0) | (data & 0x0F);
+    }
+
+    pub fn write_latch_high(&mut self, data: u8) {
+        self.latch = (self.latch & 0x0F) | (data << 4);
+    }
+
+    pub fn write_control(&mut self, data: u8) {
+        self.enable_after_ack = data & 0b001 != 0;
+        self.enabled = data & 0b010 != 0;
+        self.cycle_mode = data & 0b100 != 0;
+        self.pending = false;
+        if self.enabled {
+            self.counter = self.latch;
+            self.prescaler = VrcIrq::PRESCALER_PERIOD;
+        }
+    }
+
+    pub fn acknowledge(&mut self) {
+        self.pending = false;
+        self.enabled = self.enable_after_ack;
+    }
+
+    pub fn clock(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        if self.cycle_mode {
+            self.clock_counter();
+        } else {
+            self.prescaler -= 3;
+            if self.prescaler <= 0 {
+                self.prescaler += VrcIrq::PRESCALER_PERIOD;
+                self.clock_counter();
+            }
+        }
+    }
+
+    fn clock_counter(&mut self) {
+        if self.counter == 0xFF {
+            self.counter = self.latch;
+            self.pending = true;
+        } else {
+            self.counter += 1;
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+}
+
+impl Default for VrcIrq {
+    fn default() -> Self {
+        VrcIrq::new()
+    }
+}
+
+impl SaveState for VrcIrq {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.latch);
+        state.write_u8(self.counter);
+        state.write_i32(self.prescaler as i32);
+        state.write_bool(self.enabled);
+        state.write_bool(self.enable_after_ack);
+        state.write_bool(self.cycle_mode);
+        state.write_bool(self.pending);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.latch = state.read_u8()?;
+        self.counter = state.read_u8()?;
+        self.prescaler = state.read_i32()? as i16;
+        self.enabled = state.read_bool()?;
+        self.enable_after_ack = state.read_bool()?;
+        self.cycle_mode = state.read_bool()?;
+        self.pending = state.read_bool()?;
+        Ok(())
+    }
+}
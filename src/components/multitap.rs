@@ -0,0 +1,108 @@
+use crate::components::input_device::InputDevice;
+use crate::components::joypad::Joypad;
+use crate::components::ppu::NesPPU;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Four_player_adapters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MultitapMode {
+    // NES Four Score: both ports report 24 bits, two joypads followed by a signature
+    FourScore,
+    // Famicom expansion port adapters: players 3 and 4 come in on the second data line
+    Famicom,
+}
+
+pub struct Multitap {
+    mode: MultitapMode,
+    joypads: [Joypad; 4],
+    strobe: bool,
+    read_index: [u8; 2],
+}
+
+impl Multitap {
+    // the bit after the two reports that tells which port the adapter reads from
+    const SIGNATURES: [u32; 2] = [1 << 3, 1 << 2];
+    const REPORT_BITS: u8 = 24;
+
+    pub fn new(mode: MultitapMode) -> Self {
+        Multitap {
+            mode,
+            joypads: [Joypad::new(), Joypad::new(), Joypad::new(), Joypad::new()],
+            strobe: false,
+            read_index: [0; 2],
+        }
+    }
+
+    pub fn mode(&self) -> MultitapMode {
+        self.mode
+    }
+
+    // Players are counted from 0, player 1 and 3 are read through $4016, 2 and 4 through $4017
+    pub fn joypad(&mut self, player: usize) -> &mut Joypad {
+        &mut self.joypads[player]
+    }
+
+    pub fn write(&mut self, data: u8) {
+        self.strobe = data & 1 == 1;
+        if self.strobe {
+            self.read_index = [0; 2];
+        }
+        for joypad in self.joypads.iter_mut() {
+            joypad.write(data);
+        }
+    }
+
+    // `port` is 0 for $4016 and 1 for $4017
+    pub fn read(&mut self, port: usize) -> u8 {
+        match self.mode {
+            MultitapMode::FourScore => self.read_four_score(port),
+            MultitapMode::Famicom => self.joypads[port].read() | (self.joypads[port + 2].read() << 1),
+        }
+    }
+
+    fn read_four_score(&mut self, port: usize) -> u8 {
+        let index = self.read_index[port];
+        if index >= Multitap::REPORT_BITS {
+            return 1;
+        }
+        if !self.strobe {
+            self.read_index[port] += 1;
+        }
+
+        match index {
+            0..=7 => self.joypads[port].read(),
+            8..=15 => self.joypads[port + 2].read(),
+            _ => ((Multitap::SIGNATURES[port] >> (index - 16)) & 1) as u8,
+        }
+    }
+}
+
+impl SaveState for Multitap {
+    fn save_state(&self, state: &mut StateWriter) {
+        for joypad in self.joypads.iter() {
+            joypad.save_state(state);
+        }
+        state.write_bool(self.strobe);
+        state.write_u8(self.read_index[0]);
+        state.write_u8(self.read_index[1]);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        for joypad in self.joypads.iter_mut() {
+            joypad.load_state(state)?;
+        }
+        self.strobe = state.read_bool()?;
+        self.read_index = [state.read_u8()?, state.read_u8()?];
+        Ok(())
+    }
+}
+
+impl InputDevice for Multitap {
+    fn write_port(&mut self, data: u8) {
+        self.write(data);
+    }
+
+    fn read_port(&mut self, port: usize, _ppu: &NesPPU) -> u8 {
+        self.read(port)
+    }
+}
@@ -0,0 +1,114 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::components::apu::CPU_CLOCK_RATE;
+use crate::components::bus::Bus;
+use crate::components::cartridge::nsf::Nsf;
+use crate::components::cpu::CPU;
+use crate::components::mapper::nsf::NsfMapper;
+use crate::components::mem::Mem;
+
+// How long INIT and PLAY may run before the tune is taken as hung. INIT may unpack the tune
+// before it starts, so it gets a second instead of a single frame.
+const INIT_CYCLES: usize = CPU_CLOCK_RATE as usize;
+const FRAME_CYCLES: usize = 29781;
+
+//https://www.nesdev.org/wiki/NSF#Initializing_a_tune
+pub struct NsfPlayer {
+    pub cpu: CPU,
+    nsf: Nsf,
+    mapper: Rc<RefCell<NsfMapper>>,
+    current_track: u8,
+}
+
+impl NsfPlayer {
+    pub fn new(nsf: Nsf) -> Result<NsfPlayer, String> {
+        let mapper = Rc::new(RefCell::new(NsfMapper::new(&nsf)?));
+        let cpu = CPU::new(Bus::with_mapper(mapper.clone()));
+
+        let mut player = NsfPlayer {
+            cpu,
+            current_track: nsf.starting_song,
+            nsf,
+            mapper,
+        };
+        player.select_track(player.current_track)?;
+        Ok(player)
+    }
+
+    pub fn title(&self) -> &str {
+        &self.nsf.title
+    }
+
+    pub fn artist(&self) -> &str {
+        &self.nsf.artist
+    }
+
+    pub fn copyright(&self) -> &str {
+        &self.nsf.copyright
+    }
+
+    pub fn track_count(&self) -> u8 {
+        self.nsf.total_songs
+    }
+
+    pub fn track_label(&self, track: u8) -> Option<&str> {
+        self.nsf.track_labels.get(track as usize).map(|label| label.as_str())
+    }
+
+    pub fn current_track(&self) -> u8 {
+        self.current_track
+    }
+
+    pub fn is_pal(&self) -> bool {
+        self.nsf.pal
+    }
+
+    // How often the host has to call play_frame
+    pub fn play_period(&self) -> Duration {
+        let speed = if self.nsf.pal { self.nsf.pal_speed } else { self.nsf.ntsc_speed };
+        Duration::from_micros(speed as u64)
+    }
+
+    pub fn select_track(&mut self, track: u8) -> Result<(), String> {
+        if track >= self.nsf.total_songs {
+            return Err(format!("Track {} doesn't exist, the file has {} tracks", track, self.nsf.total_songs));
+        }
+
+        for addr in 0x0000..0x0800 {
+            self.cpu.mem_write(addr, 0);
+        }
+        for addr in 0x6000..0x8000 {
+            self.cpu.mem_write(addr, 0);
+        }
+
+        for addr in 0x4000..=0x4013 {
+            self.cpu.mem_write(addr, 0);
+        }
+        self.cpu.mem_write(0x4015, 0x0F);
+        self.cpu.mem_write(0x4017, 0x40);
+
+        self.mapper.borrow_mut().reset_banks();
+
+        self.current_track = track;
+        self.cpu.register_a = track;
+        self.cpu.register_x = if self.nsf.pal { 1 } else { 0 };
+        self.cpu.register_y = 0;
+        self.cpu.status = CPU::STATUS_RESET;
+        self.cpu.stack_pointer = CPU::STACK_RESET;
+        self.cpu.call_subroutine(self.nsf.init_address, INIT_CYCLES)
+    }
+
+    // Calls PLAY and lets the APU run on until the next call is due, so every call makes a
+    // play period worth of samples
+    pub fn play_frame(&mut self) -> Result<(), String> {
+        let play_cycles = (self.play_period().as_secs_f64() * CPU_CLOCK_RATE) as usize;
+        let next_call = self.cpu.bus.cycles() + play_cycles;
+        self.cpu.call_subroutine(self.nsf.play_address, play_cycles.max(FRAME_CYCLES))?;
+        while self.cpu.bus.cycles() < next_call {
+            self.cpu.bus.tick(1);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use crate::components::input_device::InputDevice;
+use crate::components::ppu::NesPPU;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Power_Pad
+pub struct PowerPad {
+    buttons: u16,
+    strobe: bool,
+    shift_d3: u8,
+    shift_d4: u8,
+}
+
+impl PowerPad {
+    pub const BUTTONS: usize = 12;
+
+    // the order the mat shifts its buttons out on each data line
+    const D4_ORDER: [usize; 8] = [2, 1, 5, 9, 6, 10, 11, 7];
+    const D3_ORDER: [usize; 4] = [4, 3, 12, 8];
+
+    pub fn new() -> Self {
+        PowerPad {
+            buttons: 0,
+            strobe: false,
+            shift_d3: 0,
+            shift_d4: 0,
+        }
+    }
+
+    // Buttons are numbered 1 to 12 the way they're printed on the mat
+    pub fn set_button_pressed(&mut self, button: usize, pressed: bool) {
+        if button == 0 || button > PowerPad::BUTTONS {
+            panic!("Power Pad button {} doesn't exist", button);
+        }
+        if pressed {
+            self.buttons |= 1 << (button - 1);
+        } else {
+            self.buttons &= !(1 << (button - 1));
+        }
+    }
+
+    // Bit 0 is button 1 up to bit 11 being button 12
+    pub fn set_buttons(&mut self, buttons: u16) {
+        self.buttons = buttons & 0x0FFF;
+    }
+
+    pub fn buttons(&self) -> u16 {
+        self.buttons
+    }
+
+    fn is_pressed(&self, button: usize) -> bool {
+        self.buttons & (1 << (button - 1)) != 0
+    }
+
+    fn latch(&mut self) {
+        self.shift_d4 = 0;
+        for (bit, button) in PowerPad::D4_ORDER.iter().enumerate() {
+            self.shift_d4 |= (self.is_pressed(*button) as u8) << bit;
+        }
+        // D3 only has 4 buttons, it reports 1 once they are out
+        self.shift_d3 = 0xF0;
+        for (bit, button) in PowerPad::D3_ORDER.iter().enumerate() {
+            self.shift_d3 |= (self.is_pressed(*button) as u8) << bit;
+        }
+    }
+}
+
+impl Default for PowerPad {
+    fn default() -> Self {
+        PowerPad::new()
+    }
+}
+
+impl SaveState for PowerPad {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bool(self.strobe);
+        state.write_u8(self.shift_d3);
+        state.write_u8(self.shift_d4);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.strobe = state.read_bool()?;
+        self.shift_d3 = state.read_u8()?;
+        self.shift_d4 = state.read_u8()?;
+        Ok(())
+    }
+}
+
+impl InputDevice for PowerPad {
+    fn write_port(&mut self, data: u8) {
+        self.strobe = data & 1 == 1;
+        if self.strobe {
+            self.latch();
+        }
+    }
+
+    fn read_port(&mut self, _port: usize, _ppu: &NesPPU) -> u8 {
+        if self.strobe {
+            self.latch();
+        }
+
+        let d3 = self.shift_d3 & 1;
+        let d4 = self.shift_d4 & 1;
+        if !self.strobe {
+            self.shift_d3 = (self.shift_d3 >> 1) | 0x80;
+            self.shift_d4 = (self.shift_d4 >> 1) | 0x80;
+        }
+        (d3 << 3) | (d4 << 4)
+    }
+}
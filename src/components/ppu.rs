@@ -2,29 +2,50 @@
 use registers::ControlRegister::ControlRegister;
 use registers::MaskRegister::MaskRegister;
 use registers::ScrollRegister::ScrollRegister;
+use registers::StatusRegister::StatusRegister;
 
 use crate::components::cartridge::Mirroring;
+use crate::components::frame::Frame;
+use crate::components::mapper::SharedMapper;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
 
 pub mod registers;
+pub mod palette;
+mod render;
 
 pub struct NesPPU {
-    pub chr_rom: Vec<u8>,
+    pub cartridge: SharedMapper,
     pub palette_table: [u8; 32],
     pub vram: [u8; 2048],
     pub oam_data: [u8; 256],
-    pub mirroring: Mirroring,
     pub ctrl: ControlRegister,
     mask: MaskRegister,
     scrl: ScrollRegister,
     addr: AddrRegister,
+    status: StatusRegister,
+    oam_addr: u8,
     internal_data_buf: u8,
+    // what the last register access left on the PPU's data bus, reads of write-only registers see it
+    io_latch: u8,
+
+    scanline: u16,
+    cycles: usize,
+    frame_count: usize,
+    nmi_interrupt: bool,
+    // writes to PPUCTRL, PPUMASK, PPUSCROLL and PPUADDR are ignored until the first pre-render line
+    warming_up: bool,
+    frame: Frame,
 }
 
 impl NesPPU {
-    pub fn new(chr_rom: Vec<u8>, mirroring: Mirroring) -> Self {
+    const CYCLES_PER_SCANLINE: usize = 341;
+    const VISIBLE_SCANLINES: u16 = 240;
+    const VBLANK_SCANLINE: u16 = 241;
+    const PRE_RENDER_SCANLINE: u16 = 261;
+
+    pub fn new(cartridge: SharedMapper) -> Self {
         NesPPU {
-            chr_rom: chr_rom.to_vec(),
-            mirroring: mirroring,
+            cartridge,
             vram: [0; 2048],
             oam_data: [0; 64 * 4],
             palette_table: [0; 32],
@@ -32,23 +53,171 @@ impl NesPPU {
             ctrl: ControlRegister::new(),
             scrl: ScrollRegister::new(),
             addr: AddrRegister::new(),
+            status: StatusRegister::new(),
+            oam_addr: 0,
             internal_data_buf: 0,
+            io_latch: 0,
+            scanline: 0,
+            cycles: 0,
+            frame_count: 0,
+            nmi_interrupt: false,
+            warming_up: false,
+            frame: Frame::new(),
+        }
+    }
+
+    //https://www.nesdev.org/wiki/PPU_power_up_state
+    // The registers come up cleared and the PPU needs about 29658 CPU cycles before it takes
+    // writes to them. Memory keeps whatever it held and the frame count goes on.
+    pub fn power_on(&mut self) {
+        self.ctrl = ControlRegister::new();
+        self.mask = MaskRegister::new();
+        self.scrl = ScrollRegister::new();
+        self.addr = AddrRegister::new();
+        self.status = StatusRegister::new();
+        self.oam_addr = 0;
+        self.internal_data_buf = 0;
+        self.io_latch = 0;
+        self.scanline = 0;
+        self.cycles = 0;
+        self.nmi_interrupt = false;
+        self.warming_up = true;
+    }
+
+    //https://www.nesdev.org/wiki/PPU_rendering
+    // Scanlines are drawn once the PPU is done with them, returns true when a new frame starts
+    pub fn tick(&mut self, cycles: usize) -> bool {
+        self.cycles += cycles;
+        let mut new_frame = false;
+
+        while self.cycles >= NesPPU::CYCLES_PER_SCANLINE {
+            self.cycles -= NesPPU::CYCLES_PER_SCANLINE;
+
+            let rendering = self.mask.show_background() || self.mask.show_sprites();
+            self.cartridge.borrow_mut().ppu_scanline(self.scanline, rendering);
+
+            if self.scanline < NesPPU::VISIBLE_SCANLINES {
+                self.render_scanline(self.scanline as usize);
+            }
+            self.scanline += 1;
+
+            if self.scanline == NesPPU::VBLANK_SCANLINE {
+                self.status.set_vblank_status(true);
+                if self.ctrl.generate_vblank_nmi() {
+                    self.nmi_interrupt = true;
+                }
+            }
+
+            if self.scanline == NesPPU::PRE_RENDER_SCANLINE {
+                self.status.set_vblank_status(false);
+                self.status.set_sprite_zero_hit(false);
+                self.status.set_sprite_overflow(false);
+                self.nmi_interrupt = false;
+                self.warming_up = false;
+            }
+
+            if self.scanline > NesPPU::PRE_RENDER_SCANLINE {
+                self.scanline = 0;
+                self.frame_count += 1;
+                new_frame = true;
+            }
+        }
+
+        new_frame
+    }
+
+    pub fn poll_nmi_interrupt(&mut self) -> bool {
+        let nmi = self.nmi_interrupt;
+        self.nmi_interrupt = false;
+        nmi
+    }
+
+    pub fn scanline(&self) -> u16 {
+        self.scanline
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    // Rows above the current scanline already hold this frame, the rest still show the previous one
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    //https://www.nesdev.org/wiki/Open_bus_behavior#PPU_open_bus
+    pub fn open_bus(&self) -> u8 {
+        self.io_latch
+    }
+
+    pub fn read_status(&mut self) -> u8 {
+        // only the top three bits are driven, the rest come from the latch
+        let data = self.status.snapshot() & 0b1110_0000 | self.io_latch & 0b0001_1111;
+        self.status.set_vblank_status(false);
+        self.addr.reset_latch();
+        self.scrl.reset_latch();
+        self.io_latch = data;
+        data
+    }
+
+    pub fn write_to_oam_addr(&mut self, value: u8) {
+        self.io_latch = value;
+        self.oam_addr = value;
+    }
+
+    pub fn write_to_oam_data(&mut self, value: u8) {
+        self.io_latch = value;
+        self.oam_data[self.oam_addr as usize] = value;
+        self.oam_addr = self.oam_addr.wrapping_add(1);
+    }
+
+    pub fn read_oam_data(&mut self) -> u8 {
+        self.io_latch = self.oam_data[self.oam_addr as usize];
+        self.io_latch
+    }
+
+    pub fn write_oam_dma(&mut self, data: &[u8; 256]) {
+        for x in data.iter() {
+            self.write_to_oam_data(*x);
         }
     }
 
     pub fn write_to_ppu_addr(&mut self, value: u8) {
+        self.io_latch = value;
+        if self.warming_up {
+            return;
+        }
         self.addr.update(value);
     }
 
     pub fn write_to_mask_register(&mut self, value: u8) {
+        self.io_latch = value;
+        if self.warming_up {
+            return;
+        }
         self.mask.update(value);
+        self.cartridge.borrow_mut().ppu_register_written(0x2001, value);
     }
 
     pub fn write_to_ctrl(&mut self, value: u8) {
+        self.io_latch = value;
+        if self.warming_up {
+            return;
+        }
+        let before_nmi_status = self.ctrl.generate_vblank_nmi();
         self.ctrl.update(value);
+        self.cartridge.borrow_mut().ppu_register_written(0x2000, value);
+        // enabling NMI while already in vblank fires it right away
+        if !before_nmi_status && self.ctrl.generate_vblank_nmi() && self.status.is_in_vblank() {
+            self.nmi_interrupt = true;
+        }
     }
 
     pub fn write_to_scroll_register(&mut self, value: u8) {
+        self.io_latch = value;
+        if self.warming_up {
+            return;
+        }
         self.scrl.write(value);
     }
 
@@ -60,60 +229,118 @@ impl NesPPU {
         let addr = self.addr.get();
         self.increment_vram_addr();
 
-        match addr {
+        let data = match addr {
             0..=0x1FFF => {
                 let result = self.internal_data_buf;
-                self.internal_data_buf = self.chr_rom[addr as usize];
+                self.internal_data_buf = self.cartridge.borrow_mut().read_chr(addr);
                 result
             }
             0x2000..=0x2FFF => {
                 let result = self.internal_data_buf;
-                self.internal_data_buf = self.vram[self.mirror_vram_addr(addr) as usize];
+                self.internal_data_buf = self.cartridge.borrow_mut().read_nametable(addr, &self.vram);
                 result
             }
             0x3000..=0x3eff => panic!("addr space 0x3000..0x3eff is not expected to be used, requested = {} ", addr),
             0x3f00..=0x3fff => {
-                self.palette_table[(addr - 0x3f00) as usize]
+                self.palette_table[NesPPU::palette_index(addr)]
             }
             _ => panic!("unexpected access to mirrored space {}", addr),
-        }
+        };
+        self.io_latch = data;
+        data
     }
 
     pub fn write_to_data(&mut self, data: u8) {
+        self.io_latch = data;
         let addr = self.addr.get();
 
         match addr {
-            0..=0x1FFF => panic!("Can't write to CHR ROM"),
+            0..=0x1FFF => self.cartridge.borrow_mut().write_chr(addr, data),
             0x2000..=0x2FFF => {
-                self.vram[self.mirror_vram_addr(addr) as usize] = data;
+                self.cartridge.borrow_mut().write_nametable(addr, data, &mut self.vram);
             }
             0x3000..=0x3eff => panic!("addr space 0x3000..0x3eff is not expected to be used, requested = {} ", addr),
 
-            //https://www.nesdev.org/wiki/PPU_palettes
-            //Addresses $3F10/$3F14/$3F18/$3F1C are mirrors of $3F00/$3F04/$3F08/$3F0C
-            0x3f10 | 0x3f14 | 0x3f18 | 0x3f1c => {
-                let add_mirror = addr - 0x10;
-                self.palette_table[(add_mirror - 0x3f00) as usize] = data;
-            }
-
             0x3f00..=0x3fff => {
-                self.palette_table[(addr - 0x3f00) as usize] = data;
+                self.palette_table[NesPPU::palette_index(addr)] = data;
             }
             _ => panic!("unexpected access to mirrored space {}", addr),
         }
         self.increment_vram_addr();
     }
 
+    //https://www.nesdev.org/wiki/PPU_palettes
+    //Addresses $3F10/$3F14/$3F18/$3F1C are mirrors of $3F00/$3F04/$3F08/$3F0C
+    fn palette_index(addr: u16) -> usize {
+        let index = (addr & 0x1f) as usize;
+        match index {
+            0x10 | 0x14 | 0x18 | 0x1c => index - 0x10,
+            _ => index,
+        }
+    }
+
     pub fn mirror_vram_addr(&self, addr: u16) -> u16 {
-        let mirrored_vram = addr & 0b10111111111111; // mirror down 0x3000-0x3eff to 0x2000 - 0x2eff
-        let vram_index = mirrored_vram - 0x2000; // to vram vector
-        let name_table = vram_index / 0x400;       // to table name index
-        match (&self.mirroring, name_table) {
-            (Mirroring::VERTICAL, 2) | (Mirroring::VERTICAL, 3) => vram_index - 0x800,
-            (Mirroring::HORIZONTAL, 2) => vram_index - 0x400,
-            (Mirroring::HORIZONTAL, 1) => vram_index - 0x400,
-            (Mirroring::HORIZONTAL, 3) => vram_index - 0x800,
-            _ => vram_index,
+        mirror_nametable_addr(self.cartridge.borrow().mirroring(), addr)
+    }
+}
+
+pub fn mirror_nametable_addr(mirroring: Mirroring, addr: u16) -> u16 {
+    let mirrored_vram = addr & 0b10111111111111; // mirror down 0x3000-0x3eff to 0x2000 - 0x2eff
+    let vram_index = mirrored_vram - 0x2000; // to vram vector
+    let name_table = vram_index / 0x400;       // to table name index
+    match (mirroring, name_table) {
+        (Mirroring::VERTICAL, 2) | (Mirroring::VERTICAL, 3) => vram_index - 0x800,
+        (Mirroring::HORIZONTAL, 2) => vram_index - 0x400,
+        (Mirroring::HORIZONTAL, 1) => vram_index - 0x400,
+        (Mirroring::HORIZONTAL, 3) => vram_index - 0x800,
+        (Mirroring::SINGLE_SCREEN_LOWER, _) => vram_index % 0x400,
+        (Mirroring::SINGLE_SCREEN_UPPER, _) => vram_index % 0x400 + 0x400,
+        _ => vram_index,
+    }
+}
+
+// The cartridge is saved by the bus, it's shared with the PPU
+impl SaveState for NesPPU {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_bytes(&self.palette_table);
+        state.write_bytes(&self.vram);
+        state.write_bytes(&self.oam_data);
+        self.ctrl.save_state(state);
+        self.mask.save_state(state);
+        self.scrl.save_state(state);
+        self.addr.save_state(state);
+        self.status.save_state(state);
+        state.write_u8(self.oam_addr);
+        state.write_u8(self.internal_data_buf);
+        state.write_u16(self.scanline);
+        state.write_usize(self.cycles);
+        state.write_usize(self.frame_count);
+        state.write_bool(self.nmi_interrupt);
+        state.write_bool(self.warming_up);
+        if state.includes_picture() {
+            state.write_bytes(&self.frame.data);
         }
     }
-}
\ No newline at end of file
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        state.read_bytes_into(&mut self.palette_table)?;
+        state.read_bytes_into(&mut self.vram)?;
+        state.read_bytes_into(&mut self.oam_data)?;
+        self.ctrl.load_state(state)?;
+        self.mask.load_state(state)?;
+        self.scrl.load_state(state)?;
+        self.addr.load_state(state)?;
+        self.status.load_state(state)?;
+        self.oam_addr = state.read_u8()?;
+        self.internal_data_buf = state.read_u8()?;
+        self.scanline = state.read_u16()?;
+        self.cycles = state.read_usize()?;
+        self.frame_count = state.read_usize()?;
+        self.nmi_interrupt = state.read_bool()?;
+        self.warming_up = state.read_bool()?;
+        if state.includes_picture() {
+            state.read_bytes_into(&mut self.frame.data)?;
+        }
+        Ok(())
+    }
+}
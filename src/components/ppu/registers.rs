@@ -5,4 +5,6 @@ pub mod ControlRegister;
 #[allow(non_snake_case)]
 pub mod ScrollRegister;
 #[allow(non_snake_case)]
-pub mod MaskRegister;
\ No newline at end of file
+pub mod MaskRegister;
+#[allow(non_snake_case)]
+pub mod StatusRegister;
\ No newline at end of file
@@ -1,4 +1,6 @@
-﻿#[allow(non_camel_case_types)]
+﻿use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+#[allow(non_camel_case_types)]
 pub struct AddrRegister {
     value: (u8, u8),
     hi_ptr: bool,
@@ -51,4 +53,18 @@ impl AddrRegister {
     pub fn get(&self) -> u16 {
         ((self.value.0 as u16) << 8) | (self.value.1 as u16)
     }
-}
\ No newline at end of file
+}
+
+impl SaveState for AddrRegister {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.value.0);
+        state.write_u8(self.value.1);
+        state.write_bool(self.hi_ptr);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.value = (state.read_u8()?, state.read_u8()?);
+        self.hi_ptr = state.read_bool()?;
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-﻿#[allow(non_camel_case_types)]
+﻿use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+#[allow(non_camel_case_types)]
 pub struct ControlRegister {
     status: u8,
 }
@@ -65,7 +67,7 @@ impl ControlRegister {
     }
 
     pub fn sprite_size(&self) -> u8 {
-        if self.status & ControlRegister::SPRITE_SIZE == 1 {
+        if self.status & ControlRegister::SPRITE_SIZE != 0 {
             16
         }
         else{
@@ -82,3 +84,13 @@ impl ControlRegister {
     }
 }
 
+impl SaveState for ControlRegister {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.status);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.status = state.read_u8()?;
+        Ok(())
+    }
+}
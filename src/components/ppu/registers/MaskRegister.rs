@@ -1,4 +1,6 @@
-﻿#[allow(non_camel_case_types)]
+﻿use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+#[allow(non_camel_case_types)]
 pub struct MaskRegister {
     status: u8,
 }
@@ -66,4 +68,15 @@ impl MaskRegister {
 
         result
     }
-}
\ No newline at end of file
+}
+
+impl SaveState for MaskRegister {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.status);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.status = state.read_u8()?;
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-﻿#[allow(non_camel_case_types)]
+﻿use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+#[allow(non_camel_case_types)]
 pub struct ScrollRegister {
     pub scroll_x: u8,
     pub scroll_y: u8,
@@ -27,4 +29,19 @@ impl ScrollRegister {
     pub fn reset_latch(&mut self) {
         self.latch = false;
     }
-}
\ No newline at end of file
+}
+
+impl SaveState for ScrollRegister {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.scroll_x);
+        state.write_u8(self.scroll_y);
+        state.write_bool(self.latch);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.scroll_x = state.read_u8()?;
+        self.scroll_y = state.read_u8()?;
+        self.latch = state.read_bool()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+﻿use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+#[allow(non_camel_case_types)]
+pub struct StatusRegister {
+    status: u8,
+}
+
+impl StatusRegister {
+    const SPRITE_OVERFLOW: u8 = 0b00100000;
+    const SPRITE_ZERO_HIT: u8 = 0b01000000;
+    const VBLANK_STARTED: u8 = 0b10000000;
+
+    pub fn new() -> Self {
+        StatusRegister {
+            status: 0
+        }
+    }
+
+    pub fn set_vblank_status(&mut self, status: bool) {
+        self.set(StatusRegister::VBLANK_STARTED, status);
+    }
+
+    pub fn set_sprite_zero_hit(&mut self, status: bool) {
+        self.set(StatusRegister::SPRITE_ZERO_HIT, status);
+    }
+
+    pub fn set_sprite_overflow(&mut self, status: bool) {
+        self.set(StatusRegister::SPRITE_OVERFLOW, status);
+    }
+
+    pub fn is_in_vblank(&self) -> bool {
+        self.status & StatusRegister::VBLANK_STARTED != 0
+    }
+
+    pub fn is_sprite_zero_hit(&self) -> bool {
+        self.status & StatusRegister::SPRITE_ZERO_HIT != 0
+    }
+
+    pub fn is_sprite_overflow(&self) -> bool {
+        self.status & StatusRegister::SPRITE_OVERFLOW != 0
+    }
+
+    pub fn snapshot(&self) -> u8 {
+        self.status
+    }
+
+    fn set(&mut self, flag: u8, status: bool) {
+        if status {
+            self.status |= flag;
+        } else {
+            self.status &= !flag;
+        }
+    }
+}
+
+impl Default for StatusRegister {
+    fn default() -> Self {
+        StatusRegister::new()
+    }
+}
+
+impl SaveState for StatusRegister {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.status);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.status = state.read_u8()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use crate::components::frame::Frame;
+use crate::components::mapper::PpuFetchPhase;
+use crate::components::ppu::palette::SYSTEM_PALETTE;
+use crate::components::ppu::NesPPU;
+
+const MAX_SPRITES_PER_SCANLINE: usize = 8;
+
+struct SpriteLine {
+    oam_index: usize,
+    x: u8,
+    attributes: u8,
+    low: u8,
+    high: u8,
+}
+
+impl SpriteLine {
+    fn pixel(&self, x: usize) -> u8 {
+        let column = x.wrapping_sub(self.x as usize);
+        if column >= 8 {
+            return 0;
+        }
+        let bit = if self.attributes & 0b0100_0000 != 0 { column } else { 7 - column };
+        ((self.low >> bit) & 1) | (((self.high >> bit) & 1) << 1)
+    }
+}
+
+//https://www.nesdev.org/wiki/PPU_rendering
+// Pattern data is fetched tile by tile through the mapper in the order the PPU does it,
+// background for the line first and sprites after, so mappers watching CHR reads see them in order.
+impl NesPPU {
+    pub(super) fn render_scanline(&mut self, y: usize) {
+        let show_background = self.mask.show_background();
+        let show_sprites = self.mask.show_sprites();
+        if !show_background && !show_sprites {
+            let backdrop = self.color(self.palette_table[0]);
+            for x in 0..Frame::WIDTH {
+                self.frame.set_pixel(x, y, backdrop);
+            }
+            return;
+        }
+
+        // (pixel value, palette) for every dot of the line
+        let mut background = [(0u8, 0u8); Frame::WIDTH];
+        if show_background {
+            self.cartridge.borrow_mut().ppu_fetch_phase(PpuFetchPhase::Background);
+            self.fetch_background(y, &mut background);
+        }
+        self.cartridge.borrow_mut().ppu_fetch_phase(PpuFetchPhase::Sprites);
+        let sprites = self.fetch_sprites(y);
+        self.cartridge.borrow_mut().ppu_fetch_phase(PpuFetchPhase::Idle);
+
+        for (x, (bg_pixel, bg_palette)) in background.iter().enumerate() {
+            let bg_visible = show_background && *bg_pixel != 0 && (x >= 8 || self.mask.show_background_leftmost_8_pixels());
+            let sprite_visible = show_sprites && (x >= 8 || self.mask.show_sprites_leftmost_8_pixels());
+
+            let sprite = if sprite_visible {
+                sprites
+                    .iter()
+                    .map(|sprite| (sprite, sprite.pixel(x)))
+                    .find(|(_, pixel)| *pixel != 0)
+            } else {
+                None
+            };
+
+            let mut palette_index = if bg_visible { (*bg_palette as usize) * 4 + *bg_pixel as usize } else { 0 };
+            if let Some((sprite, pixel)) = sprite {
+                if sprite.oam_index == 0 && bg_visible && x != 255 {
+                    self.status.set_sprite_zero_hit(true);
+                }
+                let behind_background = sprite.attributes & 0b0010_0000 != 0;
+                if !bg_visible || !behind_background {
+                    palette_index = 0x10 + ((sprite.attributes & 0b11) as usize) * 4 + pixel as usize;
+                }
+            }
+
+            let color = self.color(self.palette_table[NesPPU::palette_index(0x3f00 + palette_index as u16)]);
+            self.frame.set_pixel(x, y, color);
+        }
+    }
+
+    fn fetch_background(&mut self, y: usize, background: &mut [(u8, u8); Frame::WIDTH]) {
+        let base = self.ctrl.base_name_table_address() - 0x2000;
+        let base_x = (base as usize / 0x400 % 2) * Frame::WIDTH;
+        let base_y = (base as usize / 0x800) * Frame::HEIGHT;
+        let scroll_x = self.scrl.scroll_x as usize;
+        let py = (base_y + self.scrl.scroll_y as usize + y) % (Frame::HEIGHT * 2);
+        let pattern_table = self.ctrl.background_pattern_table_address();
+
+        // one more tile than fits the screen covers the fine horizontal scroll
+        for tile in 0..=Frame::WIDTH / 8 {
+            let px = (base_x + scroll_x + tile * 8) % (Frame::WIDTH * 2);
+            let name_table = 0x2000 + ((px / Frame::WIDTH) + (py / Frame::HEIGHT) * 2) as u16 * 0x400;
+            let column = (px % Frame::WIDTH) / 8;
+            let row = (py % Frame::HEIGHT) / 8;
+
+            let tile_addr = name_table + (row * 32 + column) as u16;
+            let tile_index = self.cartridge.borrow_mut().read_nametable(tile_addr, &self.vram);
+            let attribute_addr = name_table + 0x3C0 + ((row / 4) * 8 + column / 4) as u16;
+            let attribute = self.cartridge.borrow_mut().read_nametable(attribute_addr, &self.vram);
+            let shift = ((row % 4) / 2) * 4 + ((column % 4) / 2) * 2;
+            let palette = (attribute >> shift) & 0b11;
+
+            let pattern_addr = pattern_table + tile_index as u16 * 16 + (py % 8) as u16;
+            let low = self.cartridge.borrow_mut().read_chr(pattern_addr);
+            let high = self.cartridge.borrow_mut().read_chr(pattern_addr + 8);
+
+            for bit in 0..8 {
+                let x = (tile * 8 + bit) as isize - (scroll_x % 8) as isize;
+                if x < 0 || x >= Frame::WIDTH as isize {
+                    continue;
+                }
+                let pixel = ((low >> (7 - bit)) & 1) | (((high >> (7 - bit)) & 1) << 1);
+                background[x as usize] = (pixel, palette);
+            }
+        }
+    }
+
+    fn fetch_sprites(&mut self, y: usize) -> Vec<SpriteLine> {
+        let height = self.ctrl.sprite_size() as usize;
+        let mut sprites = vec![];
+
+        for oam_index in 0..64 {
+            let sprite_y = self.oam_data[oam_index * 4] as usize + 1;
+            if y < sprite_y || y >= sprite_y + height {
+                continue;
+            }
+            if sprites.len() == MAX_SPRITES_PER_SCANLINE {
+                self.status.set_sprite_overflow(true);
+                break;
+            }
+
+            let tile = self.oam_data[oam_index * 4 + 1] as u16;
+            let attributes = self.oam_data[oam_index * 4 + 2];
+            let mut row = (y - sprite_y) as u16;
+            if attributes & 0b1000_0000 != 0 {
+                row = height as u16 - 1 - row;
+            }
+
+            let pattern_addr = if height == 16 {
+                let table = (tile & 1) * 0x1000;
+                table + (tile & 0xFE) * 16 + (row / 8) * 16 + row % 8
+            } else {
+                self.ctrl.sprite_pattern_address_table() + tile * 16 + row
+            };
+
+            let low = self.cartridge.borrow_mut().read_chr(pattern_addr);
+            let high = self.cartridge.borrow_mut().read_chr(pattern_addr + 8);
+            sprites.push(SpriteLine {
+                oam_index,
+                x: self.oam_data[oam_index * 4 + 3],
+                attributes,
+                low,
+                high,
+            });
+        }
+
+        sprites
+    }
+
+    fn color(&self, palette_entry: u8) -> (u8, u8, u8) {
+        let entry = if self.mask.is_grey_scale() { palette_entry & 0x30 } else { palette_entry & 0x3F };
+        SYSTEM_PALETTE[entry as usize]
+    }
+}
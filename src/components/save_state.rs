@@ -0,0 +1,240 @@
+use crate::components::cpu::CPU;
+
+// Save states are a magic and version followed by every component writing its fields in a fixed
+// order. Bump the version whenever that order changes, old states are refused instead of misread.
+pub const MAGIC: [u8; 4] = *b"NESS";
+pub const VERSION: u16 = 3;
+
+pub trait SaveState {
+    fn save_state(&self, state: &mut StateWriter);
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String>;
+}
+
+pub fn save(cpu: &CPU) -> Vec<u8> {
+    save_with(cpu, true)
+}
+
+// A state that doesn't fit this machine leaves it untouched
+pub fn load(cpu: &mut CPU, data: &[u8]) -> Result<(), String> {
+    load_with(cpu, data, true)
+}
+
+// Rewind snapshots leave the picture out, running the next frame draws all of it again
+pub fn save_without_picture(cpu: &CPU) -> Vec<u8> {
+    save_with(cpu, false)
+}
+
+pub fn load_without_picture(cpu: &mut CPU, data: &[u8]) -> Result<(), String> {
+    load_with(cpu, data, false)
+}
+
+fn save_with(cpu: &CPU, picture: bool) -> Vec<u8> {
+    let mut state = StateWriter::new();
+    state.picture = picture;
+    state.write_bytes(&MAGIC);
+    state.write_u16(VERSION);
+    cpu.save_state(&mut state);
+    state.into_bytes()
+}
+
+fn load_with(cpu: &mut CPU, data: &[u8], picture: bool) -> Result<(), String> {
+    let mut state = StateReader::new(data);
+    state.picture = picture;
+    read_header(&mut state)?;
+
+    let backup = save_with(cpu, picture);
+    let result = cpu.load_state(&mut state).and_then(|_| state.finish());
+    if result.is_err() {
+        let mut backup_state = StateReader::new(&backup);
+        backup_state.picture = picture;
+        read_header(&mut backup_state)
+            .and_then(|_| cpu.load_state(&mut backup_state))
+            .expect("restoring the machine from its own state");
+    }
+    result
+}
+
+fn read_header(state: &mut StateReader) -> Result<(), String> {
+    if state.read_vec().ok().as_deref() != Some(MAGIC.as_slice()) {
+        return Err("Not a save state".to_string());
+    }
+    let version = state.read_u16()?;
+    if version != VERSION {
+        return Err(format!("Save state version {} is not supported, expected {}", version, VERSION));
+    }
+    Ok(())
+}
+
+pub struct StateWriter {
+    data: Vec<u8>,
+    picture: bool,
+}
+
+impl StateWriter {
+    pub fn new() -> Self {
+        StateWriter {
+            data: vec![],
+            picture: true,
+        }
+    }
+
+    pub fn includes_picture(&self) -> bool {
+        self.picture
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.data.push(value as u8);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.data.extend(value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.data.extend(value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.data.extend(value.to_le_bytes());
+    }
+
+    pub fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn write_i32(&mut self, value: i32) {
+        self.data.extend(value.to_le_bytes());
+    }
+
+    pub fn write_f32(&mut self, value: f32) {
+        self.data.extend(value.to_le_bytes());
+    }
+
+    pub fn write_f64(&mut self, value: f64) {
+        self.data.extend(value.to_le_bytes());
+    }
+
+    pub fn write_option_u8(&mut self, value: Option<u8>) {
+        self.write_bool(value.is_some());
+        self.write_u8(value.unwrap_or(0));
+    }
+
+    // Length prefixed, so a state can't be loaded into RAM of a different size
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_u32(bytes.len() as u32);
+        self.data.extend(bytes);
+    }
+}
+
+impl Default for StateWriter {
+    fn default() -> Self {
+        StateWriter::new()
+    }
+}
+
+pub struct StateReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    picture: bool,
+}
+
+impl<'a> StateReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        StateReader {
+            data,
+            pos: 0,
+            picture: true,
+        }
+    }
+
+    pub fn includes_picture(&self) -> bool {
+        self.picture
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let bytes = self.take_slice(N)?;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn take_slice(&mut self, length: usize) -> Result<&'a [u8], String> {
+        if self.data.len() - self.pos < length {
+            return Err("Save state is truncated".to_string());
+        }
+        let bytes = &self.data[self.pos..self.pos + length];
+        self.pos += length;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, String> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    pub fn read_usize(&mut self) -> Result<usize, String> {
+        Ok(self.read_u64()? as usize)
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_le_bytes(self.take()?))
+    }
+
+    pub fn read_f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_le_bytes(self.take()?))
+    }
+
+    pub fn read_option_u8(&mut self) -> Result<Option<u8>, String> {
+        let present = self.read_bool()?;
+        let value = self.read_u8()?;
+        Ok(if present { Some(value) } else { None })
+    }
+
+    pub fn read_vec(&mut self) -> Result<Vec<u8>, String> {
+        let length = self.read_u32()? as usize;
+        Ok(self.take_slice(length)?.to_vec())
+    }
+
+    // Fills memory that already has its final size, like RAM or CHR RAM
+    pub fn read_bytes_into(&mut self, target: &mut [u8]) -> Result<(), String> {
+        let length = self.read_u32()? as usize;
+        if length != target.len() {
+            return Err(format!("Save state holds {} bytes where {} were expected", length, target.len()));
+        }
+        target.copy_from_slice(self.take_slice(length)?);
+        Ok(())
+    }
+
+    pub fn finish(&self) -> Result<(), String> {
+        if self.pos != self.data.len() {
+            return Err("Save state has trailing data".to_string());
+        }
+        Ok(())
+    }
+}
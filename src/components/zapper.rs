@@ -0,0 +1,98 @@
+use crate::components::frame::Frame;
+use crate::components::input_device::InputDevice;
+use crate::components::ppu::NesPPU;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+
+//https://www.nesdev.org/wiki/Zapper
+pub struct Zapper {
+    aim: Option<(usize, usize)>,
+    trigger_pressed: bool,
+}
+
+impl Zapper {
+    const LIGHT_NOT_DETECTED: u8 = 0b0000_1000;
+    const TRIGGER_PULLED: u8 = 0b0001_0000;
+
+    // the photodiode keeps reporting light for a while after the beam passed the aimed spot
+    const LIGHT_SENSE_SCANLINES: usize = 20;
+    const LIGHT_SENSE_RADIUS: usize = 2;
+    const BRIGHTNESS_THRESHOLD: u32 = 0xC0;
+
+    pub fn new() -> Self {
+        Zapper {
+            aim: None,
+            trigger_pressed: false,
+        }
+    }
+
+    pub fn aim_at(&mut self, x: usize, y: usize) {
+        self.aim = if x < Frame::WIDTH && y < Frame::HEIGHT { Some((x, y)) } else { None };
+    }
+
+    pub fn aim_off_screen(&mut self) {
+        self.aim = None;
+    }
+
+    pub fn aim(&self) -> Option<(usize, usize)> {
+        self.aim
+    }
+
+    pub fn set_trigger(&mut self, pressed: bool) {
+        self.trigger_pressed = pressed;
+    }
+
+    // Reads use the frame the PPU is drawing and the scanline the beam is on right now
+    pub fn read(&self, frame: &Frame, scanline: u16) -> u8 {
+        let mut result = 0;
+        if !self.senses_light(frame, scanline as usize) {
+            result |= Zapper::LIGHT_NOT_DETECTED;
+        }
+        if self.trigger_pressed {
+            result |= Zapper::TRIGGER_PULLED;
+        }
+        result
+    }
+
+    fn senses_light(&self, frame: &Frame, scanline: usize) -> bool {
+        let (x, y) = match self.aim {
+            Some(aim) => aim,
+            None => return false,
+        };
+
+        let top = y.saturating_sub(Zapper::LIGHT_SENSE_RADIUS);
+        let bottom = (y + Zapper::LIGHT_SENSE_RADIUS).min(Frame::HEIGHT - 1);
+        let left = x.saturating_sub(Zapper::LIGHT_SENSE_RADIUS);
+        let right = (x + Zapper::LIGHT_SENSE_RADIUS).min(Frame::WIDTH - 1);
+
+        (top..=bottom)
+            .filter(|row| *row < scanline && scanline - row <= Zapper::LIGHT_SENSE_SCANLINES)
+            .any(|row| (left..=right).any(|column| Zapper::is_bright(frame.pixel(column, row))))
+    }
+
+    fn is_bright((r, g, b): (u8, u8, u8)) -> bool {
+        (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000 >= Zapper::BRIGHTNESS_THRESHOLD
+    }
+}
+
+impl Default for Zapper {
+    fn default() -> Self {
+        Zapper::new()
+    }
+}
+
+// aim and trigger come from the player, the light sense is worked out from the picture on each read
+impl SaveState for Zapper {
+    fn save_state(&self, _state: &mut StateWriter) {}
+
+    fn load_state(&mut self, _state: &mut StateReader) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl InputDevice for Zapper {
+    fn write_port(&mut self, _data: u8) {}
+
+    fn read_port(&mut self, _port: usize, ppu: &NesPPU) -> u8 {
+        self.read(ppu.frame(), ppu.scanline())
+    }
+}
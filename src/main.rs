@@ -1,5 +1,327 @@
-use rust_nes::roms;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rust_nes::components::bus::RamInit;
+use rust_nes::components::cartridge::nsf::Nsf;
+use rust_nes::components::cartridge::fds::FDS_TAG;
+use rust_nes::components::cartridge::{unif, Rom};
+use rust_nes::components::crc32::crc32;
+use rust_nes::components::frame::Frame;
+use rust_nes::components::nsf_player::NsfPlayer;
+use rust_nes::roms::input_script::InputScript;
+use rust_nes::roms::avi::AviWriter;
+use rust_nes::roms::game::{DiskDrive, Game};
+use rust_nes::roms::headless::InputSource;
+use rust_nes::roms::movie::Movie;
+use rust_nes::roms::{headless, runner_with_trace};
+#[cfg(feature = "sdl")]
+use rust_nes::roms::{nsf_runner, runner};
+
+const USAGE: &str = "usage:
+  nes run <rom> [--scale N] [--movie in.fm2] [--record-movie out.fm2] [--ram-init zeros|ones|alternating|random]
+                [--fds-bios disksys.rom]
+  nes trace <rom> [--start-pc C000] [--cycles N] [--fds-bios disksys.rom]
+  nes info <rom> [--fds-bios disksys.rom]
+  nes headless <rom> --frames N [--input script.txt] [--screenshot out.png] [--dump-frames dir] [--scale N]
+                    [--record out.avi] [--ram-init zeros|ones|alternating|random] [--fds-bios disksys.rom]
+  nes headless <rom> --movie in.fm2 [--frames N] [...]
+  nes nsf <file> [--song N]
+
+Famicom Disk System images (.fds) need the BIOS, which is read from disksys.rom unless --fds-bios
+says otherwise. Writes to the disk are kept in an .ips file next to the image.
+RAM starts out as zeros, or alternating when a movie is played or recorded.";
+
+const DEFAULT_FDS_BIOS: &str = "disksys.rom";
 
 fn main() {
-    roms::runner_with_trace::run();
+    if let Err(message) = run(std::env::args().skip(1).collect()) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut args = args.into_iter();
+    let command = args.next().ok_or(USAGE)?;
+    let mut options = Options::parse(args)?;
+
+    match command.as_str() {
+        "run" => run_window(options),
+        "nsf" => {
+            let song = options.take_number("--song")?;
+            let path = options.finish()?;
+            let nsf = Nsf::new(&read_file(&path)?).map_err(|e| format!("Can't load {}: {}", path, e))?;
+            let mut player = NsfPlayer::new(nsf).map_err(|e| format!("Can't play {}: {}", path, e))?;
+            // songs are counted from 1, like the players show them
+            if let Some(song) = song {
+                if song == 0 || song > player.track_count() as usize {
+                    return Err(format!("Song {} doesn't exist, the file has {} songs", song, player.track_count()));
+                }
+                player.select_track(song as u8 - 1).map_err(|e| format!("Can't play {}: {}", path, e))?;
+            }
+            print_nsf_info(&player);
+            play_nsf(player)
+        }
+        "trace" => {
+            let start_pc = options
+                .take("--start-pc")?
+                .map(|pc| u16::from_str_radix(pc.trim_start_matches('$'), 16).map_err(|_| format!("Invalid address {}", pc)))
+                .transpose()?;
+            let cycles = options.take_number("--cycles")?;
+            let fds_bios = options.take("--fds-bios")?;
+            let game = load_game(&options.finish()?, fds_bios)?;
+            runner_with_trace::run(game, start_pc, cycles)
+        }
+        "info" => {
+            let fds_bios = options.take("--fds-bios")?;
+            let path = options.finish()?;
+            let raw = read_file(&path)?;
+            match parse_game(&path, &raw, fds_bios)? {
+                Game::Cartridge(rom) => print_info(&path, &raw, &rom),
+                Game::Disk(drive) => print_disk_info(&path, &raw, &drive),
+            }
+            Ok(())
+        }
+        "headless" => {
+            let frames = options.take_number("--frames")?;
+            let script = options.take("--input")?;
+            let movie = options.take("--movie")?;
+            let screenshot = options.take("--screenshot")?;
+            let dump_dir = options.take("--dump-frames")?;
+            let record = options.take("--record")?;
+            let scale = options.take_number("--scale")?.unwrap_or(1);
+            if scale == 0 {
+                return Err("Scale must be at least 1".to_string());
+            }
+            let ram_init = take_ram_init(&mut options, movie.is_some())?;
+            let fds_bios = options.take("--fds-bios")?;
+            let game = load_game(&options.finish()?, fds_bios)?;
+
+            // a movie plays to its end unless told otherwise
+            let (input, frames): (Box<dyn InputSource>, usize) = match (script, movie) {
+                (Some(_), Some(_)) => return Err("Use either --input or --movie".to_string()),
+                (None, Some(path)) => {
+                    let movie = load_movie(&path, &game)?;
+                    let length = movie.len();
+                    (Box::new(movie), frames.unwrap_or(length))
+                }
+                (script, None) => {
+                    let script = match script {
+                        Some(path) => InputScript::parse(&read_text(&path)?).map_err(|e| format!("Can't parse {}: {}", path, e))?,
+                        None => InputScript::default(),
+                    };
+                    (Box::new(script), frames.ok_or("headless needs --frames N")?)
+                }
+            };
+
+            if let Some(dir) = &dump_dir {
+                std::fs::create_dir_all(dir).map_err(|e| format!("Can't create {}: {}", dir, e))?;
+            }
+            let mut recording = match &record {
+                Some(path) => Some(AviWriter::create(Path::new(path), headless::AUDIO_SAMPLE_RATE)?),
+                None => None,
+            };
+            let frame = headless::run(game, ram_init, frames, &*input, |number, cpu| {
+                if let Some(recording) = &mut recording {
+                    let samples = cpu.bus.apu().take_samples();
+                    recording.add_frame(cpu.bus.ppu().frame(), &samples)?;
+                }
+                match &dump_dir {
+                    Some(dir) => write_png(&Path::new(dir).join(format!("frame_{:05}.png", number)), cpu.bus.ppu().frame(), scale),
+                    None => Ok(()),
+                }
+            });
+            // whatever got recorded before an error is still worth keeping
+            if let Some(recording) = recording {
+                recording.finish()?;
+            }
+            let frame = frame?;
+            if let Some(path) = screenshot {
+                write_png(Path::new(&path), &frame, scale)?;
+            }
+            Ok(())
+        }
+        _ => Err(format!("Unknown command {}\n{}", command, USAGE)),
+    }
+}
+
+#[cfg(feature = "sdl")]
+fn run_window(mut options: Options) -> Result<(), String> {
+    let scale = options.take_number("--scale")?.unwrap_or(runner::DEFAULT_SCALE as usize);
+    if scale == 0 {
+        return Err("Scale must be at least 1".to_string());
+    }
+    let playback = options.take("--movie")?;
+    let movie_path = options.take("--record-movie")?;
+    let ram_init = take_ram_init(&mut options, playback.is_some() || movie_path.is_some())?;
+    let fds_bios = options.take("--fds-bios")?;
+    let rom_path = options.finish()?;
+    let game = load_game(&rom_path, fds_bios)?;
+    let playback = playback.map(|path| load_movie(&path, &game)).transpose()?;
+    runner::run(game, Path::new(&rom_path), scale as u32, ram_init, playback, movie_path.as_deref().map(Path::new))
+}
+
+#[cfg(not(feature = "sdl"))]
+fn run_window(_options: Options) -> Result<(), String> {
+    Err("This nes was built without the sdl feature, only trace, info and headless are available".to_string())
+}
+
+#[cfg(feature = "sdl")]
+fn play_nsf(player: NsfPlayer) -> Result<(), String> {
+    nsf_runner::run(player)
+}
+
+#[cfg(not(feature = "sdl"))]
+fn play_nsf(_player: NsfPlayer) -> Result<(), String> {
+    Err("This nes was built without the sdl feature, only trace, info and headless are available".to_string())
+}
+
+// Zeros unless asked otherwise, random RAM is different on every run. Movies default to the
+// alternating pattern FCEUX powers on with, so games that read uninitialized RAM stay in sync.
+fn take_ram_init(options: &mut Options, movie: bool) -> Result<RamInit, String> {
+    match options.take("--ram-init")?.as_deref() {
+        None if movie => Ok(RamInit::Alternating),
+        None | Some("zeros") => Ok(RamInit::Zeros),
+        Some("ones") => Ok(RamInit::Ones),
+        Some("alternating") => Ok(RamInit::Alternating),
+        Some("random") => {
+            let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_nanos() as u64);
+            Ok(RamInit::Random(seed))
+        }
+        Some(other) => Err(format!("Unknown RAM pattern {}, use zeros, ones, alternating or random", other)),
+    }
+}
+
+// `--name value` pairs plus the single ROM path, in any order
+struct Options {
+    named: Vec<(String, String)>,
+    rom_path: Option<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options { named: vec![], rom_path: None };
+        while let Some(arg) = args.next() {
+            if arg.starts_with("--") {
+                let value = args.next().ok_or_else(|| format!("{} needs a value", arg))?;
+                options.named.push((arg, value));
+            } else if options.rom_path.is_none() {
+                options.rom_path = Some(arg);
+            } else {
+                return Err(format!("Unexpected argument {}\n{}", arg, USAGE));
+            }
+        }
+        Ok(options)
+    }
+
+    fn take(&mut self, name: &str) -> Result<Option<String>, String> {
+        match self.named.iter().position(|(option, _)| option == name) {
+            Some(index) => Ok(Some(self.named.remove(index).1)),
+            None => Ok(None),
+        }
+    }
+
+    fn take_number(&mut self, name: &str) -> Result<Option<usize>, String> {
+        self.take(name)?
+            .map(|value| value.parse().map_err(|_| format!("Invalid value {} for {}", value, name)))
+            .transpose()
+    }
+
+    // Rejects options the command didn't ask for and hands back the ROM path
+    fn finish(self) -> Result<String, String> {
+        if let Some((option, _)) = self.named.first() {
+            return Err(format!("Unknown option {}\n{}", option, USAGE));
+        }
+        self.rom_path.ok_or_else(|| format!("Missing ROM path\n{}", USAGE))
+    }
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => format!("File {} does not exist", path),
+        _ => format!("Can't read {}: {}", path, e),
+    })
+}
+
+fn read_text(path: &str) -> Result<String, String> {
+    String::from_utf8(read_file(path)?).map_err(|_| format!("{} is not a text file", path))
+}
+
+// A movie made with another dump of the game may well desync, but it's worth a try
+fn load_movie(path: &str, game: &Game) -> Result<Movie, String> {
+    let movie = Movie::parse(&read_text(path)?).map_err(|e| format!("Can't parse {}: {}", path, e))?;
+    if !movie.matches(game.checksum()) {
+        eprintln!("{} was recorded with a different ROM ({})", path, movie.rom_filename);
+    }
+    Ok(movie)
+}
+
+// A cartridge, or a disk image with the BIOS of the disk system and the writes of earlier sessions
+fn load_game(path: &str, fds_bios: Option<String>) -> Result<Game, String> {
+    parse_game(path, &read_file(path)?, fds_bios)
+}
+
+fn parse_game(path: &str, raw: &[u8], fds_bios: Option<String>) -> Result<Game, String> {
+    let bios_path = fds_bios.unwrap_or_else(|| DEFAULT_FDS_BIOS.to_string());
+    let bios = || read_file(&bios_path).map_err(|e| format!("{}, the disk system BIOS can be given with --fds-bios", e));
+    Game::load(Path::new(path), raw, bios).map_err(|e| format!("Can't load {}: {}", path, e))
+}
+
+fn write_png(path: &Path, frame: &Frame, scale: usize) -> Result<(), String> {
+    std::fs::write(path, frame.to_png_scaled(scale)).map_err(|e| format!("Can't write {}: {}", path.display(), e))
+}
+
+fn print_nsf_info(player: &NsfPlayer) {
+    let track = player.current_track();
+    println!("Title:      {}", player.title());
+    println!("Artist:     {}", player.artist());
+    println!("Copyright:  {}", player.copyright());
+    match player.track_label(track) {
+        Some(label) => println!("Song:       {}/{} {}", track + 1, player.track_count(), label),
+        None => println!("Song:       {}/{}", track + 1, player.track_count()),
+    }
+    println!("System:     {}", if player.is_pal() { "PAL" } else { "NTSC" });
+}
+
+fn print_info(path: &str, raw: &[u8], rom: &Rom) {
+    let format = if raw.starts_with(&unif::UNIF_TAG) {
+        "UNIF"
+    } else if (raw[7] >> 2) & 0b11 == 0b10 {
+        "NES 2.0"
+    } else {
+        "iNES"
+    };
+    let mut prg_chr = rom.prg_rom.clone();
+    prg_chr.extend(&rom.chr_rom);
+
+    println!("File:            {}", path);
+    println!("Format:          {}", format);
+    println!("Mapper:          {} (submapper {})", rom.mapper, rom.submapper);
+    println!("PRG ROM:         {} KiB", rom.prg_rom.len() / 1024);
+    if rom.chr_rom.is_empty() {
+        println!("CHR ROM:         none (CHR RAM)");
+    } else {
+        println!("CHR ROM:         {} KiB", rom.chr_rom.len() / 1024);
+    }
+    println!("Mirroring:       {:?}", rom.screen_mirroring);
+    println!("Battery:         {}", if rom.battery { "yes" } else { "no" });
+    if format != "UNIF" {
+        println!("Trainer:         {}", if raw[6] & 0b100 != 0 { "yes" } else { "no" });
+    }
+    println!("Expansion port:  {}", rom.default_expansion_device);
+    println!("File CRC32:      {:08X}", crc32(raw));
+    println!("PRG+CHR CRC32:   {:08X}", crc32(&prg_chr));
+    println!("PRG CRC32:       {:08X}", crc32(&rom.prg_rom));
+    if !rom.chr_rom.is_empty() {
+        println!("CHR CRC32:       {:08X}", crc32(&rom.chr_rom));
+    }
+}
+
+fn print_disk_info(path: &str, raw: &[u8], drive: &DiskDrive) {
+    println!("File:            {}", path);
+    println!("Format:          {}", if raw.starts_with(&FDS_TAG) { "FDS" } else { "FDS (headerless)" });
+    println!("Sides:           {}", drive.side_count());
+    println!("File CRC32:      {:08X}", crc32(raw));
 }
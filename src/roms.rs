@@ -1,3 +1,21 @@
-﻿pub mod snake_nes_dump;
-pub mod runner_with_trace;
-mod runner;
\ No newline at end of file
+﻿pub mod runner_with_trace;
+pub mod input_config;
+#[cfg(feature = "sdl")]
+pub mod input_mapping;
+#[cfg(feature = "sdl")]
+pub mod zapper_mouse;
+#[cfg(feature = "sdl")]
+pub mod device_input;
+#[cfg(feature = "sdl")]
+pub mod runner;
+#[cfg(feature = "sdl")]
+pub mod nsf_runner;
+pub mod headless;
+pub mod input_script;
+pub mod quick_slots;
+pub mod rewind;
+pub mod speed;
+pub mod screenshot;
+pub mod avi;
+pub mod movie;
+pub mod game;
\ No newline at end of file
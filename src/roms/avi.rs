@@ -0,0 +1,234 @@
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::components::frame::Frame;
+
+// The NTSC frame rate as an exact fraction, 1789772.72 CPU cycles a second over 29780.5 per frame
+pub const FRAME_RATE_NUMERATOR: u32 = 39_375_000;
+pub const FRAME_RATE_DENOMINATOR: u32 = 655_171;
+
+const FRAME_BYTES: usize = Frame::WIDTH * Frame::HEIGHT * 3;
+// RIFF sizes are 32 bits, about six minutes of frames
+const MAX_FILE_SIZE: u64 = u32::MAX as u64;
+
+// Offsets of the fields that are only known once the recording stops
+const RIFF_SIZE: u64 = 4;
+const TOTAL_FRAMES: u64 = 48;
+const VIDEO_LENGTH: u64 = 140;
+const AUDIO_LENGTH: u64 = 264;
+const MOVI_SIZE: u64 = 316;
+
+//https://learn.microsoft.com/en-us/windows/win32/directshow/avi-riff-file-reference
+// Uncompressed 24 bit frames and 16 bit mono PCM in an AVI 1.0 file, which every player opens
+// and which loses nothing of the picture.
+pub struct AviWriter<W: Write + Seek> {
+    out: W,
+    frames: u32,
+    samples: u32,
+    size: u64,
+    // chunk id, offset from the movi list and size of every chunk, for the idx1 index
+    index: Vec<([u8; 4], u32, u32)>,
+}
+
+impl AviWriter<BufWriter<File>> {
+    pub fn create(path: &Path, sample_rate: u32) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("Can't create {}: {}", path.display(), e))?;
+        AviWriter::new(BufWriter::new(file), sample_rate)
+    }
+}
+
+impl<W: Write + Seek> AviWriter<W> {
+    pub fn new(mut out: W, sample_rate: u32) -> Result<Self, String> {
+        let header = header(sample_rate);
+        out.write_all(&header).map_err(write_error)?;
+        Ok(AviWriter {
+            out,
+            frames: 0,
+            samples: 0,
+            size: header.len() as u64,
+            index: vec![],
+        })
+    }
+
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    // A frame and the audio that played during it. Fails once the file can't grow any further.
+    pub fn add_frame(&mut self, frame: &Frame, samples: &[f32]) -> Result<(), String> {
+        let pcm: Vec<u8> = samples
+            .iter()
+            .flat_map(|sample| ((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+            .collect();
+        let index_size = (self.index.len() as u64 + 2) * 16;
+        if self.size + FRAME_BYTES as u64 + pcm.len() as u64 + 16 + index_size + 8 > MAX_FILE_SIZE {
+            return Err("The recording reached the largest size an AVI file can have".to_string());
+        }
+
+        // bottom-up rows of blue, green, red
+        let mut bitmap = Vec::with_capacity(FRAME_BYTES);
+        for row in frame.data.chunks(Frame::WIDTH * 3).rev() {
+            bitmap.extend(row.chunks(3).flat_map(|pixel| [pixel[2], pixel[1], pixel[0]]));
+        }
+        self.write_chunk(*b"00db", &bitmap)?;
+        if !pcm.is_empty() {
+            self.write_chunk(*b"01wb", &pcm)?;
+        }
+        self.frames += 1;
+        self.samples += samples.len() as u32;
+        Ok(())
+    }
+
+    // Writes the index and the lengths, the file isn't playable before this
+    pub fn finish(mut self) -> Result<W, String> {
+        let mut index = Vec::with_capacity(self.index.len() * 16);
+        for (id, offset, size) in &self.index {
+            index.extend(id);
+            index.extend(0x10u32.to_le_bytes()); // AVIIF_KEYFRAME
+            index.extend(offset.to_le_bytes());
+            index.extend(size.to_le_bytes());
+        }
+        let movi_size = self.size - MOVI_SIZE - 4;
+        self.out.write_all(b"idx1").map_err(write_error)?;
+        self.out.write_all(&(index.len() as u32).to_le_bytes()).map_err(write_error)?;
+        self.out.write_all(&index).map_err(write_error)?;
+        self.size += 8 + index.len() as u64;
+
+        for (offset, value) in [
+            (RIFF_SIZE, self.size as u32 - 8),
+            (TOTAL_FRAMES, self.frames),
+            (VIDEO_LENGTH, self.frames),
+            (AUDIO_LENGTH, self.samples),
+            (MOVI_SIZE, movi_size as u32),
+        ] {
+            self.out.seek(SeekFrom::Start(offset)).map_err(write_error)?;
+            self.out.write_all(&value.to_le_bytes()).map_err(write_error)?;
+        }
+        self.out.seek(SeekFrom::End(0)).map_err(write_error)?;
+        self.out.flush().map_err(write_error)?;
+        Ok(self.out)
+    }
+
+    fn write_chunk(&mut self, id: [u8; 4], data: &[u8]) -> Result<(), String> {
+        // offsets count from the "movi" id, just after the list size
+        let offset = self.size - MOVI_SIZE - 4;
+        self.index.push((id, offset as u32, data.len() as u32));
+        self.out.write_all(&id).map_err(write_error)?;
+        self.out.write_all(&(data.len() as u32).to_le_bytes()).map_err(write_error)?;
+        self.out.write_all(data).map_err(write_error)?;
+        self.size += 8 + data.len() as u64;
+        // chunks start on even offsets
+        if data.len() % 2 == 1 {
+            self.out.write_all(&[0]).map_err(write_error)?;
+            self.size += 1;
+        }
+        Ok(())
+    }
+}
+
+fn write_error(e: std::io::Error) -> String {
+    format!("Can't write the recording: {}", e)
+}
+
+// Everything up to the first chunk of the movi list, with the lengths left at 0
+fn header(sample_rate: u32) -> Vec<u8> {
+    let (width, height) = (Frame::WIDTH as u32, Frame::HEIGHT as u32);
+    let micros_per_frame = (1_000_000u64 * FRAME_RATE_DENOMINATOR as u64 / FRAME_RATE_NUMERATOR as u64) as u32;
+    let frames_per_second = FRAME_RATE_NUMERATOR.div_ceil(FRAME_RATE_DENOMINATOR);
+    let audio_bytes_per_frame = (sample_rate * 2).div_ceil(frames_per_second) + 2;
+
+    let mut avih = vec![];
+    for value in [
+        micros_per_frame,
+        (FRAME_BYTES as u32 + audio_bytes_per_frame) * frames_per_second,
+        0,    // padding granularity
+        0x10, // AVIF_HASINDEX
+        0,    // total frames
+        0,    // initial frames
+        2,    // streams
+        FRAME_BYTES as u32,
+        width,
+        height,
+        0,
+        0,
+        0,
+        0,
+    ] {
+        avih.extend(value.to_le_bytes());
+    }
+
+    let video_strh = stream_header(*b"vids", *b"DIB ", FRAME_RATE_DENOMINATOR, FRAME_RATE_NUMERATOR, FRAME_BYTES as u32, 0);
+    let mut video_strf = vec![];
+    video_strf.extend(40u32.to_le_bytes());
+    video_strf.extend(width.to_le_bytes());
+    video_strf.extend(height.to_le_bytes()); // positive height means bottom-up rows
+    video_strf.extend(1u16.to_le_bytes()); // planes
+    video_strf.extend(24u16.to_le_bytes()); // bits per pixel
+    video_strf.extend(0u32.to_le_bytes()); // BI_RGB
+    video_strf.extend((FRAME_BYTES as u32).to_le_bytes());
+    video_strf.extend([0; 16]);
+
+    let audio_strh = stream_header(*b"auds", [0; 4], 2, sample_rate * 2, audio_bytes_per_frame, 2);
+    let mut audio_strf = vec![];
+    audio_strf.extend(1u16.to_le_bytes()); // PCM
+    audio_strf.extend(1u16.to_le_bytes()); // mono
+    audio_strf.extend(sample_rate.to_le_bytes());
+    audio_strf.extend((sample_rate * 2).to_le_bytes());
+    audio_strf.extend(2u16.to_le_bytes()); // block align
+    audio_strf.extend(16u16.to_le_bytes()); // bits per sample
+
+    let video_strl = list(*b"strl", &[chunk(*b"strh", &video_strh), chunk(*b"strf", &video_strf)].concat());
+    let audio_strl = list(*b"strl", &[chunk(*b"strh", &audio_strh), chunk(*b"strf", &audio_strf)].concat());
+    let hdrl = list(*b"hdrl", &[chunk(*b"avih", &avih), video_strl, audio_strl].concat());
+
+    let mut header = vec![];
+    header.extend(b"RIFF");
+    header.extend(0u32.to_le_bytes());
+    header.extend(b"AVI ");
+    header.extend(hdrl);
+    header.extend(b"LIST");
+    header.extend(0u32.to_le_bytes());
+    header.extend(b"movi");
+    header
+}
+
+// `scale` and `rate` give the units of the stream per second, its length starts out as 0
+fn stream_header(kind: [u8; 4], handler: [u8; 4], scale: u32, rate: u32, buffer_size: u32, sample_size: u32) -> Vec<u8> {
+    let mut strh = vec![];
+    strh.extend(kind);
+    strh.extend(handler);
+    strh.extend(0u32.to_le_bytes()); // flags
+    strh.extend(0u32.to_le_bytes()); // priority and language
+    strh.extend(0u32.to_le_bytes()); // initial frames
+    strh.extend(scale.to_le_bytes());
+    strh.extend(rate.to_le_bytes());
+    strh.extend(0u32.to_le_bytes()); // start
+    strh.extend(0u32.to_le_bytes()); // length
+    strh.extend(buffer_size.to_le_bytes());
+    strh.extend(u32::MAX.to_le_bytes()); // default quality
+    strh.extend(sample_size.to_le_bytes());
+    if kind == *b"vids" {
+        for value in [0u16, 0, Frame::WIDTH as u16, Frame::HEIGHT as u16] {
+            strh.extend(value.to_le_bytes());
+        }
+    } else {
+        strh.extend([0; 8]);
+    }
+    strh
+}
+
+fn chunk(id: [u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = id.to_vec();
+    chunk.extend((data.len() as u32).to_le_bytes());
+    chunk.extend(data);
+    chunk
+}
+
+fn list(kind: [u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut list = b"LIST".to_vec();
+    list.extend((data.len() as u32 + 4).to_le_bytes());
+    list.extend(kind);
+    list.extend(data);
+    list
+}
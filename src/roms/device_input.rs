@@ -0,0 +1,81 @@
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
+
+use crate::components::input_device::InputDevices;
+use crate::roms::input_config::PLAYERS;
+use crate::roms::input_mapping::InputMapping;
+use crate::roms::zapper_mouse::ZapperMouse;
+
+#[rustfmt::skip]
+const FAMILY_KEYBOARD_KEYS: [(Keycode, &str); 68] = [
+    (Keycode::A, "A"), (Keycode::B, "B"), (Keycode::C, "C"), (Keycode::D, "D"), (Keycode::E, "E"),
+    (Keycode::F, "F"), (Keycode::G, "G"), (Keycode::H, "H"), (Keycode::I, "I"), (Keycode::J, "J"),
+    (Keycode::K, "K"), (Keycode::L, "L"), (Keycode::M, "M"), (Keycode::N, "N"), (Keycode::O, "O"),
+    (Keycode::P, "P"), (Keycode::Q, "Q"), (Keycode::R, "R"), (Keycode::S, "S"), (Keycode::T, "T"),
+    (Keycode::U, "U"), (Keycode::V, "V"), (Keycode::W, "W"), (Keycode::X, "X"), (Keycode::Y, "Y"),
+    (Keycode::Z, "Z"), (Keycode::Num0, "0"), (Keycode::Num1, "1"), (Keycode::Num2, "2"),
+    (Keycode::Num3, "3"), (Keycode::Num4, "4"), (Keycode::Num5, "5"), (Keycode::Num6, "6"),
+    (Keycode::Num7, "7"), (Keycode::Num8, "8"), (Keycode::Num9, "9"), (Keycode::F1, "F1"),
+    (Keycode::F2, "F2"), (Keycode::F3, "F3"), (Keycode::F4, "F4"), (Keycode::F5, "F5"),
+    (Keycode::F6, "F6"), (Keycode::F7, "F7"), (Keycode::F8, "F8"), (Keycode::Return, "RETURN"),
+    (Keycode::Space, "SPACE"), (Keycode::Backspace, "DEL"), (Keycode::Insert, "INS"),
+    (Keycode::Home, "CLR"), (Keycode::Escape, "ESC"), (Keycode::LCtrl, "CTR"),
+    (Keycode::LShift, "LSHIFT"), (Keycode::RShift, "RSHIFT"), (Keycode::LAlt, "GRPH"),
+    (Keycode::RAlt, "KANA"), (Keycode::End, "STOP"), (Keycode::Left, "LEFT"), (Keycode::Right, "RIGHT"),
+    (Keycode::Up, "UP"), (Keycode::Down, "DOWN"), (Keycode::LeftBracket, "["), (Keycode::RightBracket, "]"),
+    (Keycode::Semicolon, ";"), (Keycode::Quote, ":"), (Keycode::Minus, "-"), (Keycode::Comma, ","),
+    (Keycode::Period, "."), (Keycode::Slash, "/"),
+];
+
+// Feeds the host mouse and keyboard to the devices that aren't joypads
+pub struct DeviceInput {
+    zapper_mouse: ZapperMouse,
+    window_scale: i32,
+}
+
+impl DeviceInput {
+    pub fn new(window_scale: u32) -> Self {
+        DeviceInput {
+            zapper_mouse: ZapperMouse::new(window_scale),
+            window_scale: window_scale as i32,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event, devices: &InputDevices) {
+        if let Some(zapper) = &devices.zapper {
+            self.zapper_mouse.handle_event(event, &mut zapper.borrow_mut());
+        }
+
+        if let Some(paddle) = &devices.arkanoid_paddle {
+            let mut paddle = paddle.borrow_mut();
+            match event {
+                Event::MouseMotion { x, .. } => paddle.set_position_from_screen((*x).max(0) as usize / self.window_scale as usize),
+                Event::MouseButtonDown { mouse_btn: MouseButton::Left, .. } => paddle.set_button_pressed(true),
+                Event::MouseButtonUp { mouse_btn: MouseButton::Left, .. } => paddle.set_button_pressed(false),
+                _ => {}
+            }
+        }
+
+        if let Some(keyboard) = &devices.family_keyboard {
+            let (key, pressed) = match event {
+                Event::KeyDown { keycode: Some(key), .. } => (key, true),
+                Event::KeyUp { keycode: Some(key), .. } => (key, false),
+                _ => return,
+            };
+            if let Some((_, name)) = FAMILY_KEYBOARD_KEYS.iter().find(|(keycode, _)| keycode == key) {
+                keyboard.borrow_mut().set_key_pressed(name, pressed).unwrap();
+            }
+        }
+    }
+
+    // Copies the state of the mapped buttons into the devices, once per frame
+    pub fn update(&self, input: &InputMapping, devices: &InputDevices) {
+        for player in 0..PLAYERS {
+            devices.set_joypad_buttons(player, input.buttons(player));
+        }
+        if let Some(power_pad) = &devices.power_pad {
+            power_pad.borrow_mut().set_buttons(input.power_pad_buttons());
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::components::bus::Bus;
+use crate::components::cartridge::fds::{FdsImage, FDS_TAG};
+use crate::components::cartridge::Rom;
+use crate::components::input_device::InputSetup;
+use crate::components::mapper::fds::Fds;
+use crate::components::md5::md5;
+use crate::roms::movie;
+
+// What goes into the console, a cartridge or a disk for the Famicom Disk System
+pub enum Game {
+    Cartridge(Rom),
+    Disk(DiskDrive),
+}
+
+impl Game {
+    // Disk images are told apart by their header, or by the extension for headerless dumps.
+    // `fds_bios` is only read for those.
+    pub fn load(path: &Path, raw: &[u8], fds_bios: impl FnOnce() -> Result<Vec<u8>, String>) -> Result<Game, String> {
+        let is_disk = raw.starts_with(&FDS_TAG) || path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("fds"));
+        if is_disk {
+            Ok(Game::Disk(DiskDrive::open(path, raw, fds_bios()?)?))
+        } else {
+            Ok(Game::Cartridge(Rom::load(raw)?))
+        }
+    }
+
+    pub fn default_input_setup(&self) -> InputSetup {
+        match self {
+            Game::Cartridge(rom) => InputSetup::from_expansion_device(rom.default_expansion_device).unwrap_or(InputSetup::Joypads),
+            Game::Disk(_) => InputSetup::Joypads,
+        }
+    }
+
+    // What FCEUX identifies the game by in movies
+    pub fn checksum(&self) -> [u8; 16] {
+        match self {
+            Game::Cartridge(rom) => movie::rom_checksum(rom),
+            Game::Disk(drive) => drive.checksum,
+        }
+    }
+
+    // The drive stays reachable after the game went into the console, for switching sides
+    pub fn disk_drive(&self) -> Option<DiskDrive> {
+        match self {
+            Game::Cartridge(_) => None,
+            Game::Disk(drive) => Some(drive.clone()),
+        }
+    }
+
+    pub fn into_bus(self) -> Result<Bus, String> {
+        match self {
+            Game::Cartridge(rom) => Bus::new(rom),
+            Game::Disk(drive) => Ok(Bus::with_mapper(drive.fds)),
+        }
+    }
+}
+
+// The RAM adapter with a disk in it. What the games write to the disk is kept as an IPS patch
+// next to the image, game.fds gets game.ips, so the image itself stays a clean dump.
+#[derive(Clone)]
+pub struct DiskDrive {
+    fds: Rc<RefCell<Fds>>,
+    checksum: [u8; 16],
+    changes_path: PathBuf,
+    // the side put in the next time the disk is inserted
+    next_side: usize,
+}
+
+impl DiskDrive {
+    pub fn open(image_path: &Path, raw: &[u8], bios: Vec<u8>) -> Result<DiskDrive, String> {
+        let image = FdsImage::new(raw)?;
+        let checksum = md5(&image.sides.concat());
+        let changes_path = image_path.with_extension("ips");
+        let fds = match std::fs::read(&changes_path) {
+            Ok(changes) => Fds::with_changes(bios, image, &changes).map_err(|e| format!("Can't apply {}: {}", changes_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Fds::new(bios, image)?,
+            Err(e) => return Err(format!("Can't read {}: {}", changes_path.display(), e)),
+        };
+        Ok(DiskDrive {
+            fds: Rc::new(RefCell::new(fds)),
+            checksum,
+            changes_path,
+            next_side: 0,
+        })
+    }
+
+    pub fn side_count(&self) -> usize {
+        self.fds.borrow().side_count()
+    }
+
+    // Ejects the disk, or puts the next side in once it's out. The BIOS only notices a new side
+    // after it has seen the drive empty. Returns what's in the drive now, for the title bar.
+    pub fn switch_side(&mut self) -> Result<String, String> {
+        let mut fds = self.fds.borrow_mut();
+        match fds.inserted_side() {
+            Some(side) => {
+                fds.eject_disk();
+                self.next_side = (side + 1) % fds.side_count();
+                Ok("disk ejected".to_string())
+            }
+            None => {
+                let side = self.next_side % fds.side_count();
+                fds.insert_disk(side)?;
+                Ok(describe_side(side))
+            }
+        }
+    }
+
+    // Writes the patch when the disk was written to, an untouched disk leaves an older one alone
+    pub fn save_changes(&self) -> Result<(), String> {
+        match self.fds.borrow().disk_changes() {
+            Some(changes) => std::fs::write(&self.changes_path, changes)
+                .map_err(|e| format!("Can't write {}: {}", self.changes_path.display(), e)),
+            None => Ok(()),
+        }
+    }
+}
+
+// Sides come in pairs, disk 1 side A is the first one in the image
+fn describe_side(side: usize) -> String {
+    format!("disk {} side {}", side / 2 + 1, if side.is_multiple_of(2) { 'A' } else { 'B' })
+}
@@ -0,0 +1,68 @@
+use crate::components::bus::RamInit;
+use crate::components::cpu::CPU;
+use crate::components::frame::Frame;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::roms::game::Game;
+use crate::roms::input_script::InputScript;
+
+pub const AUDIO_SAMPLE_RATE: u32 = 44100;
+
+// Where unattended runs get their input from
+pub trait InputSource {
+    // The devices to plug in, `default` is what the cartridge asks for
+    fn input_setup(&self, default: InputSetup) -> InputSetup {
+        default
+    }
+
+    // Called right before `frame` runs
+    fn apply(&self, frame: usize, cpu: &mut CPU, devices: &InputDevices) -> Result<(), String>;
+}
+
+impl InputSource for InputScript {
+    fn apply(&self, frame: usize, _cpu: &mut CPU, devices: &InputDevices) -> Result<(), String> {
+        for (player, buttons) in self.buttons(frame).iter().enumerate() {
+            devices.set_joypad_buttons(player, *buttons);
+        }
+        Ok(())
+    }
+}
+
+// Runs a game for a number of frames without opening a window, feeding the devices from
+// `input`. `on_frame` is called after every frame, numbered from 0, and can take the audio samples
+// of it. The console is switched on with `ram_init` in its RAM. The last picture is returned.
+// What got written to a disk is saved when the run ends, also when it stopped early.
+pub fn run<I, F>(game: Game, ram_init: RamInit, frames: usize, input: &I, on_frame: F) -> Result<Frame, String>
+    where
+        I: InputSource + ?Sized,
+        F: FnMut(usize, &mut CPU) -> Result<(), String>,
+{
+    let default = game.default_input_setup();
+    let disk = game.disk_drive();
+    let mut cpu = CPU::new(game.into_bus()?);
+    let devices = InputDevices::connect(input.input_setup(default), &mut cpu.bus);
+    cpu.bus.apu().set_sample_rate(AUDIO_SAMPLE_RATE);
+    cpu.bus.set_ram_init(ram_init);
+    cpu.power_on();
+
+    let played = run_frames(&mut cpu, &devices, frames, input, on_frame);
+    let disk_saved = disk.map_or(Ok(()), |disk| disk.save_changes());
+    played?;
+    disk_saved?;
+    Ok(cpu.bus.ppu().frame().clone())
+}
+
+fn run_frames<I, F>(cpu: &mut CPU, devices: &InputDevices, frames: usize, input: &I, mut on_frame: F) -> Result<(), String>
+    where
+        I: InputSource + ?Sized,
+        F: FnMut(usize, &mut CPU) -> Result<(), String>,
+{
+    for frame in 0..frames {
+        input.apply(frame, cpu, devices)?;
+        if !cpu.run_frame() {
+            return Err(format!("CPU hit BRK at {:04X}", cpu.program_counter.wrapping_sub(1)));
+        }
+        on_frame(frame, cpu)?;
+        cpu.bus.apu().take_samples();
+    }
+    Ok(())
+}
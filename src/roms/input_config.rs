@@ -0,0 +1,202 @@
+use std::fs;
+use std::path::Path;
+
+use crate::components::joypad::JoypadButton;
+use crate::components::power_pad::PowerPad;
+
+// four players can be bound for the multitap adapters
+pub const PLAYERS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputBinding {
+    // SDL key name, e.g. "Return" or "Right Shift"
+    Key(String),
+    // SDL game controller button name, e.g. "a" or "dpup"
+    ControllerButton(String),
+    // SDL game controller axis name with the direction that presses the button
+    ControllerAxis(String, AxisDirection),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AxisDirection {
+    Negative,
+    Positive,
+}
+
+// One line per binding, several bindings may press the same button:
+//   <player>.<button> = key:<name> | button:<name> | axis:<name>(+|-)
+//   powerpad.<1-12> = key:<name>
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputConfig {
+    pub bindings: [Vec<(JoypadButton, InputBinding)>; PLAYERS],
+    pub power_pad: Vec<(usize, InputBinding)>,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        // players 3 and 4 only get a game controller
+        let keyboard = [
+            ["X", "Z", "Right Shift", "Return", "Up", "Down", "Left", "Right"],
+            ["M", "N", "Right Ctrl", "Keypad Enter", "I", "K", "J", "L"],
+        ];
+
+        let mut config = InputConfig::empty();
+        // the keys sit in the same grid as the mat buttons
+        let power_pad = ["1", "2", "3", "4", "Q", "W", "E", "R", "A", "S", "D", "F"];
+        for (button, key) in power_pad.iter().enumerate() {
+            config.power_pad.push((button + 1, InputBinding::Key(key.to_string())));
+        }
+
+        for (player, bindings) in config.bindings.iter_mut().enumerate() {
+            if let Some(keys) = keyboard.get(player) {
+                for (button, key) in JoypadButton::ALL.iter().zip(keys) {
+                    bindings.push((*button, InputBinding::Key(key.to_string())));
+                }
+            }
+
+            // face buttons follow their position on the NES pad rather than their labels
+            let controller = [
+                (JoypadButton::A, "b"),
+                (JoypadButton::B, "a"),
+                (JoypadButton::Select, "back"),
+                (JoypadButton::Start, "start"),
+                (JoypadButton::Up, "dpup"),
+                (JoypadButton::Down, "dpdown"),
+                (JoypadButton::Left, "dpleft"),
+                (JoypadButton::Right, "dpright"),
+            ];
+            for (button, name) in controller {
+                bindings.push((button, InputBinding::ControllerButton(name.to_string())));
+            }
+            bindings.push((JoypadButton::Up, InputBinding::ControllerAxis("lefty".to_string(), AxisDirection::Negative)));
+            bindings.push((JoypadButton::Down, InputBinding::ControllerAxis("lefty".to_string(), AxisDirection::Positive)));
+            bindings.push((JoypadButton::Left, InputBinding::ControllerAxis("leftx".to_string(), AxisDirection::Negative)));
+            bindings.push((JoypadButton::Right, InputBinding::ControllerAxis("leftx".to_string(), AxisDirection::Positive)));
+        }
+        config
+    }
+}
+
+impl InputConfig {
+    pub fn empty() -> Self {
+        InputConfig {
+            bindings: [vec![], vec![], vec![], vec![]],
+            power_pad: vec![],
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<InputConfig, String> {
+        let mut config = InputConfig::empty();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (target, binding) = line
+                .split_once('=')
+                .ok_or(format!("Input config line {} is not a binding", index + 1))?;
+            let (player, button) = target
+                .trim()
+                .split_once('.')
+                .ok_or(format!("Input config line {} has no player", index + 1))?;
+            let binding = parse_binding(binding.trim())
+                .ok_or(format!("Input config line {}: invalid binding {}", index + 1, binding.trim()))?;
+
+            if player == "powerpad" {
+                let button = match button.trim().parse::<usize>() {
+                    Ok(button) if (1..=PowerPad::BUTTONS).contains(&button) => button,
+                    _ => return Err(format!("Input config line {}: unknown Power Pad button {}", index + 1, button)),
+                };
+                config.power_pad.push((button, binding));
+                continue;
+            }
+
+            let player = match player.parse::<usize>() {
+                Ok(player) if (1..=PLAYERS).contains(&player) => player - 1,
+                _ => return Err(format!("Input config line {}: unknown player {}", index + 1, player)),
+            };
+            let button = button_from_name(button)
+                .ok_or(format!("Input config line {}: unknown button {}", index + 1, button))?;
+
+            config.bindings[player].push((button, binding));
+        }
+
+        Ok(config)
+    }
+
+    // Falls back to the default bindings and writes them out when there is no file yet
+    pub fn load_or_create(path: &Path) -> Result<InputConfig, String> {
+        if !path.exists() {
+            let config = InputConfig::default();
+            fs::write(path, config.to_string()).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+            return Ok(config);
+        }
+
+        let text = fs::read_to_string(path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+        InputConfig::parse(&text)
+    }
+}
+
+impl std::fmt::Display for InputConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (player, bindings) in self.bindings.iter().enumerate() {
+            for (button, binding) in bindings {
+                writeln!(f, "{}.{} = {}", player + 1, button_name(*button), binding_to_string(binding))?;
+            }
+        }
+        for (button, binding) in &self.power_pad {
+            writeln!(f, "powerpad.{} = {}", button, binding_to_string(binding))?;
+        }
+        Ok(())
+    }
+}
+
+fn binding_to_string(binding: &InputBinding) -> String {
+    match binding {
+        InputBinding::Key(name) => format!("key:{}", name),
+        InputBinding::ControllerButton(name) => format!("button:{}", name),
+        InputBinding::ControllerAxis(name, AxisDirection::Negative) => format!("axis:{}-", name),
+        InputBinding::ControllerAxis(name, AxisDirection::Positive) => format!("axis:{}+", name),
+    }
+}
+
+fn parse_binding(binding: &str) -> Option<InputBinding> {
+    let (kind, name) = binding.split_once(':')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    match kind.trim() {
+        "key" => Some(InputBinding::Key(name.to_string())),
+        "button" => Some(InputBinding::ControllerButton(name.to_string())),
+        "axis" => {
+            let direction = match name.chars().last() {
+                Some('-') => AxisDirection::Negative,
+                Some('+') => AxisDirection::Positive,
+                _ => return None,
+            };
+            Some(InputBinding::ControllerAxis(name[..name.len() - 1].to_string(), direction))
+        }
+        _ => None,
+    }
+}
+
+pub fn button_name(button: JoypadButton) -> &'static str {
+    match button {
+        JoypadButton::A => "a",
+        JoypadButton::B => "b",
+        JoypadButton::Select => "select",
+        JoypadButton::Start => "start",
+        JoypadButton::Up => "up",
+        JoypadButton::Down => "down",
+        JoypadButton::Left => "left",
+        JoypadButton::Right => "right",
+    }
+}
+
+pub fn button_from_name(name: &str) -> Option<JoypadButton> {
+    JoypadButton::ALL.iter().copied().find(|button| button_name(*button) == name.trim())
+}
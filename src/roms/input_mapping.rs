@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use sdl2::controller::{Axis, Button, GameController};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::GameControllerSubsystem;
+
+use crate::components::joypad::JoypadButton;
+use crate::roms::input_config::{AxisDirection, InputBinding, InputConfig, PLAYERS};
+
+// how far a stick has to be pushed before it counts as a press
+const AXIS_DEAD_ZONE: i16 = 16000;
+
+type Targets = Vec<(usize, JoypadButton)>;
+
+// Translates SDL keyboard and game controller events into the state of the NES joypads.
+// Controllers are handed to the players in the order they get connected.
+pub struct InputMapping {
+    keys: HashMap<Keycode, Targets>,
+    controller_buttons: HashMap<Button, Targets>,
+    controller_axes: HashMap<(Axis, AxisDirection), Targets>,
+    power_pad_keys: HashMap<Keycode, Vec<usize>>,
+    controller_subsystem: GameControllerSubsystem,
+    controllers: [Option<GameController>; PLAYERS],
+    buttons: [u8; PLAYERS],
+    power_pad_buttons: u16,
+}
+
+impl InputMapping {
+    pub fn new(config: &InputConfig, controller_subsystem: GameControllerSubsystem) -> Result<InputMapping, String> {
+        let mut keys: HashMap<Keycode, Targets> = HashMap::new();
+        let mut controller_buttons: HashMap<Button, Targets> = HashMap::new();
+        let mut controller_axes: HashMap<(Axis, AxisDirection), Targets> = HashMap::new();
+
+        for (player, bindings) in config.bindings.iter().enumerate() {
+            for (button, binding) in bindings {
+                let target = (player, *button);
+                match binding {
+                    InputBinding::Key(name) => {
+                        let key = Keycode::from_name(name).ok_or(format!("Unknown key {}", name))?;
+                        keys.entry(key).or_default().push(target);
+                    }
+                    InputBinding::ControllerButton(name) => {
+                        let button = Button::from_string(name).ok_or(format!("Unknown controller button {}", name))?;
+                        controller_buttons.entry(button).or_default().push(target);
+                    }
+                    InputBinding::ControllerAxis(name, direction) => {
+                        let axis = Axis::from_string(name).ok_or(format!("Unknown controller axis {}", name))?;
+                        controller_axes.entry((axis, *direction)).or_default().push(target);
+                    }
+                }
+            }
+        }
+
+        let mut power_pad_keys: HashMap<Keycode, Vec<usize>> = HashMap::new();
+        for (button, binding) in &config.power_pad {
+            match binding {
+                InputBinding::Key(name) => {
+                    let key = Keycode::from_name(name).ok_or(format!("Unknown key {}", name))?;
+                    power_pad_keys.entry(key).or_default().push(*button);
+                }
+                _ => return Err("Power Pad buttons can only be bound to keys".to_string()),
+            }
+        }
+
+        Ok(InputMapping {
+            keys,
+            controller_buttons,
+            controller_axes,
+            power_pad_keys,
+            controller_subsystem,
+            controllers: [None, None, None, None],
+            buttons: [0; PLAYERS],
+            power_pad_buttons: 0,
+        })
+    }
+
+    // Buttons currently held by a player, in the layout Joypad::set_buttons expects
+    pub fn buttons(&self, player: usize) -> u8 {
+        self.buttons[player]
+    }
+
+    // Mat buttons currently held, in the layout PowerPad::set_buttons expects
+    pub fn power_pad_buttons(&self) -> u16 {
+        self.power_pad_buttons
+    }
+
+    pub fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::KeyDown { keycode: Some(key), repeat: false, .. } => {
+                if let Some(targets) = self.keys.get(key) {
+                    press(&mut self.buttons, targets, true);
+                }
+                if let Some(buttons) = self.power_pad_keys.get(key) {
+                    press_power_pad(&mut self.power_pad_buttons, buttons, true);
+                }
+            }
+            Event::KeyUp { keycode: Some(key), .. } => {
+                if let Some(targets) = self.keys.get(key) {
+                    press(&mut self.buttons, targets, false);
+                }
+                if let Some(buttons) = self.power_pad_keys.get(key) {
+                    press_power_pad(&mut self.power_pad_buttons, buttons, false);
+                }
+            }
+            Event::ControllerDeviceAdded { which, .. } => self.connect_controller(*which),
+            Event::ControllerDeviceRemoved { which, .. } => self.disconnect_controller(*which),
+            Event::ControllerButtonDown { which, button, .. } => self.controller_button(*which, *button, true),
+            Event::ControllerButtonUp { which, button, .. } => self.controller_button(*which, *button, false),
+            Event::ControllerAxisMotion { which, axis, value, .. } => self.controller_axis(*which, *axis, *value),
+            _ => { /* do nothing */ }
+        }
+    }
+
+    // `joystick_index` is the device index SDL reports when a controller gets plugged in
+    fn connect_controller(&mut self, joystick_index: u32) {
+        let slot = match self.controllers.iter().position(|controller| controller.is_none()) {
+            Some(slot) => slot,
+            None => return,
+        };
+
+        match self.controller_subsystem.open(joystick_index) {
+            Ok(controller) => {
+                if self.player_of(controller.instance_id()).is_none() {
+                    self.controllers[slot] = Some(controller);
+                }
+            }
+            Err(e) => eprintln!("Can't open game controller {}: {}", joystick_index, e),
+        }
+    }
+
+    // Removal events carry the instance id instead of the device index
+    fn disconnect_controller(&mut self, instance_id: u32) {
+        if let Some(player) = self.player_of(instance_id) {
+            self.controllers[player] = None;
+            self.buttons[player] = 0;
+        }
+    }
+
+    fn controller_button(&mut self, instance_id: u32, button: Button, pressed: bool) {
+        let player = match self.player_of(instance_id) {
+            Some(player) => player,
+            None => return,
+        };
+
+        if let Some(targets) = self.controller_buttons.get(&button) {
+            press_for_player(&mut self.buttons, targets, player, pressed);
+        }
+    }
+
+    fn controller_axis(&mut self, instance_id: u32, axis: Axis, value: i16) {
+        let player = match self.player_of(instance_id) {
+            Some(player) => player,
+            None => return,
+        };
+
+        if let Some(targets) = self.controller_axes.get(&(axis, AxisDirection::Negative)) {
+            press_for_player(&mut self.buttons, targets, player, value < -AXIS_DEAD_ZONE);
+        }
+        if let Some(targets) = self.controller_axes.get(&(axis, AxisDirection::Positive)) {
+            press_for_player(&mut self.buttons, targets, player, value > AXIS_DEAD_ZONE);
+        }
+    }
+
+    fn player_of(&self, instance_id: u32) -> Option<usize> {
+        self.controllers.iter().position(|controller| match controller {
+            Some(controller) => controller.instance_id() == instance_id,
+            None => false,
+        })
+    }
+}
+
+fn press(buttons: &mut [u8; PLAYERS], targets: &Targets, pressed: bool) {
+    for (player, button) in targets {
+        set_button(&mut buttons[*player], *button, pressed);
+    }
+}
+
+// Controller bindings describe what a pad does for whichever player holds it
+fn press_for_player(buttons: &mut [u8; PLAYERS], targets: &Targets, player: usize, pressed: bool) {
+    for (bound_player, button) in targets {
+        if *bound_player == player {
+            set_button(&mut buttons[player], *button, pressed);
+        }
+    }
+}
+
+fn press_power_pad(power_pad_buttons: &mut u16, buttons: &[usize], pressed: bool) {
+    for button in buttons {
+        if pressed {
+            *power_pad_buttons |= 1 << (button - 1);
+        } else {
+            *power_pad_buttons &= !(1 << (button - 1));
+        }
+    }
+}
+
+fn set_button(buttons: &mut u8, button: JoypadButton, pressed: bool) {
+    if pressed {
+        *buttons |= button.bit();
+    } else {
+        *buttons &= !button.bit();
+    }
+}
@@ -0,0 +1,67 @@
+use crate::roms::input_config::{button_from_name, PLAYERS};
+
+// Joypad input for unattended runs, one line per change of the held buttons:
+//
+//   # frame  player 1   player 2
+//   0        -
+//   30       start
+//   32       -
+//   100      right,a    left
+//
+// A line holds its buttons until the next one, `-` releases everything and missing players
+// press nothing. Frames are counted from 0 and have to go up.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InputScript {
+    changes: Vec<(usize, [u8; PLAYERS])>,
+}
+
+impl InputScript {
+    pub fn parse(text: &str) -> Result<InputScript, String> {
+        let mut changes: Vec<(usize, [u8; PLAYERS])> = vec![];
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let frame_field = fields.next().unwrap_or("");
+            let frame: usize = frame_field
+                .parse()
+                .map_err(|_| format!("Line {}: invalid frame {}", index + 1, frame_field))?;
+            if changes.last().is_some_and(|(previous, _)| *previous >= frame) {
+                return Err(format!("Line {}: frame {} is not after the previous line", index + 1, frame));
+            }
+
+            let mut buttons = [0; PLAYERS];
+            for (player, field) in fields.enumerate() {
+                if player >= PLAYERS {
+                    return Err(format!("Line {}: only {} players are supported", index + 1, PLAYERS));
+                }
+                buttons[player] = parse_buttons(field).map_err(|e| format!("Line {}: {}", index + 1, e))?;
+            }
+            changes.push((frame, buttons));
+        }
+        Ok(InputScript { changes })
+    }
+
+    // The buttons each player holds during a frame, as Joypad::set_buttons takes them
+    pub fn buttons(&self, frame: usize) -> [u8; PLAYERS] {
+        self.changes
+            .iter()
+            .take_while(|(start, _)| *start <= frame)
+            .last()
+            .map_or([0; PLAYERS], |(_, buttons)| *buttons)
+    }
+}
+
+fn parse_buttons(field: &str) -> Result<u8, String> {
+    if field == "-" {
+        return Ok(0);
+    }
+    field.split(',').try_fold(0, |buttons, name| {
+        button_from_name(name)
+            .map(|button| buttons | button.bit())
+            .ok_or_else(|| format!("unknown button {}", name))
+    })
+}
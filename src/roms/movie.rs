@@ -0,0 +1,301 @@
+use crate::components::cartridge::Rom;
+use crate::components::cpu::CPU;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::components::md5::md5;
+use crate::roms::headless::InputSource;
+use crate::roms::input_config::PLAYERS;
+
+// The commands field of an input record
+pub const SOFT_RESET: u8 = 1;
+pub const POWER: u8 = 2;
+
+// FCEUX lists the gamepad buttons from bit 7 down to bit 0, the same bits Joypad::set_buttons takes
+const BUTTON_LETTERS: &[u8; 8] = b"RLDUTSBA";
+// the FCEUX version the written movies claim to come from, 2.2.3
+const EMU_VERSION: u32 = 22030;
+
+//https://fceux.com/web/FM2.html
+// A text FM2 movie: a header of `key value` lines and one input record per frame from power-on,
+// only gamepads on the controller ports (or a Four Score) are supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Movie {
+    pub rom_filename: String,
+    pub rom_checksum: Option<[u8; 16]>,
+    pub four_score: bool,
+    pub rerecord_count: u32,
+    pub comments: Vec<String>,
+    frames: Vec<MovieFrame>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MovieFrame {
+    pub commands: u8,
+    pub buttons: [u8; PLAYERS],
+}
+
+impl Movie {
+    // `rom_checksum` is what Game::checksum gives for the game played
+    pub fn new(rom_filename: &str, rom_checksum: [u8; 16], four_score: bool) -> Self {
+        Movie {
+            rom_filename: rom_filename.to_string(),
+            rom_checksum: Some(rom_checksum),
+            four_score,
+            rerecord_count: 0,
+            comments: vec![],
+            frames: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn frame(&self, frame: usize) -> Option<MovieFrame> {
+        self.frames.get(frame).copied()
+    }
+
+    // Stores the input of `frame`. Going back to an earlier frame, after rewinding, cuts the
+    // movie there and counts a rerecord.
+    pub fn record(&mut self, frame: usize, input: MovieFrame) {
+        if frame < self.frames.len() {
+            self.frames.truncate(frame);
+            self.rerecord_count += 1;
+        }
+        // frames that weren't seen, if any, are played without input
+        self.frames.resize(frame, MovieFrame::default());
+        self.frames.push(input);
+    }
+
+    // Whether the movie was made with the game of this checksum, movies without one are trusted
+    pub fn matches(&self, rom_checksum: [u8; 16]) -> bool {
+        self.rom_checksum.is_none_or(|checksum| checksum == rom_checksum)
+    }
+
+    pub fn input_setup(&self) -> InputSetup {
+        if self.four_score {
+            InputSetup::FourScore
+        } else {
+            InputSetup::Joypads
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Movie, String> {
+        let mut movie = Movie {
+            rom_filename: String::new(),
+            rom_checksum: None,
+            four_score: false,
+            rerecord_count: 0,
+            comments: vec![],
+            frames: vec![],
+        };
+        let mut version = None;
+        let mut length = None;
+        let mut ports = [1, 1, 0];
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            let error = |message: String| format!("Line {}: {}", index + 1, message);
+            if line.starts_with('|') {
+                let frame = movie.parse_record(line, ports).map_err(error)?;
+                movie.frames.push(frame);
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            if !movie.frames.is_empty() {
+                return Err(error("header line after the input records".to_string()));
+            }
+
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let number = || value.trim().parse::<u32>().map_err(|_| error(format!("invalid {} {}", key, value)));
+            match key {
+                "version" => version = Some(number()?),
+                "rerecordCount" => movie.rerecord_count = number()?,
+                "length" => length = Some(number()? as usize),
+                "romFilename" => movie.rom_filename = value.to_string(),
+                "romChecksum" => {
+                    let digest = value
+                        .strip_prefix("base64:")
+                        .and_then(base64_decode)
+                        .and_then(|bytes| bytes.try_into().ok())
+                        .ok_or_else(|| error(format!("invalid romChecksum {}", value)))?;
+                    movie.rom_checksum = Some(digest);
+                }
+                "comment" => movie.comments.push(value.to_string()),
+                "fourscore" => movie.four_score = number()? != 0,
+                "port0" => ports[0] = number()?,
+                "port1" => ports[1] = number()?,
+                "port2" => ports[2] = number()?,
+                "binary" if number()? != 0 => return Err(error("binary input records are not supported".to_string())),
+                "palFlag" if number()? != 0 => return Err(error("PAL movies are not supported".to_string())),
+                "FDS" if number()? != 0 => return Err(error("Famicom Disk System movies are not supported".to_string())),
+                // emuVersion, guid, microphone, NewPPU and the subtitles don't change playback
+                _ => {}
+            }
+        }
+
+        if version != Some(3) {
+            return Err("Not an FM2 movie, the version 3 header line is missing".to_string());
+        }
+        if !movie.four_score && (ports[0] > 1 || ports[1] > 1) || ports[2] != 0 {
+            return Err("Only gamepads and the Four Score are supported".to_string());
+        }
+        if let Some(length) = length.filter(|length| *length != movie.frames.len()) {
+            return Err(format!("The header says {} frames but the movie holds {}", length, movie.frames.len()));
+        }
+        Ok(movie)
+    }
+
+    fn parse_record(&self, line: &str, ports: [u32; 3]) -> Result<MovieFrame, String> {
+        let fields: Vec<&str> = line.split('|').collect();
+        // with a Four Score the four gamepads take the place of both ports
+        let gamepads: Vec<bool> = if self.four_score { vec![true; 4] } else { vec![ports[0] == 1, ports[1] == 1] };
+        // the leading and trailing | give empty fields at both ends, the expansion port comes last
+        if fields.len() != gamepads.len() + 4 {
+            return Err(format!("expected {} fields in the input record", gamepads.len() + 2));
+        }
+
+        let commands = fields[1].trim().parse().map_err(|_| format!("invalid commands {}", fields[1]))?;
+        let mut frame = MovieFrame { commands, buttons: [0; PLAYERS] };
+        for (player, (field, gamepad)) in fields[2..].iter().zip(gamepads).enumerate() {
+            if gamepad {
+                frame.buttons[player] = parse_gamepad(field)?;
+            }
+        }
+        Ok(frame)
+    }
+
+    pub fn to_fm2(&self) -> String {
+        let mut text = String::new();
+        text.push_str("version 3\n");
+        text.push_str(&format!("emuVersion {}\n", EMU_VERSION));
+        text.push_str(&format!("rerecordCount {}\n", self.rerecord_count));
+        text.push_str("palFlag 0\n");
+        text.push_str(&format!("romFilename {}\n", self.rom_filename));
+        if let Some(checksum) = &self.rom_checksum {
+            text.push_str(&format!("romChecksum base64:{}\n", base64_encode(checksum)));
+            text.push_str(&format!("guid {}\n", guid(checksum, self.frames.len())));
+        }
+        text.push_str(&format!("fourscore {}\n", self.four_score as u8));
+        text.push_str("microphone 0\n");
+        let port = if self.four_score { 0 } else { 1 };
+        text.push_str(&format!("port0 {}\nport1 {}\nport2 0\n", port, port));
+        text.push_str("FDS 0\nNewPPU 0\n");
+        for comment in &self.comments {
+            text.push_str(&format!("comment {}\n", comment));
+        }
+
+        let players = if self.four_score { 4 } else { 2 };
+        for frame in &self.frames {
+            text.push_str(&format!("|{}|", frame.commands));
+            for buttons in &frame.buttons[..players] {
+                text.push_str(&format_gamepad(*buttons));
+                text.push('|');
+            }
+            text.push_str("|\n");
+        }
+        text
+    }
+}
+
+impl InputSource for Movie {
+    fn input_setup(&self, _default: InputSetup) -> InputSetup {
+        self.input_setup()
+    }
+
+    // The machine has to be at the same frame as the movie, otherwise the input would land on
+    // the wrong frames and the replay would go its own way
+    fn apply(&self, frame: usize, cpu: &mut CPU, devices: &InputDevices) -> Result<(), String> {
+        let machine_frame = cpu.bus.ppu().frame_count();
+        if machine_frame != frame {
+            return Err(format!("Movie lost sync, frame {} of the movie ran as frame {}", frame, machine_frame));
+        }
+        let input = self.frame(frame).unwrap_or_default();
+        if input.commands & POWER != 0 {
+            cpu.power_on();
+        } else if input.commands & SOFT_RESET != 0 {
+            cpu.reset();
+        }
+        for (player, buttons) in input.buttons.iter().enumerate() {
+            devices.set_joypad_buttons(player, *buttons);
+        }
+        Ok(())
+    }
+}
+
+// FCEUX hashes the PRG followed by the CHR ROM
+pub fn rom_checksum(rom: &Rom) -> [u8; 16] {
+    let mut prg_chr = rom.prg_rom.clone();
+    prg_chr.extend(&rom.chr_rom);
+    md5(&prg_chr)
+}
+
+fn parse_gamepad(field: &str) -> Result<u8, String> {
+    if field.len() != BUTTON_LETTERS.len() {
+        return Err(format!("invalid gamepad {}", field));
+    }
+    // any character other than a space or a dot is a pressed button
+    Ok(field
+        .bytes()
+        .enumerate()
+        .filter(|(_, letter)| *letter != b' ' && *letter != b'.')
+        .fold(0, |buttons, (position, _)| buttons | (0x80 >> position)))
+}
+
+fn format_gamepad(buttons: u8) -> String {
+    BUTTON_LETTERS
+        .iter()
+        .enumerate()
+        .map(|(position, letter)| if buttons & (0x80 >> position) != 0 { *letter as char } else { '.' })
+        .collect()
+}
+
+// Movies only need to be told apart, so the checksum and length make up the GUID
+fn guid(checksum: &[u8; 16], frames: usize) -> String {
+    let mut bytes = *checksum;
+    for (byte, length_byte) in bytes.iter_mut().zip((frames as u64).to_le_bytes()) {
+        *byte ^= length_byte;
+    }
+    let hex: String = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut text = String::new();
+    for group in data.chunks(3) {
+        let bits = group.iter().enumerate().fold(0u32, |bits, (i, byte)| bits | (*byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= group.len() {
+                text.push(BASE64[(bits >> (18 - 6 * i)) as usize & 0x3F] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let values: Vec<u32> = text
+        .trim()
+        .trim_end_matches('=')
+        .bytes()
+        .map(|letter| BASE64.iter().position(|known| *known == letter).map(|value| value as u32))
+        .collect::<Option<_>>()?;
+    let mut data = vec![];
+    for group in values.chunks(4) {
+        if group.len() == 1 {
+            return None;
+        }
+        let bits = group.iter().enumerate().fold(0u32, |bits, (i, value)| bits | value << (18 - 6 * i));
+        data.extend(&bits.to_be_bytes()[1..group.len()]);
+    }
+    Some(data)
+}
@@ -0,0 +1,44 @@
+use std::thread;
+use std::time::Instant;
+
+use sdl2::audio::AudioSpecDesired;
+use sdl2::event::Event;
+
+use crate::components::nsf_player::NsfPlayer;
+
+const AUDIO_SAMPLE_RATE: u32 = 44100;
+
+// Plays the current track of `player` until the program is interrupted, there's no window
+pub fn run(mut player: NsfPlayer) -> Result<(), String> {
+    let sdl_context = sdl2::init()?;
+    let audio_subsystem = sdl_context.audio()?;
+    let spec = AudioSpecDesired {
+        freq: Some(AUDIO_SAMPLE_RATE as i32),
+        channels: Some(1),
+        samples: None,
+    };
+    let audio = audio_subsystem.open_queue::<f32, _>(None, &spec)?;
+    audio.resume();
+    let mut event_pump = sdl_context.event_pump()?;
+
+    player.cpu.bus.apu().set_sample_rate(AUDIO_SAMPLE_RATE);
+    let period = player.play_period();
+    let mut next_call = Instant::now();
+    loop {
+        // SDL turns Ctrl-C into a quit event
+        if event_pump.poll_iter().any(|event| matches!(event, Event::Quit { .. })) {
+            return Ok(());
+        }
+
+        player.play_frame()?;
+        audio.queue(&player.cpu.bus.apu().take_samples());
+
+        next_call += period;
+        let now = Instant::now();
+        if next_call > now {
+            thread::sleep(next_call - now);
+        } else if now - next_call > period * 5 {
+            next_call = now;
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use crate::components::cpu::CPU;
+use crate::components::save_state;
+
+pub const SLOTS: u8 = 10;
+
+// Numbered save states kept next to the ROM, game.nes gets game.ss0 to game.ss9
+pub struct QuickSlots {
+    rom_path: PathBuf,
+    slot: u8,
+}
+
+impl QuickSlots {
+    pub fn new(rom_path: &Path) -> Self {
+        QuickSlots {
+            rom_path: rom_path.to_path_buf(),
+            slot: 0,
+        }
+    }
+
+    pub fn slot(&self) -> u8 {
+        self.slot
+    }
+
+    pub fn next_slot(&mut self) {
+        self.slot = (self.slot + 1) % SLOTS;
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.rom_path.with_extension(format!("ss{}", self.slot))
+    }
+
+    pub fn save(&self, cpu: &CPU) -> Result<(), String> {
+        let path = self.path();
+        std::fs::write(&path, save_state::save(cpu)).map_err(|e| format!("Can't write {}: {}", path.display(), e))
+    }
+
+    pub fn load(&self, cpu: &mut CPU) -> Result<(), String> {
+        let path = self.path();
+        let data = std::fs::read(&path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+        save_state::load(cpu, &data).map_err(|e| format!("Can't load {}: {}", path.display(), e))
+    }
+}
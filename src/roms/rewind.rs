@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+
+use crate::components::cpu::CPU;
+use crate::components::input_device::InputDevices;
+use crate::components::save_state;
+use crate::roms::input_config::PLAYERS;
+
+// Snapshots are taken every `interval` frames. Only the newest one is kept whole, older ones are
+// stored as their difference to the snapshot after them, which is small since little of the
+// machine changes in a few frames. The joypads of every frame are
+// recorded too, so stepping back can replay from a snapshot to any frame in between.
+pub struct Rewind {
+    interval: usize,
+    budget: usize,
+    newest: Option<Snapshot>,
+    // oldest first, each one turns the snapshot after it into its own
+    deltas: VecDeque<Snapshot>,
+    // the buttons held during each frame from the oldest snapshot on
+    inputs: VecDeque<[u8; PLAYERS]>,
+    used: usize,
+}
+
+struct Snapshot {
+    frame: usize,
+    data: Vec<u8>,
+}
+
+impl Rewind {
+    // `budget` is in bytes, the oldest snapshots are dropped once it's exceeded
+    pub fn new(interval: usize, budget: usize) -> Self {
+        Rewind {
+            interval: interval.max(1),
+            budget,
+            newest: None,
+            deltas: VecDeque::new(),
+            inputs: VecDeque::new(),
+            used: 0,
+        }
+    }
+
+    // How many frames back the history reaches
+    pub fn frames(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn memory_used(&self) -> usize {
+        self.used
+    }
+
+    pub fn clear(&mut self) {
+        self.newest = None;
+        self.deltas.clear();
+        self.inputs.clear();
+        self.used = 0;
+    }
+
+    // Call right before running a frame, once the joypads hold the buttons for it
+    pub fn record(&mut self, cpu: &CPU, devices: &InputDevices) {
+        let frame = cpu.bus.ppu().frame_count();
+        if self.next_frame() != Some(frame) {
+            // the machine was reset or a state got loaded, the old history doesn't lead here
+            self.clear();
+        }
+
+        if self.newest.is_none() || frame.is_multiple_of(self.interval) {
+            let data = save_state::save_without_picture(cpu);
+            match self.newest.take() {
+                Some(newest) if newest.data.len() == data.len() => {
+                    let delta = compress_delta(&data, &newest.data);
+                    self.used += delta.len();
+                    self.deltas.push_back(Snapshot {
+                        frame: newest.frame,
+                        data: delta,
+                    });
+                }
+                _ => {
+                    self.clear();
+                    self.used = data.len();
+                }
+            }
+            self.newest = Some(Snapshot { frame, data });
+        }
+
+        let mut buttons = [0; PLAYERS];
+        for (player, held) in buttons.iter_mut().enumerate() {
+            *held = devices.joypad_buttons(player);
+        }
+        self.inputs.push_back(buttons);
+        self.used += PLAYERS;
+
+        while self.used > self.budget && !self.deltas.is_empty() {
+            self.drop_oldest();
+        }
+    }
+
+    // Puts the machine back to where it was one frame ago and runs that frame again, so the PPU
+    // holds the previous picture. Returns false once the history is used up.
+    pub fn step_back(&mut self, cpu: &mut CPU, devices: &InputDevices) -> bool {
+        let frame = cpu.bus.ppu().frame_count();
+        if self.next_frame() != Some(frame) || frame < 2 {
+            return false;
+        }
+        // the frame before the one on screen is run again
+        let target = frame - 2;
+
+        while self.newest.as_ref().is_some_and(|newest| newest.frame > target) {
+            if !self.drop_newest() {
+                return false;
+            }
+        }
+        let newest = match &self.newest {
+            Some(newest) => newest,
+            None => return false,
+        };
+        let first = self.first_frame().unwrap_or(newest.frame);
+        if target < first {
+            return false;
+        }
+
+        if save_state::load_without_picture(cpu, &newest.data).is_err() {
+            self.clear();
+            return false;
+        }
+        for frame in newest.frame..=target {
+            for (player, buttons) in self.inputs[frame - first].iter().enumerate() {
+                devices.set_joypad_buttons(player, *buttons);
+            }
+            if !cpu.run_frame() {
+                self.clear();
+                return false;
+            }
+        }
+        let kept = target + 1 - first;
+        self.used -= (self.inputs.len() - kept) * PLAYERS;
+        self.inputs.truncate(kept);
+        true
+    }
+
+    fn first_frame(&self) -> Option<usize> {
+        self.deltas
+            .front()
+            .or(self.newest.as_ref())
+            .map(|snapshot| snapshot.frame)
+    }
+
+    // The frame record is expected to see next
+    fn next_frame(&self) -> Option<usize> {
+        self.first_frame().map(|first| first + self.inputs.len())
+    }
+
+    fn drop_oldest(&mut self) {
+        if let Some(oldest) = self.deltas.pop_front() {
+            self.used -= oldest.data.len();
+            let next = self.first_frame().unwrap_or(oldest.frame);
+            for _ in oldest.frame..next {
+                self.inputs.pop_front();
+                self.used -= PLAYERS;
+            }
+        }
+    }
+
+    // Rebuilds the snapshot before the newest one, false if there is none
+    fn drop_newest(&mut self) -> bool {
+        match (self.deltas.pop_back(), self.newest.take()) {
+            (Some(delta), Some(newest)) => {
+                self.used -= delta.data.len();
+                self.newest = Some(Snapshot {
+                    frame: delta.frame,
+                    data: apply_delta(&newest.data, &delta.data),
+                });
+                true
+            }
+            _ => {
+                self.clear();
+                false
+            }
+        }
+    }
+}
+
+// Pairs of (unchanged run, changed run) lengths as u16s, each followed by the XOR of the changed bytes
+fn compress_delta(data: &[u8], base: &[u8]) -> Vec<u8> {
+    let mut result = vec![];
+    let mut pos = 0;
+    while pos < data.len() {
+        let unchanged_start = pos;
+        while pos < data.len() && pos - unchanged_start < u16::MAX as usize && data[pos] == base[pos] {
+            pos += 1;
+        }
+        let changed_start = pos;
+        while pos < data.len() && pos - changed_start < u16::MAX as usize && data[pos] != base[pos] {
+            pos += 1;
+        }
+        result.extend(((changed_start - unchanged_start) as u16).to_le_bytes());
+        result.extend(((pos - changed_start) as u16).to_le_bytes());
+        result.extend(data[changed_start..pos].iter().zip(&base[changed_start..pos]).map(|(a, b)| a ^ b));
+    }
+    result
+}
+
+fn apply_delta(data: &[u8], delta: &[u8]) -> Vec<u8> {
+    let mut result = data.to_vec();
+    let mut pos = 0;
+    let mut read = 0;
+    while read + 4 <= delta.len() {
+        let unchanged = u16::from_le_bytes([delta[read], delta[read + 1]]) as usize;
+        let changed = u16::from_le_bytes([delta[read + 2], delta[read + 3]]) as usize;
+        read += 4;
+        pos += unchanged;
+        for (target, value) in result[pos..pos + changed].iter_mut().zip(&delta[read..read + changed]) {
+            *target ^= value;
+        }
+        pos += changed;
+        read += changed;
+    }
+    result
+}
@@ -1,129 +1,361 @@
-﻿use rand::prelude::ThreadRng;
-use rand::Rng;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sdl2::audio::{AudioQueue, AudioSpecDesired};
 use sdl2::event::Event;
-use sdl2::EventPump;
 use sdl2::keyboard::Keycode;
-use sdl2::pixels::{Color, PixelFormatEnum};
-use sdl2::render::{Texture, TextureCreator, WindowCanvas};
-use sdl2::video::WindowContext;
-use crate::components::bus::Bus;
-use crate::components::cartridge::Rom;
-use crate::components::cpu::CPU;
-use crate::components::mem::Mem;
-
-pub fn run(game_code: Vec<u8>) {
-    let (mut screen_state, mut rng, mut canvas, mut event_pump) = initialize_sdl_components();
-
-    let mut creator = canvas.texture_creator();
-    let mut texture = initialize_texture(&mut creator);
-
-    let rom=Rom::new(&game_code).unwrap();
-    let bus=Bus::new(rom);
-    let mut cpu = CPU::new(bus);
-    cpu.reset();
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::Sdl;
 
-    cpu.run_with_callback(move |cpu| {
-        handle_user_input(cpu, &mut event_pump);
-        cpu.mem_write(0xfe, rng.gen_range(1, 16));
-
-        if read_screen_state(cpu, &mut screen_state) {
-            texture.update(None, &screen_state, 32 * 3).unwrap();
-            canvas.copy(&texture, None, None).unwrap();
-            canvas.present();
-        }
-    });
-}
+use crate::components::bus::RamInit;
+use crate::components::cpu::CPU;
+use crate::components::frame::Frame;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::roms::avi::AviWriter;
+use crate::roms::device_input::DeviceInput;
+use crate::roms::game::{DiskDrive, Game};
+use crate::roms::headless::InputSource;
+use crate::roms::input_config::{InputConfig, PLAYERS};
+use crate::roms::input_mapping::InputMapping;
+use crate::roms::movie::{Movie, MovieFrame, POWER, SOFT_RESET};
+use crate::roms::quick_slots::QuickSlots;
+use crate::roms::rewind::Rewind;
+use crate::roms::screenshot;
+use crate::roms::speed::{Speed, SpeedControl};
 
-fn initialize_texture(creator: &mut TextureCreator<WindowContext>) -> Texture {
-    let texture = creator
-        .create_texture_target(PixelFormatEnum::RGB24, 32, 32)
-        .unwrap();
-    texture
-}
+const INPUT_CONFIG_PATH: &str = "input.cfg";
+const AUDIO_SAMPLE_RATE: u32 = 44100;
 
-fn initialize_sdl_components() -> ([u8; 3072], ThreadRng, WindowCanvas, EventPump) {
-    let screen_state = [0 as u8; 32 * 3 * 32];
-    let rng = rand::thread_rng();
+// NTSC runs 29780.5 CPU cycles per frame, not the 60Hz most monitors refresh at
+pub const FRAME_RATE: f64 = 60.0988;
+pub const DEFAULT_SCALE: u32 = 3;
+// a snapshot every few frames keeps the replay when stepping back short
+const REWIND_INTERVAL: usize = 5;
+const REWIND_BUDGET: usize = 64 * 1024 * 1024;
 
-    let sdl_context = sdl2::init().unwrap();
-    let video_subsystem = sdl_context.video().unwrap();
+// Plays a game in a window of `scale` times the NES resolution until it's closed.
+// F5 saves to the selected quick slot, F7 loads it back and F6 picks the next slot.
+// Holding Backspace plays the game backwards and holding Tab fast-forwards. F9 switches between
+// 100%, 50% and 25% speed, F10 pauses and F11 advances a single frame. F12 saves a screenshot
+// and F8 starts or stops recording a video. F1 presses the reset button and F2 switches the
+// console off and on again, with `ram_init` in its RAM. F3 ejects the disk of a Famicom Disk
+// System game, pressed again it puts in the next side, and what was written to the disk is saved
+// when the window closes.
+// A `playback` movie drives the joypads until it ends, after which the player takes over. Everything
+// played from power-on is written to `movie_path` as a movie when the window closes.
+pub fn run(
+    game: Game,
+    rom_path: &Path,
+    scale: u32,
+    ram_init: RamInit,
+    playback: Option<Movie>,
+    movie_path: Option<&Path>,
+) -> Result<(), String> {
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
     let window = video_subsystem
-        .window("Snake game", (32.0 * 10.0) as u32, (32.0 * 10.0) as u32)
+        .window("NES", Frame::WIDTH as u32 * scale, Frame::HEIGHT as u32 * scale)
         .position_centered()
         .build()
-        .unwrap();
+        .map_err(|e| e.to_string())?;
 
-    let mut canvas = window.into_canvas().present_vsync().build().unwrap();
-    let event_pump = sdl_context.event_pump().unwrap();
-    canvas.set_scale(10.0, 10.0).unwrap();
+    // no vsync, frames are paced to the NES rate below
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let creator = canvas.texture_creator();
+    let mut texture = creator
+        .create_texture_streaming(PixelFormatEnum::RGB24, Frame::WIDTH as u32, Frame::HEIGHT as u32)
+        .map_err(|e| e.to_string())?;
+    let audio = open_audio(&sdl_context)?;
+    let mut event_pump = sdl_context.event_pump()?;
 
-    (screen_state, rng, canvas, event_pump)
-}
+    let config = InputConfig::load_or_create(Path::new(INPUT_CONFIG_PATH))?;
+    let mut input = InputMapping::new(&config, sdl_context.game_controller()?)?;
+    let mut device_input = DeviceInput::new(scale);
+
+    let mut setup = game.default_input_setup();
+    if let Some(movie) = &playback {
+        setup = movie.input_setup();
+    }
+    let mut recorded_movie = match movie_path {
+        Some(_) if !matches!(setup, InputSetup::Joypads | InputSetup::FourScore) => {
+            return Err("Movies can only be recorded with joypads or a Four Score".to_string());
+        }
+        Some(path) => {
+            let rom_filename = rom_path.file_name().unwrap_or_default().to_string_lossy();
+            let mut movie = Movie::new(&rom_filename, game.checksum(), setup == InputSetup::FourScore);
+            if let Some(played) = &playback {
+                movie.rerecord_count = played.rerecord_count;
+            }
+            Some((movie, path))
+        }
+        None => None,
+    };
+    let mut disk = game.disk_drive();
+    let mut cpu = CPU::new(game.into_bus()?);
+    let devices = InputDevices::connect(setup, &mut cpu.bus);
+    cpu.bus.apu().set_sample_rate(AUDIO_SAMPLE_RATE);
+    cpu.bus.set_ram_init(ram_init);
+    cpu.power_on();
 
-fn handle_user_input(cpu: &mut CPU, event_pump: &mut EventPump) {
-    for event in event_pump.poll_iter() {
-        match event {
-            Event::Quit { .. }
-            | Event::KeyDown {
-                keycode: Some(Keycode::Escape),
-                ..
-            } => std::process::exit(0),
-            Event::KeyDown {
-                keycode: Some(Keycode::W),
-                ..
-            } => {
-                cpu.mem_write(0xff, 0x77);
+    let mut quick_slots = QuickSlots::new(rom_path);
+    let mut rewind = Rewind::new(REWIND_INTERVAL, REWIND_BUDGET);
+    let mut rewinding = false;
+    let mut speed = SpeedControl::new();
+    let mut recording: Option<AviWriter<BufWriter<File>>> = None;
+    // reset and power presses wait for the next frame, so a movie can record them
+    let mut commands = 0;
+
+    let frame_duration = Duration::from_secs_f64(1.0 / FRAME_RATE);
+    let mut next_frame = Instant::now();
+    let mut last_present = next_frame;
+    loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => {
+                    if let Some(recording) = recording.take() {
+                        stop_recording(recording);
+                    }
+                    return save_on_exit(recorded_movie, disk.as_ref());
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::F8),
+                    repeat: false,
+                    ..
+                } => {
+                    let title = match recording.take() {
+                        Some(finished) => stop_recording(finished),
+                        None => {
+                            let path = screenshot::next_path(rom_path, "avi");
+                            match AviWriter::create(&path, AUDIO_SAMPLE_RATE) {
+                                Ok(started) => {
+                                    recording = Some(started);
+                                    format!("NES - recording {}", path.file_name().unwrap_or_default().to_string_lossy())
+                                }
+                                Err(message) => {
+                                    eprintln!("{}", message);
+                                    "NES - recording failed".to_string()
+                                }
+                            }
+                        }
+                    };
+                    canvas.window_mut().set_title(&title).map_err(|e| e.to_string())?;
+                }
+                Event::KeyDown {
+                    keycode: Some(key @ (Keycode::F5 | Keycode::F6 | Keycode::F7)),
+                    repeat: false,
+                    ..
+                } => {
+                    let result = match key {
+                        Keycode::F5 => quick_slots.save(&cpu).map(|_| "saved"),
+                        // a movie has to be one unbroken run from power-on
+                        Keycode::F7 if recorded_movie.is_some() => Err("Can't load a state while recording a movie".to_string()),
+                        Keycode::F7 => quick_slots.load(&mut cpu).map(|_| {
+                            rewind.clear();
+                            "loaded"
+                        }),
+                        _ => {
+                            quick_slots.next_slot();
+                            Ok("selected")
+                        }
+                    };
+                    // there's no on screen display, the title bar tells what happened
+                    let title = match result {
+                        Ok(action) => format!("NES - slot {} {}", quick_slots.slot(), action),
+                        Err(message) => {
+                            eprintln!("{}", message);
+                            format!("NES - slot {} failed", quick_slots.slot())
+                        }
+                    };
+                    canvas.window_mut().set_title(&title).map_err(|e| e.to_string())?;
+                }
+                Event::KeyDown {
+                    keycode: Some(key @ (Keycode::F9 | Keycode::F10 | Keycode::F11)),
+                    repeat: false,
+                    ..
+                } => {
+                    match key {
+                        Keycode::F9 => speed.next_slow_motion(),
+                        Keycode::F10 => speed.toggle_pause(),
+                        _ => speed.advance_frame(),
+                    }
+                    let title = format!("NES - {}", speed.describe());
+                    canvas.window_mut().set_title(&title).map_err(|e| e.to_string())?;
+                }
+                Event::KeyDown {
+                    keycode: Some(key @ (Keycode::F1 | Keycode::F2)),
+                    repeat: false,
+                    ..
+                } => {
+                    commands |= if key == Keycode::F1 { SOFT_RESET } else { POWER };
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::F3),
+                    repeat: false,
+                    ..
+                } if disk.is_some() => {
+                    if let Some(disk) = &mut disk {
+                        let title = format!("NES - {}", disk.switch_side()?);
+                        canvas.window_mut().set_title(&title).map_err(|e| e.to_string())?;
+                    }
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::F12),
+                    repeat: false,
+                    ..
+                } => {
+                    let title = match screenshot::save(rom_path, cpu.bus.ppu().frame(), scale as usize) {
+                        Ok(path) => format!("NES - saved {}", path.file_name().unwrap_or_default().to_string_lossy()),
+                        Err(message) => {
+                            eprintln!("{}", message);
+                            "NES - screenshot failed".to_string()
+                        }
+                    };
+                    canvas.window_mut().set_title(&title).map_err(|e| e.to_string())?;
+                }
+                _ => {
+                    match event {
+                        Event::KeyDown {
+                            keycode: Some(Keycode::Backspace),
+                            ..
+                        } => rewinding = true,
+                        Event::KeyUp {
+                            keycode: Some(Keycode::Backspace),
+                            ..
+                        } => rewinding = false,
+                        Event::KeyDown {
+                            keycode: Some(Keycode::Tab),
+                            ..
+                        } => speed.set_fast_forward(true),
+                        Event::KeyUp {
+                            keycode: Some(Keycode::Tab),
+                            ..
+                        } => speed.set_fast_forward(false),
+                        _ => {}
+                    }
+                    input.handle_event(&event);
+                    device_input.handle_event(&event, &devices);
+                }
+            }
+        }
+
+        let run_frame = speed.run_frame() && !rewinding;
+        if rewinding {
+            // the picture stays on the oldest frame once the history runs out
+            rewind.step_back(&mut cpu, &devices);
+            cpu.bus.apu().take_samples();
+        } else if run_frame {
+            device_input.update(&input, &devices);
+            let frame = cpu.bus.ppu().frame_count();
+            if let Some(played) = playback.as_ref().filter(|played| frame < played.len()) {
+                played.apply(frame, &mut cpu, &devices)?;
+                if frame + 1 == played.len() {
+                    canvas.window_mut().set_title("NES - movie finished").map_err(|e| e.to_string())?;
+                }
+            }
+            if commands & POWER != 0 {
+                cpu.power_on();
+            } else if commands & SOFT_RESET != 0 {
+                cpu.reset();
             }
-            Event::KeyDown {
-                keycode: Some(Keycode::S),
-                ..
-            } => {
-                cpu.mem_write(0xff, 0x73);
+            if let Some((movie, _)) = &mut recorded_movie {
+                let mut buttons = [0; PLAYERS];
+                for (player, held) in buttons.iter_mut().enumerate() {
+                    *held = devices.joypad_buttons(player);
+                }
+                let played = playback.as_ref().and_then(|played| played.frame(frame)).map_or(0, |input| input.commands);
+                movie.record(frame, MovieFrame { commands: played | commands, buttons });
             }
-            Event::KeyDown {
-                keycode: Some(Keycode::A),
-                ..
-            } => {
-                cpu.mem_write(0xff, 0x61);
+            commands = 0;
+            rewind.record(&cpu, &devices);
+            if !cpu.run_frame() {
+                if let Some(recording) = recording.take() {
+                    stop_recording(recording);
+                }
+                save_on_exit(recorded_movie, disk.as_ref())?;
+                return Err(format!("CPU hit BRK at {:04X}", cpu.program_counter.wrapping_sub(1)));
             }
-            Event::KeyDown {
-                keycode: Some(Keycode::D),
-                ..
-            } => {
-                cpu.mem_write(0xff, 0x64);
+        }
+
+        let current_speed = speed.speed();
+        // fast-forward shows a frame now and then instead of waiting on every present
+        if current_speed != Speed::FastForward || last_present.elapsed() >= frame_duration {
+            texture
+                .update(None, &cpu.bus.ppu().frame().data, Frame::WIDTH * 3)
+                .map_err(|e| e.to_string())?;
+            canvas.copy(&texture, None, None)?;
+            canvas.present();
+            last_present = Instant::now();
+        }
+        let samples = cpu.bus.apu().take_samples();
+        // the video keeps the NES frame rate whatever speed the game is played at
+        if let (true, Some(writer)) = (run_frame, &mut recording) {
+            if let Err(message) = writer.add_frame(cpu.bus.ppu().frame(), &samples) {
+                eprintln!("{}", message);
+                let title = stop_recording(recording.take().unwrap());
+                canvas.window_mut().set_title(&title).map_err(|e| e.to_string())?;
             }
-            _ => { /* do nothing */ }
+        }
+        audio.queue(&current_speed.stretch_audio(&samples));
+
+        let now = Instant::now();
+        match current_speed.frame_duration(frame_duration) {
+            Some(duration) => {
+                next_frame += duration;
+                if next_frame > now {
+                    thread::sleep(next_frame - now);
+                } else if now - next_frame > duration * 5 {
+                    // too far behind to catch up, start pacing again from here
+                    next_frame = now;
+                }
+            }
+            None => next_frame = now,
         }
     }
 }
 
-fn color(byte: u8) -> Color {
-    match byte {
-        0 => Color::BLACK,
-        1 => Color::WHITE,
-        2 | 9 => Color::GREY,
-        3 | 10 => Color::RED,
-        4 | 11 => Color::GREEN,
-        5 | 12 => Color::BLUE,
-        6 | 13 => Color::MAGENTA,
-        7 | 14 => Color::YELLOW,
-        _ => Color::CYAN,
+// Both get written even when one of them fails
+fn save_on_exit(movie: Option<(Movie, &Path)>, disk: Option<&DiskDrive>) -> Result<(), String> {
+    let disk_saved = disk.map_or(Ok(()), |disk| disk.save_changes());
+    save_movie(movie)?;
+    disk_saved
+}
+
+fn save_movie(movie: Option<(Movie, &Path)>) -> Result<(), String> {
+    match movie {
+        Some((movie, path)) => {
+            std::fs::write(path, movie.to_fm2()).map_err(|e| format!("Can't write {}: {}", path.display(), e))
+        }
+        None => Ok(()),
     }
 }
 
-fn read_screen_state(cpu: &mut CPU, frame: &mut [u8; 32 * 3 * 32]) -> bool {
-    let mut frame_idx = 0;
-    let mut update = false;
-    for i in 0x0200..0x600 {
-        let color_idx = cpu.mem_read(i as u16);
-        let (b1, b2, b3) = color(color_idx).rgb();
-        if frame[frame_idx] != b1 || frame[frame_idx + 1] != b2 || frame[frame_idx + 2] != b3 {
-            frame[frame_idx] = b1;
-            frame[frame_idx + 1] = b2;
-            frame[frame_idx + 2] = b3;
-            update = true;
+// Finishes the file and tells how it went, for the title bar
+fn stop_recording(recording: AviWriter<BufWriter<File>>) -> String {
+    let frames = recording.frames();
+    match recording.finish() {
+        Ok(_) => format!("NES - recorded {} frames", frames),
+        Err(message) => {
+            eprintln!("{}", message);
+            "NES - recording failed".to_string()
         }
-        frame_idx += 3;
     }
-    update
+}
+
+fn open_audio(sdl_context: &Sdl) -> Result<AudioQueue<f32>, String> {
+    let audio_subsystem = sdl_context.audio()?;
+    let spec = AudioSpecDesired {
+        freq: Some(AUDIO_SAMPLE_RATE as i32),
+        channels: Some(1),
+        samples: None,
+    };
+    let queue = audio_subsystem.open_queue::<f32, _>(None, &spec)?;
+    queue.resume();
+    Ok(queue)
 }
@@ -1,27 +1,31 @@
-﻿use crate::components::bus::Bus;
-use crate::components::cartridge::Rom;
-use crate::components::cpu::{AddressingMode, CPU};
-use crate::components::mem::Mem;
+﻿use crate::components::cpu::{AddressingMode, CPU};
 use crate::components::opcodes::{OpCode};
+use crate::roms::game::Game;
+
+// Prints a nestest.log style line for every instruction, starting from `start_pc` instead of
+// the reset vector when given, and stopping once `max_cycles` CPU cycles have passed
+pub fn run(game: Game, start_pc: Option<u16>, max_cycles: Option<usize>) -> Result<(), String> {
+    let mut cpu = CPU::new(game.into_bus()?);
+    cpu.power_on();
+    if let Some(pc) = start_pc {
+        cpu.program_counter = pc;
+    }
 
-pub fn run() {
-    let game_code = std::fs::read("nestest.nes").unwrap();
-    let rom = Rom::new(&game_code).unwrap();
-    let bus = Bus::new(rom);
-    let mut cpu = CPU::new(bus);
-    cpu.reset();
-    cpu.program_counter = 0xC000; // set this for the code to initialize properly
-
-    cpu.run_with_callback(move |cpu| {
+    cpu.run_while(move |cpu| {
+        if max_cycles.is_some_and(|max| cpu.bus.cycles() >= max) {
+            return false;
+        }
         println!("{}",mytrace(cpu));
+        true
     });
+    Ok(())
 }
 
 fn mytrace(cpu: &mut CPU) ->String{
     let pc = cpu.program_counter;
     let pc_hex = format!("{:04X}{}", pc, generate_padding(2));
 
-    let code = cpu.mem_read(cpu.program_counter);
+    let code = cpu.bus.peek(cpu.program_counter);
     let op_code_data = cpu.op_codes.get(code);
     let mut operands = get_machine_code(cpu, pc, op_code_data.bytes as u16);
 
@@ -72,7 +76,7 @@ fn get_command_assembly(cpu: &mut CPU, op_code: OpCode, machine_code: &mut Vec<u
         AddressingMode::Immediate | AddressingMode::NoneAddressing => (0, 0),
         _ => {
             let addr = cpu.get_absolute_address(&op_code.addressing_mode, begin + 1);
-            (addr, cpu.mem_read(addr))
+            (addr, cpu.bus.peek(addr))
         }
     };
 
@@ -82,7 +86,7 @@ fn get_command_assembly(cpu: &mut CPU, op_code: OpCode, machine_code: &mut Vec<u
             format!(" #${:02X}", machine_code_to_work_on[0])
         }
         (AddressingMode::ZeroPage, _) => {
-            format!(" ${:02X} = {:02X}", machine_code_to_work_on[0], cpu.mem_read(machine_code_to_work_on[0] as u16))
+            format!(" ${:02X} = {:02X}", machine_code_to_work_on[0], cpu.bus.peek(machine_code_to_work_on[0] as u16))
         }
         (AddressingMode::ZeroPage_X, _) => {
             format!(" ${:02X},X @ {:02} = #${:02X}", machine_code_to_work_on[0], mem_addr, stored_value)
@@ -149,7 +153,7 @@ fn get_machine_code(cpu: &mut CPU, program_counter: u16, operand_count: u16) ->
     let mut operands = vec![];
 
     for i in 0..operand_count {
-        operands.push(cpu.mem_read(program_counter + i));
+        operands.push(cpu.bus.peek(program_counter + i));
     }
 
     return operands;
@@ -0,0 +1,18 @@
+use std::path::{Path, PathBuf};
+
+use crate::components::frame::Frame;
+
+// Captures go next to the ROM as game-000.png, game-001.png and so on, never over an older one
+pub fn next_path(rom_path: &Path, extension: &str) -> PathBuf {
+    let stem = rom_path.file_stem().map_or("screenshot".into(), |stem| stem.to_string_lossy());
+    (0..)
+        .map(|number| rom_path.with_file_name(format!("{}-{:03}.{}", stem, number, extension)))
+        .find(|path| !path.exists())
+        .unwrap()
+}
+
+pub fn save(rom_path: &Path, frame: &Frame, scale: usize) -> Result<PathBuf, String> {
+    let path = next_path(rom_path, "png");
+    std::fs::write(&path, frame.to_png_scaled(scale)).map_err(|e| format!("Can't write {}: {}", path.display(), e))?;
+    Ok(path)
+}
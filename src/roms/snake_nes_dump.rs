@@ -1,5 +0,0 @@
-﻿use crate::roms::runner;
-pub fn run() {
-    let game_code=std::fs::read("snake.nes").unwrap();
-    runner::run(game_code);
-}
\ No newline at end of file
@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Speed {
+    Normal,
+    Half,
+    Quarter,
+    // as fast as the host runs, only some of the frames are shown
+    FastForward,
+}
+
+impl Speed {
+    // How long a frame stays on screen, None when frames aren't paced at all
+    pub fn frame_duration(self, normal: Duration) -> Option<Duration> {
+        match self {
+            Speed::Normal => Some(normal),
+            Speed::Half => Some(normal * 2),
+            Speed::Quarter => Some(normal * 4),
+            Speed::FastForward => None,
+        }
+    }
+
+    // Slow motion stretches the sound without lowering its pitch, see stretch.
+    // Fast-forward is muted, the queue could never keep up with it.
+    pub fn stretch_audio(self, samples: &[f32]) -> Vec<f32> {
+        match self {
+            Speed::Normal => samples.to_vec(),
+            Speed::Half => stretch(samples, 2),
+            Speed::Quarter => stretch(samples, 4),
+            Speed::FastForward => vec![],
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            Speed::Normal => "100%",
+            Speed::Half => "50%",
+            Speed::Quarter => "25%",
+            Speed::FastForward => "fast-forward",
+        }
+    }
+}
+
+// Overlap-add with waveform similarity (WSOLA): short windowed grains of the sound are laid out
+// `factor` times further apart than they were read, each one picked within a few samples of where
+// it's due so that it lines up with the one before it. The grains keep their pitch, the sound
+// just lasts longer.
+fn stretch(samples: &[f32], factor: usize) -> Vec<f32> {
+    const GRAIN: usize = 256;
+
+    let grain = GRAIN.min(samples.len() / 2 * 2);
+    if grain == 0 {
+        return samples.iter().flat_map(|sample| std::iter::repeat_n(*sample, factor)).collect();
+    }
+    let hop = grain / 2;
+    let seek = hop / 2;
+    let last_start = samples.len() - grain;
+    // sin² windows half a grain apart add up to 1
+    let window: Vec<f32> = (0..grain)
+        .map(|i| (std::f32::consts::PI * (i as f32 + 0.5) / grain as f32).sin().powi(2))
+        .collect();
+
+    let length = samples.len() * factor;
+    let mut output = vec![0.0; length];
+    let mut weights = vec![0.0; length];
+    let mut previous: Option<usize> = None;
+    for position in (0..length).step_by(hop) {
+        // the whole input gets read, the last grain starts where the last full one fits
+        let due = position * last_start / length;
+        let start = match previous {
+            Some(previous) => {
+                // what would have followed the previous grain, the next one should sound like it
+                let follows = &samples[(previous + hop).min(last_start)..][..hop];
+                (due.saturating_sub(seek)..=(due + seek).min(last_start))
+                    .max_by(|a, b| {
+                        let similarity = |start: usize| -> f32 {
+                            follows.iter().zip(&samples[start..start + hop]).map(|(x, y)| x * y).sum()
+                        };
+                        similarity(*a).total_cmp(&similarity(*b))
+                    })
+                    .unwrap_or(due)
+            }
+            None => due,
+        };
+        for (i, weight) in window.iter().enumerate().take(length - position) {
+            output[position + i] += samples[start + i] * weight;
+            weights[position + i] += weight;
+        }
+        previous = Some(start);
+    }
+    output.iter().zip(weights).map(|(sample, weight)| sample / weight).collect()
+}
+
+// What the speed hotkeys asked for. Fast-forward only lasts while its key is held and wins over
+// slow motion, frame advance pauses and then lets a single frame through.
+pub struct SpeedControl {
+    slow_motion: Speed,
+    fast_forward: bool,
+    paused: bool,
+    advance: bool,
+}
+
+impl Default for SpeedControl {
+    fn default() -> Self {
+        SpeedControl::new()
+    }
+}
+
+impl SpeedControl {
+    pub fn new() -> Self {
+        SpeedControl {
+            slow_motion: Speed::Normal,
+            fast_forward: false,
+            paused: false,
+            advance: false,
+        }
+    }
+
+    // Fast-forward only speeds up a running game, a paused one keeps its frame pacing
+    pub fn speed(&self) -> Speed {
+        if self.fast_forward && !self.paused {
+            Speed::FastForward
+        } else {
+            self.slow_motion
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_fast_forward(&mut self, held: bool) {
+        self.fast_forward = held;
+    }
+
+    // Steps from 100% to 50% to 25% and back to 100%
+    pub fn next_slow_motion(&mut self) {
+        self.slow_motion = match self.slow_motion {
+            Speed::Normal => Speed::Half,
+            Speed::Half => Speed::Quarter,
+            _ => Speed::Normal,
+        };
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.advance = false;
+    }
+
+    pub fn advance_frame(&mut self) {
+        self.paused = true;
+        self.advance = true;
+    }
+
+    // Asked once per pass of the main loop, false keeps the machine where it is
+    pub fn run_frame(&mut self) -> bool {
+        if !self.paused {
+            return true;
+        }
+        std::mem::replace(&mut self.advance, false)
+    }
+
+    // For the title bar
+    pub fn describe(&self) -> String {
+        if self.paused {
+            "paused".to_string()
+        } else {
+            self.speed().describe().to_string()
+        }
+    }
+}
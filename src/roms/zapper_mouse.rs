@@ -0,0 +1,59 @@
+use sdl2::event::{Event, WindowEvent};
+use sdl2::mouse::MouseButton;
+
+use crate::components::zapper::Zapper;
+
+// Aims the Zapper with the mouse cursor, the left button pulls the trigger.
+// The right button fires away from the screen, which some games ask for to reload.
+pub struct ZapperMouse {
+    window_scale: i32,
+    last_aim: Option<(usize, usize)>,
+}
+
+impl ZapperMouse {
+    pub fn new(window_scale: u32) -> Self {
+        ZapperMouse {
+            window_scale: window_scale as i32,
+            last_aim: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event, zapper: &mut Zapper) {
+        match event {
+            Event::MouseMotion { x, y, .. } => {
+                self.aim(*x, *y, zapper);
+            }
+            Event::MouseButtonDown { mouse_btn: MouseButton::Left, x, y, .. } => {
+                self.aim(*x, *y, zapper);
+                zapper.set_trigger(true);
+            }
+            Event::MouseButtonDown { mouse_btn: MouseButton::Right, .. } => {
+                zapper.aim_off_screen();
+                zapper.set_trigger(true);
+            }
+            Event::MouseButtonUp { mouse_btn: MouseButton::Left | MouseButton::Right, .. } => {
+                zapper.set_trigger(false);
+                if let Some((x, y)) = self.last_aim {
+                    zapper.aim_at(x, y);
+                }
+            }
+            Event::Window { win_event: WindowEvent::Leave, .. } => {
+                self.last_aim = None;
+                zapper.aim_off_screen();
+            }
+            _ => { /* do nothing */ }
+        }
+    }
+
+    fn aim(&mut self, x: i32, y: i32, zapper: &mut Zapper) {
+        if x < 0 || y < 0 {
+            self.last_aim = None;
+            zapper.aim_off_screen();
+            return;
+        }
+
+        let aim = ((x / self.window_scale) as usize, (y / self.window_scale) as usize);
+        zapper.aim_at(aim.0, aim.1);
+        self.last_aim = zapper.aim();
+    }
+}
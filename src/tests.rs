@@ -7,4 +7,42 @@ mod cpu_tests;
 
 mod ppu_tests;
 
+mod mapper_tests;
 
+mod nsf_player_tests;
+
+mod joypad_tests;
+
+mod input_config_tests;
+
+mod zapper_tests;
+
+mod multitap_tests;
+
+mod input_device_tests;
+
+mod apu_tests;
+
+mod expansion_audio_tests;
+
+mod frame_tests;
+
+mod headless_tests;
+
+mod input_script_tests;
+
+mod save_state_tests;
+
+mod rewind_tests;
+
+mod speed_tests;
+
+mod avi_tests;
+
+mod movie_tests;
+
+mod power_tests;
+
+mod game_tests;
+
+mod bus_tests;
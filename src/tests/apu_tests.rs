@@ -0,0 +1,141 @@
+use crate::components::bus::Bus;
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+fn test_bus() -> Bus {
+    Bus::new(test_rom(0x8000, None)).unwrap()
+}
+
+#[test]
+fn test_apu_status_reports_length_counters() {
+    let mut bus = test_bus();
+    bus.mem_write(0x4015, 0b0000_0101);
+
+    bus.mem_write(0x4003, 0b0000_1000);
+    bus.mem_write(0x400B, 0b0000_1000);
+    // the noise channel is disabled so its length counter isn't loaded
+    bus.mem_write(0x400F, 0b0000_1000);
+
+    assert_eq!(bus.mem_read(0x4015), 0b0000_0101);
+
+    bus.mem_write(0x4015, 0b0000_0001);
+    assert_eq!(bus.mem_read(0x4015), 0b0000_0001);
+}
+
+#[test]
+fn test_apu_length_counter_runs_out() {
+    let mut bus = test_bus();
+    bus.mem_write(0x4015, 0b0000_0001);
+    // length index 3 loads 2, counted down on half frames
+    bus.mem_write(0x4003, 0b0001_1000);
+
+    for _ in 0..30000 / 250 {
+        bus.tick(250);
+    }
+
+    assert_eq!(bus.mem_read(0x4015) & 1, 0);
+}
+
+#[test]
+fn test_apu_frame_irq() {
+    let mut bus = test_bus();
+
+    for _ in 0..29828 / 4 {
+        bus.tick(4);
+    }
+    assert!(!bus.poll_irq_status());
+
+    bus.tick(4);
+    assert!(bus.poll_irq_status());
+
+    assert_eq!(bus.mem_read(0x4015), 0b0100_0000);
+    assert!(!bus.poll_irq_status());
+}
+
+#[test]
+fn test_apu_frame_irq_inhibited() {
+    let mut bus = test_bus();
+    bus.mem_write(0x4017, 0b0100_0000);
+
+    for _ in 0..40000 / 250 {
+        bus.tick(250);
+    }
+
+    assert!(!bus.poll_irq_status());
+}
+
+#[test]
+fn test_apu_five_step_mode_has_no_irq() {
+    let mut bus = test_bus();
+    bus.mem_write(0x4017, 0b1000_0000);
+
+    for _ in 0..40000 / 250 {
+        bus.tick(250);
+    }
+
+    assert!(!bus.poll_irq_status());
+}
+
+#[test]
+fn test_apu_silent_without_sample_rate() {
+    let mut bus = test_bus();
+    bus.tick(255);
+
+    assert!(bus.apu().take_samples().is_empty());
+}
+
+#[test]
+fn test_apu_samples_at_sample_rate() {
+    let mut bus = test_bus();
+    bus.apu().set_sample_rate(44100);
+
+    // a whole frame
+    for _ in 0..29781 / 3 {
+        bus.tick(3);
+    }
+
+    // the triangle rests at the top of its sequence, leaving a constant level
+    let silence = bus.apu().mix();
+    let samples = bus.apu().take_samples();
+    assert_eq!(samples.len(), 733);
+    assert!(samples.iter().all(|sample| (sample - silence).abs() < 0.0001));
+}
+
+#[test]
+fn test_apu_pulse_mix() {
+    let mut bus = test_bus();
+    bus.apu().set_sample_rate(44100);
+    let silence = bus.apu().mix();
+    bus.mem_write(0x4015, 0b0000_0001);
+    // 50% duty, constant volume 15
+    bus.mem_write(0x4000, 0b1011_1111);
+    bus.mem_write(0x4002, 0xFD);
+    bus.mem_write(0x4003, 0b0000_1000);
+
+    for _ in 0..29781 / 3 {
+        bus.tick(3);
+    }
+
+    let samples: Vec<f32> = bus.apu().take_samples().iter().map(|sample| sample - silence).collect();
+    let max = samples.iter().cloned().fold(0.0, f32::max);
+    let average = samples.iter().sum::<f32>() / samples.len() as f32;
+    let full_volume = 95.88 / (8128.0 / 15.0 + 100.0);
+    assert!((max - full_volume).abs() < 0.001);
+    assert!((average - full_volume / 2.0).abs() < 0.01);
+}
+
+#[test]
+fn test_apu_dmc_plays_sample_from_cartridge() {
+    let mut bus = test_bus();
+    // $C000, one byte, fastest rate with the IRQ enabled
+    bus.mem_write(0x4012, 0);
+    bus.mem_write(0x4013, 0);
+    bus.mem_write(0x4010, 0b1000_1111);
+    bus.mem_write(0x4015, 0b0001_0000);
+
+    assert_eq!(bus.mem_read(0x4015) & 0b0001_0000, 0b0001_0000);
+
+    bus.tick(1);
+    assert_eq!(bus.mem_read(0x4015), 0b1000_0000);
+    assert!(bus.poll_irq_status());
+}
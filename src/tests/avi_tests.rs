@@ -0,0 +1,86 @@
+use std::io::Cursor;
+
+use crate::components::frame::Frame;
+use crate::roms::avi::{AviWriter, FRAME_RATE_DENOMINATOR, FRAME_RATE_NUMERATOR};
+
+fn u32_at(data: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap())
+}
+
+fn record(frames: &[Frame], samples_per_frame: usize) -> Vec<u8> {
+    let mut writer = AviWriter::new(Cursor::new(vec![]), 44100).unwrap();
+    for frame in frames {
+        writer.add_frame(frame, &vec![0.5; samples_per_frame]).unwrap();
+    }
+    assert_eq!(writer.frames(), frames.len() as u32);
+    writer.finish().unwrap().into_inner()
+}
+
+// The chunks of the movi list, found by walking it the way a player does
+fn movi_chunks(avi: &[u8]) -> Vec<([u8; 4], &[u8])> {
+    let movi = avi.windows(4).position(|window| window == b"movi").unwrap();
+    let end = movi + u32_at(avi, movi - 4) as usize;
+    let mut chunks = vec![];
+    let mut pos = movi + 4;
+    while pos < end {
+        let size = u32_at(avi, pos + 4) as usize;
+        chunks.push((avi[pos..pos + 4].try_into().unwrap(), &avi[pos + 8..pos + 8 + size]));
+        pos += 8 + size + size % 2;
+    }
+    assert_eq!(pos, end);
+    assert_eq!(&avi[end..end + 4], b"idx1");
+    chunks
+}
+
+#[test]
+fn test_avi_header_holds_the_lengths() {
+    let avi = record(&[Frame::new(), Frame::new(), Frame::new()], 735);
+
+    assert_eq!(&avi[0..4], b"RIFF");
+    assert_eq!(u32_at(&avi, 4) as usize, avi.len() - 8);
+    assert_eq!(&avi[8..12], b"AVI ");
+    assert_eq!(&avi[24..28], b"avih");
+    assert_eq!(u32_at(&avi, 48), 3);
+    assert_eq!(&avi[108..112], b"vids");
+    assert_eq!(u32_at(&avi, 128), FRAME_RATE_DENOMINATOR);
+    assert_eq!(u32_at(&avi, 132), FRAME_RATE_NUMERATOR);
+    assert_eq!(u32_at(&avi, 140), 3);
+    assert_eq!(&avi[232..236], b"auds");
+    assert_eq!(u32_at(&avi, 264), 3 * 735);
+}
+
+#[test]
+fn test_avi_frames_are_bottom_up_bgr() {
+    let mut frame = Frame::new();
+    frame.set_pixel(0, 0, (1, 2, 3));
+    frame.set_pixel(255, 239, (4, 5, 6));
+
+    let avi = record(&[frame], 10);
+    let chunks = movi_chunks(&avi);
+
+    assert_eq!(&chunks[0].0, b"00db");
+    let bitmap = chunks[0].1;
+    assert_eq!(bitmap.len(), Frame::WIDTH * Frame::HEIGHT * 3);
+    assert_eq!(bitmap[bitmap.len() - Frame::WIDTH * 3..][0..3], [3, 2, 1]);
+    assert_eq!(bitmap[Frame::WIDTH * 3 - 3..Frame::WIDTH * 3], [6, 5, 4]);
+
+    assert_eq!(&chunks[1].0, b"01wb");
+    assert_eq!(chunks[1].1.len(), 20);
+    assert_eq!(i16::from_le_bytes([chunks[1].1[0], chunks[1].1[1]]), i16::MAX / 2);
+}
+
+#[test]
+fn test_avi_index_points_at_every_chunk() {
+    let avi = record(&[Frame::new(), Frame::new()], 3);
+    let movi = avi.windows(4).position(|window| window == b"movi").unwrap();
+    let idx1 = avi.windows(4).rposition(|window| window == b"idx1").unwrap();
+
+    assert_eq!(u32_at(&avi, idx1 + 4), 4 * 16);
+    let ids: Vec<&[u8]> = (0..4)
+        .map(|entry| {
+            let offset = u32_at(&avi, idx1 + 8 + entry * 16 + 8) as usize;
+            &avi[movi + offset..movi + offset + 4]
+        })
+        .collect();
+    assert_eq!(ids, vec![b"00db".as_slice(), b"01wb", b"00db", b"01wb"]);
+}
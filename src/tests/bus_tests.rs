@@ -0,0 +1,175 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::components::bus::Bus;
+use crate::components::cartridge::Mirroring;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::components::joypad::JoypadButton;
+use crate::components::mapper::mmc5::Mmc5;
+use crate::components::mapper::namco163::Namco163;
+use crate::components::mapper::nrom::Nrom;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+#[test]
+fn test_peek_reads_ram_and_the_cartridge() {
+    let mut bus = Bus::new(test_rom(0x8000, None)).unwrap();
+    bus.mem_write(0x0012, 0x34);
+
+    assert_eq!(bus.peek(0x0812), 0x34);
+    assert_eq!(bus.peek(0xFFFC), bus.mem_read(0xFFFC));
+}
+
+#[test]
+fn test_peek_shows_open_bus_for_registers() {
+    let mut bus = Bus::new(test_rom(0x8000, None)).unwrap();
+    bus.mem_write(0x2003, 0x5A);
+
+    // the PPU registers show what's left on the PPU's data bus instead of being read
+    assert_eq!(bus.peek(0x2002), 0x5A);
+    assert_eq!(bus.peek(0x3FFF), 0x5A);
+    assert_eq!(bus.peek(0x4015), 0x40);
+    assert_eq!(bus.peek(0x401F), 0x40);
+}
+
+#[test]
+fn test_write_only_registers_read_open_bus() {
+    let mut bus = Bus::new(test_rom(0x8000, None)).unwrap();
+    bus.mem_write(0x2003, 0x5A);
+
+    assert_eq!(bus.mem_read(0x2000), 0x5A);
+    assert_eq!(bus.mem_read(0x2006), 0x5A);
+    assert_eq!(bus.mem_read(0x3FF8), 0x5A);
+    // PPUSTATUS only drives its top three bits
+    assert_eq!(bus.mem_read(0x2002) & 0x1F, 0x1A);
+    assert_eq!(bus.mem_read(0x4000), 0x40);
+    assert_eq!(bus.mem_read(0x4014), 0x40);
+    assert_eq!(bus.mem_read(0x401F), 0x40);
+}
+
+#[test]
+fn test_writes_to_the_test_registers_are_ignored() {
+    let mut bus = Bus::new(test_rom(0x8000, None)).unwrap();
+    bus.mem_write(0x4018, 0xFF);
+    bus.mem_write(0x401F, 0xFF);
+
+    assert_eq!(bus.mem_read(0x4018), 0x40);
+}
+
+#[test]
+fn test_peek_leaves_the_controllers_alone() {
+    let mut bus = Bus::new(test_rom(0x8000, None)).unwrap();
+    let devices = InputDevices::connect(InputSetup::Joypads, &mut bus);
+    devices.joypads[0].borrow_mut().set_button_pressed_status(JoypadButton::A, true);
+    bus.mem_write(0x4016, 1);
+    bus.mem_write(0x4016, 0);
+
+    bus.peek(0x4016);
+    bus.peek(0x4016);
+
+    assert_eq!(bus.mem_read(0x4016), 0x41);
+}
+
+#[test]
+fn test_peek_leaves_mapper_registers_alone() {
+    let mmc5 = Rc::new(RefCell::new(Mmc5::new(banked_rom(5, 16, 64))));
+    let mut bus = Bus::with_mapper(mmc5.clone());
+    bus.mem_write(0x5203, 1);
+    bus.mem_write(0x5204, 0x80);
+    mmc5.borrow_mut().ppu_scanline(0, true);
+    mmc5.borrow_mut().ppu_scanline(1, true);
+    assert!(mmc5.borrow().irq_pending());
+
+    assert_eq!(bus.peek(0x5204), 0xC0);
+    assert_eq!(bus.peek(0x5204), 0xC0);
+    assert!(mmc5.borrow().irq_pending());
+
+    assert_eq!(bus.mem_read(0x5204), 0xC0);
+    assert!(!mmc5.borrow().irq_pending());
+}
+
+#[test]
+fn test_peek_keeps_the_namco163_sound_address() {
+    let namco163 = Rc::new(RefCell::new(Namco163::new(banked_rom(19, 16, 32)).unwrap()));
+    let mut bus = Bus::with_mapper(namco163);
+    bus.mem_write(0xF800, 0x80 | 0x10);
+    bus.mem_write(0x4800, 0x11);
+    bus.mem_write(0x4800, 0x22);
+    bus.mem_write(0xF800, 0x80 | 0x10);
+
+    assert_eq!(bus.peek(0x4800), 0x11);
+    assert_eq!(bus.peek(0x4800), 0x11);
+
+    assert_eq!(bus.mem_read(0x4800), 0x11);
+    assert_eq!(bus.mem_read(0x4800), 0x22);
+}
+
+// A sound chip whose output rises by a thousandth on every cycle
+struct RampingAudioMapper {
+    nrom: Nrom,
+    cycles: u32,
+}
+
+impl Mapper for RampingAudioMapper {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        self.nrom.peek_prg(addr)
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        self.nrom.write_prg(addr, data)
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.nrom.read_chr(addr)
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        self.nrom.write_chr(addr, data)
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.nrom.mirroring()
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        self.cycles += cycles as u32;
+    }
+
+    fn power_on(&mut self) {
+        self.cycles = 0;
+    }
+
+    fn expansion_audio(&self) -> f32 {
+        self.cycles as f32 / 1000.0
+    }
+}
+
+impl SaveState for RampingAudioMapper {
+    fn save_state(&self, _state: &mut StateWriter) {}
+
+    fn load_state(&mut self, _state: &mut StateReader) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn first_sample(mapper: impl Mapper + 'static) -> f32 {
+    let mut bus = Bus::with_mapper(Rc::new(RefCell::new(mapper)));
+    bus.apu().set_sample_rate(44100);
+    bus.tick(41);
+    let samples = bus.apu().take_samples();
+    assert_eq!(samples.len(), 1);
+    samples[0]
+}
+
+#[test]
+fn test_expansion_audio_is_mixed_in_on_every_cycle() {
+    let apu_only = first_sample(Nrom::new(test_rom(0x8000, None)));
+    let with_audio = first_sample(RampingAudioMapper { nrom: Nrom::new(test_rom(0x8000, None)), cycles: 0 });
+
+    // the first sample averages the 41 cycles it took, the chip was at 1 to 41 thousandths in those
+    let expansion_audio = with_audio - apu_only;
+    assert!((expansion_audio - 0.021).abs() < 0.0001, "expansion audio is {}", expansion_audio);
+}
@@ -3,4 +3,21 @@ use crate::components::cpu::CPU;
 use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 mod legal_op_codes;
-mod illegal_op_codes;
\ No newline at end of file
+mod illegal_op_codes;
+
+#[test]
+fn test_run_while_stops_when_callback_returns_false() {
+    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)).unwrap());
+    cpu.load(vec![0xE8, 0xE8, 0xE8, 0xE8]); // INX x4
+    cpu.reset();
+
+    let mut calls = 0;
+    cpu.run_while(|_| {
+        calls += 1;
+        calls <= 2
+    });
+
+    assert_eq!(calls, 3);
+    assert_eq!(cpu.register_x, 2);
+    assert_eq!(cpu.program_counter, 0x0602);
+}
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x9f_ahx_absolute_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos: u16 = 0x1000;
@@ -37,7 +37,7 @@ fn test_0x9f_ahx_absolute_y() {
 
 #[test]
 fn test_0x93_ahx_indirect_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x4b_alr_accumulator() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x50;
@@ -24,7 +24,7 @@ fn test_0x4b_alr_accumulator() {
 
 #[test]
 fn test_0x4b_alr_accumulator_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x53;
@@ -41,7 +41,7 @@ fn test_0x4b_alr_accumulator_carry_flag() {
 
 #[test]
 fn test_0x4b_alr_accumulator_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x01;
@@ -59,7 +59,7 @@ fn test_0x4b_alr_accumulator_zero_flag() {
 
 #[test]
 fn test_0x4b_alr_accumulator_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x01;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x0b_anc_immediate() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0x03;
     let accum_value = 0x01;
@@ -23,7 +23,7 @@ fn test_0x0b_anc_immediate() {
 
 #[test]
 fn test_0x0b_anc_immediate_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0x02;
     let accum_value = 0x01;
@@ -40,7 +40,7 @@ fn test_0x0b_anc_immediate_zero_flag() {
 
 #[test]
 fn test_0x0b_anc_immediate_carry_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0xF2;
     let accum_value = 0xF1;
@@ -57,7 +57,7 @@ fn test_0x0b_anc_immediate_carry_negative_flag() {
 
 #[test]
 fn test_0x2b_anc_immediate() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0x03;
     let accum_value = 0x01;
@@ -73,7 +73,7 @@ fn test_0x2b_anc_immediate() {
 
 #[test]
 fn test_0x2b_anc_immediate_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0x02;
     let accum_value = 0x01;
@@ -90,7 +90,7 @@ fn test_0x2b_anc_immediate_zero_flag() {
 
 #[test]
 fn test_0x2b_anc_immediate_carry_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0xF2;
     let accum_value = 0xF1;
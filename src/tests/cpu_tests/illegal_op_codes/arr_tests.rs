@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x6b_arr_immediate() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0x0F;
     let accum_value = 0x03;
@@ -23,7 +23,7 @@ fn test_0x6b_arr_immediate() {
 
 #[test]
 fn test_0x6b_arr_immediate_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0x01;
     let accum_value = 0x02;
@@ -41,7 +41,7 @@ fn test_0x6b_arr_immediate_zero_flag() {
 
 #[test]
 fn test_0x6b_arr_immediate_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0b1100_0000;
     let accum_value = 0xFF;
@@ -59,7 +59,7 @@ fn test_0x6b_arr_immediate_carry_flag() {
 
 #[test]
 fn test_0x6b_arr_immediate_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0b0100_0000;
     let accum_value = 0xFF;
@@ -77,7 +77,7 @@ fn test_0x6b_arr_immediate_overflow_flag() {
 
 #[test]
 fn test_0x6b_arr_immediate_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 0x0;
     let accum_value = 0x0;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xcb_axs_immediate_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 0x80;
@@ -36,7 +36,7 @@ fn test_0xcb_axs_immediate_carry_flag() {
 
 #[test]
 fn test_0xcb_axs_immediate_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 0x80;
@@ -65,7 +65,7 @@ fn test_0xcb_axs_immediate_zero_flag() {
 
 #[test]
 fn test_0xcb_axs_immediate_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 0x80;
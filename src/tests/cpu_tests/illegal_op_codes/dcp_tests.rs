@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xc7_dcp_zero_page_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -27,7 +27,7 @@ fn test_0xc7_dcp_zero_page_zero_flag() {
 
 #[test]
 fn test_0xc7_dcp_zero_page_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -47,7 +47,7 @@ fn test_0xc7_dcp_zero_page_negative_flag() {
 
 #[test]
 fn test_0xc7_dcp_zero_page_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -67,7 +67,7 @@ fn test_0xc7_dcp_zero_page_carry_flag() {
 
 #[test]
 fn test_0xc7_dcp_zero_page_no_flags() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -87,7 +87,7 @@ fn test_0xc7_dcp_zero_page_no_flags() {
 
 #[test]
 fn test_0xd7_dcp_zero_page_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -114,7 +114,7 @@ fn test_0xd7_dcp_zero_page_x_zero_flag() {
 
 #[test]
 fn test_0xd7_dcp_zero_page_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -141,7 +141,7 @@ fn test_0xd7_dcp_zero_page_x_negative_flag() {
 
 #[test]
 fn test_0xd7_dcp_zero_page_x_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -168,7 +168,7 @@ fn test_0xd7_dcp_zero_page_x_carry_flag() {
 
 #[test]
 fn test_0xd7_dcp_zero_page_x_no_flags() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -195,7 +195,7 @@ fn test_0xd7_dcp_zero_page_x_no_flags() {
 
 #[test]
 fn test_0xcf_dcp_absolute_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -222,7 +222,7 @@ fn test_0xcf_dcp_absolute_zero_flag() {
 
 #[test]
 fn test_0xcf_dcp_absolute_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -249,7 +249,7 @@ fn test_0xcf_dcp_absolute_negative_flag() {
 
 #[test]
 fn test_0xcf_dcp_absolute_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -276,7 +276,7 @@ fn test_0xcf_dcp_absolute_carry_flag() {
 
 #[test]
 fn test_0xcf_dcp_absolute_no_flags() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -303,7 +303,7 @@ fn test_0xcf_dcp_absolute_no_flags() {
 
 #[test]
 fn test_0xdf_dcp_absolute_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -331,7 +331,7 @@ fn test_0xdf_dcp_absolute_x_zero_flag() {
 
 #[test]
 fn test_0xdf_dcp_absolute_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -359,7 +359,7 @@ fn test_0xdf_dcp_absolute_x_negative_flag() {
 
 #[test]
 fn test_0xdf_dcp_absolute_x_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -387,7 +387,7 @@ fn test_0xdf_dcp_absolute_x_carry_flag() {
 
 #[test]
 fn test_0xdf_dcp_absolute_x_no_flags() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -415,7 +415,7 @@ fn test_0xdf_dcp_absolute_x_no_flags() {
 
 #[test]
 fn test_0xdb_dcp_absolute_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -443,7 +443,7 @@ fn test_0xdb_dcp_absolute_y_zero_flag() {
 
 #[test]
 fn test_0xdb_dcp_absolute_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -471,7 +471,7 @@ fn test_0xdb_dcp_absolute_y_negative_flag() {
 
 #[test]
 fn test_0xdb_dcp_absolute_y_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -500,7 +500,7 @@ fn test_0xdb_dcp_absolute_y_carry_flag() {
 
 #[test]
 fn test_0xdb_dcp_absolute_y_no_flags() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -529,7 +529,7 @@ fn test_0xdb_dcp_absolute_y_no_flags() {
 
 #[test]
 fn test_0xc3_dcp_indirect_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -558,7 +558,7 @@ fn test_0xc3_dcp_indirect_x_zero_flag() {
 
 #[test]
 fn test_0xc3_dcp_indirect_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -587,7 +587,7 @@ fn test_0xc3_dcp_indirect_x_negative_flag() {
 
 #[test]
 fn test_0xc3_dcp_indirect_x_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -616,7 +616,7 @@ fn test_0xc3_dcp_indirect_x_carry_flag() {
 
 #[test]
 fn test_0xc3_dcp_indirect_x_no_flags() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -645,7 +645,7 @@ fn test_0xc3_dcp_indirect_x_no_flags() {
 
 #[test]
 fn test_0xd3_dcp_indirect_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -677,7 +677,7 @@ fn test_0xd3_dcp_indirect_y_zero_flag() {
 
 #[test]
 fn test_0xd3_dcp_indirect_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -709,7 +709,7 @@ fn test_0xd3_dcp_indirect_y_negative_flag() {
 
 #[test]
 fn test_0xd3_dcp_indirect_y_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -741,7 +741,7 @@ fn test_0xd3_dcp_indirect_y_carry_flag() {
 
 #[test]
 fn test_0xd3_dcp_indirect_y_no_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xe7_sbc_zero_page() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -36,7 +36,7 @@ fn test_0xe7_sbc_zero_page() {
 
 #[test]
 fn test_0xe7_sbc_zero_page_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -58,7 +58,7 @@ fn test_0xe7_sbc_zero_page_zero_flag() {
 
 #[test]
 fn test_0xe7_sbc_zero_page_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -83,7 +83,7 @@ fn test_0xe7_sbc_zero_page_negative_flag() {
 
 #[test]
 fn test_0xe7_sbc_zero_page_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -108,7 +108,7 @@ fn test_0xe7_sbc_zero_page_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xe7_sbc_zero_page_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -137,7 +137,7 @@ fn test_0xe7_sbc_zero_page_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xf7_sbc_zero_page_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -168,7 +168,7 @@ fn test_0xf7_sbc_zero_page_x() {
 
 #[test]
 fn test_0xf7_sbc_zero_page_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -197,7 +197,7 @@ fn test_0xf7_sbc_zero_page_x_zero_flag() {
 
 #[test]
 fn test_0xf7_sbc_zero_page_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -229,7 +229,7 @@ fn test_0xf7_sbc_zero_page_x_negative_flag() {
 
 #[test]
 fn test_0xf7_sbc_zero_page_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -261,7 +261,7 @@ fn test_0xf7_sbc_zero_page_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xf7_sbc_zero_page_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -291,7 +291,7 @@ fn test_0xf7_sbc_zero_page_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xef_sbc_absolute() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -321,7 +321,7 @@ fn test_0xef_sbc_absolute() {
 
 #[test]
 fn test_0xef_sbc_absolute_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -350,7 +350,7 @@ fn test_0xef_sbc_absolute_zero_flag() {
 
 #[test]
 fn test_0xef_sbc_absolute_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -382,7 +382,7 @@ fn test_0xef_sbc_absolute_negative_flag() {
 
 #[test]
 fn test_0xef_sbc_absolute_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -414,7 +414,7 @@ fn test_0xef_sbc_absolute_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xef_sbc_absolute_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -444,7 +444,7 @@ fn test_0xef_sbc_absolute_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xff_sbc_absolute_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -475,7 +475,7 @@ fn test_0xff_sbc_absolute_x() {
 
 #[test]
 fn test_0xff_sbc_absolute_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -505,7 +505,7 @@ fn test_0xff_sbc_absolute_x_zero_flag() {
 
 #[test]
 fn test_0xff_sbc_absolute_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -538,7 +538,7 @@ fn test_0xff_sbc_absolute_x_negative_flag() {
 
 #[test]
 fn test_0xff_sbc_absolute_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -571,7 +571,7 @@ fn test_0xff_sbc_absolute_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xff_sbc_absolute_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -602,7 +602,7 @@ fn test_0xff_sbc_absolute_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xfb_sbc_absolute_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -633,7 +633,7 @@ fn test_0xfb_sbc_absolute_y() {
 
 #[test]
 fn test_0xfb_sbc_absolute_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -663,7 +663,7 @@ fn test_0xfb_sbc_absolute_y_zero_flag() {
 
 #[test]
 fn test_0xfb_sbc_absolute_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -696,7 +696,7 @@ fn test_0xfb_sbc_absolute_y_negative_flag() {
 
 #[test]
 fn test_0xfb_sbc_absolute_y_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -729,7 +729,7 @@ fn test_0xfb_sbc_absolute_y_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xfb_sbc_absolute_y_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -760,7 +760,7 @@ fn test_0xfb_sbc_absolute_y_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xe3_sbc_indirect_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -791,7 +791,7 @@ fn test_0xe3_sbc_indirect_x() {
 
 #[test]
 fn test_0xe3_sbc_indirect_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -821,7 +821,7 @@ fn test_0xe3_sbc_indirect_x_zero_flag() {
 
 #[test]
 fn test_0xe3_sbc_indirect_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -854,7 +854,7 @@ fn test_0xe3_sbc_indirect_x_negative_flag() {
 
 #[test]
 fn test_0xe3_sbc_indirect_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -887,7 +887,7 @@ fn test_0xe3_sbc_indirect_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xe3_sbc_indirect_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -918,7 +918,7 @@ fn test_0xe3_sbc_indirect_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xf3_sbc_indirect_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -949,7 +949,7 @@ fn test_0xf3_sbc_indirect_y() {
 
 #[test]
 fn test_0xf3_sbc_indirect_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -979,7 +979,7 @@ fn test_0xf3_sbc_indirect_y_zero_flag() {
 
 #[test]
 fn test_0xf3_sbc_indirect_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1012,7 +1012,7 @@ fn test_0xf3_sbc_indirect_y_negative_flag() {
 
 #[test]
 fn test_0xf3_sbc_indirect_y_without_carry_flag_overflow_fla() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1045,7 +1045,7 @@ fn test_0xf3_sbc_indirect_y_without_carry_flag_overflow_fla() {
 
 #[test]
 fn test_0xf3_sbc_indirect_y_with_carry_flag_overflow_fla() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
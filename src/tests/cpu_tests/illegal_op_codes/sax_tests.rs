@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x8f_sax_zero_absolute() {
-    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)));
+    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)).unwrap());
     let address_to_test: u16 = 0x1234;
     let address_to_test_bytes = address_to_test.to_le_bytes();
     
@@ -35,7 +35,7 @@ fn test_0x8f_sax_zero_absolute() {
 
 #[test]
 fn test_0x87_sax_zero_page() {
-    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)));
+    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)).unwrap());
     let address_to_test: u8 = 0x40;
 
     let x_value = 0x03;
@@ -61,7 +61,7 @@ fn test_0x87_sax_zero_page() {
 
 #[test]
 fn test_0x97_sax_zero_page_y() {
-    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)));
+    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)).unwrap());
     let address_to_test: u8 = 0xF0;
 
     let x_value = 0x03;
@@ -91,7 +91,7 @@ fn test_0x97_sax_zero_page_y() {
 
 #[test]
 fn test_0x83_sax_zero_indirect_x() {
-    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)));
+    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)).unwrap());
     let mem_to_load: u8 = 0x40;
     let mem_pos_indirect = 0x00F1;
 
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xeb_sbc_immediate() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 80;
@@ -32,7 +32,7 @@ fn test_0xeb_sbc_immediate() {
 
 #[test]
 fn test_0xeb_sbc_immediate_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 10;
@@ -50,7 +50,7 @@ fn test_0xeb_sbc_immediate_zero_flag() {
 
 #[test]
 fn test_0xeb_sbc_immediate_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 10;
@@ -71,7 +71,7 @@ fn test_0xeb_sbc_immediate_negative_flag() {
 
 #[test]
 fn test_0xeb_sbc_immediate_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 80;
@@ -92,7 +92,7 @@ fn test_0xeb_sbc_immediate_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xeb_sbc_immediate_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = (48 as u8).wrapping_neg();
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x69_adc_immediate() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 55;
     let accum_value = 32;
@@ -24,7 +24,7 @@ fn test_0x69_adc_immediate() {
 
 #[test]
 fn test_0x69_adc_immediate_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x32;
@@ -42,7 +42,7 @@ fn test_0x69_adc_immediate_zero_flag() {
 
 #[test]
 fn test_0x69_adc_immediate_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x32;
@@ -60,7 +60,7 @@ fn test_0x69_adc_immediate_negative_flag() {
 
 #[test]
 fn test_0x69_adc_immediate_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 80;
@@ -78,7 +78,7 @@ fn test_0x69_adc_immediate_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x69_adc_immediate_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
     let value = 208;
     let add_that_overflows = 144;
@@ -95,7 +95,7 @@ fn test_0x69_adc_immediate_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x65_adc_zero_page() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -116,7 +116,7 @@ fn test_0x65_adc_zero_page() {
 
 #[test]
 fn test_0x65_adc_zero_page_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -137,7 +137,7 @@ fn test_0x65_adc_zero_page_zero_flag() {
 
 #[test]
 fn test_0x65_adc_zero_page_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -158,7 +158,7 @@ fn test_0x65_adc_zero_page_negative_flag() {
 
 #[test]
 fn test_0x65_adc_zero_page_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -179,7 +179,7 @@ fn test_0x65_adc_zero_page_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x65_adc_zero_page_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -200,7 +200,7 @@ fn test_0x65_adc_zero_page_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x75_adc_zero_page_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -229,7 +229,7 @@ fn test_0x75_adc_zero_page_x() {
 
 #[test]
 fn test_0x75_adc_zero_page_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -257,7 +257,7 @@ fn test_0x75_adc_zero_page_x_zero_flag() {
 
 #[test]
 fn test_0x75_adc_zero_page_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -285,7 +285,7 @@ fn test_0x75_adc_zero_page_x_negative_flag() {
 
 #[test]
 fn test_0x75_adc_zero_page_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -313,7 +313,7 @@ fn test_0x75_adc_zero_page_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x75_adc_zero_page_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -341,7 +341,7 @@ fn test_0x75_adc_zero_page_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x6d_adc_absolute() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -370,7 +370,7 @@ fn test_0x6d_adc_absolute() {
 
 #[test]
 fn test_0x6d_adc_absolute_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -398,7 +398,7 @@ fn test_0x6d_adc_absolute_zero_flag() {
 
 #[test]
 fn test_0x6d_adc_absolute_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -426,7 +426,7 @@ fn test_0x6d_adc_absolute_negative_flag() {
 
 #[test]
 fn test_0x6d_adc_absolute_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -454,7 +454,7 @@ fn test_0x6d_adc_absolute_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x6d_adc_absolute_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -482,7 +482,7 @@ fn test_0x6d_adc_absolute_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x7d_adc_absolute_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -512,7 +512,7 @@ fn test_0x7d_adc_absolute_x() {
 
 #[test]
 fn test_0x7d_adc_absolute_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -541,7 +541,7 @@ fn test_0x7d_adc_absolute_x_zero_flag() {
 
 #[test]
 fn test_0x7d_adc_absolute_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -570,7 +570,7 @@ fn test_0x7d_adc_absolute_x_negative_flag() {
 
 #[test]
 fn test_0x7d_adc_absolute_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -599,7 +599,7 @@ fn test_0x7d_adc_absolute_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x7d_adc_absolute_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -628,7 +628,7 @@ fn test_0x7d_adc_absolute_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x79_adc_absolute_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -658,7 +658,7 @@ fn test_0x79_adc_absolute_y() {
 
 #[test]
 fn test_0x79_adc_absolute_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -687,7 +687,7 @@ fn test_0x79_adc_absolute_y_zero_flag() {
 
 #[test]
 fn test_0x79_adc_absolute_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -716,7 +716,7 @@ fn test_0x79_adc_absolute_y_negative_flag() {
 
 #[test]
 fn test_0x79_adc_absolute_y_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -746,7 +746,7 @@ fn test_0x79_adc_absolute_y_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x79_adc_absolute_y_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -776,7 +776,7 @@ fn test_0x79_adc_absolute_y_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x61_adc_indirect_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -807,7 +807,7 @@ fn test_0x61_adc_indirect_x() {
 
 #[test]
 fn test_0x61_adc_indirect_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -837,7 +837,7 @@ fn test_0x61_adc_indirect_x_zero_flag() {
 
 #[test]
 fn test_0x61_adc_indirect_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -867,7 +867,7 @@ fn test_0x61_adc_indirect_x_negative_flag() {
 
 #[test]
 fn test_0x61_adc_indirect_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -897,7 +897,7 @@ fn test_0x61_adc_indirect_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x61_adc_indirect_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -927,7 +927,7 @@ fn test_0x61_adc_indirect_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0x71_adc_indirect_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -958,7 +958,7 @@ fn test_0x71_adc_indirect_y() {
 
 #[test]
 fn test_0x71_adc_indirect_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -988,7 +988,7 @@ fn test_0x71_adc_indirect_y_zero_flag() {
 
 #[test]
 fn test_0x71_adc_indirect_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1018,7 +1018,7 @@ fn test_0x71_adc_indirect_y_negative_flag() {
 
 #[test]
 fn test_0x71_adc_indirect_y_without_carry_flag_overflow_fla() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1048,7 +1048,7 @@ fn test_0x71_adc_indirect_y_without_carry_flag_overflow_fla() {
 
 #[test]
 fn test_0x71_adc_indirect_y_with_carry_flag_overflow_fla() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x29_and_immediate_and() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -20,7 +20,7 @@ fn test_0x29_and_immediate_and() {
 
 #[test]
 fn test_0x29_and_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -32,7 +32,7 @@ fn test_0x29_and_zero_flag() {
 
 #[test]
 fn test_0x29_and_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -44,7 +44,7 @@ fn test_0x29_and_negative_flag() {
 
 #[test]
 fn test_0x25_and_zero_page() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x55);
@@ -58,7 +58,7 @@ fn test_0x25_and_zero_page() {
 
 #[test]
 fn test_0x25_and_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0xF0);
@@ -71,7 +71,7 @@ fn test_0x25_and_negative_flag() {
 
 #[test]
 fn test_0x25_and_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x00);
@@ -84,7 +84,7 @@ fn test_0x25_and_zero_flag() {
 
 #[test]
 fn test_0x35_and_zero_page_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x55);
@@ -106,7 +106,7 @@ fn test_0x35_and_zero_page_x() {
 
 #[test]
 fn test_0x35_and_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x00);
@@ -127,7 +127,7 @@ fn test_0x35_and_zero_flag() {
 
 #[test]
 fn test_0x35_and_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0xF5);
@@ -148,7 +148,7 @@ fn test_0x35_and_negative_flag() {
 
 #[test]
 fn test_0x2d_and_absolute() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0x55);
@@ -162,7 +162,7 @@ fn test_0x2d_and_absolute() {
 
 #[test]
 fn test_0x2d_and_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0x01);
@@ -175,7 +175,7 @@ fn test_0x2d_and_zero_flag() {
 
 #[test]
 fn test_0x2d_and_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0xFF);
@@ -188,7 +188,7 @@ fn test_0x2d_and_negative_flag() {
 
 #[test]
 fn test_0x3d_and_absolute_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0x55);
@@ -210,7 +210,7 @@ fn test_0x3d_and_absolute_x() {
 
 #[test]
 fn test_0x3d_and_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0x00);
@@ -231,7 +231,7 @@ fn test_0x3d_and_zero_flag() {
 
 #[test]
 fn test_0x3d_and_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0xF9);
@@ -252,7 +252,7 @@ fn test_0x3d_and_negative_flag() {
 
 #[test]
 fn test_0x39_and_absolute_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0x55);
@@ -274,7 +274,7 @@ fn test_0x39_and_absolute_y() {
 
 #[test]
 fn test_0x39_and_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0x00);
@@ -295,7 +295,7 @@ fn test_0x39_and_zero_flag() {
 
 #[test]
 fn test_0x39_and_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x1000, 0xF1);
@@ -316,7 +316,7 @@ fn test_0x39_and_negative_flag() {
 
 #[test]
 fn test_0x21_and_indirect_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -341,7 +341,7 @@ fn test_0x21_and_indirect_x() {
 
 #[test]
 fn test_0x21_and_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -365,7 +365,7 @@ fn test_0x21_and_zero_flag() {
 
 #[test]
 fn test_0x21_and_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -387,7 +387,7 @@ fn test_0x21_and_negative_flag() {
 
 #[test]
 fn test_0x31_and_indirect_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -410,7 +410,7 @@ fn test_0x31_and_indirect_y() {
 
 #[test]
 fn test_0x31_and_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -432,7 +432,7 @@ fn test_0x31_and_zero_flag() {
 
 #[test]
 fn test_0x31_and_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xb0_bcs_negative_offset() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 0xFF;
@@ -34,7 +34,7 @@ fn test_0xb0_bcs_negative_offset() {
 
 #[test]
 fn test_0xb0_bcs_positive_offset() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 20;
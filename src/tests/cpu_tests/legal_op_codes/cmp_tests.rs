@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xc9_cmp_immediate_access() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -19,7 +19,7 @@ fn test_0xc9_cmp_immediate_access() {
 
 #[test]
 fn test_0xc9_cmp_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -31,7 +31,7 @@ fn test_0xc9_cmp_zero_flag() {
 
 #[test]
 fn test_0xc9_cmp_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -43,7 +43,7 @@ fn test_0xc9_cmp_carry_flag() {
 
 #[test]
 fn test_0xc9_cmp_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -55,7 +55,7 @@ fn test_0xc9_cmp_negative_flag() {
 
 #[test]
 fn test_0xc5_cmp_zero_page() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x5);
@@ -68,7 +68,7 @@ fn test_0xc5_cmp_zero_page() {
 
 #[test]
 fn test_0xc5_cmp_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x5);
@@ -81,7 +81,7 @@ fn test_0xc5_cmp_zero_flag() {
 
 #[test]
 fn test_0xc5_cmp_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x5);
@@ -94,7 +94,7 @@ fn test_0xc5_cmp_carry_flag() {
 
 #[test]
 fn test_0xc5_cmp_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x5);
@@ -107,7 +107,7 @@ fn test_0xc5_cmp_negative_flag() {
 
 #[test]
 fn test_0xd5_cmp_zero_page_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_write: u8 = 0x10;
@@ -128,7 +128,7 @@ fn test_0xd5_cmp_zero_page_x() {
 
 #[test]
 fn test_0xd5_cmp_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_write: u16 = 0x10;
@@ -151,7 +151,7 @@ fn test_0xd5_cmp_zero_flag() {
 
 #[test]
 fn test_0xd5_cmp_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_write: u16 = 0x10;
@@ -174,7 +174,7 @@ fn test_0xd5_cmp_carry_flag() {
 
 #[test]
 fn test_0xd5_cmp_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_write: u16 = 0x10;
@@ -197,7 +197,7 @@ fn test_0xd5_cmp_negative_flag() {
 
 #[test]
 fn test_0xcd_cmp_absolute() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -219,7 +219,7 @@ fn test_0xcd_cmp_absolute() {
 
 #[test]
 fn test_0xcd_cmp_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -241,7 +241,7 @@ fn test_0xcd_cmp_zero_flag() {
 
 #[test]
 fn test_0xcd_cmp_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -263,7 +263,7 @@ fn test_0xcd_cmp_carry_flag() {
 
 #[test]
 fn test_0xcd_cmp_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -285,7 +285,7 @@ fn test_0xcd_cmp_negative_flag() {
 
 #[test]
 fn test_0xdd_cmp_absolute_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -308,7 +308,7 @@ fn test_0xdd_cmp_absolute_x() {
 
 #[test]
 fn test_0xdd_cmp_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -331,7 +331,7 @@ fn test_0xdd_cmp_zero_flag() {
 
 #[test]
 fn test_0xdd_cmp_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -354,7 +354,7 @@ fn test_0xdd_cmp_carry_flag() {
 
 #[test]
 fn test_0xdd_cmp_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -377,7 +377,7 @@ fn test_0xdd_cmp_negative_flag() {
 
 #[test]
 fn test_0xd9_cmp_absolute_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -400,7 +400,7 @@ fn test_0xd9_cmp_absolute_y() {
 
 #[test]
 fn test_0xd9_cmp_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -423,7 +423,7 @@ fn test_0xd9_cmp_zero_flag() {
 
 #[test]
 fn test_0xd9_cmp_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -446,7 +446,7 @@ fn test_0xd9_cmp_carry_flag() {
 
 #[test]
 fn test_0xd9_cmp_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -469,7 +469,7 @@ fn test_0xd9_cmp_negative_flag() {
 
 #[test]
 fn test_0xc1_cmp_indirect_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -491,7 +491,7 @@ fn test_0xc1_cmp_indirect_x() {
 
 #[test]
 fn test_0xc1_cmp_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -513,7 +513,7 @@ fn test_0xc1_cmp_zero_flag() {
 
 #[test]
 fn test_0xc1_cmp_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -535,7 +535,7 @@ fn test_0xc1_cmp_carry_flag() {
 
 #[test]
 fn test_0xc1_cmp_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -557,7 +557,7 @@ fn test_0xc1_cmp_negative_flag() {
 
 #[test]
 fn test_0xd1_cmp_indirect_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -579,7 +579,7 @@ fn test_0xd1_cmp_indirect_y() {
 
 #[test]
 fn test_0xd1_cmp_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -602,7 +602,7 @@ fn test_0xd1_cmp_zero_flag() {
 
 #[test]
 fn test_0xd1_cmp_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -625,7 +625,7 @@ fn test_0xd1_cmp_carry_flag() {
 
 #[test]
 fn test_0xd1_cmp_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
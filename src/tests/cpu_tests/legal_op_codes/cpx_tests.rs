@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xe0_cpx_immediate_access() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -18,7 +18,7 @@ fn test_0xe0_cpx_immediate_access() {
 
 #[test]
 fn test_0xe0_cpx_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -29,7 +29,7 @@ fn test_0xe0_cpx_zero_flag() {
 
 #[test]
 fn test_0xe0_cpx_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -40,7 +40,7 @@ fn test_0xe0_cpx_carry_flag() {
 
 #[test]
 fn test_0xe0_cpx_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -51,7 +51,7 @@ fn test_0xe0_cpx_negative_flag() {
 
 #[test]
 fn test_0xe4_cpx_zero_page() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x1);
@@ -63,7 +63,7 @@ fn test_0xe4_cpx_zero_page() {
 
 #[test]
 fn test_0xe4_cpx_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x1);
@@ -75,7 +75,7 @@ fn test_0xe4_cpx_zero_flag() {
 
 #[test]
 fn test_0xe4_cpx_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x1);
@@ -87,7 +87,7 @@ fn test_0xe4_cpx_carry_flag() {
 
 #[test]
 fn test_0xe4_cpx_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.mem_write(0x10, 0x5);
@@ -99,7 +99,7 @@ fn test_0xe4_cpx_negative_flag() {
 
 #[test]
 fn test_0xec_cpx_absolute() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -121,7 +121,7 @@ fn test_0xec_cpx_absolute() {
 
 #[test]
 fn test_0xec_cpx_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -141,7 +141,7 @@ fn test_0xec_cpx_zero_flag() {
 
 #[test]
 fn test_0xec_cpx_carry_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -161,7 +161,7 @@ fn test_0xec_cpx_carry_flag() {
 
 #[test]
 fn test_0xec_cpx_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem: u16 = 0x1000;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xe8_inx_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     cpu.register_x = 0xFE;
@@ -24,7 +24,7 @@ fn test_0xe8_inx_negative_flag() {
 
 #[test]
 fn test_0xe8_inx_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_x_to_max = cpu_test_helper::set_register_x_to_value(0xff);
@@ -40,7 +40,7 @@ fn test_0xe8_inx_zero_flag() {
 
 #[test]
 fn test_0xe8_inx_overflow() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_x_to_max = cpu_test_helper::set_register_x_to_value(0xff);
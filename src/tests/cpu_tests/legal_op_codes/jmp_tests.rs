@@ -8,7 +8,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 #[test]
 fn test_0x4c_jmp_absolute() {
     let initial_program_counter = 0x0600;
-    let bus = Bus::new(test_rom(initial_program_counter, None));
+    let bus = Bus::new(test_rom(initial_program_counter, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 0xFF;
@@ -34,7 +34,7 @@ fn test_0x4c_jmp_absolute() {
 #[test]
 fn test_0x6c_jmp_indirect() {
     let initial_program_counter = 0x0600;
-    let bus = Bus::new(test_rom(initial_program_counter, None));
+    let bus = Bus::new(test_rom(initial_program_counter, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 0xFF;
@@ -65,7 +65,7 @@ fn test_0x6c_jmp_indirect() {
 #[test]
 fn test_0x6c_jmp_indirect_page_boundary() {
     let initial_program_counter = 0x0600;
-    let bus = Bus::new(test_rom(initial_program_counter, None));
+    let bus = Bus::new(test_rom(initial_program_counter, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_initial_value = 0xFF;
@@ -11,7 +11,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 #[test]
 fn test_0x20_jsr_0x60_rts_subroutines() {
     let pc_counter_start = 0x0600;
-    let bus = Bus::new(test_rom(pc_counter_start,None));
+    let bus = Bus::new(test_rom(pc_counter_start,None)).unwrap();
     let mut cpu = CPU::new(bus);
     let target_value = 5;
 
@@ -78,3 +78,22 @@ fn build_program_parts(target_value: u8, initial_program_counter: u16) -> Vec<Ve
 }
 
 
+
+#[test]
+fn test_call_subroutine_returns_after_rts() {
+    let pc_counter_start = 0x0600;
+    let bus = Bus::new(test_rom(pc_counter_start, None)).unwrap();
+    let mut cpu = CPU::new(bus);
+    cpu.reset();
+
+    let subroutine_address = 0x0700;
+    let set_x = cpu_test_helper::set_register_x_to_value(0x42);
+    cpu.mem_write(subroutine_address, set_x[0]);
+    cpu.mem_write(subroutine_address + 1, set_x[1]);
+    cpu.mem_write(subroutine_address + 2, 0x60);
+
+    cpu.call_subroutine(subroutine_address, 100).unwrap();
+
+    assert_eq!(cpu.register_x, 0x42);
+    assert_eq!(cpu.stack_pointer, CPU::STACK_RESET);
+}
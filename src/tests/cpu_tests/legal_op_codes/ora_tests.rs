@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x09_ora_immediate() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -22,7 +22,7 @@ fn test_0x09_ora_immediate() {
 
 #[test]
 fn test_0x09_ora_immediate_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -38,7 +38,7 @@ fn test_0x09_ora_immediate_zero_flag() {
 
 #[test]
 fn test_0x09_ora_immediate_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -53,7 +53,7 @@ fn test_0x09_ora_immediate_negative_flag() {
 
 #[test]
 fn test_0x05_ora_zero_page() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -71,7 +71,7 @@ fn test_0x05_ora_zero_page() {
 
 #[test]
 fn test_0x05_ora_zero_page_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -89,7 +89,7 @@ fn test_0x05_ora_zero_page_zero_flag() {
 
 #[test]
 fn test_0x05_ora_zero_page_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x01;
@@ -107,7 +107,7 @@ fn test_0x05_ora_zero_page_negative_flag() {
 
 #[test]
 fn test_0x15_ora_zero_page_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -132,7 +132,7 @@ fn test_0x15_ora_zero_page_x() {
 
 #[test]
 fn test_0x15_ora_zero_page_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -157,7 +157,7 @@ fn test_0x15_ora_zero_page_x_zero_flag() {
 
 #[test]
 fn test_0x15_ora_zero_page_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x1;
@@ -182,7 +182,7 @@ fn test_0x15_ora_zero_page_x_negative_flag() {
 
 #[test]
 fn test_0x0d_ora_absolute() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -207,7 +207,7 @@ fn test_0x0d_ora_absolute() {
 
 #[test]
 fn test_0x0d_ora_absolute_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -232,7 +232,7 @@ fn test_0x0d_ora_absolute_zero_flag() {
 
 #[test]
 fn test_0x0d_ora_absolute_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x1;
@@ -257,7 +257,7 @@ fn test_0x0d_ora_absolute_negative_flag() {
 
 #[test]
 fn test_0x1d_ora_absolute_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x1;
@@ -283,7 +283,7 @@ fn test_0x1d_ora_absolute_x() {
 
 #[test]
 fn test_0x1d_ora_absolute_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -309,7 +309,7 @@ fn test_0x1d_ora_absolute_x_zero_flag() {
 
 #[test]
 fn test_0x1d_ora_absolute_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x1;
@@ -335,7 +335,7 @@ fn test_0x1d_ora_absolute_x_negative_flag() {
 
 #[test]
 fn test_0x19_ora_absolute_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -361,7 +361,7 @@ fn test_0x19_ora_absolute_y() {
 
 #[test]
 fn test_0x19_ora_absolute_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x0;
@@ -387,7 +387,7 @@ fn test_0x19_ora_absolute_y_zero_flag() {
 
 #[test]
 fn test_0x19_ora_absolute_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_value = 0x1;
@@ -413,7 +413,7 @@ fn test_0x19_ora_absolute_y_negative_flag() {
 
 #[test]
 fn test_0x01_ora_indirect_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -440,7 +440,7 @@ fn test_0x01_ora_indirect_x() {
 
 #[test]
 fn test_0x01_ora_indirect_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -467,7 +467,7 @@ fn test_0x01_ora_indirect_x_zero_flag() {
 
 #[test]
 fn test_0x01_ora_indirect_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -494,7 +494,7 @@ fn test_0x01_ora_indirect_x_negative_flag() {
 
 #[test]
 fn test_0x11_ora_indirect_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -521,7 +521,7 @@ fn test_0x11_ora_indirect_y() {
 
 #[test]
 fn test_0x11_ora_indirect_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -548,7 +548,7 @@ fn test_0x11_ora_indirect_y_zero_flag() {
 
 #[test]
 fn test_0x11_ora_indirect_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
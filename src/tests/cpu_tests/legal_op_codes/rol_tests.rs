@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x2a_rol_accumulator_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x51;
@@ -35,7 +35,7 @@ fn test_0x2a_rol_accumulator_base() {
 
 #[test]
 fn test_0x2a_rol_accumulator_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x80;
@@ -52,7 +52,7 @@ fn test_0x2a_rol_accumulator_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x2a_rol_accumulator_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0xF0;
@@ -69,7 +69,7 @@ fn test_0x2a_rol_accumulator_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x2a_rol_accumulator_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x50;
@@ -85,7 +85,7 @@ fn test_0x2a_rol_accumulator_negative_flag() {
 
 #[test]
 fn test_0x26_rol_zero_page_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -120,7 +120,7 @@ fn test_0x26_rol_zero_page_base() {
 
 #[test]
 fn test_0x26_rol_zero_page_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -137,7 +137,7 @@ fn test_0x26_rol_zero_page_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x26_rol_zero_page_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -154,7 +154,7 @@ fn test_0x26_rol_zero_page_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x26_rol_zero_page_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -170,7 +170,7 @@ fn test_0x26_rol_zero_page_negative_flag() {
 
 #[test]
 fn test_0x36_rol_zero_page_x_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -206,7 +206,7 @@ fn test_0x36_rol_zero_page_x_base() {
 
 #[test]
 fn test_0x36_rol_zero_page_x_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -228,7 +228,7 @@ fn test_0x36_rol_zero_page_x_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x36_rol_zero_page_x_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -250,7 +250,7 @@ fn test_0x36_rol_zero_page_x_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x36_rol_zero_page_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -271,7 +271,7 @@ fn test_0x36_rol_zero_page_x_negative_flag() {
 
 #[test]
 fn test_0x2e_rol_absolute_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -317,7 +317,7 @@ fn test_0x2e_rol_absolute_base() {
 
 #[test]
 fn test_0x2e_rol_absolute_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -336,7 +336,7 @@ fn test_0x2e_rol_absolute_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x2e_rol_absolute_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -355,7 +355,7 @@ fn test_0x2e_rol_absolute_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x2e_rol_absolute_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -373,7 +373,7 @@ fn test_0x2e_rol_absolute_negative_flag() {
 
 #[test]
 fn test_0x3e_rol_absolute_x_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -419,7 +419,7 @@ fn test_0x3e_rol_absolute_x_base() {
 
 #[test]
 fn test_0x3e_rol_absolute_x_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -443,7 +443,7 @@ fn test_0x3e_rol_absolute_x_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x3e_rol_absolute_x_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -467,7 +467,7 @@ fn test_0x3e_rol_absolute_x_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x3e_rol_absolute_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x6a_ror_accumulator_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x51;
@@ -35,7 +35,7 @@ fn test_0x6a_ror_accumulator_base() {
 
 #[test]
 fn test_0x6a_ror_accumulator_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0x01;
@@ -52,7 +52,7 @@ fn test_0x6a_ror_accumulator_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x6a_ror_accumulator_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = 0b0000_0011;
@@ -75,7 +75,7 @@ fn test_0x6a_ror_accumulator_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x66_ror_zero_page_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -110,7 +110,7 @@ fn test_0x66_ror_zero_page_base() {
 
 #[test]
 fn test_0x66_ror_zero_page_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -127,7 +127,7 @@ fn test_0x66_ror_zero_page_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x66_ror_zero_page_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -150,7 +150,7 @@ fn test_0x66_ror_zero_page_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x76_ror_zero_page_x_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -186,7 +186,7 @@ fn test_0x76_ror_zero_page_x_base() {
 
 #[test]
 fn test_0x76_ror_zero_page_x_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -208,7 +208,7 @@ fn test_0x76_ror_zero_page_x_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x76_ror_zero_page_x_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x10;
@@ -232,7 +232,7 @@ fn test_0x76_ror_zero_page_x_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x6e_ror_absolute_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -278,7 +278,7 @@ fn test_0x6e_ror_absolute_base() {
 
 #[test]
 fn test_0x6e_ror_absolute_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -297,7 +297,7 @@ fn test_0x6e_ror_absolute_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x6e_ror_absolute_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -324,7 +324,7 @@ fn test_0x6e_ror_absolute_carry_flag_negative_flag() {
 
 #[test]
 fn test_0x7e_ror_absolute_x_base() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -370,7 +370,7 @@ fn test_0x7e_ror_absolute_x_base() {
 
 #[test]
 fn test_0x7e_ror_absolute_x_carry_flag_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -394,7 +394,7 @@ fn test_0x7e_ror_absolute_x_carry_flag_zero_flag() {
 
 #[test]
 fn test_0x7e_ror_absolute_x_carry_flag_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_shift = 0x1000;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xe9_sbc_immediate() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 80;
@@ -32,7 +32,7 @@ fn test_0xe9_sbc_immediate() {
 
 #[test]
 fn test_0xe9_sbc_immediate_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 10;
@@ -50,7 +50,7 @@ fn test_0xe9_sbc_immediate_zero_flag() {
 
 #[test]
 fn test_0xe9_sbc_immediate_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 10;
@@ -71,7 +71,7 @@ fn test_0xe9_sbc_immediate_negative_flag() {
 
 #[test]
 fn test_0xe9_sbc_immediate_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = 80;
@@ -92,7 +92,7 @@ fn test_0xe9_sbc_immediate_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xe9_sbc_immediate_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let accum_value = (48 as u8).wrapping_neg();
@@ -117,7 +117,7 @@ fn test_0xe9_sbc_immediate_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xe5_sbc_zero_page() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -145,7 +145,7 @@ fn test_0xe5_sbc_zero_page() {
 
 #[test]
 fn test_0xe5_sbc_zero_page_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -166,7 +166,7 @@ fn test_0xe5_sbc_zero_page_zero_flag() {
 
 #[test]
 fn test_0xe5_sbc_zero_page_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -190,7 +190,7 @@ fn test_0xe5_sbc_zero_page_negative_flag() {
 
 #[test]
 fn test_0xe5_sbc_zero_page_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -214,7 +214,7 @@ fn test_0xe5_sbc_zero_page_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xe5_sbc_zero_page_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -242,7 +242,7 @@ fn test_0xe5_sbc_zero_page_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xf5_sbc_zero_page_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -272,7 +272,7 @@ fn test_0xf5_sbc_zero_page_x() {
 
 #[test]
 fn test_0xf5_sbc_zero_page_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -300,7 +300,7 @@ fn test_0xf5_sbc_zero_page_x_zero_flag() {
 
 #[test]
 fn test_0xf5_sbc_zero_page_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -332,7 +332,7 @@ fn test_0xf5_sbc_zero_page_x_negative_flag() {
 
 #[test]
 fn test_0xf5_sbc_zero_page_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -363,7 +363,7 @@ fn test_0xf5_sbc_zero_page_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xf5_sbc_zero_page_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x10;
@@ -392,7 +392,7 @@ fn test_0xf5_sbc_zero_page_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xed_sbc_absolute() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -421,7 +421,7 @@ fn test_0xed_sbc_absolute() {
 
 #[test]
 fn test_0xed_sbc_absolute_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -449,7 +449,7 @@ fn test_0xed_sbc_absolute_zero_flag() {
 
 #[test]
 fn test_0xed_sbc_absolute_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -480,7 +480,7 @@ fn test_0xed_sbc_absolute_negative_flag() {
 
 #[test]
 fn test_0xed_sbc_absolute_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -511,7 +511,7 @@ fn test_0xed_sbc_absolute_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xed_sbc_absolute_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -540,7 +540,7 @@ fn test_0xed_sbc_absolute_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xfd_sbc_absolute_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -570,7 +570,7 @@ fn test_0xfd_sbc_absolute_x() {
 
 #[test]
 fn test_0xfd_sbc_absolute_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -599,7 +599,7 @@ fn test_0xfd_sbc_absolute_x_zero_flag() {
 
 #[test]
 fn test_0xfd_sbc_absolute_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -631,7 +631,7 @@ fn test_0xfd_sbc_absolute_x_negative_flag() {
 
 #[test]
 fn test_0xfd_sbc_absolute_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -663,7 +663,7 @@ fn test_0xfd_sbc_absolute_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xfd_sbc_absolute_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -693,7 +693,7 @@ fn test_0xfd_sbc_absolute_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xf9_sbc_absolute_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -723,7 +723,7 @@ fn test_0xf9_sbc_absolute_y() {
 
 #[test]
 fn test_0xf9_sbc_absolute_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -752,7 +752,7 @@ fn test_0xf9_sbc_absolute_y_zero_flag() {
 
 #[test]
 fn test_0xf9_sbc_absolute_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -784,7 +784,7 @@ fn test_0xf9_sbc_absolute_y_negative_flag() {
 
 #[test]
 fn test_0xf9_sbc_absolute_y_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -816,7 +816,7 @@ fn test_0xf9_sbc_absolute_y_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xf9_sbc_absolute_y_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_pos = 0x1000;
@@ -846,7 +846,7 @@ fn test_0xf9_sbc_absolute_y_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xe1_sbc_indirect_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -876,7 +876,7 @@ fn test_0xe1_sbc_indirect_x() {
 
 #[test]
 fn test_0xe1_sbc_indirect_x_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -905,7 +905,7 @@ fn test_0xe1_sbc_indirect_x_zero_flag() {
 
 #[test]
 fn test_0xe1_sbc_indirect_x_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -937,7 +937,7 @@ fn test_0xe1_sbc_indirect_x_negative_flag() {
 
 #[test]
 fn test_0xe1_sbc_indirect_x_without_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -969,7 +969,7 @@ fn test_0xe1_sbc_indirect_x_without_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xe1_sbc_indirect_x_with_carry_flag_overflow_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -999,7 +999,7 @@ fn test_0xe1_sbc_indirect_x_with_carry_flag_overflow_flag() {
 
 #[test]
 fn test_0xf1_sbc_indirect_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1029,7 +1029,7 @@ fn test_0xf1_sbc_indirect_y() {
 
 #[test]
 fn test_0xf1_sbc_indirect_y_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1058,7 +1058,7 @@ fn test_0xf1_sbc_indirect_y_zero_flag() {
 
 #[test]
 fn test_0xf1_sbc_indirect_y_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1090,7 +1090,7 @@ fn test_0xf1_sbc_indirect_y_negative_flag() {
 
 #[test]
 fn test_0xf1_sbc_indirect_y_without_carry_flag_overflow_fla() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1122,7 +1122,7 @@ fn test_0xf1_sbc_indirect_y_without_carry_flag_overflow_fla() {
 
 #[test]
 fn test_0xf1_sbc_indirect_y_with_carry_flag_overflow_fla() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let mem_to_load: u8 = 0x40;
@@ -1152,7 +1152,7 @@ fn test_0xf1_sbc_indirect_y_with_carry_flag_overflow_fla() {
 
 // #[test]
 // fn test_0xe5_sbc_zero_page_complex() {
-//     let bus = Bus::new(test_rom());
+//     let bus = Bus::new(test_rom()).unwrap();
 
 //let mut cpu = CPU::new(bus);
 //     let minuend : u16=30000;
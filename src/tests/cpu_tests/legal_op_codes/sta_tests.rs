@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x85_sta_zero_page() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_a_to_5 = cpu_test_helper::set_accumulator_to_value(0x05);
@@ -20,7 +20,7 @@ fn test_0x85_sta_zero_page() {
 
 #[test]
 fn test_0x95_sta_zero_page_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_a_to_5 = cpu_test_helper::set_accumulator_to_value(0x05);
@@ -42,7 +42,7 @@ fn test_0x95_sta_zero_page_x() {
 
 #[test]
 fn test_0x8d_sta_absolute() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_a_to_5 = cpu_test_helper::set_accumulator_to_value(0x05);
@@ -65,7 +65,7 @@ fn test_0x8d_sta_absolute() {
 
 #[test]
 fn test_0x9d_sta_from_memory_absolute_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_a_to_5 = cpu_test_helper::set_accumulator_to_value(0x05);
@@ -89,7 +89,7 @@ fn test_0x9d_sta_from_memory_absolute_x() {
 
 #[test]
 fn test_0x99_sta_from_memory_absolute_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_a_to_5 = cpu_test_helper::set_accumulator_to_value(0x05);
@@ -113,7 +113,7 @@ fn test_0x99_sta_from_memory_absolute_y() {
 
 #[test]
 fn test_0x81_sta_from_memory_indirect_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let addr_to_write: u8 = 0x40;
@@ -137,7 +137,7 @@ fn test_0x81_sta_from_memory_indirect_x() {
 
 #[test]
 fn test_0x91_sta_from_memory_indirect_y() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let addr_to_write: u8 = 0x40;
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xaa_tax_move_a_to_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_a_to_value = cpu_test_helper::set_accumulator_to_value(10);
@@ -24,7 +24,7 @@ fn test_0xaa_tax_move_a_to_x() {
 
 #[test]
 fn test_0xaa_tax_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -35,7 +35,7 @@ fn test_0xaa_tax_zero_flag() {
 
 #[test]
 fn test_0xaa_tax_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
 
@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0xba_tsx_move_stack_to_x() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let target_value = 0xF;
@@ -32,7 +32,7 @@ fn test_0xba_tsx_move_stack_to_x() {
 
 #[test]
 fn test_0xba_tsx_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let target_value = 0;
@@ -57,7 +57,7 @@ fn test_0xba_tsx_zero_flag() {
 
 #[test]
 fn test_0xba_tsx_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let target_value = 0xFC;
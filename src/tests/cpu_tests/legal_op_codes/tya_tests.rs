@@ -7,7 +7,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_0x98_tya_move_y_to_accumulator() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_y_to_value = cpu_test_helper::set_register_y_to_value(10);
@@ -26,7 +26,7 @@ fn test_0x98_tya_move_y_to_accumulator() {
 
 #[test]
 fn test_0x98_tya_zero_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let set_y_to_value = cpu_test_helper::set_register_y_to_value(0);
@@ -45,7 +45,7 @@ fn test_0x98_tya_zero_flag() {
 
 #[test]
 fn test_0x98_tya_negative_flag() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
 
     let mut cpu = CPU::new(bus);
     let value = (10 as u8).wrapping_neg();
@@ -0,0 +1,333 @@
+use crate::components::apu::{DMC_FULL_VOLUME, PULSE_FULL_VOLUME};
+use crate::components::expansion_audio::mmc5::Mmc5Audio;
+use crate::components::expansion_audio::namco163::Namco163Audio;
+use crate::components::expansion_audio::sunsoft5b::Sunsoft5bAudio;
+use crate::components::expansion_audio::vrc7::{
+    Vrc7Audio, BUILT_IN_PATCHES as VRC7_PATCHES, LEVEL as VRC7_LEVEL, SAMPLE_RATE as VRC7_SAMPLE_RATE,
+};
+use std::f32::consts::PI;
+
+fn assert_level(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 0.0001, "expected {} but got {}", expected, actual);
+}
+
+fn clock_cycles(mut clock: impl FnMut(), cycles: usize) {
+    for _ in 0..cycles {
+        clock();
+    }
+}
+
+fn write_namco163_ram(audio: &mut Namco163Audio, addr: u8, data: &[u8]) {
+    audio.write_address(0x80 | addr);
+    for value in data {
+        audio.write_data(*value);
+    }
+}
+
+#[test]
+fn test_namco163_ram_auto_increment() {
+    let mut audio = Namco163Audio::new();
+    write_namco163_ram(&mut audio, 0x7E, &[1, 2, 3]);
+
+    audio.write_address(0x7E);
+    assert_eq!(audio.read_data(), 1);
+    assert_eq!(audio.read_data(), 1);
+
+    audio.write_address(0x80 | 0x7F);
+    assert_eq!(audio.read_data(), 2);
+    // wraps around to the start of RAM
+    assert_eq!(audio.read_data(), 3);
+}
+
+#[test]
+fn test_namco163_plays_wavetable() {
+    let mut audio = Namco163Audio::new();
+    // samples 15, 0, 15, 0
+    write_namco163_ram(&mut audio, 0x00, &[0x0F, 0x0F]);
+    // last channel, one sample per update, 4 sample wave at 0, full volume
+    write_namco163_ram(&mut audio, 0x78, &[0x00, 0x00, 0x00, 0x00, 0xFD, 0x00, 0x00, 0x0F]);
+
+    clock_cycles(|| audio.clock(), 15);
+    assert_level(audio.output(), -8.0 * 15.0 * PULSE_FULL_VOLUME * 3.0 / (7.0 * 15.0));
+
+    // a lone channel peaks at three 2A03 pulses
+    clock_cycles(|| audio.clock(), 15);
+    assert_level(audio.output(), 3.0 * PULSE_FULL_VOLUME);
+}
+
+#[test]
+fn test_namco163_channels_share_the_dac() {
+    let mut audio = Namco163Audio::new();
+    write_namco163_ram(&mut audio, 0x00, &[0xFF]);
+    write_namco163_ram(&mut audio, 0x70, &[0x00, 0x00, 0x00, 0x00, 0xFC, 0x00, 0x00, 0x0F]);
+    // two channels enabled
+    write_namco163_ram(&mut audio, 0x78, &[0x00, 0x00, 0x00, 0x00, 0xFC, 0x00, 0x00, 0x1F]);
+
+    clock_cycles(|| audio.clock(), 15);
+    assert_level(audio.output(), 1.5 * PULSE_FULL_VOLUME);
+
+    clock_cycles(|| audio.clock(), 15);
+    assert_level(audio.output(), 3.0 * PULSE_FULL_VOLUME);
+}
+
+fn write_sunsoft5b(audio: &mut Sunsoft5bAudio, register: u8, data: u8) {
+    audio.write_address(register);
+    audio.write_data(data);
+}
+
+#[test]
+fn test_sunsoft5b_tone() {
+    let mut audio = Sunsoft5bAudio::new();
+    write_sunsoft5b(&mut audio, 0, 1);
+    write_sunsoft5b(&mut audio, 7, 0b0011_1110);
+    write_sunsoft5b(&mut audio, 8, 0x0F);
+
+    assert_level(audio.output(), 0.0);
+    clock_cycles(|| audio.clock(), 16);
+    // a full volume channel is twice a 2A03 pulse
+    assert_level(audio.output(), 2.0 * PULSE_FULL_VOLUME);
+    clock_cycles(|| audio.clock(), 16);
+    assert_level(audio.output(), 0.0);
+}
+
+#[test]
+fn test_sunsoft5b_volume_is_logarithmic() {
+    let mut audio = Sunsoft5bAudio::new();
+    write_sunsoft5b(&mut audio, 7, 0b0011_1111);
+
+    // two steps down are 6dB, about half the amplitude
+    write_sunsoft5b(&mut audio, 8, 0x0D);
+    assert_level(audio.output(), 2.0 * PULSE_FULL_VOLUME * 10f32.powf(-6.0 / 20.0));
+
+    write_sunsoft5b(&mut audio, 8, 0x00);
+    assert_level(audio.output(), 0.0);
+}
+
+#[test]
+fn test_sunsoft5b_envelope_attack_and_hold() {
+    let mut audio = Sunsoft5bAudio::new();
+    write_sunsoft5b(&mut audio, 7, 0b0011_1111);
+    write_sunsoft5b(&mut audio, 8, 0x10);
+    write_sunsoft5b(&mut audio, 11, 1);
+    write_sunsoft5b(&mut audio, 13, 0b1101);
+
+    assert_level(audio.output(), 0.0);
+    clock_cycles(|| audio.clock(), 31 * 8);
+    assert_level(audio.output(), 2.0 * PULSE_FULL_VOLUME);
+    clock_cycles(|| audio.clock(), 1000);
+    assert_level(audio.output(), 2.0 * PULSE_FULL_VOLUME);
+}
+
+#[test]
+fn test_sunsoft5b_envelope_decays_to_silence() {
+    let mut audio = Sunsoft5bAudio::new();
+    write_sunsoft5b(&mut audio, 7, 0b0011_1111);
+    write_sunsoft5b(&mut audio, 8, 0x10);
+    write_sunsoft5b(&mut audio, 11, 1);
+    write_sunsoft5b(&mut audio, 13, 0b0000);
+
+    assert_level(audio.output(), 2.0 * PULSE_FULL_VOLUME);
+    clock_cycles(|| audio.clock(), 32 * 8);
+    assert_level(audio.output(), 0.0);
+}
+
+#[test]
+fn test_mmc5_pulse_matches_2a03_pulse() {
+    let mut audio = Mmc5Audio::new();
+    audio.write(0x5015, 0b01);
+    // 75% duty starts high, constant volume 15
+    audio.write(0x5000, 0b1111_1111);
+    // periods below 8 aren't muted without a sweep unit
+    audio.write(0x5002, 0x02);
+    audio.write(0x5003, 0x08);
+
+    assert_level(audio.output(), PULSE_FULL_VOLUME);
+    assert_eq!(audio.read(0x5015), 0b01);
+}
+
+#[test]
+fn test_mmc5_pulse_length_counter_runs_at_240hz() {
+    let mut audio = Mmc5Audio::new();
+    audio.write(0x5015, 0b10);
+    // length index 3 loads 2
+    audio.write(0x5004, 0b0000_1111);
+    audio.write(0x5007, 0b0001_1000);
+
+    clock_cycles(|| audio.clock(), 7457);
+    assert_eq!(audio.read(0x5015), 0b10);
+    clock_cycles(|| audio.clock(), 7457);
+    assert_eq!(audio.read(0x5015), 0);
+}
+
+#[test]
+fn test_mmc5_pcm_write_mode() {
+    let mut audio = Mmc5Audio::new();
+
+    audio.write(0x5011, 0xFF);
+    assert_level(audio.output(), DMC_FULL_VOLUME);
+
+    audio.write(0x5011, 0x00);
+    assert_level(audio.output(), DMC_FULL_VOLUME);
+}
+
+#[test]
+fn test_mmc5_pcm_read_mode() {
+    let mut audio = Mmc5Audio::new();
+    audio.write(0x5010, 0b1000_0001);
+
+    audio.observe_prg_read(0xC000, 0x80);
+    assert_level(audio.output(), 0.0);
+    audio.observe_prg_read(0x8000, 0x80);
+    assert_level(audio.output(), 0x80 as f32 * DMC_FULL_VOLUME / 255.0);
+
+    audio.observe_prg_read(0x8001, 0x00);
+    assert!(audio.irq_pending());
+    assert_eq!(audio.read(0x5010), 0b1000_0001);
+    assert!(!audio.irq_pending());
+}
+
+// There's no capture of a real VRC7 to compare against, so the waveform tests below check the
+// synthesis against sines worked out from the register values. They catch mistakes in the
+// operator math, not differences to the chip's own tables.
+fn write_vrc7(audio: &mut Vrc7Audio, register: u8, data: u8) {
+    audio.write_address(register);
+    audio.write_data(data);
+}
+
+// Carrier only sine, the modulator never attacks so it stays silent
+fn vrc7_sine_patch(audio: &mut Vrc7Audio, carrier_waveform: u8) {
+    for (register, data) in [0x01, 0x21, 0x3F, carrier_waveform, 0x00, 0xF0, 0x00, 0x00].iter().enumerate() {
+        write_vrc7(audio, register as u8, *data);
+    }
+}
+
+// fnum 256 at block 4, key on
+fn vrc7_key_on(audio: &mut Vrc7Audio, volume: u8) {
+    write_vrc7(audio, 0x30, volume);
+    write_vrc7(audio, 0x10, 0x00);
+    write_vrc7(audio, 0x20, 0x10 | 4 << 1 | 1);
+}
+
+fn vrc7_phase(sample: usize) -> f32 {
+    let frequency = 256.0 * 16.0 * VRC7_SAMPLE_RATE / (1 << 19) as f32;
+    2.0 * PI * frequency * (sample + 1) as f32 / VRC7_SAMPLE_RATE
+}
+
+fn assert_waveform(actual: &[f32], expected: impl Fn(usize) -> f32, tolerance: f32) {
+    for (sample, value) in actual.iter().enumerate() {
+        let reference = expected(sample);
+        assert!(
+            (value - reference).abs() < tolerance,
+            "sample {} is {} instead of {}",
+            sample,
+            value,
+            reference
+        );
+    }
+}
+
+#[test]
+fn test_vrc7_sine_carrier_matches_analytic_sine() {
+    let mut audio = Vrc7Audio::new();
+    vrc7_sine_patch(&mut audio, 0x00);
+    vrc7_key_on(&mut audio, 0);
+
+    let samples: Vec<f32> = (0..400).map(|_| audio.generate_sample()).collect();
+
+    assert_waveform(&samples, |sample| vrc7_phase(sample).sin(), 0.01);
+}
+
+#[test]
+fn test_vrc7_volume_steps_match_analytic_3db() {
+    let mut audio = Vrc7Audio::new();
+    vrc7_sine_patch(&mut audio, 0x00);
+    vrc7_key_on(&mut audio, 2);
+
+    let samples: Vec<f32> = (0..400).map(|_| audio.generate_sample()).collect();
+
+    let amplitude = 10f32.powf(-6.0 / 20.0);
+    assert_waveform(&samples, |sample| vrc7_phase(sample).sin() * amplitude, 0.01);
+}
+
+#[test]
+fn test_vrc7_half_sine_carrier_matches_analytic_half_sine() {
+    let mut audio = Vrc7Audio::new();
+    vrc7_sine_patch(&mut audio, 0x10);
+    vrc7_key_on(&mut audio, 0);
+
+    let samples: Vec<f32> = (0..400).map(|_| audio.generate_sample()).collect();
+
+    assert_waveform(&samples, |sample| vrc7_phase(sample).sin().max(0.0), 0.01);
+}
+
+#[test]
+fn test_vrc7_frequency_modulation_matches_analytic_fm() {
+    let mut audio = Vrc7Audio::new();
+    // both operators at the same frequency, the modulator 24dB down
+    for (register, data) in [0x21, 0x21, 0x20, 0x00, 0xF0, 0xF0, 0x00, 0x00].iter().enumerate() {
+        write_vrc7(&mut audio, register as u8, *data);
+    }
+    vrc7_key_on(&mut audio, 0);
+
+    let samples: Vec<f32> = (0..400).map(|_| audio.generate_sample()).collect();
+
+    // full scale modulation shifts the carrier by 8 pi
+    let modulation_index = 8.0 * PI * 10f32.powf(-24.0 / 20.0);
+    assert_waveform(
+        &samples,
+        |sample| (vrc7_phase(sample) + modulation_index * vrc7_phase(sample).sin()).sin(),
+        0.03,
+    );
+}
+
+#[test]
+fn test_vrc7_key_off_releases() {
+    let mut audio = Vrc7Audio::new();
+    vrc7_sine_patch(&mut audio, 0x00);
+    // fastest release
+    write_vrc7(&mut audio, 0x07, 0x0F);
+    vrc7_key_on(&mut audio, 0);
+    (0..100).for_each(|_| {
+        audio.generate_sample();
+    });
+
+    write_vrc7(&mut audio, 0x20, 4 << 1 | 1);
+    let samples: Vec<f32> = (0..100).map(|_| audio.generate_sample()).collect();
+
+    assert!(samples[0].abs() > 0.0);
+    assert!(samples[70..].iter().all(|sample| *sample == 0.0));
+}
+
+// Instrument 3 (Wurly), with or without a custom patch written first
+fn vrc7_built_in_instrument_samples(custom_patch: bool) -> Vec<f32> {
+    let mut audio = Vrc7Audio::new();
+    if custom_patch {
+        vrc7_sine_patch(&mut audio, 0x00);
+    }
+    vrc7_key_on(&mut audio, 0x30);
+    (0..400).map(|_| audio.generate_sample()).collect()
+}
+
+#[test]
+fn test_vrc7_built_in_patches_ignore_custom_registers() {
+    assert_eq!(VRC7_PATCHES[0], [0x03, 0x21, 0x05, 0x06, 0xE8, 0x81, 0x42, 0x27]);
+
+    let built_in = vrc7_built_in_instrument_samples(false);
+
+    assert!(built_in.iter().any(|sample| sample.abs() > 0.1));
+    assert_eq!(vrc7_built_in_instrument_samples(true), built_in);
+}
+
+#[test]
+fn test_vrc7_outputs_a_sample_every_36_cpu_cycles() {
+    let mut audio = Vrc7Audio::new();
+    vrc7_sine_patch(&mut audio, 0x00);
+    vrc7_key_on(&mut audio, 0);
+
+    clock_cycles(|| audio.clock(), 35);
+    assert_eq!(audio.output(), 0.0);
+    clock_cycles(|| audio.clock(), 1);
+    assert_level(audio.output(), vrc7_phase(0).sin() * VRC7_LEVEL);
+    clock_cycles(|| audio.clock(), 35);
+    assert_level(audio.output(), vrc7_phase(0).sin() * VRC7_LEVEL);
+}
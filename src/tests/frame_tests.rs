@@ -0,0 +1,105 @@
+use crate::components::crc32::crc32;
+use crate::components::frame::Frame;
+use crate::roms::screenshot;
+
+#[test]
+fn test_crc32_check_value() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crc32(&[]), 0);
+}
+
+#[test]
+fn test_png_header_and_chunks() {
+    let png = Frame::new().to_png();
+
+    assert_eq!(png[0..8], [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+    let chunks = read_chunks(&png);
+    let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| kind.as_slice()).collect();
+    assert_eq!(kinds, vec![b"IHDR".as_slice(), b"IDAT", b"IEND"]);
+    assert_eq!(chunks[0].1, [0, 0, 1, 0, 0, 0, 0, 240, 8, 2, 0, 0, 0]);
+}
+
+#[test]
+fn test_png_holds_the_frame_pixels() {
+    let mut frame = Frame::new();
+    frame.set_pixel(0, 0, (1, 2, 3));
+    frame.set_pixel(255, 239, (0xFF, 0x80, 0x40));
+
+    let chunks = read_chunks(&frame.to_png());
+    let scanlines = inflate_stored(&chunks[1].1);
+
+    assert_eq!(scanlines.len(), Frame::HEIGHT * (Frame::WIDTH * 3 + 1));
+    assert_eq!(scanlines[0..4], [0, 1, 2, 3]);
+    assert_eq!(scanlines[scanlines.len() - 3..], [0xFF, 0x80, 0x40]);
+    for (row, scanline) in scanlines.chunks(Frame::WIDTH * 3 + 1).enumerate() {
+        assert_eq!(scanline[0], 0, "filter byte of row {}", row);
+        assert_eq!(scanline[1..], frame.data[row * Frame::WIDTH * 3..(row + 1) * Frame::WIDTH * 3]);
+    }
+}
+
+#[test]
+fn test_scaled_png_repeats_every_pixel() {
+    let mut frame = Frame::new();
+    frame.set_pixel(1, 0, (9, 8, 7));
+
+    let chunks = read_chunks(&frame.to_png_scaled(2));
+    assert_eq!(chunks[0].1[0..8], [0, 0, 2, 0, 0, 0, 1, 0xE0]);
+    let scanlines = inflate_stored(&chunks[1].1);
+    let row = Frame::WIDTH * 2 * 3 + 1;
+
+    assert_eq!(scanlines.len(), Frame::HEIGHT * 2 * row);
+    assert_eq!(scanlines[0..row], scanlines[row..row * 2]);
+    assert_eq!(scanlines[1..16], [0, 0, 0, 0, 0, 0, 9, 8, 7, 9, 8, 7, 0, 0, 0]);
+}
+
+#[test]
+fn test_screenshots_never_replace_older_ones() {
+    let dir = std::env::temp_dir().join(format!("screenshot_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let rom_path = dir.join("game.nes");
+
+    let first = screenshot::save(&rom_path, &Frame::new(), 1).unwrap();
+    let second = screenshot::save(&rom_path, &Frame::new(), 3).unwrap();
+    let second_png = std::fs::read(&second).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(first, dir.join("game-000.png"));
+    assert_eq!(second, dir.join("game-001.png"));
+    assert_eq!(read_chunks(&second_png)[0].1[0..8], [0, 0, 3, 0, 0, 0, 2, 0xD0]);
+}
+
+// Splits a PNG into its chunks, checking the CRC of each one
+fn read_chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+    let mut chunks = vec![];
+    let mut pos = 8;
+    while pos < png.len() {
+        let length = u32::from_be_bytes(png[pos..pos + 4].try_into().unwrap()) as usize;
+        let kind: [u8; 4] = png[pos + 4..pos + 8].try_into().unwrap();
+        let data = png[pos + 8..pos + 8 + length].to_vec();
+        let crc = u32::from_be_bytes(png[pos + 8 + length..pos + 12 + length].try_into().unwrap());
+        assert_eq!(crc, crc32(&png[pos + 4..pos + 8 + length]), "CRC of {:?}", kind);
+        chunks.push((kind, data));
+        pos += 12 + length;
+    }
+    chunks
+}
+
+fn inflate_stored(zlib: &[u8]) -> Vec<u8> {
+    assert_eq!(zlib[0..2], [0x78, 0x01]);
+    let mut result = vec![];
+    let mut pos = 2;
+    loop {
+        let last = zlib[pos] & 1 != 0;
+        assert_eq!(zlib[pos] >> 1, 0, "only stored blocks are expected");
+        let length = u16::from_le_bytes([zlib[pos + 1], zlib[pos + 2]]);
+        let inverted = u16::from_le_bytes([zlib[pos + 3], zlib[pos + 4]]);
+        assert_eq!(length, !inverted);
+        result.extend(&zlib[pos + 5..pos + 5 + length as usize]);
+        pos += 5 + length as usize;
+        if last {
+            break;
+        }
+    }
+    assert_eq!(pos + 4, zlib.len());
+    result
+}
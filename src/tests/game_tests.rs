@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+
+use crate::components::cartridge::fds::{FdsImage, FDS_TAG};
+use crate::components::cartridge::ips;
+use crate::components::input_device::InputSetup;
+use crate::components::md5::md5;
+use crate::roms::game::Game;
+use crate::tests::test_helpers::fds_test_helper::{test_bios, test_disk_side};
+use crate::tests::test_helpers::rom_test_helper::{create_rom, TestRom};
+
+fn two_sided_disk() -> Vec<u8> {
+    [test_disk_side(&[1]), test_disk_side(&[2])].concat()
+}
+
+fn bios() -> Result<Vec<u8>, String> {
+    Ok(test_bios(0xE000, 0xE000))
+}
+
+// A directory of its own for every test, the .ips files go next to the image
+fn image_path(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("game_test_{}_{}", test, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("game.fds")
+}
+
+#[test]
+fn test_cartridges_load_without_the_bios() {
+    let raw = create_rom(TestRom {
+        header: vec![0x4E, 0x45, 0x53, 0x1A, 0x01, 0x01, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0, 0],
+        trainer: None,
+        pgp_rom: vec![0; 0x4000],
+        chr_rom: vec![0; 0x2000],
+    });
+
+    let game = Game::load(Path::new("game.nes"), &raw, || Err("no BIOS".to_string())).unwrap();
+
+    assert!(matches!(game, Game::Cartridge(_)));
+    assert!(game.disk_drive().is_none());
+    assert_eq!(game.default_input_setup(), InputSetup::Joypads);
+}
+
+#[test]
+fn test_disk_images_need_the_bios() {
+    let result = Game::load(Path::new("game.fds"), &two_sided_disk(), || Err("no BIOS".to_string()));
+
+    assert_eq!(result.err(), Some("no BIOS".to_string()));
+}
+
+#[test]
+fn test_disk_images_are_recognized_by_their_header() {
+    let mut raw = FDS_TAG.to_vec();
+    raw.push(2);
+    raw.resize(16, 0);
+    raw.extend(two_sided_disk());
+
+    let game = Game::load(Path::new("game.bin"), &raw, bios).unwrap();
+
+    assert!(game.disk_drive().is_some());
+    assert_eq!(game.checksum(), md5(&two_sided_disk()));
+}
+
+#[test]
+fn test_disk_sides_are_switched_by_ejecting_first() {
+    let game = Game::load(Path::new("game.fds"), &two_sided_disk(), bios).unwrap();
+    let mut drive = game.disk_drive().unwrap();
+
+    assert_eq!(drive.switch_side().unwrap(), "disk ejected");
+    assert_eq!(drive.switch_side().unwrap(), "disk 1 side B");
+    assert_eq!(drive.switch_side().unwrap(), "disk ejected");
+    assert_eq!(drive.switch_side().unwrap(), "disk 1 side A");
+}
+
+#[test]
+fn test_disk_changes_are_loaded_from_the_patch_next_to_the_image() {
+    let path = image_path("patch");
+    let original = two_sided_disk();
+    let mut changed = original.clone();
+    changed[100] = 0x42;
+    std::fs::write(path.with_extension("ips"), ips::create(&original, &changed).unwrap()).unwrap();
+
+    let game = Game::load(&path, &original, bios).unwrap();
+    // nothing new was written, the patch is left as it was
+    game.disk_drive().unwrap().save_changes().unwrap();
+    let kept = std::fs::read(path.with_extension("ips")).unwrap();
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    // movies still identify the game by the clean dump
+    assert_eq!(game.checksum(), md5(&original));
+    let restored = FdsImage::with_changes(&original, &kept).unwrap();
+    assert_eq!(restored.sides[0][100], 0x42);
+}
+
+#[test]
+fn test_broken_disk_changes_are_reported() {
+    let path = image_path("broken");
+    std::fs::write(path.with_extension("ips"), b"not a patch").unwrap();
+
+    let result = Game::load(&path, &two_sided_disk(), bios);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    assert!(result.err().unwrap().ends_with("game.ips: File is not an IPS patch"));
+}
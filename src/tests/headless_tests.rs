@@ -0,0 +1,137 @@
+use std::path::Path;
+
+use crate::components::bus::{Bus, RamInit};
+use crate::components::cartridge::Rom;
+use crate::components::frame::Frame;
+use crate::components::joypad::JoypadButton;
+use crate::components::mem::Mem;
+use crate::roms::game::Game;
+use crate::roms::headless;
+use crate::roms::input_script::InputScript;
+use crate::tests::test_helpers::fds_test_helper::{disk_info_block, test_bios, test_disk_side};
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+fn looping_rom() -> Rom {
+    let mut rom = test_rom(0x8000, None);
+    rom.prg_rom[0..3].copy_from_slice(&[0x4C, 0x00, 0x80]); // JMP $8000
+    rom
+}
+
+#[test]
+fn test_headless_run_returns_last_frame() {
+    let mut seen = vec![];
+    let frame = headless::run(Game::Cartridge(looping_rom()), RamInit::Zeros, 3, &InputScript::default(), |number, _| {
+        seen.push(number);
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(seen, vec![0, 1, 2]);
+    assert_eq!(frame.data.len(), Frame::WIDTH * Frame::HEIGHT * 3);
+}
+
+#[test]
+fn test_headless_run_reports_brk() {
+    let result = headless::run(Game::Cartridge(test_rom(0x8000, None)), RamInit::Zeros, 1, &InputScript::default(), |_, _| Ok(()));
+
+    assert_eq!(result.err(), Some("CPU hit BRK at 8000".to_string()));
+}
+
+#[test]
+fn test_headless_run_refuses_unsupported_mapper() {
+    let mut rom = looping_rom();
+    rom.mapper = 1;
+    let result = headless::run(Game::Cartridge(rom), RamInit::Zeros, 1, &InputScript::default(), |_, _| Ok(()));
+
+    assert_eq!(result.err(), Some("Mapper 1 is not supported".to_string()));
+}
+
+#[test]
+fn test_headless_run_stops_on_frame_callback_error() {
+    let result = headless::run(Game::Cartridge(looping_rom()), RamInit::Zeros, 5, &InputScript::default(), |number, _| match number {
+        1 => Err("disk full".to_string()),
+        _ => Ok(()),
+    });
+
+    assert_eq!(result.err(), Some("disk full".to_string()));
+}
+
+fn wait_for_transfer(bus: &mut Bus) {
+    for _ in 0..1_000_000 {
+        bus.tick(1);
+        if bus.mem_read(0x4030) & 0b10 != 0 {
+            return;
+        }
+    }
+    panic!("The disk drive didn't transfer a byte");
+}
+
+// Writes the disk info block to the start of the side, the way the BIOS does
+fn write_disk_info_block(bus: &mut Bus) {
+    bus.mem_write(0x4023, 0x01);
+    bus.mem_write(0x4025, 0b0010_0001);
+    for _ in 0..10 {
+        wait_for_transfer(bus);
+    }
+    bus.mem_write(0x4024, 0x80);
+    bus.mem_write(0x4025, 0b0110_0001);
+    for byte in disk_info_block() {
+        wait_for_transfer(bus);
+        bus.mem_write(0x4024, byte);
+    }
+    wait_for_transfer(bus);
+    bus.mem_write(0x4025, 0b0111_0001);
+    for _ in 0..300 {
+        bus.tick(1);
+    }
+    bus.mem_write(0x4025, 0b0010_0000);
+}
+
+#[test]
+fn test_headless_run_saves_disk_changes_when_it_stops() {
+    let dir = std::env::temp_dir().join(format!("headless_test_disk_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("game.fds");
+    let mut bios = test_bios(0xE000, 0xE000);
+    bios[0..3].copy_from_slice(&[0x4C, 0x00, 0xE0]); // JMP $E000
+    let blank_side = vec![0; test_disk_side(&[]).len()];
+    let game = Game::load(&path, &blank_side, || Ok(bios)).unwrap();
+
+    let result = headless::run(game, RamInit::Zeros, 5, &InputScript::default(), |_, cpu| {
+        write_disk_info_block(&mut cpu.bus);
+        Err("stopped".to_string())
+    });
+    let saved = std::fs::read(path.with_extension("ips"));
+    std::fs::remove_dir_all(Path::new(&dir)).unwrap();
+
+    assert_eq!(result.err(), Some("stopped".to_string()));
+    assert!(saved.is_ok());
+}
+
+#[test]
+fn test_headless_run_feeds_scripted_buttons() {
+    // every frame strobes the joypad and keeps its first 8 reads at $00-$07
+    #[rustfmt::skip]
+    let program = [
+        0xA9, 0x01, 0x8D, 0x16, 0x40,       // LDA #$01, STA $4016
+        0xA9, 0x00, 0x8D, 0x16, 0x40,       // LDA #$00, STA $4016
+        0xA2, 0x00,                         // LDX #$00
+        0xAD, 0x16, 0x40,                   // LDA $4016
+        0x95, 0x00,                         // STA $00,X
+        0xE8, 0xE0, 0x08, 0xD0, 0xF6,       // INX, CPX #$08, BNE -10
+        0x4C, 0x00, 0x80,                   // JMP $8000
+    ];
+    let mut rom = test_rom(0x8000, None);
+    rom.prg_rom[0..program.len()].copy_from_slice(&program);
+    let script = InputScript::parse("0 -\n2 start,right\n").unwrap();
+
+    let mut buttons = vec![];
+    headless::run(Game::Cartridge(rom), RamInit::Zeros, 4, &script, |_, cpu| {
+        buttons.push((0..8).fold(0, |bits, i| bits | (cpu.mem_read(i) & 1) << i));
+        Ok(())
+    })
+    .unwrap();
+
+    let pressed = JoypadButton::Start.bit() | JoypadButton::Right.bit();
+    assert_eq!(buttons, vec![0, 0, pressed, pressed]);
+}
@@ -0,0 +1,106 @@
+use crate::components::joypad::JoypadButton;
+use crate::roms::input_config::{AxisDirection, InputBinding, InputConfig};
+
+#[test]
+fn test_parse_input_config() {
+    let text = "
+        # player 1
+        1.a = key:X
+        1.start = key:Right Shift
+        2.up = button:dpup
+        2.left = axis:leftx-
+    ";
+
+    let config = InputConfig::parse(text).unwrap();
+
+    assert_eq!(
+        config.bindings[0],
+        vec![
+            (JoypadButton::A, InputBinding::Key("X".to_string())),
+            (JoypadButton::Start, InputBinding::Key("Right Shift".to_string())),
+        ]
+    );
+    assert_eq!(
+        config.bindings[1],
+        vec![
+            (JoypadButton::Up, InputBinding::ControllerButton("dpup".to_string())),
+            (JoypadButton::Left, InputBinding::ControllerAxis("leftx".to_string(), AxisDirection::Negative)),
+        ]
+    );
+}
+
+#[test]
+fn test_default_input_config_binds_every_button() {
+    let config = InputConfig::default();
+
+    for (player, bindings) in config.bindings.iter().enumerate() {
+        for button in JoypadButton::ALL {
+            let has_key = bindings.iter().any(|(bound, binding)| *bound == button && matches!(binding, InputBinding::Key(_)));
+            assert_eq!(has_key, player < 2);
+            assert!(bindings.iter().any(|(bound, binding)| *bound == button && matches!(binding, InputBinding::ControllerButton(_))));
+        }
+    }
+}
+
+#[test]
+fn test_input_config_four_players() {
+    let config = InputConfig::parse("3.b = button:a\n4.start = key:P").unwrap();
+
+    assert_eq!(config.bindings[2], vec![(JoypadButton::B, InputBinding::ControllerButton("a".to_string()))]);
+    assert_eq!(config.bindings[3], vec![(JoypadButton::Start, InputBinding::Key("P".to_string()))]);
+}
+
+#[test]
+fn test_input_config_round_trip() {
+    let config = InputConfig::default();
+
+    assert_eq!(InputConfig::parse(&config.to_string()).unwrap(), config);
+}
+
+#[test]
+fn test_input_config_unknown_player() {
+    match InputConfig::parse("5.a = key:X") {
+        Result::Ok(_) => assert!(false, "should not parse config"),
+        Result::Err(str) => assert_eq!(str, "Input config line 1: unknown player 5"),
+    }
+}
+
+#[test]
+fn test_input_config_unknown_button() {
+    match InputConfig::parse("\n1.turbo = key:X") {
+        Result::Ok(_) => assert!(false, "should not parse config"),
+        Result::Err(str) => assert_eq!(str, "Input config line 2: unknown button turbo"),
+    }
+}
+
+#[test]
+fn test_input_config_invalid_binding() {
+    match InputConfig::parse("1.up = axis:lefty") {
+        Result::Ok(_) => assert!(false, "should not parse config"),
+        Result::Err(str) => assert_eq!(str, "Input config line 1: invalid binding axis:lefty"),
+    }
+}
+
+#[test]
+fn test_input_config_line_without_binding() {
+    match InputConfig::parse("1.up") {
+        Result::Ok(_) => assert!(false, "should not parse config"),
+        Result::Err(str) => assert_eq!(str, "Input config line 1 is not a binding"),
+    }
+}
+
+#[test]
+fn test_input_config_power_pad() {
+    let config = InputConfig::parse("powerpad.12 = key:F").unwrap();
+
+    assert_eq!(config.power_pad, vec![(12, InputBinding::Key("F".to_string()))]);
+    assert_eq!(InputConfig::default().power_pad.len(), 12);
+}
+
+#[test]
+fn test_input_config_unknown_power_pad_button() {
+    match InputConfig::parse("powerpad.13 = key:F") {
+        Result::Ok(_) => assert!(false, "should not parse config"),
+        Result::Err(str) => assert_eq!(str, "Input config line 1: unknown Power Pad button 13"),
+    }
+}
@@ -0,0 +1,174 @@
+use crate::components::arkanoid_paddle::ArkanoidPaddle;
+use crate::components::bus::Bus;
+use crate::components::family_keyboard::FamilyKeyboard;
+use crate::components::input_device::{InputDevice, InputDevices, InputSetup};
+use crate::components::mem::Mem;
+use crate::components::power_pad::PowerPad;
+use crate::components::ppu::NesPPU;
+use crate::tests::test_helpers::ppu_test_helper::test_ppu;
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+fn read_bits(device: &mut dyn InputDevice, ppu: &NesPPU, port: usize, count: usize) -> Vec<u8> {
+    (0..count).map(|_| device.read_port(port, ppu)).collect()
+}
+
+#[test]
+fn test_input_setup_from_nes2_expansion_device() {
+    assert_eq!(InputSetup::from_expansion_device(0x01), Some(InputSetup::Joypads));
+    assert_eq!(InputSetup::from_expansion_device(0x02), Some(InputSetup::FourScore));
+    assert_eq!(InputSetup::from_expansion_device(0x08), Some(InputSetup::Zapper));
+    assert_eq!(InputSetup::from_expansion_device(0x0B), Some(InputSetup::PowerPad));
+    assert_eq!(InputSetup::from_expansion_device(0x0F), Some(InputSetup::ArkanoidNes));
+    assert_eq!(InputSetup::from_expansion_device(0x10), Some(InputSetup::ArkanoidFamicom));
+    assert_eq!(InputSetup::from_expansion_device(0x23), Some(InputSetup::FamilyKeyboard));
+    assert_eq!(InputSetup::from_expansion_device(0x00), None);
+}
+
+#[test]
+fn test_nes_arkanoid_paddle_reports_position_inverted() {
+    let ppu = test_ppu(vec![]);
+    let mut paddle = ArkanoidPaddle::nes();
+    paddle.set_position(0b1010_1100);
+    paddle.set_button_pressed(true);
+
+    paddle.write_port(1);
+    paddle.write_port(0);
+
+    assert_eq!(
+        read_bits(&mut paddle, &ppu, 1, 9),
+        vec![0x08, 0x18, 0x08, 0x18, 0x08, 0x08, 0x18, 0x18, 0x18]
+    );
+}
+
+#[test]
+fn test_famicom_arkanoid_paddle_uses_expansion_lines() {
+    let ppu = test_ppu(vec![]);
+    let mut paddle = ArkanoidPaddle::famicom();
+    paddle.set_position(0xF0);
+
+    paddle.write_port(1);
+    paddle.write_port(0);
+
+    assert_eq!(paddle.read_port(0, &ppu), 0);
+    assert_eq!(read_bits(&mut paddle, &ppu, 1, 5), vec![0, 0, 0, 0, 0b10]);
+    paddle.set_button_pressed(true);
+    assert_eq!(paddle.read_port(0, &ppu), 0b10);
+}
+
+#[test]
+fn test_arkanoid_paddle_position_from_screen() {
+    let mut paddle = ArkanoidPaddle::nes();
+
+    paddle.set_position_from_screen(0);
+    assert_eq!(paddle.position(), ArkanoidPaddle::MIN_POSITION);
+
+    paddle.set_position_from_screen(1000);
+    assert_eq!(paddle.position(), ArkanoidPaddle::MAX_POSITION);
+
+    paddle.set_position(0);
+    assert_eq!(paddle.position(), ArkanoidPaddle::MIN_POSITION);
+}
+
+#[test]
+fn test_power_pad_report_order() {
+    let ppu = test_ppu(vec![]);
+    let mut power_pad = PowerPad::new();
+    power_pad.set_button_pressed(1, true);
+    power_pad.set_button_pressed(7, true);
+    power_pad.set_button_pressed(3, true);
+    power_pad.set_button_pressed(8, true);
+
+    power_pad.write_port(1);
+    power_pad.write_port(0);
+
+    let d4: Vec<u8> = read_bits(&mut power_pad, &ppu, 1, 8).iter().map(|bits| (bits >> 4) & 1).collect();
+    assert_eq!(d4, vec![0, 1, 0, 0, 0, 0, 0, 1]);
+
+    power_pad.write_port(1);
+    power_pad.write_port(0);
+
+    let d3: Vec<u8> = read_bits(&mut power_pad, &ppu, 1, 6).iter().map(|bits| (bits >> 3) & 1).collect();
+    assert_eq!(d3, vec![0, 1, 0, 1, 1, 1]);
+}
+
+#[test]
+#[should_panic(expected = "Power Pad button 13 doesn't exist")]
+fn test_power_pad_has_12_buttons() {
+    PowerPad::new().set_button_pressed(13, true);
+}
+
+#[test]
+fn test_family_keyboard_scans_matrix() {
+    let ppu = test_ppu(vec![]);
+    let mut keyboard = FamilyKeyboard::new();
+    keyboard.set_key_pressed("RETURN", true).unwrap();
+    keyboard.set_key_pressed("x", true).unwrap();
+
+    keyboard.write_port(0b101);
+    keyboard.write_port(0b100);
+    assert_eq!(keyboard.read_port(1, &ppu), 0b1_0110);
+    keyboard.write_port(0b110);
+    assert_eq!(keyboard.read_port(1, &ppu), 0b1_1110);
+
+    for _ in 1..6 {
+        keyboard.write_port(0b100);
+        keyboard.write_port(0b110);
+    }
+    keyboard.write_port(0b100);
+    keyboard.write_port(0b110);
+    assert_eq!(keyboard.read_port(1, &ppu), 0b0_1110);
+
+    // past the last row nothing is pressed
+    for _ in 0..3 {
+        keyboard.write_port(0b100);
+        keyboard.write_port(0b110);
+    }
+    assert_eq!(keyboard.read_port(1, &ppu), 0b1_1110);
+}
+
+#[test]
+fn test_family_keyboard_disabled() {
+    let ppu = test_ppu(vec![]);
+    let mut keyboard = FamilyKeyboard::new();
+    keyboard.set_key_pressed("A", true).unwrap();
+
+    keyboard.write_port(0b001);
+
+    assert_eq!(keyboard.read_port(1, &ppu), 0);
+    assert_eq!(keyboard.read_port(0, &ppu), 0);
+}
+
+#[test]
+fn test_family_keyboard_unknown_key() {
+    match FamilyKeyboard::new().set_key_pressed("WINDOWS", true) {
+        Result::Ok(_) => assert!(false, "should not press key"),
+        Result::Err(str) => assert_eq!(str, "Family BASIC keyboard has no key WINDOWS"),
+    }
+}
+
+#[test]
+fn test_bus_reads_expansion_port_device_with_joypads() {
+    let mut bus = Bus::new(test_rom(0x0600, None)).unwrap();
+    let devices = InputDevices::connect(InputSetup::FamilyKeyboard, &mut bus);
+    devices.set_joypad_buttons(0, 0b1);
+    devices.family_keyboard.as_ref().unwrap().borrow_mut().set_key_pressed("]", true).unwrap();
+
+    bus.mem_write(0x4016, 0b101);
+    bus.mem_write(0x4016, 0b100);
+
+    assert_eq!(bus.mem_read(0x4016), 0x41);
+    assert_eq!(bus.mem_read(0x4017), 0x40 | 0b1_1100);
+}
+
+#[test]
+fn test_input_setup_replaces_devices() {
+    let mut bus = Bus::new(test_rom(0x0600, None)).unwrap();
+    InputDevices::connect(InputSetup::Joypads, &mut bus);
+    let devices = InputDevices::connect(InputSetup::ArkanoidNes, &mut bus);
+
+    assert_eq!(devices.joypads.len(), 1);
+    devices.arkanoid_paddle.as_ref().unwrap().borrow_mut().set_button_pressed(true);
+
+    bus.mem_write(0x4016, 1);
+    assert_eq!(bus.mem_read(0x4017) & 0b1000, 0b1000);
+}
@@ -0,0 +1,48 @@
+use crate::components::joypad::JoypadButton;
+use crate::roms::input_script::InputScript;
+
+#[test]
+fn test_input_script_holds_buttons_until_next_line() {
+    let script = InputScript::parse(
+        "
+        # frame  player 1   player 2
+        0        -
+        30       start
+        32       -          a
+        100      right,a    left  # comment
+    ",
+    )
+    .unwrap();
+
+    assert_eq!(script.buttons(0), [0, 0, 0, 0]);
+    assert_eq!(script.buttons(29), [0, 0, 0, 0]);
+    assert_eq!(script.buttons(30), [JoypadButton::Start.bit(), 0, 0, 0]);
+    assert_eq!(script.buttons(31), [JoypadButton::Start.bit(), 0, 0, 0]);
+    assert_eq!(script.buttons(32), [0, JoypadButton::A.bit(), 0, 0]);
+    assert_eq!(
+        script.buttons(5000),
+        [JoypadButton::Right.bit() | JoypadButton::A.bit(), JoypadButton::Left.bit(), 0, 0]
+    );
+}
+
+#[test]
+fn test_input_script_before_first_line_presses_nothing() {
+    let script = InputScript::parse("10 b").unwrap();
+
+    assert_eq!(script.buttons(9), [0, 0, 0, 0]);
+    assert_eq!(script.buttons(10), [JoypadButton::B.bit(), 0, 0, 0]);
+}
+
+#[test]
+fn test_input_script_errors() {
+    assert_eq!(InputScript::parse("x start"), Err("Line 1: invalid frame x".to_string()));
+    assert_eq!(InputScript::parse("0 jump"), Err("Line 1: unknown button jump".to_string()));
+    assert_eq!(
+        InputScript::parse("5 a\n5 b"),
+        Err("Line 2: frame 5 is not after the previous line".to_string())
+    );
+    assert_eq!(
+        InputScript::parse("0 a b a b a"),
+        Err("Line 1: only 4 players are supported".to_string())
+    );
+}
@@ -0,0 +1,105 @@
+use crate::components::bus::Bus;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::components::joypad::{Joypad, JoypadButton};
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+fn read_report(joypad: &mut Joypad) -> Vec<u8> {
+    (0..8).map(|_| joypad.read()).collect()
+}
+
+#[test]
+fn test_joypad_reports_buttons_in_order() {
+    let mut joypad = Joypad::new();
+    joypad.set_button_pressed_status(JoypadButton::A, true);
+    joypad.set_button_pressed_status(JoypadButton::Start, true);
+    joypad.set_button_pressed_status(JoypadButton::Right, true);
+
+    joypad.write(1);
+    joypad.write(0);
+
+    assert_eq!(read_report(&mut joypad), vec![1, 0, 0, 1, 0, 0, 0, 1]);
+}
+
+#[test]
+fn test_joypad_reports_1_after_all_buttons() {
+    let mut joypad = Joypad::new();
+
+    joypad.write(1);
+    joypad.write(0);
+    read_report(&mut joypad);
+
+    assert_eq!(joypad.read(), 1);
+    assert_eq!(joypad.read(), 1);
+}
+
+#[test]
+fn test_joypad_strobe_keeps_reporting_a() {
+    let mut joypad = Joypad::new();
+    joypad.set_button_pressed_status(JoypadButton::A, true);
+    joypad.set_button_pressed_status(JoypadButton::B, true);
+
+    joypad.write(1);
+
+    assert_eq!(read_report(&mut joypad), vec![1; 8]);
+
+    joypad.set_button_pressed_status(JoypadButton::A, false);
+    assert_eq!(joypad.read(), 0);
+}
+
+#[test]
+fn test_joypad_strobe_restarts_report() {
+    let mut joypad = Joypad::new();
+    joypad.set_buttons(0b0000_0010);
+
+    joypad.write(1);
+    joypad.write(0);
+    joypad.read();
+    joypad.read();
+
+    joypad.write(1);
+    joypad.write(0);
+
+    assert_eq!(read_report(&mut joypad), vec![0, 1, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_joypad_set_buttons_replaces_state() {
+    let mut joypad = Joypad::new();
+    joypad.set_button_pressed_status(JoypadButton::Up, true);
+
+    joypad.set_buttons(JoypadButton::Left.bit() | JoypadButton::B.bit());
+
+    assert!(!joypad.is_pressed(JoypadButton::Up));
+    assert!(joypad.is_pressed(JoypadButton::Left));
+    assert!(joypad.is_pressed(JoypadButton::B));
+    assert_eq!(joypad.buttons(), 0b0100_0010);
+}
+
+#[test]
+fn test_bus_reads_controller_ports_with_open_bus_bits() {
+    let mut bus = Bus::new(test_rom(0x8000, None)).unwrap();
+    let devices = InputDevices::connect(InputSetup::Joypads, &mut bus);
+    devices.joypads[0].borrow_mut().set_button_pressed_status(JoypadButton::Select, true);
+    devices.joypads[1].borrow_mut().set_button_pressed_status(JoypadButton::B, true);
+
+    bus.mem_write(0x4016, 1);
+    bus.mem_write(0x4016, 0);
+
+    let port1: Vec<u8> = (0..8).map(|_| bus.mem_read(0x4016)).collect();
+    let port2: Vec<u8> = (0..8).map(|_| bus.mem_read(0x4017)).collect();
+
+    assert_eq!(port1, vec![0x40, 0x40, 0x41, 0x40, 0x40, 0x40, 0x40, 0x40]);
+    assert_eq!(port2, vec![0x40, 0x41, 0x40, 0x40, 0x40, 0x40, 0x40, 0x40]);
+}
+
+#[test]
+fn test_bus_reads_open_bus_without_devices() {
+    let mut bus = Bus::new(test_rom(0x8000, None)).unwrap();
+
+    bus.mem_write(0x4016, 1);
+    bus.mem_write(0x4016, 0);
+
+    assert_eq!(bus.mem_read(0x4016), 0x40);
+    assert_eq!(bus.mem_read(0x4017), 0x40);
+}
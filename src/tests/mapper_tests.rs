@@ -0,0 +1,16 @@
+mod bandai_fcg_tests;
+mod bnrom_tests;
+mod camerica_tests;
+mod color_dreams_tests;
+mod eeprom_tests;
+mod fds_tests;
+mod fme7_tests;
+mod mmc2_tests;
+mod mmc5_tests;
+mod namco108_tests;
+mod namco163_tests;
+mod nsf_tests;
+mod taito_tc0190_tests;
+mod vrc4_tests;
+mod vrc6_tests;
+mod vrc7_tests;
@@ -0,0 +1,128 @@
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::bandai_fcg::BandaiFcg;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::mapper_test_helper::{
+    bank_switching_rom, banked_rom, copy_byte, copy_chr_byte, run_program, write_register, I2cHost,
+};
+
+fn bandai_fcg(mapper: u8, submapper: u8) -> BandaiFcg {
+    let mut rom = banked_rom(mapper, 16, 256);
+    rom.submapper = submapper;
+    BandaiFcg::new(rom)
+}
+
+#[test]
+fn test_bandai_fcg_rom_switches_banks() {
+    let mut program = write_register(0x8008, 3);
+    program.extend(write_register(0x8002, 9));
+    program.extend(write_register(0x8007, 0x21));
+    program.extend(copy_byte(0x8000, 0x10));
+    program.extend(copy_byte(0xC000, 0x11));
+    program.extend(copy_chr_byte(0x0800, 0x12));
+    program.extend(copy_chr_byte(0x1C00, 0x13));
+    program.push(0x00);
+
+    let mut cpu = run_program(bank_switching_rom(16, 5, 16, 256), program);
+
+    // 16KB bank 3 starts at 8KB bank 6
+    assert_eq!(cpu.mem_read(0x10), 6);
+    assert_eq!(cpu.mem_read(0x11), 14);
+    assert_eq!(cpu.mem_read(0x12), 9);
+    assert_eq!(cpu.mem_read(0x13), 0x21);
+}
+
+#[test]
+fn test_bandai_fcg_register_ranges_follow_submapper() {
+    let mut fcg = bandai_fcg(16, 4);
+    let mut lz93d50 = bandai_fcg(16, 5);
+    let mut unknown = bandai_fcg(16, 0);
+    for mapper in [&mut fcg, &mut lz93d50, &mut unknown] {
+        mapper.write_prg(0x6008, 1);
+        mapper.write_prg(0x8009, 1);
+    }
+
+    assert_eq!(fcg.read_prg(0x8000), 2);
+    assert_eq!(lz93d50.read_prg(0x8000), 0);
+    assert_eq!(unknown.read_prg(0x8000), 2);
+    assert_eq!(lz93d50.mirroring(), Mirroring::HORIZONTAL);
+}
+
+#[test]
+fn test_lz93d50_irq_reloads_from_latch() {
+    let mut mapper = bandai_fcg(16, 5);
+    mapper.write_prg(0x800B, 3);
+    mapper.write_prg(0x800C, 0);
+    mapper.write_prg(0x800A, 1);
+
+    mapper.tick(3);
+    assert!(!mapper.irq_pending());
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+
+    mapper.write_prg(0x800A, 0);
+    assert!(!mapper.irq_pending());
+}
+
+#[test]
+fn test_fcg_irq_counter_is_written_directly() {
+    let mut mapper = bandai_fcg(16, 4);
+    mapper.write_prg(0x600A, 1);
+    mapper.write_prg(0x600B, 2);
+    mapper.write_prg(0x600C, 0);
+
+    mapper.tick(3);
+    assert!(mapper.irq_pending());
+}
+
+fn eeprom_host(mapper: &mut BandaiFcg, msb_first: bool) -> I2cHost<impl FnMut(bool, bool) -> bool + '_> {
+    I2cHost {
+        set_lines: move |scl, sda| {
+            mapper.write_prg(0x800D, (scl as u8) << 5 | (sda as u8) << 6 | 0x80);
+            mapper.read_prg(0x6000) & 0b1_0000 != 0
+        },
+        msb_first,
+    }
+}
+
+#[test]
+fn test_bandai_fcg_eeprom_is_saved() {
+    let mut mapper = bandai_fcg(16, 5);
+
+    let mut i2c = eeprom_host(&mut mapper, true);
+    i2c.start();
+    i2c.send_byte(0xA0);
+    i2c.send_byte(0x02);
+    i2c.send_byte(0x55);
+    i2c.stop();
+    drop(i2c);
+
+    assert_eq!(mapper.eeprom_data().unwrap()[2], 0x55);
+
+    let mut restored = bandai_fcg(16, 5);
+    restored.load_eeprom(mapper.eeprom_data().unwrap()).unwrap();
+    let mut i2c = eeprom_host(&mut restored, true);
+    i2c.start();
+    i2c.send_byte(0xA0);
+    i2c.send_byte(0x02);
+    i2c.start();
+    i2c.send_byte(0xA1);
+    assert_eq!(i2c.read_byte(false), 0x55);
+    i2c.stop();
+}
+
+#[test]
+fn test_mapper_159_uses_a_24c01() {
+    let mut mapper = bandai_fcg(159, 0);
+    assert_eq!(mapper.eeprom_data().unwrap().len(), 128);
+
+    let mut i2c = eeprom_host(&mut mapper, false);
+    i2c.start();
+    i2c.send_byte(0x01);
+    i2c.send_byte(0x77);
+    i2c.stop();
+    drop(i2c);
+
+    assert_eq!(mapper.eeprom_data().unwrap()[1], 0x77);
+    assert!(bandai_fcg(16, 4).eeprom_data().is_none());
+}
@@ -0,0 +1,48 @@
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::mapper_test_helper::{bank_switching_rom, copy_byte, copy_chr_byte, run_program, write_register};
+
+#[test]
+fn test_bnrom_rom_switches_banks() {
+    let mut program = write_register(0x8000, 2);
+    program.extend(copy_byte(0x8000, 0x10));
+    program.extend(copy_byte(0xE000, 0x11));
+    program.push(0x00);
+
+    let mut cpu = run_program(bank_switching_rom(34, 0, 16, 0), program);
+
+    assert_eq!(cpu.mem_read(0x10), 8);
+    assert_eq!(cpu.mem_read(0x11), 11);
+}
+
+#[test]
+fn test_nina001_rom_switches_banks() {
+    let mut program = write_register(0x7FFD, 1);
+    program.extend(write_register(0x7FFE, 3));
+    program.extend(write_register(0x7FFF, 5));
+    program.extend(write_register(0x6000, 0x42));
+    program.extend(copy_byte(0x8000, 0x10));
+    program.extend(copy_byte(0x6000, 0x11));
+    program.extend(copy_chr_byte(0x0000, 0x12));
+    program.extend(copy_chr_byte(0x1400, 0x13));
+    program.push(0x00);
+
+    let mut cpu = run_program(bank_switching_rom(34, 0, 8, 64), program);
+
+    assert_eq!(cpu.mem_read(0x10), 4);
+    assert_eq!(cpu.mem_read(0x11), 0x42);
+    // 4KB banks are four 1KB ones
+    assert_eq!(cpu.mem_read(0x12), 12);
+    assert_eq!(cpu.mem_read(0x13), 21);
+}
+
+#[test]
+fn test_mapper_34_submapper_picks_the_board() {
+    let mut program = write_register(0x8000, 1);
+    program.extend(copy_byte(0x8000, 0x10));
+    program.push(0x00);
+
+    // a BNROM board with CHR ROM, which the size alone would take for NINA-001
+    let mut cpu = run_program(bank_switching_rom(34, 2, 8, 64), program);
+
+    assert_eq!(cpu.mem_read(0x10), 4);
+}
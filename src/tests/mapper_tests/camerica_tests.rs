@@ -0,0 +1,30 @@
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::camerica::Camerica;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::mapper_test_helper::{bank_switching_rom, banked_rom, copy_byte, run_program, write_register};
+
+#[test]
+fn test_camerica_rom_switches_banks() {
+    let mut program = write_register(0xC000, 5);
+    program.extend(copy_byte(0x8000, 0x10));
+    program.extend(copy_byte(0xA000, 0x11));
+    program.extend(copy_byte(0xC000, 0x12));
+    program.push(0x00);
+
+    let mut cpu = run_program(bank_switching_rom(71, 0, 16, 0), program);
+
+    assert_eq!(cpu.mem_read(0x10), 10);
+    assert_eq!(cpu.mem_read(0x11), 11);
+    assert_eq!(cpu.mem_read(0x12), 14);
+}
+
+#[test]
+fn test_camerica_fire_hawk_mirroring() {
+    let mut mapper = Camerica::new(banked_rom(71, 16, 0));
+
+    mapper.write_prg(0x9000, 0b1_0000);
+    assert_eq!(mapper.mirroring(), Mirroring::SINGLE_SCREEN_UPPER);
+    mapper.write_prg(0x9000, 0);
+    assert_eq!(mapper.mirroring(), Mirroring::SINGLE_SCREEN_LOWER);
+}
@@ -0,0 +1,28 @@
+use crate::components::mapper::color_dreams::ColorDreams;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::mapper_test_helper::{banked_rom, bank_switching_rom, copy_byte, copy_chr_byte, run_program, write_register};
+
+#[test]
+fn test_color_dreams_rom_switches_banks() {
+    let mut program = write_register(0x8000, 0x32);
+    program.extend(copy_byte(0x8000, 0x10));
+    program.extend(copy_byte(0xE000, 0x11));
+    program.extend(copy_chr_byte(0x0400, 0x12));
+    program.push(0x00);
+
+    let mut cpu = run_program(bank_switching_rom(11, 0, 16, 64), program);
+
+    assert_eq!(cpu.mem_read(0x10), 8);
+    assert_eq!(cpu.mem_read(0x11), 11);
+    // 8KB CHR bank 3 starts at 1KB bank 24
+    assert_eq!(cpu.mem_read(0x12), 25);
+}
+
+#[test]
+fn test_color_dreams_without_chr_rom_gets_chr_ram() {
+    let mut mapper = ColorDreams::new(banked_rom(11, 4, 0));
+    mapper.write_chr(0x1234, 0x56);
+
+    assert_eq!(mapper.read_chr(0x1234), 0x56);
+}
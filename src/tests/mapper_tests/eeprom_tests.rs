@@ -0,0 +1,77 @@
+use crate::components::mapper::eeprom::{Eeprom, EepromKind};
+use crate::tests::test_helpers::mapper_test_helper::I2cHost;
+
+fn host(eeprom: &mut Eeprom, msb_first: bool) -> I2cHost<impl FnMut(bool, bool) -> bool + '_> {
+    I2cHost {
+        set_lines: move |scl, sda| {
+            eeprom.write(scl, sda);
+            eeprom.output()
+        },
+        msb_first,
+    }
+}
+
+#[test]
+fn test_24c02_write_and_random_read() {
+    let mut eeprom = Eeprom::new(EepromKind::X24C02);
+
+    let mut i2c = host(&mut eeprom, true);
+    i2c.start();
+    assert!(i2c.send_byte(0xA0));
+    assert!(i2c.send_byte(0x10));
+    assert!(i2c.send_byte(0x42));
+    assert!(i2c.send_byte(0x43));
+    i2c.stop();
+
+    i2c.start();
+    assert!(i2c.send_byte(0xA0));
+    assert!(i2c.send_byte(0x10));
+    i2c.start();
+    assert!(i2c.send_byte(0xA1));
+    assert_eq!(i2c.read_byte(true), 0x42);
+    assert_eq!(i2c.read_byte(false), 0x43);
+    i2c.stop();
+    drop(i2c);
+
+    assert_eq!(&eeprom.data()[0x10..0x12], &[0x42, 0x43]);
+}
+
+#[test]
+fn test_24c02_ignores_other_devices() {
+    let mut eeprom = Eeprom::new(EepromKind::X24C02);
+
+    let mut i2c = host(&mut eeprom, true);
+    i2c.start();
+    assert!(!i2c.send_byte(0x50));
+    i2c.stop();
+}
+
+#[test]
+fn test_24c01_write_and_read() {
+    let mut eeprom = Eeprom::new(EepromKind::X24C01);
+
+    let mut i2c = host(&mut eeprom, false);
+    i2c.start();
+    // 7 bit address followed by the write bit
+    assert!(i2c.send_byte(0x05));
+    assert!(i2c.send_byte(0x99));
+    i2c.stop();
+
+    i2c.start();
+    assert!(i2c.send_byte(0x80 | 0x05));
+    assert_eq!(i2c.read_byte(false), 0x99);
+    i2c.stop();
+    drop(i2c);
+
+    assert_eq!(eeprom.data().len(), 128);
+    assert_eq!(eeprom.data()[5], 0x99);
+}
+
+#[test]
+fn test_eeprom_load_checks_size() {
+    let mut eeprom = Eeprom::new(EepromKind::X24C02);
+
+    assert!(eeprom.load(&[0; 128]).is_err());
+    assert!(eeprom.load(&[7; 256]).is_ok());
+    assert_eq!(eeprom.data()[255], 7);
+}
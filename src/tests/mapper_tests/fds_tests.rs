@@ -0,0 +1,289 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::components::bus::Bus;
+use crate::components::cartridge::fds::FdsImage;
+use crate::components::cartridge::ips;
+use crate::components::cartridge::Mirroring;
+use crate::components::cpu::CPU;
+use crate::components::mapper::fds::Fds;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+use crate::tests::test_helpers::cpu_test_helper;
+use crate::tests::test_helpers::fds_test_helper::{disk_info_block, test_bios, test_disk_side, wait_for_transfer};
+
+fn test_fds(sides: Vec<Vec<u8>>) -> Fds {
+    let raw: Vec<u8> = sides.into_iter().flatten().collect();
+    Fds::new(test_bios(0x6000, 0x7000), FdsImage::new(&raw).unwrap()).unwrap()
+}
+
+#[test]
+fn test_fds_bios_with_wrong_size_not_supported() {
+    let image = FdsImage::new(&test_disk_side(&[])).unwrap();
+
+    match Fds::new(vec![0; 0x1000], image) {
+        Result::Ok(_) => assert!(false, "should not create fds"),
+        Result::Err(str) => assert_eq!(str, "FDS BIOS must be 8192 bytes, got 4096"),
+    }
+}
+
+#[test]
+fn test_fds_memory_map() {
+    let mut fds = test_fds(vec![test_disk_side(&[])]);
+
+    fds.write_prg(0x6000, 0x11);
+    fds.write_prg(0xDFFF, 0x22);
+    fds.write_chr(0x1FFF, 0x33);
+    fds.write_prg(0xFFFC, 0xFF);
+
+    assert_eq!(fds.read_prg(0x6000), 0x11);
+    assert_eq!(fds.read_prg(0xDFFF), 0x22);
+    assert_eq!(fds.read_chr(0x1FFF), 0x33);
+    assert_eq!(fds.read_prg(0xFFFD), 0x60);
+}
+
+#[test]
+fn test_fds_mirroring_is_controlled_by_4025() {
+    let mut fds = test_fds(vec![test_disk_side(&[])]);
+    fds.write_prg(0x4023, 0x01);
+
+    fds.write_prg(0x4025, 0b0010_1000);
+    assert_eq!(fds.mirroring(), Mirroring::HORIZONTAL);
+
+    fds.write_prg(0x4025, 0b0010_0000);
+    assert_eq!(fds.mirroring(), Mirroring::VERTICAL);
+}
+
+#[test]
+fn test_fds_timer_irq_interrupts_cpu() {
+    let fds = Rc::new(RefCell::new(test_fds(vec![test_disk_side(&[])])));
+    let mut cpu = CPU::new(Bus::with_mapper(fds.clone()));
+
+    let mut program = vec![];
+    program.extend(cpu_test_helper::set_accumulator_to_value(0x01));
+    program.extend(cpu_test_helper::store_accumulator_to_memory_address(0x4023));
+    program.extend(cpu_test_helper::set_accumulator_to_value(0x40));
+    program.extend(cpu_test_helper::store_accumulator_to_memory_address(0x4020));
+    program.extend(cpu_test_helper::set_accumulator_to_value(0x00));
+    program.extend(cpu_test_helper::store_accumulator_to_memory_address(0x4021));
+    program.extend(cpu_test_helper::set_accumulator_to_value(0x02));
+    program.extend(cpu_test_helper::store_accumulator_to_memory_address(0x4022));
+    program.push(0x58); // CLI
+    let loop_address = (0x6000 + program.len() as u16).to_le_bytes();
+    program.extend([0x4C, loop_address[0], loop_address[1]]); // JMP to itself
+
+    let irq_handler = cpu_test_helper::set_register_x_to_value(0x42);
+    cpu.mem_write(0x7000, irq_handler[0]);
+    cpu.mem_write(0x7001, irq_handler[1]);
+    cpu.mem_write(0x7002, 0x00);
+
+    cpu.load_and_run(program);
+
+    assert_eq!(cpu.register_x, 0x42);
+    cpu_test_helper::assert_active_interrupt_flag(&cpu);
+    assert!(fds.borrow().irq_pending());
+    assert_eq!(cpu.mem_read(0x4030) & 0b1, 0b1);
+    assert_eq!(cpu.mem_read(0x4030) & 0b1, 0);
+}
+
+#[test]
+fn test_fds_timer_irq_needs_disk_registers_enabled() {
+    let mut fds = test_fds(vec![test_disk_side(&[])]);
+
+    fds.write_prg(0x4020, 0x02);
+    fds.write_prg(0x4021, 0x00);
+    fds.write_prg(0x4022, 0x02);
+    fds.tick(10);
+
+    assert!(!fds.irq_pending());
+}
+
+#[test]
+fn test_fds_timer_irq_repeats() {
+    let mut fds = test_fds(vec![test_disk_side(&[])]);
+    fds.write_prg(0x4023, 0x01);
+    fds.write_prg(0x4020, 0x02);
+    fds.write_prg(0x4021, 0x00);
+    fds.write_prg(0x4022, 0x03);
+
+    fds.tick(3);
+    assert!(fds.irq_pending());
+    fds.read_prg(0x4030);
+    assert!(!fds.irq_pending());
+
+    fds.tick(3);
+    assert!(fds.irq_pending());
+}
+
+#[test]
+fn test_fds_drive_reads_disk_blocks() {
+    let mut fds = test_fds(vec![test_disk_side(&[])]);
+    fds.write_prg(0x4023, 0x01);
+    fds.write_prg(0x4025, 0b0110_0101); // ready, read mode, motor on
+
+    wait_for_transfer(&mut fds);
+    assert_eq!(fds.read_prg(0x4031), 0x80);
+
+    for expected in disk_info_block().iter().take(15) {
+        wait_for_transfer(&mut fds);
+        assert_eq!(fds.read_prg(0x4031), *expected);
+    }
+}
+
+#[test]
+fn test_fds_drive_raises_irq_on_byte_transfer() {
+    let mut fds = test_fds(vec![test_disk_side(&[])]);
+    fds.write_prg(0x4023, 0x01);
+    fds.write_prg(0x4025, 0b1110_0101); // irq enabled, ready, read mode, motor on
+
+    while !fds.irq_pending() {
+        fds.tick(1);
+    }
+
+    assert_eq!(fds.read_prg(0x4031), 0x01);
+    assert!(!fds.irq_pending());
+}
+
+#[test]
+fn test_fds_side_switching() {
+    let mut fds = test_fds(vec![test_disk_side(&[1]), test_disk_side(&[2])]);
+    fds.write_prg(0x4023, 0x01);
+
+    assert_eq!(fds.side_count(), 2);
+    assert_eq!(fds.inserted_side(), Some(0));
+
+    fds.eject_disk();
+    assert_eq!(fds.inserted_side(), None);
+    assert_eq!(fds.read_prg(0x4032) & 0b1, 0b1);
+
+    fds.insert_disk(1).unwrap();
+    assert_eq!(fds.inserted_side(), Some(1));
+    assert_eq!(fds.read_prg(0x4032) & 0b1, 0);
+}
+
+#[test]
+fn test_fds_refuses_to_insert_a_missing_side() {
+    let mut fds = test_fds(vec![test_disk_side(&[1])]);
+    fds.eject_disk();
+
+    assert_eq!(fds.insert_disk(1), Err("Disk side 1 doesn't exist, the image has 1 sides".to_string()));
+    assert_eq!(fds.inserted_side(), None);
+}
+
+#[test]
+fn test_fds_state_with_disk_position_past_the_side_is_refused() {
+    let mut fds = test_fds(vec![test_disk_side(&[])]);
+    fds.write_prg(0x4023, 0x01);
+    fds.write_prg(0x4025, 0b0110_0101);
+    wait_for_transfer(&mut fds);
+
+    let mut state = StateWriter::new();
+    fds.save_state(&mut state);
+    let mut data = state.into_bytes();
+    // the position is followed by the delay, three flags and the CRC
+    let position = data.len() - 17;
+    data[position..position + 8].copy_from_slice(&(1u64 << 20).to_le_bytes());
+
+    let result = fds.load_state(&mut StateReader::new(&data));
+
+    assert_eq!(result, Err("Invalid disk position 1048576 in save state".to_string()));
+}
+
+#[test]
+fn test_fds_state_with_missing_side_inserted_is_refused() {
+    let mut fds = test_fds(vec![test_disk_side(&[1]), test_disk_side(&[2])]);
+    let mut state = StateWriter::new();
+    fds.save_state(&mut state);
+    let mut data = state.into_bytes();
+    // the inserted side comes before 39 bytes of drive and timer registers
+    let side = data.len() - 39 - 8;
+    data[side..side + 8].copy_from_slice(&2u64.to_le_bytes());
+
+    let result = fds.load_state(&mut StateReader::new(&data));
+
+    assert_eq!(result, Err("Save state has disk side 2 inserted, the disk only has 2".to_string()));
+}
+
+#[test]
+fn test_fds_drive_reads_changes_from_an_earlier_session() {
+    let original = test_disk_side(&[]);
+    let mut changed = original.clone();
+    changed[1] = b'X';
+    let patch = ips::create(&original, &changed).unwrap();
+    let mut fds = Fds::with_changes(test_bios(0x6000, 0x7000), FdsImage::new(&original).unwrap(), &patch).unwrap();
+    fds.write_prg(0x4023, 0x01);
+    fds.write_prg(0x4025, 0b0110_0101);
+
+    for expected in [0x80, 0x01, b'X', b'N'] {
+        wait_for_transfer(&mut fds);
+        assert_eq!(fds.read_prg(0x4031), expected);
+    }
+    assert_eq!(fds.disk_changes(), None);
+}
+
+#[test]
+fn test_fds_unmodified_disk_has_no_changes() {
+    let mut fds = test_fds(vec![test_disk_side(&[1, 2, 3])]);
+    fds.write_prg(0x4023, 0x01);
+    fds.write_prg(0x4025, 0b0110_0101);
+    fds.tick(255);
+
+    assert_eq!(fds.disk_changes(), None);
+}
+
+#[test]
+fn test_fds_disk_writes_are_saved_as_changes() {
+    let blank_side = vec![0; test_disk_side(&[]).len()];
+    let original = FdsImage::new(&blank_side).unwrap();
+    let mut fds = test_fds(vec![blank_side.clone()]);
+    fds.write_prg(0x4023, 0x01);
+
+    fds.write_prg(0x4025, 0b0010_0001); // write mode, motor on, writing the gap
+    for _ in 0..10 {
+        wait_for_transfer(&mut fds);
+    }
+
+    fds.write_prg(0x4024, 0x80);
+    fds.write_prg(0x4025, 0b0110_0001);
+    let block = disk_info_block();
+    for byte in &block {
+        wait_for_transfer(&mut fds);
+        fds.write_prg(0x4024, *byte);
+    }
+    wait_for_transfer(&mut fds);
+
+    fds.write_prg(0x4025, 0b0111_0001); // write the CRC, the drive doesn't flag these transfers
+    for _ in 0..300 {
+        fds.tick(1);
+    }
+    fds.write_prg(0x4025, 0b0010_0000);
+
+    let changes = fds.disk_changes().unwrap();
+    let restored = FdsImage::with_changes(&original.to_bytes(), &changes).unwrap();
+
+    assert_eq!(restored.sides[0][..56], block[..]);
+    assert_eq!(restored.sides[0][56..], blank_side[56..]);
+}
+
+#[test]
+fn test_fds_power_on_stops_the_drive_and_keeps_the_disk() {
+    let mut fds = test_fds(vec![test_disk_side(&[1]), test_disk_side(&[2])]);
+    fds.eject_disk();
+    fds.insert_disk(1).unwrap();
+    fds.write_prg(0x6000, 0x11);
+    fds.write_prg(0x4023, 0x01);
+    fds.write_prg(0x4025, 0b1110_1101); // irq enabled, horizontal, ready, read mode, motor on
+    while !fds.irq_pending() {
+        fds.tick(1);
+    }
+
+    fds.power_on();
+
+    assert!(!fds.irq_pending());
+    assert_eq!(fds.mirroring(), Mirroring::VERTICAL);
+    assert_eq!(fds.inserted_side(), Some(1));
+    assert_eq!(fds.read_prg(0x6000), 0x11);
+    // the disk registers are off again
+    assert_eq!(fds.read_prg(0x4032), 0);
+}
@@ -0,0 +1,126 @@
+use crate::components::apu::PULSE_FULL_VOLUME;
+use crate::components::bus::Bus;
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::fme7::Fme7;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+
+fn fme7() -> Fme7 {
+    Fme7::new(banked_rom(69, 16, 32))
+}
+
+fn write_command(mapper: &mut Fme7, command: u8, parameter: u8) {
+    mapper.write_prg(0x8000, command);
+    mapper.write_prg(0xA000, parameter);
+}
+
+#[test]
+fn test_fme7_prg_banking() {
+    let mut mapper = fme7();
+    write_command(&mut mapper, 0x9, 3);
+    write_command(&mut mapper, 0xA, 4);
+    write_command(&mut mapper, 0xB, 5);
+    write_command(&mut mapper, 0x8, 6);
+
+    assert_eq!(mapper.read_prg(0x6000), 6);
+    assert_eq!(mapper.read_prg(0x8000), 3);
+    assert_eq!(mapper.read_prg(0xA000), 4);
+    assert_eq!(mapper.read_prg(0xC000), 5);
+    assert_eq!(mapper.read_prg(0xE000), 15);
+}
+
+#[test]
+fn test_fme7_prg_ram() {
+    let mut mapper = fme7();
+
+    // selected but disabled
+    write_command(&mut mapper, 0x8, 0b0100_0000);
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0);
+
+    write_command(&mut mapper, 0x8, 0b1100_0000);
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0x42);
+}
+
+#[test]
+fn test_fme7_chr_banking_and_mirroring() {
+    let mut mapper = fme7();
+    for command in 0..8 {
+        write_command(&mut mapper, command, 20 + command);
+    }
+    write_command(&mut mapper, 0xC, 2);
+
+    assert_eq!(mapper.read_chr(0x0000), 20);
+    assert_eq!(mapper.read_chr(0x1C00), 27);
+    assert_eq!(mapper.mirroring(), Mirroring::SINGLE_SCREEN_LOWER);
+}
+
+#[test]
+fn test_fme7_irq_fires_when_the_counter_wraps() {
+    let mut mapper = fme7();
+    write_command(&mut mapper, 0xE, 3);
+    write_command(&mut mapper, 0xF, 0);
+    write_command(&mut mapper, 0xD, 0b1000_0001);
+
+    mapper.tick(3);
+    assert!(!mapper.irq_pending());
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+
+    // writing the control register acknowledges it
+    write_command(&mut mapper, 0xD, 0b1000_0001);
+    assert!(!mapper.irq_pending());
+}
+
+#[test]
+fn test_fme7_irq_counter_can_run_without_irq() {
+    let mut mapper = fme7();
+    write_command(&mut mapper, 0xD, 0b1000_0000);
+
+    mapper.tick(10);
+    assert!(!mapper.irq_pending());
+}
+
+#[test]
+fn test_fme7_plays_sunsoft_5b_audio() {
+    let mut bus = Bus::new(banked_rom(69, 16, 32)).unwrap();
+    bus.apu().set_sample_rate(44100);
+    let silence = bus.apu().mix();
+
+    // tone A alone at full volume, high after its first step
+    for (register, value) in [(0, 0x01), (7, 0b0011_1110), (8, 0x0F)] {
+        bus.mem_write(0xC000, register);
+        bus.mem_write(0xE000, value);
+    }
+    // expansion audio is read once per tick, single cycles follow every edge
+    for _ in 0..1020 {
+        bus.tick(1);
+    }
+
+    // the square wave spends half its time at twice a full 2A03 pulse
+    let samples = bus.apu().take_samples();
+    let average = samples.iter().map(|sample| sample - silence).sum::<f32>() / samples.len() as f32;
+    assert!((average - PULSE_FULL_VOLUME).abs() < 0.05 * PULSE_FULL_VOLUME, "average level {}", average);
+}
+
+#[test]
+fn test_fme7_power_on_resets_registers_and_keeps_ram() {
+    let mut mapper = fme7();
+    write_command(&mut mapper, 0x8, 0b1100_0000);
+    mapper.write_prg(0x6000, 0x42);
+    write_command(&mut mapper, 0x9, 3);
+    write_command(&mut mapper, 0xC, 2);
+    write_command(&mut mapper, 0xD, 0b1000_0001);
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+
+    mapper.power_on();
+
+    assert!(!mapper.irq_pending());
+    assert_eq!(mapper.read_prg(0x8000), 0);
+    assert_eq!(mapper.mirroring(), Mirroring::VERTICAL);
+    write_command(&mut mapper, 0x8, 0b1100_0000);
+    assert_eq!(mapper.read_prg(0x6000), 0x42);
+}
@@ -0,0 +1,153 @@
+use crate::components::bus::Bus;
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::cpu::CPU;
+use crate::components::mapper::mmc2::Mmc2;
+use crate::components::mapper::Mapper;
+use crate::components::ppu::palette::SYSTEM_PALETTE;
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+use crate::tests::test_helpers::rom_test_helper::{create_rom, TestRom};
+
+fn mmc2(mapper: u8) -> Mmc2 {
+    Mmc2::new(banked_rom(mapper, 16, 64))
+}
+
+#[test]
+fn test_mmc2_prg_banking() {
+    let mut mapper = mmc2(9);
+    mapper.write_prg(0xA000, 5);
+
+    assert_eq!(mapper.read_prg(0x8000), 5);
+    assert_eq!(mapper.read_prg(0xA000), 13);
+    assert_eq!(mapper.read_prg(0xC000), 14);
+    assert_eq!(mapper.read_prg(0xE000), 15);
+}
+
+#[test]
+fn test_mmc4_prg_banking() {
+    let mut mapper = mmc2(10);
+    mapper.write_prg(0xA000, 2);
+
+    assert_eq!(mapper.read_prg(0x8000), 4);
+    assert_eq!(mapper.read_prg(0xA000), 5);
+    assert_eq!(mapper.read_prg(0xC000), 14);
+    assert_eq!(mapper.read_prg(0xE000), 15);
+
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0x42);
+}
+
+#[test]
+fn test_mmc2_latches_flip_after_the_triggering_fetch() {
+    let mut mapper = mmc2(9);
+    mapper.write_prg(0xB000, 1);
+    mapper.write_prg(0xC000, 2);
+    mapper.write_prg(0xD000, 3);
+    mapper.write_prg(0xE000, 4);
+
+    // both latches power on as $FE
+    assert_eq!(mapper.read_chr(0x0000), 8);
+    assert_eq!(mapper.read_chr(0x1000), 16);
+
+    assert_eq!(mapper.read_chr(0x0FD8), 11);
+    assert_eq!(mapper.read_chr(0x0000), 4);
+    assert_eq!(mapper.read_chr(0x1000), 16);
+
+    assert_eq!(mapper.read_chr(0x1FDC), 19);
+    assert_eq!(mapper.read_chr(0x1000), 12);
+
+    mapper.read_chr(0x0FE8);
+    mapper.read_chr(0x1FE8);
+    assert_eq!(mapper.read_chr(0x0000), 8);
+    assert_eq!(mapper.read_chr(0x1000), 16);
+}
+
+#[test]
+fn test_mmc2_left_latch_only_reacts_to_exact_addresses() {
+    let mut mmc2 = mmc2(9);
+    let mut mmc4 = Mmc2::new(banked_rom(10, 16, 64));
+    for mapper in [&mut mmc2, &mut mmc4] {
+        mapper.write_prg(0xB000, 1);
+        mapper.write_prg(0xC000, 2);
+        mapper.read_chr(0x0FDA);
+    }
+
+    assert_eq!(mmc2.read_chr(0x0000), 8);
+    assert_eq!(mmc4.read_chr(0x0000), 4);
+}
+
+#[test]
+fn test_mmc2_without_chr_rom_gets_chr_ram() {
+    let mut mapper = Mmc2::new(banked_rom(9, 16, 0));
+    mapper.write_chr(0x1234, 0x56);
+
+    assert_eq!(mapper.read_chr(0x1234), 0x56);
+}
+
+#[test]
+fn test_mmc2_mirroring() {
+    let mut mapper = mmc2(9);
+    mapper.write_prg(0xF000, 1);
+    assert_eq!(mapper.mirroring(), Mirroring::HORIZONTAL);
+    mapper.write_prg(0xF000, 0);
+    assert_eq!(mapper.mirroring(), Mirroring::VERTICAL);
+}
+
+fn store(value: u8, addr: u16) -> Vec<u8> {
+    let addr = addr.to_le_bytes();
+    vec![0xA9, value, 0x8D, addr[0], addr[1]] // LDA #value, STA addr
+}
+
+// A mapper 9 cartridge whose program draws 1, $FD, 1, $FE, 1 on the first row. Tile 1 is solid
+// in the $FE bank and empty in the $FD one, so the middle tile only shows the latch flipping.
+fn latch_test_rom() -> Rom {
+    let mut program = vec![];
+    program.extend(store(1, 0xB000));
+    program.extend(store(0, 0xC000));
+    program.extend(store(0x3F, 0x2006));
+    program.extend(store(0x00, 0x2006));
+    program.extend(store(0x0F, 0x2007));
+    program.extend(store(0x30, 0x2007));
+    program.extend(store(0x20, 0x2006));
+    program.extend(store(0x00, 0x2006));
+    for tile in [1, 0xFD, 1, 0xFE, 1] {
+        program.extend(store(tile, 0x2007));
+    }
+    program.extend(store(0b0000_1010, 0x2001));
+    program.push(0x00);
+
+    let mut prg_rom = vec![0; 2 * Rom::PRG_ROM_PAGE_SIZE];
+    let last_bank = prg_rom.len() - 0x2000;
+    prg_rom[last_bank..last_bank + program.len()].copy_from_slice(&program);
+    prg_rom[0x7FFC] = 0x00;
+    prg_rom[0x7FFD] = 0xE0;
+
+    let mut chr_rom = vec![0; Rom::CHR_ROM_PAGE_SIZE];
+    for row in 0..8 {
+        chr_rom[16 + row] = 0xFF;
+    }
+
+    let raw = create_rom(TestRom {
+        header: vec![0x4E, 0x45, 0x53, 0x1A, 2, 1, 0x90, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        trainer: None,
+        pgp_rom: prg_rom,
+        chr_rom,
+    });
+    Rom::new(&raw).unwrap()
+}
+
+#[test]
+fn test_mmc2_rom_flips_bank_mid_scanline() {
+    let mut cpu = CPU::new(Bus::new(latch_test_rom()).unwrap());
+    cpu.reset();
+    cpu.run();
+    while cpu.bus.ppu().scanline() != 1 {
+        cpu.bus.tick(1);
+    }
+
+    let frame = cpu.bus.ppu().frame();
+    let white = SYSTEM_PALETTE[0x30];
+    let black = SYSTEM_PALETTE[0x0F];
+    assert_eq!(frame.pixel(0, 0), white);
+    assert_eq!(frame.pixel(16, 0), black);
+    assert_eq!(frame.pixel(32, 0), white);
+}
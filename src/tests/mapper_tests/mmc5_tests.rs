@@ -0,0 +1,258 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::components::mapper::mmc5::Mmc5;
+use crate::components::mapper::{Mapper, PpuFetchPhase};
+use crate::components::ppu::palette::SYSTEM_PALETTE;
+use crate::components::ppu::NesPPU;
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+use crate::tests::test_helpers::ppu_test_helper::{run_ppu_to_scanline, write_ppu_memory};
+
+fn mmc5() -> Mmc5 {
+    Mmc5::new(banked_rom(5, 16, 64))
+}
+
+#[test]
+fn test_mmc5_powers_on_with_last_bank_everywhere_in_mode_3() {
+    let mut mapper = mmc5();
+
+    assert_eq!(mapper.read_prg(0xE000), 15);
+    assert_eq!(mapper.read_prg(0xFFFF), 15);
+}
+
+#[test]
+fn test_mmc5_prg_mode_0_maps_32k() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5100, 0);
+    mapper.write_prg(0x5117, 0x85);
+
+    // the low two bits are ignored for a 32KB bank
+    assert_eq!(mapper.read_prg(0x8000), 4);
+    assert_eq!(mapper.read_prg(0xA000), 5);
+    assert_eq!(mapper.read_prg(0xE000), 7);
+}
+
+#[test]
+fn test_mmc5_prg_mode_1_maps_two_16k_banks() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5100, 1);
+    mapper.write_prg(0x5115, 0x83);
+    mapper.write_prg(0x5117, 0x8B);
+
+    assert_eq!(mapper.read_prg(0x8000), 2);
+    assert_eq!(mapper.read_prg(0xA000), 3);
+    assert_eq!(mapper.read_prg(0xC000), 10);
+    assert_eq!(mapper.read_prg(0xE000), 11);
+}
+
+#[test]
+fn test_mmc5_prg_mode_3_maps_8k_banks() {
+    let mut mapper = mmc5();
+    for (register, bank) in [(0x5114, 0x81), (0x5115, 0x82), (0x5116, 0x83), (0x5117, 0x04)] {
+        mapper.write_prg(register, bank);
+    }
+
+    assert_eq!(mapper.read_prg(0x8000), 1);
+    assert_eq!(mapper.read_prg(0xA000), 2);
+    assert_eq!(mapper.read_prg(0xC000), 3);
+    // $5117 always maps ROM
+    assert_eq!(mapper.read_prg(0xE000), 4);
+}
+
+#[test]
+fn test_mmc5_prg_ram_needs_both_protect_registers() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5113, 2);
+
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0);
+
+    mapper.write_prg(0x5102, 0b10);
+    mapper.write_prg(0x5103, 0b01);
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0x42);
+
+    // the same RAM bank can be mapped into the ROM area
+    mapper.write_prg(0x5114, 0x02);
+    assert_eq!(mapper.read_prg(0x8000), 0x42);
+}
+
+#[test]
+fn test_mmc5_8x16_sprites_use_separate_chr_sets() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5101, 3);
+    for register in 0..8 {
+        mapper.write_prg(0x5120 + register, 10 + register as u8);
+    }
+    for register in 0..4 {
+        mapper.write_prg(0x5128 + register, 20 + register as u8);
+    }
+
+    // 8x8 sprites use set A for everything
+    mapper.ppu_fetch_phase(PpuFetchPhase::Background);
+    assert_eq!(mapper.read_chr(0x0400), 11);
+
+    mapper.ppu_register_written(0x2000, 0b0010_0000);
+    assert_eq!(mapper.read_chr(0x0400), 21);
+    assert_eq!(mapper.read_chr(0x1400), 21);
+
+    mapper.ppu_fetch_phase(PpuFetchPhase::Sprites);
+    assert_eq!(mapper.read_chr(0x1400), 15);
+
+    // outside rendering the last written set is used
+    mapper.ppu_fetch_phase(PpuFetchPhase::Idle);
+    assert_eq!(mapper.read_chr(0x0800), 22);
+    mapper.write_prg(0x5122, 12);
+    assert_eq!(mapper.read_chr(0x0800), 12);
+}
+
+#[test]
+fn test_mmc5_nametable_mapping() {
+    let mut mapper = mmc5();
+    let mut ciram = [0; 2048];
+    // CIRAM page 1, ExRAM, fill, CIRAM page 0
+    mapper.write_prg(0x5105, 0b00_11_10_01);
+    mapper.write_prg(0x5106, 0x42);
+    mapper.write_prg(0x5107, 2);
+
+    mapper.write_nametable(0x2005, 0x11, &mut ciram);
+    mapper.write_nametable(0x2405, 0x22, &mut ciram);
+    mapper.write_nametable(0x2C05, 0x33, &mut ciram);
+
+    assert_eq!(ciram[0x405], 0x11);
+    assert_eq!(ciram[0x005], 0x33);
+    assert_eq!(mapper.read_nametable(0x2405, &ciram), 0x22);
+    assert_eq!(mapper.read_prg(0x5C05), 0);
+    assert_eq!(mapper.read_nametable(0x2805, &ciram), 0x42);
+    assert_eq!(mapper.read_nametable(0x2BC0, &ciram), 0xAA);
+}
+
+#[test]
+fn test_mmc5_exram_as_work_ram() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5104, 2);
+    mapper.write_prg(0x5C10, 0x42);
+    assert_eq!(mapper.read_prg(0x5C10), 0x42);
+
+    // read only in mode 3
+    mapper.write_prg(0x5104, 3);
+    mapper.write_prg(0x5C10, 0x24);
+    assert_eq!(mapper.read_prg(0x5C10), 0x42);
+}
+
+#[test]
+fn test_mmc5_extended_attributes() {
+    let mut mapper = mmc5();
+    let ciram = [0; 2048];
+    mapper.write_prg(0x5104, 1);
+    mapper.write_prg(0x5C03, 0b1100_0010);
+
+    mapper.ppu_fetch_phase(PpuFetchPhase::Background);
+    mapper.read_nametable(0x2003, &ciram);
+    assert_eq!(mapper.read_nametable(0x23C0, &ciram), 0xFF);
+    // 4KB page 2 starts at 1KB bank 8
+    assert_eq!(mapper.read_chr(0x0010), 8);
+    assert_eq!(mapper.read_chr(0x1C00), 11);
+}
+
+#[test]
+fn test_mmc5_vertical_split() {
+    let mut mapper = mmc5();
+    let mut ciram = [0; 2048];
+    ciram[2] = 0x77;
+    mapper.write_prg(0x5104, 0);
+    mapper.write_prg(0x5200, 0x80 | 2);
+    mapper.write_prg(0x5201, 8);
+    mapper.write_prg(0x5202, 1);
+    // the split scrolled down by 8 starts at its second row
+    mapper.write_prg(0x5C20, 0x11);
+    mapper.write_prg(0x5C21, 0x12);
+
+    mapper.ppu_scanline(0, true);
+    mapper.ppu_fetch_phase(PpuFetchPhase::Background);
+
+    assert_eq!(mapper.read_nametable(0x2000, &ciram), 0x11);
+    mapper.read_nametable(0x23C0, &ciram);
+    assert_eq!(mapper.read_chr(0x0110), 4);
+    assert_eq!(mapper.read_nametable(0x2001, &ciram), 0x12);
+    mapper.read_nametable(0x23C0, &ciram);
+    assert_eq!(mapper.read_nametable(0x2002, &ciram), 0x77);
+}
+
+#[test]
+fn test_mmc5_scanline_irq() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5203, 3);
+    mapper.write_prg(0x5204, 0x80);
+
+    for scanline in 0..3 {
+        mapper.ppu_scanline(scanline, true);
+    }
+    assert!(!mapper.irq_pending());
+    assert_eq!(mapper.read_prg(0x5204), 0x40);
+
+    mapper.ppu_scanline(3, true);
+    assert!(mapper.irq_pending());
+    assert_eq!(mapper.read_prg(0x5204), 0xC0);
+    assert!(!mapper.irq_pending());
+
+    mapper.ppu_scanline(240, true);
+    assert_eq!(mapper.read_prg(0x5204), 0);
+}
+
+#[test]
+fn test_mmc5_multiplier() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5205, 200);
+    mapper.write_prg(0x5206, 123);
+
+    let product = 200u16 * 123;
+    assert_eq!(mapper.read_prg(0x5205), product as u8);
+    assert_eq!(mapper.read_prg(0x5206), (product >> 8) as u8);
+}
+
+#[test]
+fn test_mmc5_fill_mode_renders_through_ppu() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5101, 3);
+    // every byte of 1KB bank 1 is 0x01, giving color 3 on the last pixel of each tile row
+    mapper.write_prg(0x5120, 1);
+    mapper.write_prg(0x5105, 0xFF);
+    mapper.write_prg(0x5107, 2);
+    let mut ppu = NesPPU::new(Rc::new(RefCell::new(mapper)));
+
+    write_ppu_memory(&mut ppu, 0x3F00, &[0x0F]);
+    write_ppu_memory(&mut ppu, 0x3F0B, &[0x30]);
+    ppu.write_to_mask_register(0b0000_1010);
+    run_ppu_to_scanline(&mut ppu, 1);
+
+    assert_eq!(ppu.frame().pixel(0, 0), SYSTEM_PALETTE[0x0F]);
+    assert_eq!(ppu.frame().pixel(7, 0), SYSTEM_PALETTE[0x30]);
+    assert_eq!(ppu.frame().pixel(15, 0), SYSTEM_PALETTE[0x30]);
+}
+
+#[test]
+fn test_mmc5_reset_leaves_the_frame_and_keeps_the_banks() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5117, 3);
+    mapper.ppu_scanline(0, true);
+    assert_eq!(mapper.read_prg(0x5204), 0x40);
+
+    mapper.reset();
+
+    assert_eq!(mapper.read_prg(0x5204), 0);
+    assert_eq!(mapper.read_prg(0xE000), 3);
+}
+
+#[test]
+fn test_mmc5_power_on_goes_back_to_mode_3() {
+    let mut mapper = mmc5();
+    mapper.write_prg(0x5100, 0);
+    mapper.write_prg(0x5117, 3);
+    mapper.write_prg(0x5205, 2);
+
+    mapper.power_on();
+
+    assert_eq!(mapper.read_prg(0x8000), mmc5().read_prg(0x8000));
+    assert_eq!(mapper.read_prg(0x5205), (0xFFu16 * 0xFF) as u8);
+}
@@ -0,0 +1,33 @@
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::mapper_test_helper::{bank_switching_rom, copy_byte, copy_chr_byte, run_program, write_register};
+
+fn select_bank(register: u8, bank: u8) -> Vec<u8> {
+    let mut program = write_register(0x8000, register);
+    program.extend(write_register(0x8001, bank));
+    program
+}
+
+#[test]
+fn test_namco108_rom_switches_banks() {
+    let mut program = select_bank(6, 3);
+    program.extend(select_bank(7, 4));
+    program.extend(select_bank(1, 11));
+    program.extend(select_bank(5, 40));
+    program.extend(copy_byte(0x8000, 0x10));
+    program.extend(copy_byte(0xA000, 0x11));
+    program.extend(copy_byte(0xC000, 0x12));
+    program.extend(copy_chr_byte(0x0800, 0x13));
+    program.extend(copy_chr_byte(0x0C00, 0x14));
+    program.extend(copy_chr_byte(0x1C00, 0x15));
+    program.push(0x00);
+
+    let mut cpu = run_program(bank_switching_rom(206, 0, 16, 64), program);
+
+    assert_eq!(cpu.mem_read(0x10), 3);
+    assert_eq!(cpu.mem_read(0x11), 4);
+    assert_eq!(cpu.mem_read(0x12), 14);
+    // 2KB banks ignore the lowest bit
+    assert_eq!(cpu.mem_read(0x13), 10);
+    assert_eq!(cpu.mem_read(0x14), 11);
+    assert_eq!(cpu.mem_read(0x15), 40);
+}
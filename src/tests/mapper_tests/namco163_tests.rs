@@ -0,0 +1,116 @@
+use crate::components::apu::PULSE_FULL_VOLUME;
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::namco163::Namco163;
+use crate::components::mapper::Mapper;
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+
+fn namco163() -> Namco163 {
+    Namco163::new(banked_rom(19, 16, 32)).unwrap()
+}
+
+#[test]
+fn test_namco163_prg_banking() {
+    let mut mapper = namco163();
+
+    mapper.write_prg(0xE000, 3);
+    mapper.write_prg(0xE800, 4);
+    mapper.write_prg(0xF000, 5);
+
+    assert_eq!(mapper.read_prg(0x8000), 3);
+    assert_eq!(mapper.read_prg(0xA000), 4);
+    assert_eq!(mapper.read_prg(0xC000), 5);
+    assert_eq!(mapper.read_prg(0xE000), 15);
+}
+
+#[test]
+fn test_namco163_refuses_prg_rom_under_8kb() {
+    let mut rom = banked_rom(19, 0, 32);
+    rom.prg_rom = vec![0; 0x1000];
+
+    assert_eq!(Namco163::new(rom).err(), Some("Namco 163 needs at least 8KB of PRG ROM, the ROM has 4096 bytes".to_string()));
+}
+
+#[test]
+fn test_namco163_chr_banking() {
+    let mut mapper = namco163();
+
+    for slot in 0..8 {
+        mapper.write_prg(0x8000 + slot * 0x800, slot as u8 + 10);
+    }
+
+    assert_eq!(mapper.read_chr(0x0000), 10);
+    assert_eq!(mapper.read_chr(0x0400), 11);
+    assert_eq!(mapper.read_chr(0x1FFF), 17);
+}
+
+#[test]
+fn test_namco163_nametable_mirroring() {
+    let mut mapper = namco163();
+
+    for (slot, bank) in [0xE0, 0xE0, 0xE1, 0xE1].iter().enumerate() {
+        mapper.write_prg(0xC000 + slot as u16 * 0x800, *bank);
+    }
+    assert_eq!(mapper.mirroring(), Mirroring::HORIZONTAL);
+
+    for (slot, bank) in [0xE0, 0xE1, 0xE0, 0xE1].iter().enumerate() {
+        mapper.write_prg(0xC000 + slot as u16 * 0x800, *bank);
+    }
+    assert_eq!(mapper.mirroring(), Mirroring::VERTICAL);
+
+    for slot in 0..4 {
+        mapper.write_prg(0xC000 + slot * 0x800, 0xE1);
+    }
+    assert_eq!(mapper.mirroring(), Mirroring::SINGLE_SCREEN_UPPER);
+}
+
+#[test]
+fn test_namco163_irq() {
+    let mut mapper = namco163();
+    mapper.write_prg(0x5000, 0xFD);
+    mapper.write_prg(0x5800, 0xFF);
+
+    mapper.tick(1);
+    assert!(!mapper.irq_pending());
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+    assert_eq!(mapper.read_prg(0x5800), 0xFF);
+
+    // the counter stops at the end and the write acknowledges
+    mapper.tick(10);
+    assert_eq!(mapper.read_prg(0x5000), 0xFF);
+    mapper.write_prg(0x5800, 0x80);
+    assert!(!mapper.irq_pending());
+}
+
+#[test]
+fn test_namco163_sound_can_be_disabled() {
+    let mut mapper = namco163();
+    // a constant full level wave on the last channel
+    mapper.write_prg(0xF800, 0x80);
+    mapper.write_prg(0x4800, 0xFF);
+    mapper.write_prg(0xF800, 0x80 | 0x7C);
+    for value in [0xFC, 0x00, 0x00, 0x0F] {
+        mapper.write_prg(0x4800, value);
+    }
+    mapper.tick(15);
+    assert!((mapper.expansion_audio() - 3.0 * PULSE_FULL_VOLUME).abs() < 0.0001);
+
+    mapper.write_prg(0xE000, 0b0100_0000);
+    assert_eq!(mapper.expansion_audio(), 0.0);
+}
+
+#[test]
+fn test_namco163_power_on_keeps_the_sound_ram() {
+    let mut mapper = namco163();
+    mapper.write_prg(0xE000, 3);
+    mapper.write_prg(0xF800, 0x10);
+    mapper.write_prg(0x4800, 0x42);
+    mapper.write_prg(0x5800, 0x80);
+
+    mapper.power_on();
+
+    assert_eq!(mapper.read_prg(0x8000), 0);
+    assert!(!mapper.irq_pending());
+    mapper.write_prg(0xF800, 0x10);
+    assert_eq!(mapper.read_prg(0x4800), 0x42);
+}
@@ -0,0 +1,124 @@
+use crate::components::apu::{DMC_FULL_VOLUME, PULSE_FULL_VOLUME};
+use crate::components::cartridge::nsf::Nsf;
+use crate::components::mapper::nsf::NsfMapper;
+use crate::components::mapper::Mapper;
+use crate::tests::test_helpers::nsf_test_helper::{create_nsf, TestNsf};
+
+fn test_nsf(load_address: u16, bank_switch: [u8; 8], data: Vec<u8>) -> Nsf {
+    Nsf::new(&create_nsf(TestNsf {
+        total_songs: 1,
+        starting_song: 1,
+        load_address,
+        init_address: load_address,
+        play_address: load_address,
+        bank_switch,
+        data,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_nsf_data_is_loaded_at_load_address() {
+    let mut mapper = NsfMapper::new(&test_nsf(0xC000, [0; 8], vec![1, 2, 3])).unwrap();
+
+    assert_eq!(mapper.read_prg(0xBFFF), 0);
+    assert_eq!(mapper.read_prg(0xC000), 1);
+    assert_eq!(mapper.read_prg(0xC002), 3);
+}
+
+#[test]
+fn test_nsf_load_address_below_rom_not_supported() {
+    match NsfMapper::new(&test_nsf(0x6000, [0; 8], vec![1, 2, 3])) {
+        Result::Ok(_) => assert!(false, "should not create mapper"),
+        Result::Err(str) => assert_eq!(str, "NSF load address 6000 is below $8000"),
+    }
+}
+
+#[test]
+fn test_nsf_bank_switching() {
+    let mut data = vec![];
+    for bank in 0..4 {
+        data.extend(vec![bank as u8 + 1; 0x1000]);
+    }
+    let mut mapper = NsfMapper::new(&test_nsf(0x8000, [3, 2, 1, 0, 0, 0, 0, 0], data)).unwrap();
+
+    assert_eq!(mapper.read_prg(0x8000), 4);
+    assert_eq!(mapper.read_prg(0x9000), 3);
+    assert_eq!(mapper.read_prg(0xA000), 2);
+    assert_eq!(mapper.read_prg(0xB000), 1);
+
+    mapper.write_prg(0x5FF8, 2);
+    mapper.write_prg(0x5FFF, 3);
+    assert_eq!(mapper.read_prg(0x8000), 3);
+    assert_eq!(mapper.read_prg(0xFFFF), 4);
+
+    mapper.reset_banks();
+    assert_eq!(mapper.read_prg(0x8000), 4);
+}
+
+#[test]
+fn test_nsf_bank_switched_data_is_offset_by_load_address() {
+    let mut mapper = NsfMapper::new(&test_nsf(0x8100, [0, 1, 0, 0, 0, 0, 0, 0], vec![7; 0x1000])).unwrap();
+
+    assert_eq!(mapper.read_prg(0x80FF), 0);
+    assert_eq!(mapper.read_prg(0x8100), 7);
+    assert_eq!(mapper.read_prg(0x90FF), 7);
+    assert_eq!(mapper.read_prg(0x9100), 0);
+}
+
+#[test]
+fn test_nsf_prg_ram() {
+    let mut mapper = NsfMapper::new(&test_nsf(0x8000, [0; 8], vec![])).unwrap();
+
+    mapper.write_prg(0x6000, 0x12);
+    mapper.write_prg(0x7FFF, 0x34);
+
+    assert_eq!(mapper.read_prg(0x6000), 0x12);
+    assert_eq!(mapper.read_prg(0x7FFF), 0x34);
+}
+
+#[test]
+fn test_nsf_expansion_audio_chips_come_from_header() {
+    let mut nsf = test_nsf(0x8000, [0; 8], vec![]);
+    let mut mapper = NsfMapper::new(&nsf).unwrap();
+    mapper.write_prg(0x9000, 0x8F);
+    mapper.write_prg(0x9002, 0x80);
+    assert_eq!(mapper.expansion_audio(), 0.0);
+
+    nsf.expansion_audio = 0b0000_0001;
+    let mut mapper = NsfMapper::new(&nsf).unwrap();
+    mapper.write_prg(0x9000, 0x8F);
+    mapper.write_prg(0x9002, 0x80);
+    assert_eq!(mapper.expansion_audio(), PULSE_FULL_VOLUME);
+}
+
+#[test]
+fn test_nsf_mixes_every_chip() {
+    let mut nsf = test_nsf(0x8000, [0; 8], vec![]);
+    nsf.expansion_audio = 0b0011_1001;
+    let mut mapper = NsfMapper::new(&nsf).unwrap();
+
+    mapper.write_prg(0x9000, 0x8F);
+    mapper.write_prg(0x9002, 0x80);
+    mapper.write_prg(0x5011, 0xFF);
+    mapper.write_prg(0xC000, 7);
+    mapper.write_prg(0xE000, 0b0011_1111);
+    mapper.write_prg(0xC000, 8);
+    mapper.write_prg(0xE000, 0x0F);
+
+    let expected = PULSE_FULL_VOLUME + DMC_FULL_VOLUME + 2.0 * PULSE_FULL_VOLUME;
+    assert!((mapper.expansion_audio() - expected).abs() < 0.0001);
+}
+
+#[test]
+fn test_nsf_mmc5_multiplier() {
+    let mut nsf = test_nsf(0x8000, [0; 8], vec![]);
+    nsf.expansion_audio = 0b0000_1000;
+    let mut mapper = NsfMapper::new(&nsf).unwrap();
+
+    mapper.write_prg(0x5205, 200);
+    mapper.write_prg(0x5206, 3);
+
+    assert_eq!(mapper.read_prg(0x5205), 0x58);
+    assert_eq!(mapper.read_prg(0x5206), 0x02);
+}
@@ -0,0 +1,70 @@
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::taito_tc0190::TaitoTc0190;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::mapper_test_helper::{
+    bank_switching_rom, banked_rom, copy_byte, copy_chr_byte, run_program, write_register,
+};
+
+#[test]
+fn test_taito_tc0190_rom_switches_banks() {
+    let mut program = write_register(0x8000, 0x45);
+    program.extend(write_register(0x8001, 6));
+    program.extend(write_register(0x8003, 3));
+    program.extend(write_register(0xA002, 30));
+    program.extend(copy_byte(0x8000, 0x10));
+    program.extend(copy_byte(0xA000, 0x11));
+    program.extend(copy_byte(0xE000, 0x12));
+    program.extend(copy_chr_byte(0x0C00, 0x13));
+    program.extend(copy_chr_byte(0x1800, 0x14));
+    program.push(0x00);
+
+    let mut cpu = run_program(bank_switching_rom(33, 0, 16, 64), program);
+
+    assert_eq!(cpu.mem_read(0x10), 5);
+    assert_eq!(cpu.mem_read(0x11), 6);
+    assert_eq!(cpu.mem_read(0x12), 15);
+    assert_eq!(cpu.mem_read(0x13), 7);
+    assert_eq!(cpu.mem_read(0x14), 30);
+}
+
+#[test]
+fn test_taito_tc0190_mirroring_moves_on_tc0690() {
+    let mut tc0190 = TaitoTc0190::new(banked_rom(33, 16, 64));
+    let mut tc0690 = TaitoTc0190::new(banked_rom(48, 16, 64));
+    for mapper in [&mut tc0190, &mut tc0690] {
+        mapper.write_prg(0x8000, 0x40);
+    }
+    assert_eq!(tc0190.mirroring(), Mirroring::HORIZONTAL);
+    assert_eq!(tc0690.mirroring(), Mirroring::VERTICAL);
+
+    tc0690.write_prg(0xE000, 0x40);
+    assert_eq!(tc0690.mirroring(), Mirroring::HORIZONTAL);
+}
+
+#[test]
+fn test_taito_tc0690_scanline_irq() {
+    let mut mapper = TaitoTc0190::new(banked_rom(48, 16, 64));
+    mapper.write_prg(0xC000, 2);
+    mapper.write_prg(0xC001, 0);
+    mapper.write_prg(0xC002, 0);
+
+    // the first clock reloads the counter
+    for scanline in 0..2 {
+        mapper.ppu_scanline(scanline, true);
+    }
+    assert!(!mapper.irq_pending());
+    mapper.ppu_scanline(2, true);
+    assert!(mapper.irq_pending());
+
+    mapper.write_prg(0xC003, 0);
+    assert!(!mapper.irq_pending());
+
+    // nothing is counted while rendering is off
+    mapper.write_prg(0xC001, 0);
+    mapper.write_prg(0xC002, 0);
+    for scanline in 0..10 {
+        mapper.ppu_scanline(scanline, false);
+    }
+    assert!(!mapper.irq_pending());
+}
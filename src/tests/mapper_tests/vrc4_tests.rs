@@ -0,0 +1,109 @@
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::vrc4::Vrc4;
+use crate::components::mapper::Mapper;
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+
+fn vrc4(mapper: u8, submapper: u8) -> Vrc4 {
+    let mut rom = banked_rom(mapper, 16, 256);
+    rom.submapper = submapper;
+    Vrc4::new(rom)
+}
+
+// Writes a CHR bank through the low and high nibble registers at the given offsets
+fn write_chr_bank(mapper: &mut Vrc4, base: u16, low: u16, high: u16, bank: u8) {
+    mapper.write_prg(base + low, bank & 0x0F);
+    mapper.write_prg(base + high, bank >> 4);
+}
+
+#[test]
+fn test_vrc4_prg_banking_and_swap_mode() {
+    let mut mapper = vrc4(21, 1);
+    mapper.write_prg(0x8000, 3);
+    mapper.write_prg(0xA000, 5);
+
+    assert_eq!(mapper.read_prg(0x8000), 3);
+    assert_eq!(mapper.read_prg(0xA000), 5);
+    assert_eq!(mapper.read_prg(0xC000), 14);
+    assert_eq!(mapper.read_prg(0xE000), 15);
+
+    mapper.write_prg(0x9004, 0b10);
+    assert_eq!(mapper.read_prg(0x8000), 14);
+    assert_eq!(mapper.read_prg(0xC000), 3);
+}
+
+#[test]
+fn test_vrc4_submapper_address_lines() {
+    // (mapper, submapper, register 1 offset, register 2 offset)
+    let wirings = [
+        (21, 1, 0x002, 0x004),
+        (21, 2, 0x040, 0x080),
+        (23, 1, 0x001, 0x002),
+        (23, 2, 0x004, 0x008),
+        (25, 1, 0x002, 0x001),
+        (25, 2, 0x008, 0x004),
+    ];
+
+    for (number, submapper, register_1, register_2) in wirings {
+        let mut mapper = vrc4(number, submapper);
+        write_chr_bank(&mut mapper, 0xB000, register_2, register_2 | register_1, 0x23);
+        assert_eq!(mapper.read_chr(0x0400), 0x23, "mapper {} submapper {}", number, submapper);
+    }
+}
+
+#[test]
+fn test_vrc4_without_submapper_decodes_both_wirings() {
+    let mut mapper = vrc4(25, 0);
+    write_chr_bank(&mut mapper, 0xC000, 0x000, 0x002, 0x42);
+    write_chr_bank(&mut mapper, 0xC000, 0x004, 0x00C, 0x43);
+
+    assert_eq!(mapper.read_chr(0x0800), 0x42);
+    assert_eq!(mapper.read_chr(0x0C00), 0x43);
+}
+
+#[test]
+fn test_vrc2a_ignores_the_lowest_chr_bank_bit() {
+    let mut mapper = vrc4(22, 0);
+    write_chr_bank(&mut mapper, 0xE000, 0x000, 0x002, 0x0B);
+
+    assert_eq!(mapper.read_chr(0x1800), 0x05);
+}
+
+#[test]
+fn test_vrc4_mirroring() {
+    let mut mapper = vrc4(23, 1);
+    mapper.write_prg(0x9000, 3);
+    assert_eq!(mapper.mirroring(), Mirroring::SINGLE_SCREEN_UPPER);
+
+    // VRC2 only has the vertical/horizontal bit
+    let mut vrc2 = vrc4(23, 3);
+    vrc2.write_prg(0x9000, 3);
+    assert_eq!(vrc2.mirroring(), Mirroring::HORIZONTAL);
+}
+
+#[test]
+fn test_vrc4_cycle_irq() {
+    let mut mapper = vrc4(23, 1);
+    mapper.write_prg(0xF000, 0x0C);
+    mapper.write_prg(0xF001, 0x0F);
+    // cycle mode, enabled
+    mapper.write_prg(0xF002, 0b110);
+
+    mapper.tick(3);
+    assert!(!mapper.irq_pending());
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+
+    mapper.write_prg(0xF003, 0);
+    assert!(!mapper.irq_pending());
+}
+
+#[test]
+fn test_vrc2_has_no_irq() {
+    let mut mapper = vrc4(23, 3);
+    mapper.write_prg(0xF000, 0x0F);
+    mapper.write_prg(0xF001, 0x0F);
+    mapper.write_prg(0xF002, 0b110);
+
+    mapper.tick(10);
+    assert!(!mapper.irq_pending());
+}
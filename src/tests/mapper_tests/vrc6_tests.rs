@@ -0,0 +1,199 @@
+use crate::components::apu::PULSE_FULL_VOLUME;
+use crate::components::bus::Bus;
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::vrc6::Vrc6;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+
+fn vrc6(mapper: u8) -> Vrc6 {
+    Vrc6::new(banked_rom(mapper, 16, 32))
+}
+
+#[test]
+fn test_vrc6_prg_banking() {
+    let mut mapper = vrc6(24);
+
+    mapper.write_prg(0x8000, 3);
+    mapper.write_prg(0xC000, 5);
+
+    // 16KB bank 3 holds the 8KB banks 6 and 7
+    assert_eq!(mapper.read_prg(0x8000), 6);
+    assert_eq!(mapper.read_prg(0xBFFF), 7);
+    assert_eq!(mapper.read_prg(0xC000), 5);
+    assert_eq!(mapper.read_prg(0xE000), 15);
+}
+
+#[test]
+fn test_vrc6_chr_banking() {
+    let mut mapper = vrc6(24);
+
+    for register in 0..4 {
+        mapper.write_prg(0xD000 + register, register as u8 + 10);
+        mapper.write_prg(0xE000 + register, register as u8 + 20);
+    }
+
+    assert_eq!(mapper.read_chr(0x0000), 10);
+    assert_eq!(mapper.read_chr(0x0C00), 13);
+    assert_eq!(mapper.read_chr(0x1000), 20);
+    assert_eq!(mapper.read_chr(0x1FFF), 23);
+}
+
+#[test]
+fn test_vrc6_chr_2k_banking_mode() {
+    let mut mapper = vrc6(24);
+    mapper.write_prg(0xD001, 7);
+
+    mapper.write_prg(0xB003, 0b0000_0001);
+    assert_eq!(mapper.read_chr(0x0800), 7);
+    assert_eq!(mapper.read_chr(0x0C00), 7);
+
+    // A10 comes from the PPU address
+    mapper.write_prg(0xB003, 0b0010_0001);
+    assert_eq!(mapper.read_chr(0x0800), 6);
+    assert_eq!(mapper.read_chr(0x0C00), 7);
+}
+
+#[test]
+fn test_vrc6_mapper_26_swaps_address_lines() {
+    let mut mapper = vrc6(26);
+
+    mapper.write_prg(0xD001, 4);
+    mapper.write_prg(0xD002, 9);
+
+    assert_eq!(mapper.read_chr(0x0400), 9);
+    assert_eq!(mapper.read_chr(0x0800), 4);
+}
+
+#[test]
+fn test_vrc6_mirroring() {
+    let mut mapper = vrc6(24);
+
+    assert_eq!(mapper.mirroring(), Mirroring::VERTICAL);
+    mapper.write_prg(0xB003, 0b0000_0100);
+    assert_eq!(mapper.mirroring(), Mirroring::HORIZONTAL);
+    mapper.write_prg(0xB003, 0b0000_1000);
+    assert_eq!(mapper.mirroring(), Mirroring::SINGLE_SCREEN_LOWER);
+    mapper.write_prg(0xB003, 0b0000_1100);
+    assert_eq!(mapper.mirroring(), Mirroring::SINGLE_SCREEN_UPPER);
+}
+
+#[test]
+fn test_vrc6_prg_ram_needs_enabling() {
+    let mut mapper = vrc6(24);
+
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0);
+
+    mapper.write_prg(0xB003, 0b1000_0000);
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0x42);
+}
+
+#[test]
+fn test_vrc6_irq_cycle_mode() {
+    let mut mapper = vrc6(24);
+    mapper.write_prg(0xF000, 0xF0);
+    mapper.write_prg(0xF001, 0b110);
+
+    mapper.tick(15);
+    assert!(!mapper.irq_pending());
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+
+    mapper.write_prg(0xF002, 0);
+    assert!(!mapper.irq_pending());
+    // acknowledging without the E bit disables the counter
+    mapper.tick(255);
+    assert!(!mapper.irq_pending());
+}
+
+#[test]
+fn test_vrc6_irq_scanline_mode() {
+    let mut mapper = vrc6(24);
+    mapper.write_prg(0xF000, 0xFF);
+    mapper.write_prg(0xF001, 0b011);
+
+    mapper.tick(113);
+    assert!(!mapper.irq_pending());
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+
+    // stays enabled after the acknowledge because of the E bit
+    mapper.write_prg(0xF002, 0);
+    mapper.tick(113);
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+}
+
+#[test]
+fn test_vrc6_pulse_output() {
+    let mut mapper = vrc6(24);
+    assert_eq!(mapper.expansion_audio(), 0.0);
+
+    // duty ignored, full volume
+    mapper.write_prg(0x9000, 0x8F);
+    mapper.write_prg(0x9002, 0x80);
+
+    assert_eq!(mapper.expansion_audio(), PULSE_FULL_VOLUME);
+}
+
+#[test]
+fn test_vrc6_pulse_duty() {
+    let mut mapper = vrc6(24);
+    // duty 0 is high on one step out of 16
+    mapper.write_prg(0xA000, 0x0F);
+    mapper.write_prg(0xA001, 0);
+    mapper.write_prg(0xA002, 0x80);
+
+    let mut high_steps = 0;
+    for _ in 0..16 {
+        mapper.tick(1);
+        if mapper.expansion_audio() > 0.0 {
+            high_steps += 1;
+        }
+    }
+    assert_eq!(high_steps, 1);
+}
+
+#[test]
+fn test_vrc6_sawtooth_output() {
+    let mut mapper = vrc6(24);
+    mapper.write_prg(0xB000, 0x08);
+    mapper.write_prg(0xB001, 0);
+    mapper.write_prg(0xB002, 0x80);
+
+    mapper.tick(12);
+    assert_eq!(mapper.expansion_audio(), 6.0 * PULSE_FULL_VOLUME / 15.0);
+
+    mapper.tick(2);
+    assert_eq!(mapper.expansion_audio(), 0.0);
+}
+
+#[test]
+fn test_vrc6_audio_halt() {
+    let mut mapper = vrc6(24);
+    mapper.write_prg(0xB000, 0x08);
+    mapper.write_prg(0xB002, 0x80);
+    mapper.write_prg(0x9003, 0x01);
+
+    mapper.tick(12);
+    assert_eq!(mapper.expansion_audio(), 0.0);
+}
+
+#[test]
+fn test_vrc6_audio_is_mixed_into_bus_output() {
+    let mut bus = Bus::new(banked_rom(24, 16, 32)).unwrap();
+    bus.apu().set_sample_rate(44100);
+    let silence = bus.apu().mix();
+    bus.mem_write(0x9000, 0x8F);
+    bus.mem_write(0x9002, 0x80);
+
+    for _ in 0..4 {
+        bus.tick(255);
+    }
+
+    let samples = bus.apu().take_samples();
+    assert_eq!(samples.len(), 1020 * 44100 / 1789773);
+    assert!(samples.iter().all(|sample| (sample - silence - PULSE_FULL_VOLUME).abs() < 0.0001));
+}
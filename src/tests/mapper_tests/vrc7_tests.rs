@@ -0,0 +1,106 @@
+use crate::components::cartridge::Mirroring;
+use crate::components::mapper::vrc7::Vrc7;
+use crate::components::mapper::Mapper;
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+
+fn vrc7() -> Vrc7 {
+    Vrc7::new(banked_rom(85, 16, 32))
+}
+
+#[test]
+fn test_vrc7_prg_banking() {
+    let mut mapper = vrc7();
+
+    mapper.write_prg(0x8000, 3);
+    mapper.write_prg(0x8010, 4);
+    mapper.write_prg(0x9000, 5);
+
+    assert_eq!(mapper.read_prg(0x8000), 3);
+    assert_eq!(mapper.read_prg(0xA000), 4);
+    assert_eq!(mapper.read_prg(0xC000), 5);
+    assert_eq!(mapper.read_prg(0xE000), 15);
+
+    // VRC7b boards use A3
+    mapper.write_prg(0x8008, 6);
+    assert_eq!(mapper.read_prg(0xA000), 6);
+}
+
+#[test]
+fn test_vrc7_chr_banking() {
+    let mut mapper = vrc7();
+
+    for register in 0..8u16 {
+        let addr = 0xA000 + (register / 2) * 0x1000 + (register % 2) * 0x10;
+        mapper.write_prg(addr, register as u8 + 20);
+    }
+
+    for register in 0..8u16 {
+        assert_eq!(mapper.read_chr(register * 0x400), register as u8 + 20);
+    }
+}
+
+#[test]
+fn test_vrc7_mirroring_and_prg_ram() {
+    let mut mapper = vrc7();
+
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0);
+
+    mapper.write_prg(0xE000, 0b1000_0001);
+    assert_eq!(mapper.mirroring(), Mirroring::HORIZONTAL);
+    mapper.write_prg(0x6000, 0x42);
+    assert_eq!(mapper.read_prg(0x6000), 0x42);
+
+    mapper.write_prg(0xE000, 0b0000_0011);
+    assert_eq!(mapper.mirroring(), Mirroring::SINGLE_SCREEN_UPPER);
+}
+
+#[test]
+fn test_vrc7_irq() {
+    let mut mapper = vrc7();
+    mapper.write_prg(0xE010, 0xFE);
+    mapper.write_prg(0xF000, 0b110);
+
+    mapper.tick(1);
+    assert!(!mapper.irq_pending());
+    mapper.tick(1);
+    assert!(mapper.irq_pending());
+
+    mapper.write_prg(0xF010, 0);
+    assert!(!mapper.irq_pending());
+}
+
+fn play_sine(mapper: &mut Vrc7) {
+    for (register, data) in [0x01, 0x21, 0x3F, 0x00, 0x00, 0xF0, 0x00, 0x00].iter().enumerate() {
+        mapper.write_prg(0x9010, register as u8);
+        mapper.write_prg(0x9030, *data);
+    }
+    for (register, data) in [(0x30, 0x00), (0x10, 0x00), (0x20, 0x19)] {
+        mapper.write_prg(0x9010, register);
+        mapper.write_prg(0x9030, data);
+    }
+}
+
+#[test]
+fn test_vrc7_audio() {
+    let mut mapper = vrc7();
+    play_sine(&mut mapper);
+
+    mapper.tick(36 * 4);
+    assert!(mapper.expansion_audio() > 0.0);
+}
+
+#[test]
+fn test_vrc7_silence_resets_audio() {
+    let mut mapper = vrc7();
+    play_sine(&mut mapper);
+    mapper.tick(36 * 4);
+
+    mapper.write_prg(0xE000, 0b0100_0000);
+    assert_eq!(mapper.expansion_audio(), 0.0);
+
+    // the registers were cleared, so nothing plays after unsilencing
+    mapper.write_prg(0xE000, 0);
+    mapper.tick(36 * 4);
+    assert_eq!(mapper.expansion_audio(), 0.0);
+}
@@ -0,0 +1,183 @@
+use crate::components::bus::{Bus, RamInit};
+use crate::components::cartridge::Rom;
+use crate::components::cpu::CPU;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::components::joypad::JoypadButton;
+use crate::components::md5::md5;
+use crate::components::mem::Mem;
+use crate::roms::game::Game;
+use crate::roms::headless::{self, InputSource};
+use crate::roms::input_config::PLAYERS;
+use crate::roms::movie::{rom_checksum, Movie, MovieFrame, SOFT_RESET};
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+// every frame strobes the joypad and keeps its first 8 reads at $00-$07
+fn joypad_reading_rom() -> Rom {
+    #[rustfmt::skip]
+    let program = [
+        0xA9, 0x01, 0x8D, 0x16, 0x40,       // LDA #$01, STA $4016
+        0xA9, 0x00, 0x8D, 0x16, 0x40,       // LDA #$00, STA $4016
+        0xA2, 0x00,                         // LDX #$00
+        0xAD, 0x16, 0x40,                   // LDA $4016
+        0x95, 0x00,                         // STA $00,X
+        0xE8, 0xE0, 0x08, 0xD0, 0xF6,       // INX, CPX #$08, BNE -10
+        0x4C, 0x00, 0x80,                   // JMP $8000
+    ];
+    let mut rom = test_rom(0x8000, None);
+    rom.prg_rom[0..program.len()].copy_from_slice(&program);
+    rom
+}
+
+fn input(player_1: u8, player_2: u8) -> MovieFrame {
+    let mut buttons = [0; PLAYERS];
+    buttons[0] = player_1;
+    buttons[1] = player_2;
+    MovieFrame { commands: 0, buttons }
+}
+
+const MOVIE: &str = "version 3
+emuVersion 22020
+rerecordCount 7
+palFlag 0
+romFilename Some Game
+romChecksum base64:kAFQmDzST7DWlj99KOF/cg==
+guid 452DE2C3-EF43-2FA9-77AC-0677FC51543B
+fourscore 0
+microphone 0
+port0 1
+port1 1
+port2 0
+FDS 0
+NewPPU 0
+comment author someone
+|1|........|........||
+|0|R..U...A|........||
+|0|...... .|.L..T.B.||
+";
+
+#[test]
+fn test_md5_digests() {
+    let hex = |data: &[u8]| md5(data).iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+    assert_eq!(hex(b""), "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(hex(b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+    assert_eq!(hex(b"The quick brown fox jumps over the lazy dog"), "9e107d9d372bb6826bd81d3542a419d6");
+    assert_eq!(
+        hex(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"),
+        "57edf4a22be3c955ac49da2e2107b67a"
+    );
+}
+
+#[test]
+fn test_parse_fm2() {
+    let movie = Movie::parse(MOVIE).unwrap();
+
+    assert_eq!(movie.rom_filename, "Some Game");
+    assert_eq!(movie.rom_checksum, Some(md5(b"abc")));
+    assert_eq!(movie.rerecord_count, 7);
+    assert_eq!(movie.comments, vec!["author someone".to_string()]);
+    assert!(!movie.four_score);
+    assert_eq!(movie.len(), 3);
+
+    assert_eq!(movie.frame(0).unwrap().commands, SOFT_RESET);
+    let right_up_a = JoypadButton::Right.bit() | JoypadButton::Up.bit() | JoypadButton::A.bit();
+    assert_eq!(movie.frame(1), Some(input(right_up_a, 0)));
+    let left_start_b = JoypadButton::Left.bit() | JoypadButton::Start.bit() | JoypadButton::B.bit();
+    assert_eq!(movie.frame(2), Some(input(0, left_start_b)));
+    assert_eq!(movie.frame(3), None);
+}
+
+#[test]
+fn test_fm2_round_trip() {
+    let mut movie = Movie::new("game.nes", rom_checksum(&joypad_reading_rom()), true);
+    movie.comments.push("author someone".to_string());
+    movie.record(0, input(0xFF, 0));
+    let mut four_players = input(JoypadButton::Select.bit(), JoypadButton::Down.bit());
+    four_players.buttons[3] = JoypadButton::A.bit();
+    movie.record(1, four_players);
+
+    let text = movie.to_fm2();
+    assert!(text.contains("\n|0|RLDUTSBA|........|........|........||\n"), "{}", text);
+    assert!(text.contains("\nfourscore 1\n"));
+    assert_eq!(Movie::parse(&text).unwrap(), movie);
+}
+
+#[test]
+fn test_recording_over_earlier_frames_counts_a_rerecord() {
+    let mut movie = Movie::new("game.nes", rom_checksum(&joypad_reading_rom()), false);
+    for frame in 0..5 {
+        movie.record(frame, input(frame as u8, 0));
+    }
+    movie.record(2, input(0x80, 0));
+
+    assert_eq!(movie.len(), 3);
+    assert_eq!(movie.rerecord_count, 1);
+    assert_eq!(movie.frame(2), Some(input(0x80, 0)));
+}
+
+#[test]
+fn test_invalid_movies_are_refused() {
+    assert_eq!(
+        Movie::parse("|0|........|........||\n").err(),
+        Some("Not an FM2 movie, the version 3 header line is missing".to_string())
+    );
+    assert_eq!(
+        Movie::parse("version 3\nbinary 1\n").err(),
+        Some("Line 2: binary input records are not supported".to_string())
+    );
+    assert_eq!(
+        Movie::parse("version 3\n|0|.......|........||\n").err(),
+        Some("Line 2: invalid gamepad .......".to_string())
+    );
+    assert_eq!(
+        Movie::parse("version 3\nport1 2\n").err(),
+        Some("Only gamepads and the Four Score are supported".to_string())
+    );
+    assert_eq!(
+        Movie::parse("version 3\nlength 2\n|0|........|........||\n").err(),
+        Some("The header says 2 frames but the movie holds 1".to_string())
+    );
+}
+
+#[test]
+fn test_movie_drives_the_joypads() {
+    let rom = joypad_reading_rom();
+    let mut movie = Movie::new("game.nes", rom_checksum(&rom), false);
+    assert!(movie.matches(rom_checksum(&rom)));
+    movie.record(0, input(0, 0));
+    movie.record(1, input(JoypadButton::B.bit(), 0));
+    movie.record(2, input(JoypadButton::Up.bit(), 0));
+
+    let mut buttons = vec![];
+    headless::run(Game::Cartridge(rom), RamInit::Zeros, movie.len() + 1, &movie, |_, cpu| {
+        buttons.push((0..8).fold(0, |bits, i| bits | (cpu.mem_read(i) & 1) << i));
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(buttons, vec![0, JoypadButton::B.bit(), JoypadButton::Up.bit(), 0]);
+}
+
+#[test]
+fn test_movie_notices_lost_sync() {
+    let rom = joypad_reading_rom();
+    let mut movie = Movie::new("game.nes", rom_checksum(&rom), false);
+    movie.record(0, input(0, 0));
+    movie.record(1, input(0, 0));
+    let mut cpu = CPU::new(Bus::new(rom).unwrap());
+    let devices = InputDevices::connect(InputSetup::Joypads, &mut cpu.bus);
+    cpu.reset();
+
+    assert!(cpu.run_frame());
+    assert_eq!(
+        movie.apply(0, &mut cpu, &devices),
+        Err("Movie lost sync, frame 0 of the movie ran as frame 1".to_string())
+    );
+    assert_eq!(movie.apply(1, &mut cpu, &devices), Ok(()));
+}
+
+#[test]
+fn test_movie_from_another_rom_does_not_match() {
+    let movie = Movie::new("game.nes", rom_checksum(&joypad_reading_rom()), false);
+
+    assert!(!movie.matches(rom_checksum(&test_rom(0x8000, None))));
+}
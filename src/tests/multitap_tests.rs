@@ -0,0 +1,80 @@
+use crate::components::bus::Bus;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::components::joypad::JoypadButton;
+use crate::components::mem::Mem;
+use crate::components::multitap::{Multitap, MultitapMode};
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+fn read_bits(multitap: &mut Multitap, port: usize, count: usize) -> Vec<u8> {
+    (0..count).map(|_| multitap.read(port)).collect()
+}
+
+fn test_multitap(mode: MultitapMode) -> Multitap {
+    let mut multitap = Multitap::new(mode);
+    multitap.joypad(0).set_button_pressed_status(JoypadButton::A, true);
+    multitap.joypad(1).set_button_pressed_status(JoypadButton::B, true);
+    multitap.joypad(2).set_button_pressed_status(JoypadButton::Start, true);
+    multitap.joypad(3).set_button_pressed_status(JoypadButton::Right, true);
+    multitap.write(1);
+    multitap.write(0);
+    multitap
+}
+
+#[test]
+fn test_four_score_port_1_report() {
+    let mut multitap = test_multitap(MultitapMode::FourScore);
+
+    assert_eq!(read_bits(&mut multitap, 0, 8), vec![1, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(read_bits(&mut multitap, 0, 8), vec![0, 0, 0, 1, 0, 0, 0, 0]);
+    assert_eq!(read_bits(&mut multitap, 0, 8), vec![0, 0, 0, 1, 0, 0, 0, 0]);
+    assert_eq!(read_bits(&mut multitap, 0, 2), vec![1, 1]);
+}
+
+#[test]
+fn test_four_score_port_2_report() {
+    let mut multitap = test_multitap(MultitapMode::FourScore);
+
+    assert_eq!(read_bits(&mut multitap, 1, 8), vec![0, 1, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(read_bits(&mut multitap, 1, 8), vec![0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(read_bits(&mut multitap, 1, 8), vec![0, 0, 1, 0, 0, 0, 0, 0]);
+    assert_eq!(read_bits(&mut multitap, 1, 2), vec![1, 1]);
+}
+
+#[test]
+fn test_four_score_ports_are_read_independently() {
+    let mut multitap = test_multitap(MultitapMode::FourScore);
+
+    read_bits(&mut multitap, 0, 8);
+
+    assert_eq!(read_bits(&mut multitap, 1, 8), vec![0, 1, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_four_score_strobe_keeps_reporting_player_1_a() {
+    let mut multitap = test_multitap(MultitapMode::FourScore);
+    multitap.write(1);
+
+    assert_eq!(read_bits(&mut multitap, 0, 30), vec![1; 30]);
+}
+
+#[test]
+fn test_famicom_adapter_reports_on_two_data_lines() {
+    let mut multitap = test_multitap(MultitapMode::Famicom);
+
+    assert_eq!(read_bits(&mut multitap, 0, 8), vec![0b01, 0, 0, 0b10, 0, 0, 0, 0]);
+    assert_eq!(read_bits(&mut multitap, 1, 8), vec![0, 0b01, 0, 0, 0, 0, 0, 0b10]);
+}
+
+#[test]
+fn test_bus_reads_four_score() {
+    let mut bus = Bus::new(test_rom(0x0600, None)).unwrap();
+    let devices = InputDevices::connect(InputSetup::FourScore, &mut bus);
+    devices.set_joypad_buttons(2, JoypadButton::Select.bit());
+
+    bus.mem_write(0x4016, 1);
+    bus.mem_write(0x4016, 0);
+
+    let report: Vec<u8> = (0..24).map(|_| bus.mem_read(0x4016) & 1).collect();
+    assert_eq!(report[8..], [0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0]);
+    assert_eq!(bus.mem_read(0x4017), 0x40);
+}
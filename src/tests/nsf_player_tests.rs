@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use crate::components::cartridge::nsf::Nsf;
+use crate::components::mem::Mem;
+use crate::components::nsf_player::NsfPlayer;
+use crate::tests::test_helpers::nsf_test_helper::{create_nsf, create_nsfe, test_nsf_program, TestNsf};
+
+fn test_player() -> NsfPlayer {
+    let nsf = Nsf::new(&create_nsf(TestNsf {
+        total_songs: 3,
+        starting_song: 2,
+        load_address: 0x8000,
+        init_address: 0x8000,
+        play_address: 0x8010,
+        bank_switch: [0; 8],
+        data: test_nsf_program(),
+    }))
+    .unwrap();
+    NsfPlayer::new(nsf).unwrap()
+}
+
+#[test]
+fn test_nsf_player_runs_init_for_starting_track() {
+    let mut player = test_player();
+
+    assert_eq!(player.current_track(), 1);
+    assert_eq!(player.cpu.mem_read(0x0200), 1);
+    assert_eq!(player.cpu.mem_read(0x0201), 0);
+}
+
+#[test]
+fn test_nsf_player_calls_play() {
+    let mut player = test_player();
+
+    player.play_frame().unwrap();
+    player.play_frame().unwrap();
+
+    assert_eq!(player.cpu.mem_read(0x0202), 2);
+}
+
+#[test]
+fn test_nsf_player_select_track_clears_ram() {
+    let mut player = test_player();
+    player.play_frame().unwrap();
+
+    player.select_track(2).unwrap();
+
+    assert_eq!(player.current_track(), 2);
+    assert_eq!(player.cpu.mem_read(0x0200), 2);
+    assert_eq!(player.cpu.mem_read(0x0202), 0);
+}
+
+#[test]
+fn test_nsf_player_select_missing_track() {
+    let mut player = test_player();
+
+    match player.select_track(3) {
+        Result::Ok(_) => assert!(false, "should not select track"),
+        Result::Err(str) => assert_eq!(str, "Track 3 doesn't exist, the file has 3 tracks"),
+    }
+}
+
+#[test]
+fn test_nsf_player_metadata() {
+    let player = test_player();
+
+    assert_eq!(player.title(), "Test song");
+    assert_eq!(player.artist(), "Test artist");
+    assert_eq!(player.copyright(), "2024 Test");
+    assert_eq!(player.track_count(), 3);
+    assert_eq!(player.track_label(0), None);
+    assert_eq!(player.play_period(), Duration::from_micros(16639));
+}
+
+#[test]
+fn test_nsf_player_nsfe_track_labels() {
+    let mut info = vec![];
+    info.extend((0x8000 as u16).to_le_bytes());
+    info.extend((0x8000 as u16).to_le_bytes());
+    info.extend((0x8010 as u16).to_le_bytes());
+    info.extend([0, 0, 2, 0]);
+    let nsf = Nsf::new(&create_nsfe(vec![
+        ("INFO", info),
+        ("DATA", test_nsf_program()),
+        ("tlbl", b"First\0Second\0".to_vec()),
+        ("NEND", vec![]),
+    ]))
+    .unwrap();
+
+    let player = NsfPlayer::new(nsf).unwrap();
+
+    assert_eq!(player.track_label(0), Some("First"));
+    assert_eq!(player.track_label(1), Some("Second"));
+}
+
+#[test]
+fn test_nsf_player_stops_play_routines_that_never_return() {
+    let mut data = test_nsf_program();
+    // JMP $8014 forever
+    data.extend([0x4C, 0x14, 0x80]);
+    let nsf = Nsf::new(&create_nsf(TestNsf {
+        total_songs: 1,
+        starting_song: 1,
+        load_address: 0x8000,
+        init_address: 0x8000,
+        play_address: 0x8014,
+        bank_switch: [0; 8],
+        data,
+    }))
+    .unwrap();
+    let mut player = NsfPlayer::new(nsf).unwrap();
+    let stack_pointer = player.cpu.stack_pointer;
+
+    match player.play_frame() {
+        Result::Ok(_) => assert!(false, "should not return"),
+        Result::Err(str) => assert_eq!(str, "Subroutine at 8014 didn't return within 29781 cycles"),
+    }
+    assert_eq!(player.cpu.stack_pointer, stack_pointer);
+}
+
+#[test]
+fn test_nsf_player_runs_a_whole_play_period() {
+    let mut player = test_player();
+    let start = player.cpu.bus.cycles();
+
+    player.play_frame().unwrap();
+
+    // 16639µs at 1.789773MHz, give or take the instruction running when the period ends
+    let cycles = player.cpu.bus.cycles() - start;
+    assert!((29779..29786).contains(&cycles), "{} cycles", cycles);
+}
+
+#[test]
+fn test_nsf_player_plays_a_whole_period_of_samples() {
+    let mut player = test_player();
+    player.cpu.bus.apu().set_sample_rate(44100);
+
+    player.play_frame().unwrap();
+
+    // 16639µs at 44100Hz
+    let samples = player.cpu.bus.apu().take_samples().len();
+    assert!((733..=734).contains(&samples), "{} samples", samples);
+}
@@ -0,0 +1,203 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::components::bus::{Bus, RamInit};
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::cpu::CPU;
+use crate::components::mapper::nrom::Nrom;
+use crate::components::mapper::Mapper;
+use crate::components::mem::Mem;
+use crate::components::save_state::{SaveState, StateReader, StateWriter};
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+// JMP $8000 forever
+fn looping_rom() -> Rom {
+    let mut rom = test_rom(0x8000, None);
+    rom.prg_rom[0..3].copy_from_slice(&[0x4C, 0x00, 0x80]);
+    rom
+}
+
+fn powered_on(ram_init: RamInit) -> CPU {
+    let mut cpu = CPU::new(Bus::new(looping_rom()).unwrap());
+    cpu.bus.set_ram_init(ram_init);
+    cpu.power_on();
+    cpu
+}
+
+fn ram(cpu: &mut CPU) -> Vec<u8> {
+    (0..0x800).map(|addr| cpu.mem_read(addr)).collect()
+}
+
+#[test]
+fn test_power_on_sets_the_registers_of_a_fresh_console() {
+    let cpu = powered_on(RamInit::Zeros);
+
+    assert_eq!(cpu.register_a, 0);
+    assert_eq!(cpu.register_x, 0);
+    assert_eq!(cpu.register_y, 0);
+    assert_eq!(cpu.status, CPU::STATUS_RESET);
+    assert_eq!(cpu.stack_pointer, CPU::STACK_RESET);
+    assert_eq!(cpu.program_counter, 0x8000);
+}
+
+#[test]
+fn test_reset_keeps_ram_and_registers() {
+    let mut cpu = powered_on(RamInit::Zeros);
+    cpu.mem_write(0x0123, 0x45);
+    cpu.register_a = 0x11;
+    cpu.register_x = 0x22;
+    cpu.register_y = 0x33;
+    cpu.status = 0;
+    cpu.stack_pointer = 0xF0;
+    cpu.program_counter = 0x9000;
+
+    cpu.reset();
+
+    assert_eq!(cpu.mem_read(0x0123), 0x45);
+    assert_eq!(cpu.register_a, 0x11);
+    assert_eq!(cpu.register_x, 0x22);
+    assert_eq!(cpu.register_y, 0x33);
+    assert_eq!(cpu.status, 0b0000_0100);
+    assert_eq!(cpu.stack_pointer, 0xED);
+    assert_eq!(cpu.program_counter, 0x8000);
+}
+
+#[test]
+fn test_power_on_fills_ram_with_the_pattern() {
+    let mut cpu = powered_on(RamInit::Zeros);
+    assert!(ram(&mut cpu).iter().all(|value| *value == 0x00));
+
+    let mut cpu = powered_on(RamInit::Ones);
+    assert!(ram(&mut cpu).iter().all(|value| *value == 0xFF));
+
+    let mut cpu = powered_on(RamInit::Alternating);
+    assert_eq!(ram(&mut cpu)[0..16], [0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF]);
+}
+
+#[test]
+fn test_random_ram_follows_the_seed() {
+    let first = ram(&mut powered_on(RamInit::Random(1234)));
+    let again = ram(&mut powered_on(RamInit::Random(1234)));
+    let other = ram(&mut powered_on(RamInit::Random(5678)));
+
+    assert_eq!(first, again);
+    assert_ne!(first, other);
+    assert!(first.iter().any(|value| *value != first[0]));
+}
+
+#[test]
+fn test_power_on_clears_ram_written_before() {
+    let mut cpu = powered_on(RamInit::Zeros);
+    cpu.mem_write(0x0123, 0x45);
+
+    cpu.power_on();
+
+    assert_eq!(cpu.mem_read(0x0123), 0x00);
+}
+
+#[test]
+fn test_ppu_ignores_ctrl_writes_while_warming_up() {
+    let mut cpu = powered_on(RamInit::Zeros);
+
+    cpu.mem_write(0x2000, 0x84);
+    assert_eq!(cpu.bus.ppu().ctrl.vram_addr_increment(), 1);
+    assert!(!cpu.bus.ppu().ctrl.generate_vblank_nmi());
+
+    // the warm-up lasts until the pre-render line, which ends the first frame
+    assert!(cpu.run_frame());
+    cpu.mem_write(0x2000, 0x04);
+    assert_eq!(cpu.bus.ppu().ctrl.vram_addr_increment(), 32);
+}
+
+#[test]
+fn test_reset_leaves_the_ppu_running() {
+    let mut cpu = powered_on(RamInit::Zeros);
+    assert!(cpu.run_frame());
+    assert!(cpu.run_frame());
+    cpu.mem_write(0x2000, 0x04);
+
+    cpu.reset();
+    cpu.mem_write(0x2000, 0x00);
+
+    assert_eq!(cpu.bus.ppu().ctrl.vram_addr_increment(), 1);
+    assert_eq!(cpu.bus.ppu().frame_count(), 2);
+}
+
+#[test]
+fn test_reset_silences_the_apu() {
+    let mut cpu = powered_on(RamInit::Zeros);
+    cpu.mem_write(0x4015, 0x0F);
+    cpu.mem_write(0x4003, 0x08);
+    cpu.mem_write(0x400F, 0x08);
+    assert_eq!(cpu.bus.apu().read_status() & 0x0F, 0x09);
+
+    cpu.reset();
+
+    assert_eq!(cpu.bus.apu().read_status() & 0x0F, 0);
+}
+
+// Counts the power ons and resets the board has seen
+struct ResetCountingMapper {
+    nrom: Nrom,
+    power_ons: u8,
+    resets: u8,
+}
+
+impl Mapper for ResetCountingMapper {
+    fn peek_prg(&self, addr: u16) -> u8 {
+        self.nrom.peek_prg(addr)
+    }
+
+    fn write_prg(&mut self, addr: u16, data: u8) {
+        self.nrom.write_prg(addr, data)
+    }
+
+    fn read_chr(&mut self, addr: u16) -> u8 {
+        self.nrom.read_chr(addr)
+    }
+
+    fn write_chr(&mut self, addr: u16, data: u8) {
+        self.nrom.write_chr(addr, data)
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.nrom.mirroring()
+    }
+
+    fn power_on(&mut self) {
+        self.power_ons += 1;
+    }
+
+    fn reset(&mut self) {
+        self.resets += 1;
+    }
+}
+
+impl SaveState for ResetCountingMapper {
+    fn save_state(&self, state: &mut StateWriter) {
+        state.write_u8(self.power_ons);
+        state.write_u8(self.resets);
+    }
+
+    fn load_state(&mut self, state: &mut StateReader) -> Result<(), String> {
+        self.power_ons = state.read_u8()?;
+        self.resets = state.read_u8()?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_power_on_and_reset_reach_the_mapper() {
+    let mapper = Rc::new(RefCell::new(ResetCountingMapper {
+        nrom: Nrom::new(looping_rom()),
+        power_ons: 0,
+        resets: 0,
+    }));
+    let mut cpu = CPU::new(Bus::with_mapper(mapper.clone()));
+
+    cpu.power_on();
+    assert_eq!((mapper.borrow().power_ons, mapper.borrow().resets), (1, 0));
+
+    cpu.reset();
+    assert_eq!((mapper.borrow().power_ons, mapper.borrow().resets), (1, 1));
+}
@@ -1 +1,2 @@
-﻿pub mod vram_tests;
\ No newline at end of file
+﻿pub mod vram_tests;
+pub mod rendering_tests;
\ No newline at end of file
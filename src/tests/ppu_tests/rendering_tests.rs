@@ -0,0 +1,230 @@
+use crate::components::bus::Bus;
+use crate::components::cpu::CPU;
+use crate::components::frame::Frame;
+use crate::components::mem::Mem;
+use crate::components::ppu::palette::SYSTEM_PALETTE;
+use crate::tests::test_helpers::cpu_test_helper;
+use crate::tests::test_helpers::ppu_test_helper::{run_ppu_to_scanline, test_ppu, write_ppu_memory};
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+// tile 1 is a solid block of color 1, tile 2 is a single column of color 3 on the left edge
+fn test_chr() -> Vec<u8> {
+    let mut chr = vec![0; 0x2000];
+    for row in 0..8 {
+        chr[16 + row] = 0xFF;
+        chr[32 + row] = 0x80;
+        chr[32 + 8 + row] = 0x80;
+    }
+    chr
+}
+
+#[test]
+fn test_ppu_vblank_and_nmi() {
+    let mut ppu = test_ppu(test_chr());
+    ppu.write_to_ctrl(0b1000_0000);
+
+    run_ppu_to_scanline(&mut ppu, 240);
+    assert!(!ppu.poll_nmi_interrupt());
+
+    run_ppu_to_scanline(&mut ppu, 241);
+    assert!(ppu.poll_nmi_interrupt());
+    assert!(!ppu.poll_nmi_interrupt());
+
+    assert_eq!(ppu.read_status() & 0b1000_0000, 0b1000_0000);
+    assert_eq!(ppu.read_status() & 0b1000_0000, 0);
+}
+
+#[test]
+fn test_ppu_enabling_nmi_during_vblank_fires_it() {
+    let mut ppu = test_ppu(test_chr());
+
+    run_ppu_to_scanline(&mut ppu, 250);
+    assert!(!ppu.poll_nmi_interrupt());
+
+    ppu.write_to_ctrl(0b1000_0000);
+    assert!(ppu.poll_nmi_interrupt());
+}
+
+#[test]
+fn test_ppu_frame_wraps_after_pre_render_scanline() {
+    let mut ppu = test_ppu(test_chr());
+
+    let mut new_frame = false;
+    for _ in 0..262 {
+        new_frame |= ppu.tick(341);
+    }
+
+    assert!(new_frame);
+    assert_eq!(ppu.scanline(), 0);
+    assert_eq!(ppu.frame_count(), 1);
+}
+
+#[test]
+fn test_cpu_handles_nmi() {
+    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)).unwrap());
+
+    let mut program = vec![];
+    program.extend(cpu_test_helper::set_accumulator_to_value(0x80));
+    program.extend(cpu_test_helper::store_accumulator_to_memory_address(0x2000));
+    program.extend([0x4C, 0x05, 0x06]); // JMP to itself
+
+    // the NMI vector of the test rom is empty, so the handler lives at $0000
+    let nmi_handler = cpu_test_helper::set_register_x_to_value(0x42);
+    cpu.mem_write(0x0000, nmi_handler[0]);
+    cpu.mem_write(0x0001, nmi_handler[1]);
+    cpu.mem_write(0x0002, 0x00);
+
+    cpu.load_and_run(program);
+
+    assert_eq!(cpu.register_x, 0x42);
+    cpu_test_helper::assert_active_interrupt_flag(&cpu);
+}
+
+#[test]
+fn test_cpu_runs_one_frame_at_a_time() {
+    let mut cpu = CPU::new(Bus::new(test_rom(0x0600, None)).unwrap());
+    cpu.load(vec![0x4C, 0x00, 0x06]); // JMP to itself
+    cpu.reset();
+
+    assert!(cpu.run_frame());
+    assert_eq!(cpu.bus.ppu().frame_count(), 1);
+    assert!(cpu.run_frame());
+    assert_eq!(cpu.bus.ppu().frame_count(), 2);
+    assert_eq!(cpu.bus.ppu().scanline(), 0);
+}
+
+#[test]
+fn test_ppu_draws_backdrop_while_rendering_is_disabled() {
+    let mut ppu = test_ppu(test_chr());
+    write_ppu_memory(&mut ppu, 0x3F00, &[0x21]);
+
+    run_ppu_to_scanline(&mut ppu, 241);
+
+    assert_eq!(ppu.frame().pixel(0, 0), SYSTEM_PALETTE[0x21]);
+    assert_eq!(ppu.frame().pixel(255, 239), SYSTEM_PALETTE[0x21]);
+}
+
+#[test]
+fn test_ppu_draws_background_tiles_with_attributes() {
+    let mut ppu = test_ppu(test_chr());
+    write_ppu_memory(&mut ppu, 0x3F00, &[0x0F, 0x30, 0x00, 0x00, 0x0F, 0x16]);
+    write_ppu_memory(&mut ppu, 0x2000, &[1, 1, 1, 1]);
+    // the top left 16x16 area uses palette 0, the one right of it palette 1
+    write_ppu_memory(&mut ppu, 0x23C0, &[0b0000_0100]);
+    ppu.write_to_mask_register(0b0000_1010);
+
+    run_ppu_to_scanline(&mut ppu, 241);
+
+    assert_eq!(ppu.frame().pixel(0, 0), SYSTEM_PALETTE[0x30]);
+    assert_eq!(ppu.frame().pixel(15, 7), SYSTEM_PALETTE[0x30]);
+    assert_eq!(ppu.frame().pixel(16, 0), SYSTEM_PALETTE[0x16]);
+    assert_eq!(ppu.frame().pixel(32, 0), SYSTEM_PALETTE[0x0F]);
+    assert_eq!(ppu.frame().pixel(0, 8), SYSTEM_PALETTE[0x0F]);
+}
+
+#[test]
+fn test_ppu_background_scrolling() {
+    let mut ppu = test_ppu(test_chr());
+    write_ppu_memory(&mut ppu, 0x3F00, &[0x0F, 0x30]);
+    write_ppu_memory(&mut ppu, 0x2001, &[1]);
+    ppu.write_to_mask_register(0b0000_1010);
+    ppu.write_to_scroll_register(4);
+    ppu.write_to_scroll_register(0);
+
+    run_ppu_to_scanline(&mut ppu, 1);
+
+    assert_eq!(ppu.frame().pixel(3, 0), SYSTEM_PALETTE[0x0F]);
+    assert_eq!(ppu.frame().pixel(4, 0), SYSTEM_PALETTE[0x30]);
+    assert_eq!(ppu.frame().pixel(11, 0), SYSTEM_PALETTE[0x30]);
+    assert_eq!(ppu.frame().pixel(12, 0), SYSTEM_PALETTE[0x0F]);
+}
+
+#[test]
+fn test_ppu_draws_sprites_and_detects_sprite_zero_hit() {
+    let mut ppu = test_ppu(test_chr());
+    write_ppu_memory(&mut ppu, 0x3F00, &[0x0F, 0x30]);
+    write_ppu_memory(&mut ppu, 0x3F10, &[0x0F, 0x00, 0x00, 0x16]);
+    write_ppu_memory(&mut ppu, 0x2000, &[1]);
+    // sprite 0 on the background tile, flipped so its column ends up on the right edge
+    for value in [9, 2, 0b0100_0000, 0] {
+        ppu.write_to_oam_data(value);
+    }
+    ppu.write_to_mask_register(0b0001_1110);
+
+    run_ppu_to_scanline(&mut ppu, 11);
+
+    assert_eq!(ppu.frame().pixel(7, 10), SYSTEM_PALETTE[0x16]);
+    assert_eq!(ppu.frame().pixel(0, 10), SYSTEM_PALETTE[0x0F]);
+    assert_eq!(ppu.read_status() & 0b0100_0000, 0);
+
+    ppu.write_to_oam_addr(0);
+    ppu.write_to_oam_data(0);
+    run_ppu_to_scanline(&mut ppu, 0);
+    run_ppu_to_scanline(&mut ppu, 2);
+
+    assert_eq!(ppu.frame().pixel(7, 1), SYSTEM_PALETTE[0x16]);
+    assert_eq!(ppu.read_status() & 0b0100_0000, 0b0100_0000);
+}
+
+#[test]
+fn test_ppu_sprite_behind_background() {
+    let mut ppu = test_ppu(test_chr());
+    write_ppu_memory(&mut ppu, 0x3F00, &[0x0F, 0x30]);
+    write_ppu_memory(&mut ppu, 0x3F10, &[0x0F, 0x00, 0x00, 0x16]);
+    write_ppu_memory(&mut ppu, 0x2000, &[1]);
+    for value in [0, 2, 0b0010_0000, 0, 0, 2, 0, 8] {
+        ppu.write_to_oam_data(value);
+    }
+    ppu.write_to_mask_register(0b0001_1110);
+
+    run_ppu_to_scanline(&mut ppu, 2);
+
+    assert_eq!(ppu.frame().pixel(0, 1), SYSTEM_PALETTE[0x30]);
+    assert_eq!(ppu.frame().pixel(8, 1), SYSTEM_PALETTE[0x16]);
+}
+
+#[test]
+fn test_ppu_sprite_overflow() {
+    let mut ppu = test_ppu(test_chr());
+    for sprite in 0..9 {
+        for value in [0, 2, 0, sprite * 8] {
+            ppu.write_to_oam_data(value);
+        }
+    }
+    ppu.write_to_mask_register(0b0001_0000);
+
+    run_ppu_to_scanline(&mut ppu, 1);
+    assert_eq!(ppu.read_status() & 0b0010_0000, 0);
+
+    run_ppu_to_scanline(&mut ppu, 2);
+    assert_eq!(ppu.read_status() & 0b0010_0000, 0b0010_0000);
+}
+
+#[test]
+fn test_oam_dma_copies_a_cpu_page() {
+    let mut bus = Bus::new(test_rom(0x0600, None)).unwrap();
+    for i in 0..256u16 {
+        bus.mem_write(0x0200 + i, i as u8);
+    }
+    let cycles = bus.cycles();
+
+    bus.mem_write(0x2003, 0x00);
+    bus.mem_write(0x4014, 0x02);
+
+    assert_eq!(bus.cycles() - cycles, 513);
+    assert_eq!(bus.ppu().oam_data[0x00], 0x00);
+    assert_eq!(bus.ppu().oam_data[0x7F], 0x7F);
+    assert_eq!(bus.ppu().oam_data[0xFF], 0xFF);
+    bus.mem_write(0x2003, 0x10);
+    assert_eq!(bus.mem_read(0x2004), 0x10);
+}
+
+#[test]
+fn test_frame_pixels() {
+    let mut frame = Frame::new();
+
+    frame.set_pixel(255, 239, (1, 2, 3));
+
+    assert_eq!(frame.pixel(255, 239), (1, 2, 3));
+    assert_eq!(frame.data.len(), Frame::WIDTH * Frame::HEIGHT * 3);
+}
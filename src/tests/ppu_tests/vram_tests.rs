@@ -1,5 +1,4 @@
-﻿use sdl2::mouse::SystemCursor::No;
-use crate::components::bus::Bus;
+﻿use crate::components::bus::Bus;
 use crate::components::cartridge::Mirroring;
 use crate::components::cpu::CPU;
 use crate::components::mem::Mem;
@@ -9,7 +8,7 @@ use crate::tests::test_helpers::rom_test_helper::test_rom;
 
 #[test]
 fn test_ppu_vram_read_write() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
 
     let data_1: u8 = 0x66;
@@ -32,7 +31,7 @@ fn test_ppu_vram_read_write() {
 
 #[test]
 fn test_ppu_vram_read_write_mirror_down() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
 
     let data: u8 = 0x66;
@@ -54,7 +53,7 @@ fn test_ppu_vram_read_write_mirror_down() {
 
 #[test]
 fn test_ppu_vram_read_write_step32() {
-    let bus = Bus::new(test_rom(0x0600, None));
+    let bus = Bus::new(test_rom(0x0600, None)).unwrap();
     let mut cpu = CPU::new(bus);
 
     let data_1: u8 = 0x66;
@@ -81,7 +80,7 @@ fn test_ppu_vram_read_write_step32() {
 
 #[test]
 fn test_ppu_vram_read_write_horizontal_mirroring() {
-    let bus = Bus::new(test_rom(0x0600, Some(Mirroring::HORIZONTAL)));
+    let bus = Bus::new(test_rom(0x0600, Some(Mirroring::HORIZONTAL))).unwrap();
     let mut cpu = CPU::new(bus);
 
     // store results in x;
@@ -114,7 +113,7 @@ fn test_ppu_vram_read_write_horizontal_mirroring() {
 }
 #[test]
 fn test_ppu_vram_read_write_vertical_mirroring() {
-    let bus = Bus::new(test_rom(0x0600, Some(Mirroring::VERTICAL)));
+    let bus = Bus::new(test_rom(0x0600, Some(Mirroring::VERTICAL))).unwrap();
     let mut cpu = CPU::new(bus);
 
     // store results in x;
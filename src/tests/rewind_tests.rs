@@ -0,0 +1,141 @@
+use crate::components::bus::Bus;
+use crate::components::cartridge::Rom;
+use crate::components::cpu::CPU;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::components::joypad::JoypadButton;
+use crate::components::save_state;
+use crate::roms::rewind::Rewind;
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+// Adds the A button to a counter every pass and shows the counter as the backdrop color,
+// so the pictures depend on the input that was held
+fn input_driven_rom() -> Rom {
+    #[rustfmt::skip]
+    let program = [
+        0xA9, 0x01, 0x8D, 0x16, 0x40,       // LDA #$01, STA $4016
+        0xA9, 0x00, 0x8D, 0x16, 0x40,       // LDA #$00, STA $4016
+        0xAD, 0x16, 0x40, 0x29, 0x01,       // LDA $4016, AND #$01
+        0x18, 0x65, 0x00, 0x85, 0x00,       // CLC, ADC $00, STA $00
+        0xA9, 0x3F, 0x8D, 0x06, 0x20,       // LDA #$3F, STA $2006
+        0xA9, 0x00, 0x8D, 0x06, 0x20,       // LDA #$00, STA $2006
+        0xA5, 0x00, 0x29, 0x3F, 0x8D, 0x07, 0x20, // LDA $00, AND #$3F, STA $2007
+        0x4C, 0x00, 0x80,                   // JMP $8000
+    ];
+    let mut rom = test_rom(0x8000, None);
+    rom.prg_rom[0..program.len()].copy_from_slice(&program);
+    rom
+}
+
+fn machine() -> (CPU, InputDevices) {
+    let mut cpu = CPU::new(Bus::new(input_driven_rom()).unwrap());
+    let devices = InputDevices::connect(InputSetup::Joypads, &mut cpu.bus);
+    cpu.reset();
+    (cpu, devices)
+}
+
+// Plays frames while recording them, with A held on every third one, and returns the pictures
+fn play(cpu: &mut CPU, devices: &InputDevices, rewind: &mut Rewind, frames: usize) -> Vec<Vec<u8>> {
+    (0..frames)
+        .map(|_| {
+            let frame = cpu.bus.ppu().frame_count();
+            let buttons = if frame % 3 == 0 { JoypadButton::A.bit() } else { 0 };
+            devices.set_joypad_buttons(0, buttons);
+            rewind.record(cpu, devices);
+            assert!(cpu.run_frame());
+            cpu.bus.ppu().frame().data.clone()
+        })
+        .collect()
+}
+
+#[test]
+fn test_rewind_steps_back_through_every_frame() {
+    let (mut cpu, devices) = machine();
+    let mut rewind = Rewind::new(4, usize::MAX);
+    let pictures = play(&mut cpu, &devices, &mut rewind, 20);
+    assert_ne!(pictures[0], pictures[3]);
+
+    for shown in (0..19).rev() {
+        assert!(rewind.step_back(&mut cpu, &devices));
+        assert_eq!(cpu.bus.ppu().frame_count(), shown + 1);
+        assert!(cpu.bus.ppu().frame().data == pictures[shown], "picture of frame {}", shown);
+    }
+    assert!(!rewind.step_back(&mut cpu, &devices));
+    assert!(cpu.bus.ppu().frame().data == pictures[0]);
+}
+
+#[test]
+fn test_rewind_ends_up_in_the_recorded_state() {
+    let (mut cpu, devices) = machine();
+    let mut rewind = Rewind::new(3, usize::MAX);
+    play(&mut cpu, &devices, &mut rewind, 7);
+    let state = save_state::save(&cpu);
+    play(&mut cpu, &devices, &mut rewind, 6);
+
+    for _ in 0..6 {
+        assert!(rewind.step_back(&mut cpu, &devices));
+    }
+
+    assert_eq!(save_state::save(&cpu), state);
+}
+
+#[test]
+fn test_playing_after_rewinding_replaces_the_history() {
+    let (mut cpu, devices) = machine();
+    let mut rewind = Rewind::new(4, usize::MAX);
+    play(&mut cpu, &devices, &mut rewind, 10);
+    for _ in 0..5 {
+        assert!(rewind.step_back(&mut cpu, &devices));
+    }
+    assert_eq!(rewind.frames(), 5);
+
+    let pictures = play(&mut cpu, &devices, &mut rewind, 8);
+    assert_eq!(rewind.frames(), 13);
+    assert!(rewind.step_back(&mut cpu, &devices));
+    assert!(cpu.bus.ppu().frame().data == pictures[6]);
+}
+
+#[test]
+fn test_rewind_keeps_to_its_memory_budget() {
+    let (mut cpu, devices) = machine();
+    let snapshot_size = save_state::save_without_picture(&cpu).len();
+    let budget = snapshot_size + 2000;
+    let mut rewind = Rewind::new(2, budget);
+
+    let pictures = play(&mut cpu, &devices, &mut rewind, 100);
+
+    assert!(rewind.memory_used() <= budget);
+    let frames = rewind.frames();
+    assert!(frames > 2 && frames < 100, "{} frames kept", frames);
+
+    let mut steps = 0;
+    while rewind.step_back(&mut cpu, &devices) {
+        steps += 1;
+        assert!(cpu.bus.ppu().frame().data == pictures[99 - steps], "picture {} steps back of {}", steps, frames);
+    }
+    assert!(steps + 2 >= frames, "{} steps back for {} frames", steps, frames);
+}
+
+#[test]
+fn test_rewind_snapshots_are_stored_as_small_deltas() {
+    let (mut cpu, devices) = machine();
+    let snapshot_size = save_state::save_without_picture(&cpu).len();
+    let mut rewind = Rewind::new(1, usize::MAX);
+
+    play(&mut cpu, &devices, &mut rewind, 50);
+
+    assert!(rewind.memory_used() < snapshot_size * 2, "{} bytes used", rewind.memory_used());
+}
+
+#[test]
+fn test_loading_a_state_clears_the_history() {
+    let (mut cpu, devices) = machine();
+    let mut rewind = Rewind::new(4, usize::MAX);
+    let state = save_state::save(&cpu);
+    play(&mut cpu, &devices, &mut rewind, 10);
+
+    save_state::load(&mut cpu, &state).unwrap();
+    assert!(!rewind.step_back(&mut cpu, &devices));
+    play(&mut cpu, &devices, &mut rewind, 1);
+
+    assert_eq!(rewind.frames(), 1);
+}
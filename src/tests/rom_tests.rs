@@ -1 +1,4 @@
-﻿mod loading_tests;
\ No newline at end of file
+﻿mod loading_tests;
+mod unif_loading_tests;
+mod fds_image_tests;
+mod nsf_loading_tests;
\ No newline at end of file
@@ -0,0 +1,94 @@
+use crate::components::cartridge::fds::{add_gaps, remove_gaps, FdsImage, SIDE_SIZE};
+use crate::components::cartridge::ips;
+use crate::tests::test_helpers::fds_test_helper::test_disk_side;
+
+#[test]
+fn load_fds_image_with_header() {
+    let mut raw = vec![0x46, 0x44, 0x53, 0x1A, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    raw.extend(test_disk_side(&[1, 2, 3]));
+    raw.extend(test_disk_side(&[4, 5, 6]));
+
+    let image = FdsImage::new(&raw).unwrap();
+
+    assert_eq!(image.side_count(), 2);
+    assert_eq!(image.sides[1], test_disk_side(&[4, 5, 6]));
+    assert_eq!(image.to_bytes(), raw);
+}
+
+#[test]
+fn load_fds_image_without_header() {
+    let raw = test_disk_side(&[1, 2, 3]);
+
+    let image = FdsImage::new(&raw).unwrap();
+
+    assert_eq!(image.side_count(), 1);
+    assert_eq!(image.to_bytes(), raw);
+}
+
+#[test]
+fn load_fds_image_with_partial_side_not_supported() {
+    let raw = vec![0; SIDE_SIZE + 1];
+
+    match FdsImage::new(&raw) {
+        Result::Ok(_) => assert!(false, "should not load disk"),
+        Result::Err(str) => assert_eq!(str, "File is not in FDS file format"),
+    }
+}
+
+#[test]
+fn disk_gaps_round_trip() {
+    let side = test_disk_side(&[0xA9, 0x00, 0x60]);
+
+    let disk = add_gaps(&side);
+
+    assert!(disk.len() > SIDE_SIZE);
+    assert_eq!(disk[28300 / 8], 0x80);
+    assert_eq!(disk[28300 / 8 + 1], 0x01);
+    assert_eq!(remove_gaps(&disk), side);
+}
+
+#[test]
+fn fds_image_changes_are_stored_as_patch() {
+    let original = FdsImage::new(&test_disk_side(&[1, 2, 3])).unwrap();
+    let modified = original.with_sides(vec![test_disk_side(&[1, 9, 3])]);
+
+    let changes = modified.changes_since(&original).unwrap();
+    let restored = FdsImage::with_changes(&original.to_bytes(), &changes).unwrap();
+
+    assert_eq!(restored.sides, modified.sides);
+}
+
+#[test]
+fn ips_patch_round_trip() {
+    let original = vec![0; 0x100];
+    let mut modified = original.clone();
+    modified[0x10] = 1;
+    modified[0x11] = 2;
+    modified[0x80] = 3;
+    modified.push(4);
+
+    let patch = ips::create(&original, &modified).unwrap();
+
+    assert_eq!(&patch[..5], b"PATCH");
+    assert_eq!(&patch[patch.len() - 3..], b"EOF");
+    assert_eq!(ips::apply(&original, &patch).unwrap(), modified);
+}
+
+#[test]
+fn ips_patch_applies_rle_records() {
+    let patch = vec![
+        b'P', b'A', b'T', b'C', b'H', 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0xEE, b'E', b'O', b'F',
+    ];
+
+    assert_eq!(ips::apply(&[0; 6], &patch).unwrap(), vec![0, 0, 0xEE, 0xEE, 0xEE, 0]);
+}
+
+#[test]
+fn ips_patch_truncated_not_supported() {
+    let patch = vec![b'P', b'A', b'T', b'C', b'H', 0x00, 0x00, 0x02, 0x00, 0x05, 0x01];
+
+    match ips::apply(&[0; 6], &patch) {
+        Result::Ok(_) => assert!(false, "should not apply patch"),
+        Result::Err(str) => assert_eq!(str, "IPS patch is truncated"),
+    }
+}
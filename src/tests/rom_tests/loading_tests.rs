@@ -56,19 +56,75 @@ fn load_rom_dump_with_trainer_ignores_trainer() {
 }
 
 #[test]
-fn load_rom_dump_nes2_is_not_supported() {
+fn load_rom_dump_nes2() {
     let test_rom = create_rom(TestRom {
         header: vec![
-            0x4E, 0x45, 0x53, 0x1A, 0x01, 0x01, 0x31, 0x8, 00, 00, 00, 00, 00, 00, 00, 00,
+            0x4E, 0x45, 0x53, 0x1A, 0x01, 0x01, 0x31, 0x8, 0x20, 00, 00, 00, 00, 00, 00, 0x08,
         ],
         trainer: None,
         pgp_rom: vec![1; 1 * Rom::PRG_ROM_PAGE_SIZE],
         chr_rom: vec![2; 1 * Rom::CHR_ROM_PAGE_SIZE],
     });
+
+    let rom = Rom::new(&test_rom).unwrap();
+
+    assert_eq!(rom.prg_rom, vec!(1; 1 * Rom::PRG_ROM_PAGE_SIZE));
+    assert_eq!(rom.chr_rom, vec!(2; 1 * Rom::CHR_ROM_PAGE_SIZE));
+    assert_eq!(rom.mapper, 3);
+    assert_eq!(rom.submapper, 2);
+    assert_eq!(rom.default_expansion_device, 0x08);
+    assert_eq!(rom.screen_mirroring, Mirroring::VERTICAL);
+}
+
+#[test]
+fn load_rom_dump_nes2_exponent_rom_size() {
+    let test_rom = create_rom(TestRom {
+        header: vec![
+            // PRG is 2^13 * 3 bytes, CHR uses the regular notation
+            0x4E, 0x45, 0x53, 0x1A, 0b0011_0101, 0x01, 0x00, 0x8, 00, 0x0F, 00, 00, 00, 00, 00, 00,
+        ],
+        trainer: None,
+        pgp_rom: vec![1; 3 * 0x2000],
+        chr_rom: vec![2; 1 * Rom::CHR_ROM_PAGE_SIZE],
+    });
+
+    let rom = Rom::new(&test_rom).unwrap();
+
+    assert_eq!(rom.prg_rom.len(), 3 * 0x2000);
+    assert_eq!(rom.chr_rom, vec!(2; 1 * Rom::CHR_ROM_PAGE_SIZE));
+}
+
+#[test]
+fn load_rom_dump_nes2_mapper_above_255_is_not_supported() {
+    let test_rom = create_rom(TestRom {
+        header: vec![
+            0x4E, 0x45, 0x53, 0x1A, 0x01, 0x01, 0x31, 0x8, 0x01, 00, 00, 00, 00, 00, 00, 00,
+        ],
+        trainer: None,
+        pgp_rom: vec![1; 1 * Rom::PRG_ROM_PAGE_SIZE],
+        chr_rom: vec![2; 1 * Rom::CHR_ROM_PAGE_SIZE],
+    });
+    let rom = Rom::new(&test_rom);
+    match rom {
+        Result::Ok(_) => assert!(false, "should not load rom"),
+        Result::Err(str) => assert_eq!(str, "NES2.0 mapper 259 is not supported"),
+    }
+}
+
+#[test]
+fn load_rom_dump_truncated() {
+    let test_rom = create_rom(TestRom {
+        header: vec![
+            0x4E, 0x45, 0x53, 0x1A, 0x02, 0x01, 0x31, 00, 00, 00, 00, 00, 00, 00, 00, 00,
+        ],
+        trainer: None,
+        pgp_rom: vec![1; 1 * Rom::PRG_ROM_PAGE_SIZE],
+        chr_rom: vec![],
+    });
     let rom = Rom::new(&test_rom);
     match rom {
         Result::Ok(_) => assert!(false, "should not load rom"),
-        Result::Err(str) => assert_eq!(str, "NES2.0 format is not supported"),
+        Result::Err(str) => assert_eq!(str, "ROM file is smaller than its header says"),
     }
 }
 
@@ -87,4 +143,13 @@ fn load_rom_dump_file_not_ines_not_supported() {
         Result::Ok(_) => assert!(false, "should not load rom"),
         Result::Err(str) => assert_eq!(str, "File is not in iNES file format"),
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn load_rom_dump_shorter_than_header() {
+    let rom = Rom::new(&vec![0x4E, 0x45, 0x53, 0x1A, 0x01]);
+    match rom {
+        Result::Ok(_) => assert!(false, "should not load rom"),
+        Result::Err(str) => assert_eq!(str, "File is too short to hold an iNES header"),
+    }
+}
@@ -0,0 +1,214 @@
+use crate::components::cartridge::nsf::Nsf;
+use crate::tests::test_helpers::nsf_test_helper::{create_nsf, create_nsfe, TestNsf};
+
+#[test]
+fn load_nsf_file() {
+    let raw = create_nsf(TestNsf {
+        total_songs: 5,
+        starting_song: 2,
+        load_address: 0x8000,
+        init_address: 0x8003,
+        play_address: 0x8006,
+        bank_switch: [0; 8],
+        data: vec![1, 2, 3],
+    });
+
+    let nsf = Nsf::new(&raw).unwrap();
+
+    assert_eq!(nsf.title, "Test song");
+    assert_eq!(nsf.artist, "Test artist");
+    assert_eq!(nsf.copyright, "2024 Test");
+    assert_eq!(nsf.total_songs, 5);
+    assert_eq!(nsf.starting_song, 1);
+    assert_eq!(nsf.load_address, 0x8000);
+    assert_eq!(nsf.init_address, 0x8003);
+    assert_eq!(nsf.play_address, 0x8006);
+    assert_eq!(nsf.ntsc_speed, 16639);
+    assert!(!nsf.pal);
+    assert!(!nsf.is_bank_switched());
+    assert_eq!(nsf.data, vec![1, 2, 3]);
+}
+
+#[test]
+fn load_nsf_file_with_zero_speed_uses_default_rates() {
+    let mut raw = create_nsf(TestNsf {
+        total_songs: 1,
+        starting_song: 1,
+        load_address: 0x8000,
+        init_address: 0x8000,
+        play_address: 0x8000,
+        bank_switch: [0; 8],
+        data: vec![0x60],
+    });
+    raw[0x6E..0x70].fill(0);
+    raw[0x78..0x7A].fill(0);
+
+    let nsf = Nsf::new(&raw).unwrap();
+
+    assert_eq!(nsf.ntsc_speed, 16639);
+    assert_eq!(nsf.pal_speed, 19997);
+}
+
+#[test]
+fn load_nsf_file_with_starting_song_past_the_last_starts_at_the_first() {
+    let raw = create_nsf(TestNsf {
+        total_songs: 3,
+        starting_song: 4,
+        load_address: 0x8000,
+        init_address: 0x8000,
+        play_address: 0x8000,
+        bank_switch: [0; 8],
+        data: vec![0x60],
+    });
+
+    let nsf = Nsf::new(&raw).unwrap();
+
+    assert_eq!(nsf.starting_song, 0);
+}
+
+#[test]
+fn load_bank_switched_nsf_file() {
+    let raw = create_nsf(TestNsf {
+        total_songs: 1,
+        starting_song: 1,
+        load_address: 0x8000,
+        init_address: 0x8000,
+        play_address: 0x8000,
+        bank_switch: [0, 1, 2, 3, 0, 0, 0, 0],
+        data: vec![0; 0x4000],
+    });
+
+    let nsf = Nsf::new(&raw).unwrap();
+
+    assert!(nsf.is_bank_switched());
+    assert_eq!(nsf.bank_switch, [0, 1, 2, 3, 0, 0, 0, 0]);
+}
+
+#[test]
+fn load_nsf_file_without_songs_not_supported() {
+    let raw = create_nsf(TestNsf {
+        total_songs: 0,
+        starting_song: 1,
+        load_address: 0x8000,
+        init_address: 0x8000,
+        play_address: 0x8000,
+        bank_switch: [0; 8],
+        data: vec![],
+    });
+
+    match Nsf::new(&raw) {
+        Result::Ok(_) => assert!(false, "should not load nsf"),
+        Result::Err(str) => assert_eq!(str, "NSF file has no songs"),
+    }
+}
+
+#[test]
+fn load_bank_switched_nsf_file_without_program_data_not_supported() {
+    let raw = create_nsf(TestNsf {
+        total_songs: 1,
+        starting_song: 1,
+        load_address: 0x8000,
+        init_address: 0x8000,
+        play_address: 0x8000,
+        bank_switch: [0, 1, 0, 0, 0, 0, 0, 0],
+        data: vec![],
+    });
+
+    match Nsf::new(&raw) {
+        Result::Ok(_) => assert!(false, "should not load nsf"),
+        Result::Err(str) => assert_eq!(str, "Bank switched NSF file has no program data"),
+    }
+}
+
+#[test]
+fn load_nsfe_file() {
+    let raw = create_nsfe(vec![
+        ("INFO", vec![0x00, 0x80, 0x03, 0x80, 0x06, 0x80, 0x00, 0x00, 0x03, 0x01]),
+        ("DATA", vec![1, 2, 3]),
+        ("auth", b"Title\0Artist\0Copyright\0Ripper\0".to_vec()),
+        ("tlbl", b"Intro\0Level 1\0Boss\0".to_vec()),
+        ("RATE", vec![0x0A, 0x41]),
+        ("NEND", vec![]),
+    ]);
+
+    let nsf = Nsf::new(&raw).unwrap();
+
+    assert_eq!(nsf.title, "Title");
+    assert_eq!(nsf.artist, "Artist");
+    assert_eq!(nsf.copyright, "Copyright");
+    assert_eq!(nsf.total_songs, 3);
+    assert_eq!(nsf.starting_song, 1);
+    assert_eq!(nsf.load_address, 0x8000);
+    assert_eq!(nsf.init_address, 0x8003);
+    assert_eq!(nsf.play_address, 0x8006);
+    assert_eq!(nsf.ntsc_speed, 0x410A);
+    assert_eq!(nsf.track_labels, vec!["Intro", "Level 1", "Boss"]);
+    assert_eq!(nsf.data, vec![1, 2, 3]);
+}
+
+#[test]
+fn load_nsfe_file_with_zero_rate_and_starting_song_past_the_last() {
+    let raw = create_nsfe(vec![
+        ("INFO", vec![0x00, 0x80, 0x03, 0x80, 0x06, 0x80, 0x00, 0x00, 0x03, 0x03]),
+        ("DATA", vec![1, 2, 3]),
+        ("RATE", vec![0x00, 0x00, 0x00, 0x00]),
+        ("NEND", vec![]),
+    ]);
+
+    let nsf = Nsf::new(&raw).unwrap();
+
+    assert_eq!(nsf.starting_song, 0);
+    assert_eq!(nsf.ntsc_speed, 16639);
+    assert_eq!(nsf.pal_speed, 19997);
+}
+
+#[test]
+fn load_nsfe_file_with_unknown_required_chunk_not_supported() {
+    let raw = create_nsfe(vec![
+        ("INFO", vec![0x00, 0x80, 0x03, 0x80, 0x06, 0x80, 0x00, 0x00]),
+        ("DATA", vec![1, 2, 3]),
+        ("ABCD", vec![]),
+        ("NEND", vec![]),
+    ]);
+
+    match Nsf::new(&raw) {
+        Result::Ok(_) => assert!(false, "should not load nsf"),
+        Result::Err(str) => assert_eq!(str, "NSFe chunk ABCD is not supported"),
+    }
+}
+
+#[test]
+fn load_nsfe_file_without_data_not_supported() {
+    let raw = create_nsfe(vec![
+        ("INFO", vec![0x00, 0x80, 0x03, 0x80, 0x06, 0x80, 0x00, 0x00]),
+        ("NEND", vec![]),
+    ]);
+
+    match Nsf::new(&raw) {
+        Result::Ok(_) => assert!(false, "should not load nsf"),
+        Result::Err(str) => assert_eq!(str, "NSFe file needs both INFO and DATA chunks"),
+    }
+}
+
+#[test]
+fn load_bank_switched_nsfe_file_with_empty_data_not_supported() {
+    let raw = create_nsfe(vec![
+        ("INFO", vec![0x00, 0x80, 0x03, 0x80, 0x06, 0x80, 0x00, 0x00]),
+        ("DATA", vec![]),
+        ("BANK", vec![0, 1]),
+        ("NEND", vec![]),
+    ]);
+
+    match Nsf::new(&raw) {
+        Result::Ok(_) => assert!(false, "should not load nsf"),
+        Result::Err(str) => assert_eq!(str, "Bank switched NSF file has no program data"),
+    }
+}
+
+#[test]
+fn load_file_not_nsf_not_supported() {
+    match Nsf::new(&[0x4E, 0x45, 0x53, 0x1A]) {
+        Result::Ok(_) => assert!(false, "should not load nsf"),
+        Result::Err(str) => assert_eq!(str, "File is not in NSF file format"),
+    }
+}
@@ -0,0 +1,141 @@
+use crate::components::cartridge::unif::mapper_for_board;
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::tests::test_helpers::rom_test_helper::{create_rom, create_unif_rom, TestRom};
+
+fn board_name(name: &str) -> Vec<u8> {
+    let mut result = name.as_bytes().to_vec();
+    result.push(0);
+    result
+}
+
+#[test]
+fn load_unif_dump() {
+    let test_rom = create_unif_rom(vec![
+        ("NAME", board_name("Test game")),
+        ("MAPR", board_name("NES-NROM-256")),
+        ("PRG0", vec![1; 2 * Rom::PRG_ROM_PAGE_SIZE]),
+        ("CHR0", vec![2; 1 * Rom::CHR_ROM_PAGE_SIZE]),
+        ("MIRR", vec![1]),
+    ]);
+
+    let rom = Rom::load(&test_rom).unwrap();
+
+    assert_eq!(rom.prg_rom, vec!(1; 2 * Rom::PRG_ROM_PAGE_SIZE));
+    assert_eq!(rom.chr_rom, vec!(2; 1 * Rom::CHR_ROM_PAGE_SIZE));
+    assert_eq!(rom.mapper, 0);
+    assert_eq!(rom.screen_mirroring, Mirroring::VERTICAL);
+    assert!(!rom.battery);
+}
+
+#[test]
+fn load_unif_dump_joins_chunks_by_index() {
+    let test_rom = create_unif_rom(vec![
+        ("MAPR", board_name("NES-SNROM")),
+        ("PRG1", vec![2; Rom::PRG_ROM_PAGE_SIZE]),
+        ("CHR1", vec![4; Rom::CHR_ROM_PAGE_SIZE]),
+        ("PRG0", vec![1; Rom::PRG_ROM_PAGE_SIZE]),
+        ("CHR0", vec![3; Rom::CHR_ROM_PAGE_SIZE]),
+    ]);
+
+    let rom = Rom::load(&test_rom).unwrap();
+
+    assert_eq!(rom.prg_rom[..Rom::PRG_ROM_PAGE_SIZE], vec!(1; Rom::PRG_ROM_PAGE_SIZE));
+    assert_eq!(rom.prg_rom[Rom::PRG_ROM_PAGE_SIZE..], vec!(2; Rom::PRG_ROM_PAGE_SIZE));
+    assert_eq!(rom.chr_rom[..Rom::CHR_ROM_PAGE_SIZE], vec!(3; Rom::CHR_ROM_PAGE_SIZE));
+    assert_eq!(rom.chr_rom[Rom::CHR_ROM_PAGE_SIZE..], vec!(4; Rom::CHR_ROM_PAGE_SIZE));
+    assert_eq!(rom.mapper, 1);
+}
+
+#[test]
+fn load_unif_dump_without_mirroring_chunk_defaults_to_horizontal() {
+    let test_rom = create_unif_rom(vec![
+        ("MAPR", board_name("UNL-Sachen-8259A")),
+        ("PRG0", vec![1; Rom::PRG_ROM_PAGE_SIZE]),
+    ]);
+
+    let rom = Rom::load(&test_rom).unwrap();
+
+    assert_eq!(rom.mapper, 141);
+    assert_eq!(rom.screen_mirroring, Mirroring::HORIZONTAL);
+    assert!(rom.chr_rom.is_empty());
+}
+
+#[test]
+fn load_unif_dump_with_battery_and_single_screen_mirroring() {
+    let test_rom = create_unif_rom(vec![
+        ("MAPR", board_name("NES-AOROM")),
+        ("PRG0", vec![1; Rom::PRG_ROM_PAGE_SIZE]),
+        ("MIRR", vec![3]),
+        ("BATR", vec![1]),
+    ]);
+
+    let rom = Rom::load(&test_rom).unwrap();
+
+    assert_eq!(rom.mapper, 7);
+    assert_eq!(rom.screen_mirroring, Mirroring::SINGLE_SCREEN_UPPER);
+    assert!(rom.battery);
+}
+
+#[test]
+fn load_unif_dump_unknown_board_not_supported() {
+    let test_rom = create_unif_rom(vec![
+        ("MAPR", board_name("UNL-NOT-A-BOARD")),
+        ("PRG0", vec![1; Rom::PRG_ROM_PAGE_SIZE]),
+    ]);
+
+    match Rom::load(&test_rom) {
+        Result::Ok(_) => assert!(false, "should not load rom"),
+        Result::Err(str) => assert_eq!(str, "UNIF board UNL-NOT-A-BOARD is not supported"),
+    }
+}
+
+#[test]
+fn load_unif_dump_without_board_not_supported() {
+    let test_rom = create_unif_rom(vec![("PRG0", vec![1; Rom::PRG_ROM_PAGE_SIZE])]);
+
+    match Rom::load(&test_rom) {
+        Result::Ok(_) => assert!(false, "should not load rom"),
+        Result::Err(str) => assert_eq!(str, "UNIF file has no MAPR chunk"),
+    }
+}
+
+#[test]
+fn load_unif_dump_truncated_chunk_not_supported() {
+    let mut test_rom = create_unif_rom(vec![
+        ("MAPR", board_name("NES-NROM-128")),
+        ("PRG0", vec![1; Rom::PRG_ROM_PAGE_SIZE]),
+    ]);
+    test_rom.truncate(test_rom.len() - 1);
+
+    match Rom::load(&test_rom) {
+        Result::Ok(_) => assert!(false, "should not load rom"),
+        Result::Err(str) => assert_eq!(str, "UNIF chunk PRG0 is truncated"),
+    }
+}
+
+#[test]
+fn load_picks_ines_parser_for_ines_dump() {
+    let test_rom = create_rom(TestRom {
+        header: vec![
+            0x4E, 0x45, 0x53, 0x1A, 0x01, 0x01, 0x31 | 0b10, 00, 00, 00, 00, 00, 00, 00, 00, 00,
+        ],
+        trainer: None,
+        pgp_rom: vec![1; 1 * Rom::PRG_ROM_PAGE_SIZE],
+        chr_rom: vec![2; 1 * Rom::CHR_ROM_PAGE_SIZE],
+    });
+
+    let rom = Rom::load(&test_rom).unwrap();
+
+    assert_eq!(rom.mapper, 3);
+    assert!(rom.battery);
+}
+
+#[test]
+fn unif_board_names_map_to_mapper_numbers() {
+    assert_eq!(mapper_for_board("NES-TLROM"), Some(4));
+    assert_eq!(mapper_for_board("HVC-TLROM"), Some(4));
+    assert_eq!(mapper_for_board("UNROM"), Some(2));
+    assert_eq!(mapper_for_board("AVE-NINA-01"), Some(34));
+    assert_eq!(mapper_for_board("BMC-Super24in1SC03"), Some(176));
+    assert_eq!(mapper_for_board("NES-UNKNOWN"), None);
+}
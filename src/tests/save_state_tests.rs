@@ -0,0 +1,212 @@
+use std::path::Path;
+
+use crate::components::bus::Bus;
+use crate::components::cartridge::Rom;
+use crate::components::cpu::CPU;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::components::joypad::JoypadButton;
+use crate::components::mem::Mem;
+use crate::components::save_state::{self, StateWriter, MAGIC, VERSION};
+use crate::roms::quick_slots::QuickSlots;
+use crate::tests::test_helpers::mapper_test_helper::banked_rom;
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+// Keeps changing the backdrop color and a pulse channel, so every frame looks different
+fn busy_rom() -> Rom {
+    #[rustfmt::skip]
+    let program = [
+        0xA9, 0x01, 0x8D, 0x15, 0x40,       // LDA #$01, STA $4015
+        0xE6, 0x00,                         // INC $00
+        0xA9, 0x3F, 0x8D, 0x06, 0x20,       // LDA #$3F, STA $2006
+        0xA9, 0x00, 0x8D, 0x06, 0x20,       // LDA #$00, STA $2006
+        0xA5, 0x00, 0x29, 0x3F, 0x8D, 0x07, 0x20, // LDA $00, AND #$3F, STA $2007
+        0xA5, 0x00, 0x8D, 0x00, 0x40,       // LDA $00, STA $4000
+        0x8D, 0x03, 0x40,                   // STA $4003
+        0x4C, 0x05, 0x80,                   // JMP $8005
+    ];
+    let mut rom = test_rom(0x8000, None);
+    rom.prg_rom[0..program.len()].copy_from_slice(&program);
+    rom
+}
+
+fn run_frames(cpu: &mut CPU, frames: usize) -> Vec<Vec<u8>> {
+    (0..frames)
+        .map(|_| {
+            assert!(cpu.run_frame());
+            cpu.bus.ppu().frame().data.clone()
+        })
+        .collect()
+}
+
+#[test]
+fn test_restored_state_produces_identical_frames() {
+    let mut cpu = CPU::new(Bus::new(busy_rom()).unwrap());
+    cpu.reset();
+    run_frames(&mut cpu, 5);
+
+    let state = save_state::save(&cpu);
+    let expected = run_frames(&mut cpu, 10);
+    assert_ne!(expected[0], expected[1]);
+
+    save_state::load(&mut cpu, &state).unwrap();
+    assert_eq!(run_frames(&mut cpu, 10), expected);
+}
+
+#[test]
+fn test_state_loads_into_a_fresh_machine() {
+    let mut cpu = CPU::new(Bus::new(busy_rom()).unwrap());
+    cpu.reset();
+    run_frames(&mut cpu, 3);
+    let state = save_state::save(&cpu);
+    let expected = run_frames(&mut cpu, 3);
+
+    let mut other = CPU::new(Bus::new(busy_rom()).unwrap());
+    save_state::load(&mut other, &state).unwrap();
+
+    assert_eq!(other.bus.ppu().frame_count(), 3);
+    assert_eq!(run_frames(&mut other, 3), expected);
+    assert_eq!(save_state::save(&other), save_state::save(&cpu));
+}
+
+#[test]
+fn test_state_restores_mapper_registers_and_ram() {
+    // FME-7
+    let mut cpu = CPU::new(Bus::new(banked_rom(69, 16, 32)).unwrap());
+    cpu.mem_write(0x8000, 0x9);
+    cpu.mem_write(0xA000, 3);
+    cpu.mem_write(0x8000, 0x8);
+    cpu.mem_write(0xA000, 0b1100_0000);
+    cpu.mem_write(0x6000, 0x42);
+    let state = save_state::save(&cpu);
+
+    cpu.mem_write(0x6000, 0x24);
+    cpu.mem_write(0x8000, 0x9);
+    cpu.mem_write(0xA000, 5);
+    assert_eq!(cpu.mem_read(0x8000), 5);
+
+    save_state::load(&mut cpu, &state).unwrap();
+    assert_eq!(cpu.mem_read(0x8000), 3);
+    assert_eq!(cpu.mem_read(0x6000), 0x42);
+}
+
+#[test]
+fn test_state_header() {
+    let cpu = CPU::new(Bus::new(busy_rom()).unwrap());
+    let state = save_state::save(&cpu);
+
+    assert_eq!(state[0..4], [4, 0, 0, 0]);
+    assert_eq!(state[4..8], MAGIC);
+    assert_eq!(state[8..10], VERSION.to_le_bytes());
+}
+
+#[test]
+fn test_invalid_states_are_refused() {
+    let mut cpu = CPU::new(Bus::new(busy_rom()).unwrap());
+    let state = save_state::save(&cpu);
+
+    assert_eq!(save_state::load(&mut cpu, b"garbage"), Err("Not a save state".to_string()));
+
+    let mut newer = StateWriter::new();
+    newer.write_bytes(&MAGIC);
+    newer.write_u16(VERSION + 1);
+    assert_eq!(
+        save_state::load(&mut cpu, &newer.into_bytes()),
+        Err(format!("Save state version {} is not supported, expected {}", VERSION + 1, VERSION))
+    );
+
+    let mut longer = state.clone();
+    longer.push(0);
+    assert_eq!(save_state::load(&mut cpu, &longer), Err("Save state has trailing data".to_string()));
+}
+
+#[test]
+fn test_failed_load_leaves_machine_untouched() {
+    let mut cpu = CPU::new(Bus::new(busy_rom()).unwrap());
+    cpu.reset();
+    let state = save_state::save(&cpu);
+
+    run_frames(&mut cpu, 2);
+    cpu.register_a = 0x99;
+    cpu.mem_write(0x0010, 0x77);
+    let before = save_state::save(&cpu);
+
+    let result = save_state::load(&mut cpu, &state[..state.len() - 1]);
+
+    assert_eq!(result, Err("Save state is truncated".to_string()));
+    assert_eq!(save_state::save(&cpu), before);
+    assert_eq!(cpu.register_a, 0x99);
+    assert_eq!(cpu.mem_read(0x0010), 0x77);
+}
+
+#[test]
+fn test_state_from_another_board_is_refused() {
+    let cpu = CPU::new(Bus::new(banked_rom(69, 16, 32)).unwrap());
+    let state = save_state::save(&cpu);
+    let mut other = CPU::new(Bus::new(busy_rom()).unwrap());
+
+    assert!(save_state::load(&mut other, &state).is_err());
+}
+
+#[test]
+fn test_state_restores_where_the_joypad_is_read_from() {
+    let mut cpu = CPU::new(Bus::new(busy_rom()).unwrap());
+    let devices = InputDevices::connect(InputSetup::Joypads, &mut cpu.bus);
+    devices.set_joypad_buttons(0, JoypadButton::B.bit());
+    cpu.mem_write(0x4016, 1);
+    cpu.mem_write(0x4016, 0);
+    assert_eq!(cpu.mem_read(0x4016) & 1, 0);
+
+    let state = save_state::save(&cpu);
+    assert_eq!(cpu.mem_read(0x4016) & 1, 1);
+    save_state::load(&mut cpu, &state).unwrap();
+
+    assert_eq!(cpu.mem_read(0x4016) & 1, 1);
+}
+
+#[test]
+fn test_state_with_other_input_devices_is_refused() {
+    let mut cpu = CPU::new(Bus::new(busy_rom()).unwrap());
+    InputDevices::connect(InputSetup::Joypads, &mut cpu.bus);
+    let state = save_state::save(&cpu);
+    InputDevices::connect(InputSetup::Zapper, &mut cpu.bus);
+
+    assert_eq!(
+        save_state::load(&mut cpu, &state),
+        Err("Save state was made with other input devices connected".to_string())
+    );
+}
+
+#[test]
+fn test_quick_slots_are_named_after_the_rom() {
+    let mut slots = QuickSlots::new(Path::new("roms/game.nes"));
+    assert_eq!(slots.path(), Path::new("roms/game.ss0"));
+
+    for _ in 0..3 {
+        slots.next_slot();
+    }
+    assert_eq!(slots.slot(), 3);
+    assert_eq!(slots.path(), Path::new("roms/game.ss3"));
+
+    for _ in 0..7 {
+        slots.next_slot();
+    }
+    assert_eq!(slots.slot(), 0);
+}
+
+#[test]
+fn test_quick_slot_save_and_load() {
+    let rom_path = std::env::temp_dir().join(format!("quick_slot_test_{}.nes", std::process::id()));
+    let slots = QuickSlots::new(&rom_path);
+    let mut cpu = CPU::new(Bus::new(busy_rom()).unwrap());
+    cpu.reset();
+    run_frames(&mut cpu, 2);
+
+    slots.save(&cpu).unwrap();
+    let expected = run_frames(&mut cpu, 2);
+    slots.load(&mut cpu).unwrap();
+    let result = run_frames(&mut cpu, 2);
+    std::fs::remove_file(slots.path()).unwrap();
+
+    assert_eq!(result, expected);
+    assert!(slots.load(&mut cpu).unwrap_err().starts_with("Can't read"));
+}
@@ -0,0 +1,106 @@
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use crate::roms::speed::{Speed, SpeedControl};
+
+#[test]
+fn test_frame_durations() {
+    let normal = Duration::from_millis(16);
+    assert_eq!(Speed::Normal.frame_duration(normal), Some(normal));
+    assert_eq!(Speed::Half.frame_duration(normal), Some(Duration::from_millis(32)));
+    assert_eq!(Speed::Quarter.frame_duration(normal), Some(Duration::from_millis(64)));
+    assert_eq!(Speed::FastForward.frame_duration(normal), None);
+}
+
+#[test]
+fn test_audio_is_stretched_or_muted() {
+    let samples = [0.25, -0.5, 0.75];
+    assert_eq!(Speed::Normal.stretch_audio(&samples), vec![0.25, -0.5, 0.75]);
+    assert_eq!(Speed::Half.stretch_audio(&samples).len(), 6);
+    assert_eq!(Speed::Quarter.stretch_audio(&samples).len(), 12);
+    assert!(Speed::FastForward.stretch_audio(&samples).is_empty());
+}
+
+// A 441 Hz sine, a period is 100 samples at 44.1 kHz
+fn sine(length: usize) -> Vec<f32> {
+    (0..length).map(|i| (2.0 * PI * i as f32 / 100.0).sin()).collect()
+}
+
+fn rising_zero_crossings(samples: &[f32]) -> usize {
+    samples.windows(2).filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0).count()
+}
+
+#[test]
+fn test_slow_motion_keeps_the_pitch() {
+    let samples = sine(735);
+
+    for speed in [Speed::Half, Speed::Quarter] {
+        let stretched = speed.stretch_audio(&samples);
+        let periods = rising_zero_crossings(&stretched);
+
+        // the sound lasts longer with as many samples per period as before
+        let expected = stretched.len() / 100;
+        assert!(periods.abs_diff(expected) <= 1, "{:?} has {} periods instead of {}", speed, periods, expected);
+        assert!(stretched.iter().all(|sample| sample.abs() <= 1.01));
+    }
+}
+
+#[test]
+fn test_slow_motion_cycles_and_fast_forward_wins() {
+    let mut control = SpeedControl::new();
+    assert_eq!(control.speed(), Speed::Normal);
+    control.next_slow_motion();
+    assert_eq!(control.speed(), Speed::Half);
+    control.next_slow_motion();
+    assert_eq!(control.speed(), Speed::Quarter);
+
+    control.set_fast_forward(true);
+    assert_eq!(control.speed(), Speed::FastForward);
+    control.set_fast_forward(false);
+    assert_eq!(control.speed(), Speed::Quarter);
+
+    control.next_slow_motion();
+    assert_eq!(control.speed(), Speed::Normal);
+}
+
+#[test]
+fn test_pause_and_frame_advance() {
+    let mut control = SpeedControl::new();
+    assert!(control.run_frame());
+
+    control.toggle_pause();
+    assert!(!control.run_frame());
+    assert_eq!(control.describe(), "paused");
+
+    control.advance_frame();
+    assert!(control.run_frame());
+    assert!(!control.run_frame());
+
+    control.toggle_pause();
+    assert!(control.run_frame());
+    assert!(control.run_frame());
+    assert_eq!(control.describe(), "100%");
+}
+
+#[test]
+fn test_fast_forward_keeps_a_paused_game_paced() {
+    let mut control = SpeedControl::new();
+    control.next_slow_motion();
+    control.set_fast_forward(true);
+    control.toggle_pause();
+
+    assert_eq!(control.speed(), Speed::Half);
+
+    control.toggle_pause();
+    assert_eq!(control.speed(), Speed::FastForward);
+}
+
+#[test]
+fn test_frame_advance_pauses_a_running_game() {
+    let mut control = SpeedControl::new();
+    control.advance_frame();
+
+    assert!(control.is_paused());
+    assert!(control.run_frame());
+    assert!(!control.run_frame());
+}
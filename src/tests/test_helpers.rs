@@ -2,4 +2,7 @@ pub mod cpu_test_helper;
 
 pub mod rom_test_helper;
 
-pub mod ppu_test_helper;
\ No newline at end of file
+pub mod ppu_test_helper;
+pub mod fds_test_helper;
+pub mod nsf_test_helper;
+pub mod mapper_test_helper;
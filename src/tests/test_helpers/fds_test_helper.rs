@@ -0,0 +1,51 @@
+use crate::components::cartridge::fds::SIDE_SIZE;
+use crate::components::mapper::fds::Fds;
+use crate::components::mapper::Mapper;
+
+pub fn test_bios(reset_vector: u16, irq_vector: u16) -> Vec<u8> {
+    let mut bios = vec![0; Fds::BIOS_SIZE];
+    let reset_vector_bytes = reset_vector.to_le_bytes();
+    let irq_vector_bytes = irq_vector.to_le_bytes();
+    bios[0x1FFC] = reset_vector_bytes[0];
+    bios[0x1FFD] = reset_vector_bytes[1];
+    bios[0x1FFE] = irq_vector_bytes[0];
+    bios[0x1FFF] = irq_vector_bytes[1];
+    bios
+}
+
+pub fn disk_info_block() -> Vec<u8> {
+    let mut block = vec![0x01];
+    block.extend(b"*NINTENDO-HVC*");
+    block.resize(56, 0);
+    block
+}
+
+// One side holding a single file with the given contents
+pub fn test_disk_side(file: &[u8]) -> Vec<u8> {
+    let mut side = disk_info_block();
+
+    side.extend([0x02, 0x01]); // file amount
+
+    let file_size = (file.len() as u16).to_le_bytes();
+    side.extend([0x03, 0x00, 0x00]); // file header, number and id
+    side.extend(b"TESTFILE");
+    side.extend([0x00, 0x60]); // load address
+    side.extend(file_size);
+    side.push(0x00); // PRG
+
+    side.push(0x04);
+    side.extend(file);
+
+    side.resize(SIDE_SIZE, 0);
+    side
+}
+
+pub fn wait_for_transfer(fds: &mut Fds) {
+    for _ in 0..1_000_000 {
+        fds.tick(1);
+        if fds.read_prg(0x4030) & 0b10 != 0 {
+            return;
+        }
+    }
+    panic!("The disk drive didn't transfer a byte");
+}
@@ -0,0 +1,137 @@
+use crate::components::bus::Bus;
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::cpu::CPU;
+use crate::tests::test_helpers::cpu_test_helper;
+use crate::tests::test_helpers::rom_test_helper::{create_rom, TestRom};
+
+// Every 8KB PRG bank is filled with its bank number and every 1KB CHR bank with its own,
+// so a read tells which bank is mapped in
+pub fn banked_rom(mapper: u8, prg_8k_banks: usize, chr_1k_banks: usize) -> Rom {
+    let mut prg_rom = vec![];
+    for bank in 0..prg_8k_banks {
+        prg_rom.extend(vec![bank as u8; 0x2000]);
+    }
+    let mut chr_rom = vec![];
+    for bank in 0..chr_1k_banks {
+        chr_rom.extend(vec![bank as u8; 0x400]);
+    }
+
+    Rom {
+        prg_rom,
+        chr_rom,
+        mapper,
+        screen_mirroring: Mirroring::VERTICAL,
+        battery: false,
+        submapper: 0,
+        default_expansion_device: 0,
+    }
+}
+
+// banked_rom as an NES 2.0 file. Every 8KB PRG bank ends with a reset vector to $0600, where
+// test programs are loaded, so they keep running whatever gets switched in
+pub fn bank_switching_rom(mapper: u8, submapper: u8, prg_8k_banks: usize, chr_1k_banks: usize) -> Rom {
+    let banked = banked_rom(mapper, prg_8k_banks, chr_1k_banks);
+    let mut prg_rom = banked.prg_rom;
+    for bank in prg_rom.chunks_mut(0x2000) {
+        bank[0x1FFC] = 0x00;
+        bank[0x1FFD] = 0x06;
+    }
+
+    let raw = create_rom(TestRom {
+        header: vec![
+            0x4E, 0x45, 0x53, 0x1A,
+            (prg_8k_banks / 2) as u8,
+            (chr_1k_banks / 8) as u8,
+            (mapper << 4) | 1,
+            (mapper & 0xF0) | 0b1000,
+            submapper << 4,
+            0, 0, 0, 0, 0, 0, 0,
+        ],
+        trainer: None,
+        pgp_rom: prg_rom,
+        chr_rom: banked.chr_rom,
+    });
+    Rom::new(&raw).unwrap()
+}
+
+// Runs the program from $0600 until its BRK
+pub fn run_program(rom: Rom, program: Vec<u8>) -> CPU {
+    let mut cpu = CPU::new(Bus::new(rom).unwrap());
+    cpu.load_and_run(program);
+    cpu
+}
+
+pub fn write_register(addr: u16, value: u8) -> Vec<u8> {
+    let mut program = cpu_test_helper::set_accumulator_to_value(value).to_vec();
+    program.extend(cpu_test_helper::store_accumulator_to_memory_address(addr));
+    program
+}
+
+// Copies a byte from the CPU address space to the zero page
+pub fn copy_byte(addr: u16, zero_page: u8) -> Vec<u8> {
+    let mut program = cpu_test_helper::set_register_x_from_memory(addr).to_vec();
+    program.extend(cpu_test_helper::store_register_x_to_zero_page(zero_page));
+    program
+}
+
+// Reads through $2007, where the first read only fills the PPU's buffer
+pub fn copy_chr_byte(addr: u16, zero_page: u8) -> Vec<u8> {
+    let [high, low] = addr.to_be_bytes();
+    let mut program = write_register(0x2006, high);
+    program.extend(write_register(0x2006, low));
+    program.extend(cpu_test_helper::set_register_x_from_memory(0x2007));
+    program.extend(copy_byte(0x2007, zero_page));
+    program
+}
+
+// Talks to a serial EEPROM the way games do, through a callback that sets the clock and data
+// lines and returns the data line as driven by the EEPROM
+pub struct I2cHost<F: FnMut(bool, bool) -> bool> {
+    pub set_lines: F,
+    pub msb_first: bool,
+}
+
+impl<F: FnMut(bool, bool) -> bool> I2cHost<F> {
+    pub fn start(&mut self) {
+        (self.set_lines)(false, true);
+        (self.set_lines)(true, true);
+        (self.set_lines)(true, false);
+        (self.set_lines)(false, false);
+    }
+
+    pub fn stop(&mut self) {
+        (self.set_lines)(false, false);
+        (self.set_lines)(true, false);
+        (self.set_lines)(true, true);
+    }
+
+    fn clock_bit(&mut self, sda: bool) -> bool {
+        (self.set_lines)(false, sda);
+        let data = (self.set_lines)(true, sda);
+        (self.set_lines)(false, sda);
+        data
+    }
+
+    fn bit_position(&self, bit: u8) -> u8 {
+        if self.msb_first { 7 - bit } else { bit }
+    }
+
+    // Returns whether the EEPROM acknowledged the byte
+    pub fn send_byte(&mut self, byte: u8) -> bool {
+        for bit in 0..8 {
+            let position = self.bit_position(bit);
+            self.clock_bit(byte & (1 << position) != 0);
+        }
+        !self.clock_bit(true)
+    }
+
+    pub fn read_byte(&mut self, acknowledge: bool) -> u8 {
+        let mut byte = 0;
+        for bit in 0..8 {
+            let position = self.bit_position(bit);
+            byte |= (self.clock_bit(true) as u8) << position;
+        }
+        self.clock_bit(!acknowledge);
+        byte
+    }
+}
@@ -0,0 +1,55 @@
+pub struct TestNsf {
+    pub total_songs: u8,
+    pub starting_song: u8,
+    pub load_address: u16,
+    pub init_address: u16,
+    pub play_address: u16,
+    pub bank_switch: [u8; 8],
+    pub data: Vec<u8>,
+}
+
+pub fn create_nsf(nsf: TestNsf) -> Vec<u8> {
+    let mut result = vec![0x4E, 0x45, 0x53, 0x4D, 0x1A, 0x01]; // NESM^Z string and version
+    result.push(nsf.total_songs);
+    result.push(nsf.starting_song);
+    result.extend(nsf.load_address.to_le_bytes());
+    result.extend(nsf.init_address.to_le_bytes());
+    result.extend(nsf.play_address.to_le_bytes());
+    result.extend(padded_string("Test song", 32));
+    result.extend(padded_string("Test artist", 32));
+    result.extend(padded_string("2024 Test", 32));
+    result.extend((16639 as u16).to_le_bytes()); // NTSC speed
+    result.extend(nsf.bank_switch);
+    result.extend((19997 as u16).to_le_bytes()); // PAL speed
+    result.push(0); // NTSC
+    result.push(0); // no expansion audio
+    result.extend([0; 4]); // Reserved
+    result.extend(nsf.data);
+    result
+}
+
+pub fn create_nsfe(chunks: Vec<(&str, Vec<u8>)>) -> Vec<u8> {
+    let mut result = vec![0x4E, 0x53, 0x46, 0x45]; // NSFE string to recognise the file
+
+    for (id, data) in chunks {
+        result.extend((data.len() as u32).to_le_bytes());
+        result.extend(id.as_bytes());
+        result.extend(data);
+    }
+
+    result
+}
+
+fn padded_string(value: &str, length: usize) -> Vec<u8> {
+    let mut result = value.as_bytes().to_vec();
+    result.resize(length, 0);
+    result
+}
+
+// INIT stores A and X to $0200/$0201, PLAY increments $0202
+pub fn test_nsf_program() -> Vec<u8> {
+    let mut program = vec![0x8D, 0x00, 0x02, 0x8E, 0x01, 0x02, 0x60];
+    program.resize(0x10, 0xEA);
+    program.extend([0xEE, 0x02, 0x02, 0x60]);
+    program
+}
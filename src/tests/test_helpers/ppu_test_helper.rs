@@ -1,6 +1,6 @@
 ﻿use std::collections::btree_map::Values;
-use sdl2::sys::va_list;
-use crate::components::cartridge::Mirroring;
+use crate::components::cartridge::{Mirroring, Rom};
+use crate::components::mapper;
 use crate::components::ppu::NesPPU;
 use crate::tests::test_helpers::cpu_test_helper;
 
@@ -77,4 +77,31 @@ pub fn write_data_to_PPUCTRL(value : u8) -> Vec<u8>{
     ];
 
     return set_data_in_ppu;
-}
\ No newline at end of file
+}
+pub fn test_ppu(chr_rom: Vec<u8>) -> NesPPU {
+    let rom = Rom {
+        prg_rom: vec![0; Rom::PRG_ROM_PAGE_SIZE],
+        chr_rom,
+        mapper: 0,
+        screen_mirroring: Mirroring::HORIZONTAL,
+        battery: false,
+        submapper: 0,
+        default_expansion_device: 0,
+    };
+    NesPPU::new(mapper::for_rom(rom).unwrap())
+}
+
+pub fn write_ppu_memory(ppu: &mut NesPPU, address: u16, data: &[u8]) {
+    let address = address.to_be_bytes();
+    ppu.write_to_ppu_addr(address[0]);
+    ppu.write_to_ppu_addr(address[1]);
+    for value in data {
+        ppu.write_to_data(*value);
+    }
+}
+
+pub fn run_ppu_to_scanline(ppu: &mut NesPPU, scanline: u16) {
+    while ppu.scanline() != scanline {
+        ppu.tick(1);
+    }
+}
@@ -17,7 +17,7 @@ pub fn test_rom(pc_counter_start: u16, mirroring : Option<Mirroring>) -> Rom {
     
     let mirror=mirroring.unwrap_or(Mirroring::VERTICAL);
 
-    let mut controlByte1=0x30;
+    let mut controlByte1=0x00;
     
     match mirror {
         Mirroring::VERTICAL => {
@@ -29,6 +29,9 @@ pub fn test_rom(pc_counter_start: u16, mirroring : Option<Mirroring>) -> Rom {
         Mirroring::FOUR_SCREEN => {
             controlByte1 |=0b1000 ;
         }
+        Mirroring::SINGLE_SCREEN_LOWER | Mirroring::SINGLE_SCREEN_UPPER => {
+            panic!("iNES headers can't describe single screen mirroring");
+        }
     }
     
     
@@ -71,3 +74,17 @@ pub fn create_rom(rom: TestRom) -> Vec<u8> {
 
     result
 }
+
+pub fn create_unif_rom(chunks: Vec<(&str, Vec<u8>)>) -> Vec<u8> {
+    let mut result = vec![0x55, 0x4E, 0x49, 0x46]; // UNIF string to recognise rom
+    result.extend((7 as u32).to_le_bytes()); // revision
+    result.extend(vec![0; 24]); // Reserved, must be zeroes
+
+    for (id, data) in chunks {
+        result.extend(id.as_bytes());
+        result.extend((data.len() as u32).to_le_bytes());
+        result.extend(data);
+    }
+
+    result
+}
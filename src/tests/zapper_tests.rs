@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::components::bus::Bus;
+use crate::components::frame::Frame;
+use crate::components::input_device::{InputDevices, InputSetup};
+use crate::components::mem::Mem;
+use crate::components::zapper::Zapper;
+use crate::tests::test_helpers::rom_test_helper::test_rom;
+
+fn run_bus_to_scanline(bus: &mut Bus, scanline: u16) {
+    while bus.ppu().scanline() != scanline {
+        bus.tick(1);
+    }
+}
+
+// with rendering disabled every line gets drawn with the backdrop color
+fn bus_with_backdrop(color: u8) -> (Bus, Rc<RefCell<Zapper>>) {
+    let mut bus = Bus::new(test_rom(0x0600, None)).unwrap();
+    let zapper = InputDevices::connect(InputSetup::Zapper, &mut bus).zapper.unwrap();
+    bus.mem_write(0x2006, 0x3F);
+    bus.mem_write(0x2006, 0x00);
+    bus.mem_write(0x2007, color);
+    (bus, zapper)
+}
+
+fn white_frame() -> Frame {
+    let mut frame = Frame::new();
+    for value in frame.data.iter_mut() {
+        *value = 0xFF;
+    }
+    frame
+}
+
+#[test]
+fn test_zapper_senses_light_after_the_beam_passed() {
+    let frame = white_frame();
+    let mut zapper = Zapper::new();
+    zapper.aim_at(100, 50);
+
+    assert_eq!(zapper.read(&frame, 40), 0b0000_1000);
+    assert_eq!(zapper.read(&frame, 52), 0);
+    assert_eq!(zapper.read(&frame, 60), 0);
+    assert_eq!(zapper.read(&frame, 100), 0b0000_1000);
+}
+
+#[test]
+fn test_zapper_doesnt_sense_dark_pixels() {
+    let frame = Frame::new();
+    let mut zapper = Zapper::new();
+    zapper.aim_at(100, 50);
+
+    assert_eq!(zapper.read(&frame, 55), 0b0000_1000);
+}
+
+#[test]
+fn test_zapper_aimed_off_screen() {
+    let frame = white_frame();
+    let mut zapper = Zapper::new();
+
+    zapper.aim_at(300, 50);
+    assert_eq!(zapper.aim(), None);
+    assert_eq!(zapper.read(&frame, 55), 0b0000_1000);
+
+    zapper.aim_at(10, 10);
+    zapper.aim_off_screen();
+    assert_eq!(zapper.read(&frame, 15), 0b0000_1000);
+}
+
+#[test]
+fn test_zapper_trigger() {
+    let frame = Frame::new();
+    let mut zapper = Zapper::new();
+
+    zapper.set_trigger(true);
+    assert_eq!(zapper.read(&frame, 0), 0b0001_1000);
+
+    zapper.set_trigger(false);
+    assert_eq!(zapper.read(&frame, 0), 0b0000_1000);
+}
+
+#[test]
+fn test_zapper_on_port_2_sees_rendered_frame() {
+    let (mut bus, zapper) = bus_with_backdrop(0x30);
+    zapper.borrow_mut().aim_at(128, 120);
+    zapper.borrow_mut().set_trigger(true);
+
+    run_bus_to_scanline(&mut bus, 100);
+    assert_eq!(bus.mem_read(0x4017), 0x40 | 0b0001_1000);
+
+    run_bus_to_scanline(&mut bus, 125);
+    assert_eq!(bus.mem_read(0x4017), 0x40 | 0b0001_0000);
+}
+
+#[test]
+fn test_zapper_on_port_2_dark_screen() {
+    let (mut bus, zapper) = bus_with_backdrop(0x0F);
+    zapper.borrow_mut().aim_at(128, 120);
+
+    run_bus_to_scanline(&mut bus, 125);
+
+    assert_eq!(bus.mem_read(0x4017), 0x40 | 0b0000_1000);
+}